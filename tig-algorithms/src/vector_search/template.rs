@@ -21,6 +21,8 @@ pub fn solve_challenge(challenge: &Challenge) -> Result<Option<Solution>> {
     // return Err(<msg>) if your algorithm encounters an error
     // return Ok(None) if your algorithm finds no solution or needs to exit early
     // return Ok(Solution { .. }) if your algorithm finds a solution
+    // seed any randomness with tig_challenges::RngStream::Solving.rng(challenge.seeds), which doesn't
+    // overlap the numbers drawn to generate the instance
     Err(anyhow!("Not implemented"))
 }
 
@@ -6,18 +6,31 @@ compile_error!("features `request` and `request-js` are mutually exclusive");
 use anyhow::{anyhow, Result};
 use query_map::QueryMap;
 use serde::de::DeserializeOwned;
-use std::{collections::HashMap, vec};
+use std::{collections::HashMap, sync::Mutex, vec};
 pub use tig_structs::api::*;
 use tig_utils::{dejsonify, get, jsonify, post};
 
 pub struct Api {
     api_url: String,
     api_key: String,
+    schema_versions: Vec<u32>,
+    schema_version: Mutex<Option<u32>>,
 }
 
 impl Api {
     pub fn new(api_url: String, api_key: String) -> Self {
-        Self { api_url, api_key }
+        Self {
+            api_url,
+            api_key,
+            schema_versions: SOLUTION_SCHEMA_VERSIONS.to_vec(),
+            schema_version: Mutex::new(None),
+        }
+    }
+
+    // overrides the SolutionData schema versions the client can submit
+    pub fn with_schema_versions(mut self, schema_versions: Vec<u32>) -> Self {
+        self.schema_versions = schema_versions;
+        self
     }
 
     async fn get<T>(&self, path: String) -> Result<T>
@@ -121,17 +134,118 @@ impl Api {
             .await
     }
 
+    // latest block's active challenges with their difficulty parameters and frontiers
+    pub async fn get_block_config(&self) -> Result<GetBlockConfigResp> {
+        let block = self
+            .get_block(GetBlockReq {
+                id: None,
+                round: None,
+                height: None,
+                include_data: false,
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to query latest block: {}", e))?
+            .block
+            .ok_or_else(|| anyhow!("Expecting latest block to exist"))?;
+        let GetChallengesResp { challenges, .. } = self
+            .get_challenges(GetChallengesReq {
+                block_id: block.id.clone(),
+            })
+            .await
+            .map_err(|e| anyhow!("Failed to query challenges: {}", e))?;
+        let config = block
+            .config
+            .as_ref()
+            .ok_or_else(|| anyhow!("Expecting latest block to have a config"))?;
+        let mut challenge_configs = Vec::new();
+        for challenge in challenges.into_iter().filter(|c| {
+            c.state
+                .as_ref()
+                .and_then(|s| s.round_active)
+                .is_some_and(|r| r <= block.details.round)
+        }) {
+            let difficulty_parameters = config
+                .difficulty
+                .parameters
+                .get(&challenge.id)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Missing difficulty parameters for challenge {}",
+                        challenge.id
+                    )
+                })?
+                .clone();
+            let (base_frontier, scaled_frontier) = match challenge.block_data {
+                Some(block_data) => (block_data.base_frontier, block_data.scaled_frontier),
+                None => (None, None),
+            };
+            challenge_configs.push(ChallengeConfig {
+                challenge_id: challenge.id,
+                name: challenge.details.name,
+                difficulty_parameters,
+                base_frontier,
+                scaled_frontier,
+            });
+        }
+        Ok(GetBlockConfigResp {
+            block_id: block.id,
+            block_details: block.details,
+            challenges: challenge_configs,
+        })
+    }
+
     pub async fn submit_algorithm(&self, req: SubmitAlgorithmReq) -> Result<SubmitAlgorithmResp> {
         self.post("submit-algorithm".to_string(), jsonify(&req))
             .await
     }
 
-    pub async fn submit_benchmark(&self, req: SubmitBenchmarkReq) -> Result<SubmitBenchmarkResp> {
+    pub async fn get_schema_versions(&self) -> Result<GetSchemaVersionsResp> {
+        self.get("get-schema-versions".to_string()).await
+    }
+
+    // newest SolutionData schema version both the client and the server support. negotiated with
+    // the server on first use, so a client newer than the server downgrades to what it accepts
+    pub async fn schema_version(&self) -> Result<u32> {
+        if let Some(version) = *self.schema_version.lock().unwrap() {
+            return Ok(version);
+        }
+        let GetSchemaVersionsResp { versions } = self
+            .get_schema_versions()
+            .await
+            .map_err(|e| anyhow!("Failed to query schema versions: {}", e))?;
+        let version = self
+            .schema_versions
+            .iter()
+            .filter(|v| versions.contains(v))
+            .max()
+            .cloned()
+            .ok_or_else(|| {
+                anyhow!(
+                    "No compatible solution schema version: client supports {:?}, server supports {:?}",
+                    self.schema_versions,
+                    versions
+                )
+            })?;
+        *self.schema_version.lock().unwrap() = Some(version);
+        Ok(version)
+    }
+
+    // the request's schema_version is set to the negotiated one, unless already set
+    pub async fn submit_benchmark(
+        &self,
+        mut req: SubmitBenchmarkReq,
+    ) -> Result<SubmitBenchmarkResp> {
+        if req.schema_version.is_none() {
+            req.schema_version = Some(self.schema_version().await?);
+        }
         self.post("submit-benchmark".to_string(), jsonify(&req))
             .await
     }
 
-    pub async fn submit_proof(&self, req: SubmitProofReq) -> Result<SubmitProofResp> {
+    pub async fn submit_proof(&self, mut req: SubmitProofReq) -> Result<SubmitProofResp> {
+        if req.schema_version.is_none() {
+            req.schema_version = Some(self.schema_version().await?);
+        }
         self.post("submit-proof".to_string(), jsonify(&req)).await
     }
 }
@@ -0,0 +1,202 @@
+#[cfg(feature = "request")]
+mod tests {
+    use serde_json::{json, Value};
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+    };
+    use tig_api::Api;
+    use tig_structs::config::DifficultyParameter;
+
+    // serves each path's json until the test process exits. unknown paths get a 500
+    fn mock_api(routes: Vec<(&'static str, Value)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                }
+                let path = request_line.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = match routes.iter().find(|(p, _)| path.starts_with(p)) {
+                    Some((_, body)) => ("200 OK", body.to_string()),
+                    None => ("500 Internal Server Error", "{}".to_string()),
+                };
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        url
+    }
+
+    fn block() -> Value {
+        json!({
+            "block": {
+                "id": "block1",
+                "details": {
+                    "prev_block_id": "block0",
+                    "height": 100,
+                    "round": 3,
+                },
+                "config": {
+                    "erc20": {
+                        "rpc_url": "",
+                        "chain_id": "",
+                        "token_address": "",
+                        "burn_address": "",
+                    },
+                    "benchmark_submissions": {
+                        "min_num_solutions": 2,
+                        "submission_delay_multiplier": 3,
+                        "max_samples": 3,
+                        "lifespan_period": 120,
+                    },
+                    "wasm_vm": {"max_memory": 1000000000u64, "max_fuel": 2000000000u64},
+                    "solution_signature": {"equilibrium_rate_multiplier": 1.0},
+                    "qualifiers": {
+                        "cutoff_multiplier": 2.0,
+                        "total_qualifiers_threshold": 1000,
+                    },
+                    "difficulty": {
+                        "max_scaling_factor": 1.8,
+                        "parameters": {
+                            "c001": [
+                                {"name": "num_variables", "min_value": 50, "max_value": 2000},
+                                {"name": "clauses_to_variables_percent", "min_value": 300, "max_value": 500},
+                            ],
+                            "c002": [
+                                {"name": "num_nodes", "min_value": 40, "max_value": 200},
+                                {"name": "better_than_baseline", "min_value": 250, "max_value": 1000},
+                            ],
+                        },
+                    },
+                    "optimisable_proof_of_work": {"imbalance_multiplier": 3.0},
+                    "rounds": {"blocks_per_round": 10080},
+                    "algorithm_submissions": {
+                        "submission_fee": "5000000000000000000",
+                        "adoption_threshold": 0.25,
+                        "merge_points_threshold": 5040,
+                        "push_delay": 3,
+                    },
+                    "rewards": {
+                        "distribution": {
+                            "benchmarkers": 0.75,
+                            "optimisations": 0.25,
+                            "breakthroughs": 0.0,
+                        },
+                        "schedule": [{"block_reward": 1.0, "round_start": 0}],
+                    },
+                },
+            }
+        })
+    }
+
+    fn challenges() -> Value {
+        json!({
+            "block_id": "block1",
+            "block_details": {
+                "prev_block_id": "block0",
+                "height": 100,
+                "round": 3,
+            },
+            "challenges": [
+                {
+                    "id": "c001",
+                    "details": {"name": "satisfiability"},
+                    "state": {"block_confirmed": 1, "round_active": 1},
+                    "block_data": {
+                        "base_frontier": [[60, 400], [70, 350]],
+                        "scaled_frontier": [[80, 420]],
+                    },
+                },
+                {
+                    "id": "c002",
+                    "details": {"name": "vehicle_routing"},
+                    "state": {"block_confirmed": 1, "round_active": 1},
+                },
+                {
+                    // not active until a later round
+                    "id": "c003",
+                    "details": {"name": "knapsack"},
+                    "state": {"block_confirmed": 90, "round_active": 4},
+                },
+            ],
+        })
+    }
+
+    #[tokio::test]
+    async fn test_get_block_config() {
+        let url = mock_api(vec![
+            ("/get-block", block()),
+            ("/get-challenges", challenges()),
+        ]);
+        let api = Api::new(url, "key".to_string());
+        let config = api.get_block_config().await.unwrap();
+
+        assert_eq!(config.block_id, "block1");
+        assert_eq!(config.block_details.round, 3);
+        assert_eq!(config.challenges.len(), 2);
+
+        let satisfiability = &config.challenges[0];
+        assert_eq!(satisfiability.challenge_id, "c001");
+        assert_eq!(satisfiability.name, "satisfiability");
+        assert_eq!(
+            satisfiability.difficulty_parameters,
+            vec![
+                DifficultyParameter {
+                    name: "num_variables".to_string(),
+                    min_value: 50,
+                    max_value: 2000,
+                },
+                DifficultyParameter {
+                    name: "clauses_to_variables_percent".to_string(),
+                    min_value: 300,
+                    max_value: 500,
+                },
+            ]
+        );
+        assert_eq!(
+            satisfiability.base_frontier,
+            Some([vec![60, 400], vec![70, 350]].into_iter().collect())
+        );
+        assert_eq!(
+            satisfiability.scaled_frontier,
+            Some([vec![80, 420]].into_iter().collect())
+        );
+
+        let vehicle_routing = &config.challenges[1];
+        assert_eq!(vehicle_routing.challenge_id, "c002");
+        assert_eq!(vehicle_routing.difficulty_parameters[0].min_value, 40);
+        assert_eq!(vehicle_routing.base_frontier, None);
+    }
+
+    #[tokio::test]
+    async fn test_get_block_config_network_error() {
+        let url = mock_api(vec![("/get-block", block())]);
+        let api = Api::new(url, "key".to_string());
+        let err = api.get_block_config().await.unwrap_err();
+        assert!(err.to_string().contains("Failed to query challenges"));
+
+        // nothing listening
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        let api = Api::new(url, "key".to_string());
+        let err = api.get_block_config().await.unwrap_err();
+        assert!(err.to_string().contains("Failed to query latest block"));
+    }
+}
@@ -0,0 +1,124 @@
+#[cfg(feature = "request")]
+mod tests {
+    use serde_json::{json, Value};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        thread,
+    };
+    use tig_api::{Api, SubmitProofReq};
+
+    // advertises the schema versions and accepts every proof, recording the paths requested and the
+    // bodies posted
+    fn mock_api(versions: Vec<u32>) -> (String, Arc<Mutex<Vec<(String, String)>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or("")
+                    .to_string();
+                let resp = if path.starts_with("/get-schema-versions") {
+                    json!({ "versions": versions })
+                } else {
+                    json!({ "verified": { "Ok": null } })
+                }
+                .to_string();
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push((path, String::from_utf8(body).unwrap()));
+                write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    resp.len(),
+                    resp
+                )
+                .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn req() -> SubmitProofReq {
+        SubmitProofReq {
+            benchmark_id: "benchmark1".to_string(),
+            solutions_data: Vec::new(),
+            schema_version: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_negotiates_newest_common_version() {
+        // the server is behind the client, so the client downgrades
+        let (url, requests) = mock_api(vec![1, 2]);
+        let api = Api::new(url, "key".to_string()).with_schema_versions(vec![1, 2, 3]);
+        assert_eq!(api.schema_version().await.unwrap(), 2);
+
+        api.submit_proof(req()).await.unwrap();
+        api.submit_proof(req()).await.unwrap();
+        let requests = requests.lock().unwrap().clone();
+        // negotiated once
+        assert_eq!(
+            requests.iter().map(|(p, _)| p.as_str()).collect::<Vec<_>>(),
+            vec!["/get-schema-versions", "/submit-proof", "/submit-proof"]
+        );
+        let body: Value = serde_json::from_str(&requests[1].1).unwrap();
+        assert_eq!(body["schema_version"], json!(2));
+
+        // the server is ahead of the client
+        let (url, _) = mock_api(vec![1, 2, 3, 4]);
+        let api = Api::new(url, "key".to_string()).with_schema_versions(vec![1, 2, 3]);
+        assert_eq!(api.schema_version().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_explicit_version_is_kept() {
+        let (url, requests) = mock_api(vec![1]);
+        let api = Api::new(url, "key".to_string());
+        api.submit_proof(SubmitProofReq {
+            schema_version: Some(7),
+            ..req()
+        })
+        .await
+        .unwrap();
+        let requests = requests.lock().unwrap().clone();
+        assert_eq!(requests.len(), 1);
+        let body: Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(body["schema_version"], json!(7));
+    }
+
+    #[tokio::test]
+    async fn test_no_compatible_version() {
+        let (url, requests) = mock_api(vec![4, 5]);
+        let api = Api::new(url, "key".to_string()).with_schema_versions(vec![1, 2, 3]);
+        let err = api.submit_proof(req()).await.unwrap_err().to_string();
+        assert!(err.contains("No compatible solution schema version"));
+        assert!(err.contains("client supports [1, 2, 3], server supports [4, 5]"));
+        // nothing was submitted
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,104 @@
+use std::fmt;
+use tig_challenges::{knapsack, satisfiability, vector_search, vehicle_routing};
+use tig_worker::{deserialize_instance, Solution};
+
+type Predicate<C, S> = Box<dyn Fn(&C, &S) -> bool + Send + Sync>;
+
+// a quality bar on top of validity, e.g. routes shorter than some distance. execute only records
+// solutions the predicate of their challenge accepts, so a run focuses on high value results.
+// challenges without a predicate accept every valid solution
+#[derive(Default)]
+pub struct AcceptIf {
+    satisfiability: Option<Predicate<satisfiability::Challenge, satisfiability::Solution>>,
+    vehicle_routing: Option<Predicate<vehicle_routing::Challenge, vehicle_routing::Solution>>,
+    knapsack: Option<Predicate<knapsack::Challenge, knapsack::Solution>>,
+    vector_search: Option<Predicate<vector_search::Challenge, vector_search::Solution>>,
+}
+
+impl AcceptIf {
+    pub fn satisfiability(
+        mut self,
+        accept_if: impl Fn(&satisfiability::Challenge, &satisfiability::Solution) -> bool
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.satisfiability = Some(Box::new(accept_if));
+        self
+    }
+
+    pub fn vehicle_routing(
+        mut self,
+        accept_if: impl Fn(&vehicle_routing::Challenge, &vehicle_routing::Solution) -> bool
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.vehicle_routing = Some(Box::new(accept_if));
+        self
+    }
+
+    pub fn knapsack(
+        mut self,
+        accept_if: impl Fn(&knapsack::Challenge, &knapsack::Solution) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.knapsack = Some(Box::new(accept_if));
+        self
+    }
+
+    pub fn vector_search(
+        mut self,
+        accept_if: impl Fn(&vector_search::Challenge, &vector_search::Solution) -> bool
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.vector_search = Some(Box::new(accept_if));
+        self
+    }
+
+    // a solution whose instance or type can't be decoded isn't accepted, as the predicate can't
+    // tell whether it qualifies
+    pub fn accepts(&self, challenge_id: &str, instance_bytes: &[u8], solution: &Solution) -> bool {
+        match challenge_id {
+            "c001" => check(&self.satisfiability, instance_bytes, solution),
+            "c002" => check(&self.vehicle_routing, instance_bytes, solution),
+            "c003" => check(&self.knapsack, instance_bytes, solution),
+            "c004" => check(&self.vector_search, instance_bytes, solution),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for AcceptIf {
+    // predicates can't be printed, so lists the challenges that have one
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AcceptIf")
+            .field("satisfiability", &self.satisfiability.is_some())
+            .field("vehicle_routing", &self.vehicle_routing.is_some())
+            .field("knapsack", &self.knapsack.is_some())
+            .field("vector_search", &self.vector_search.is_some())
+            .finish()
+    }
+}
+
+fn check<C, S>(
+    predicate: &Option<Predicate<C, S>>,
+    instance_bytes: &[u8],
+    solution: &Solution,
+) -> bool
+where
+    C: serde::de::DeserializeOwned,
+    S: TryFrom<Solution>,
+{
+    match predicate {
+        None => true,
+        Some(predicate) => match (
+            deserialize_instance::<C>(instance_bytes),
+            S::try_from(solution.clone()),
+        ) {
+            (Ok(challenge), Ok(solution)) => predicate(&challenge, &solution),
+            _ => false,
+        },
+    }
+}
@@ -0,0 +1,101 @@
+use super::{Job, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{solution_hash, solution_qualifier, Solution};
+
+// a solution the run accepted, as kept for dispute resolution and compliance review
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub benchmark_id: String,
+    pub nonce: u64,
+    // ChallengeTrait::qualifier of the solution
+    pub qualifier: f64,
+    // solution_hash of the solution, equal for solutions that are equivalent for the instance
+    pub fingerprint: String,
+    // ms when it was accepted, on the run's clock
+    pub timestamp: u64,
+}
+
+impl AuditRecord {
+    // regenerates the nonce's instance for the qualifier and fingerprint, so only worth it when an
+    // audit log is kept
+    pub fn new(job: &Job, nonce: u64, solution: &Solution, timestamp: u64) -> Result<Self> {
+        Ok(Self {
+            benchmark_id: job.benchmark_id.clone(),
+            nonce,
+            qualifier: solution_qualifier(&job.settings, nonce, solution)
+                .map_err(|e| format!("Failed to audit nonce {}: {}", nonce, e))?,
+            fingerprint: solution_hash(&job.settings, nonce, solution)
+                .map_err(|e| format!("Failed to audit nonce {}: {}", nonce, e))?,
+            timestamp,
+        })
+    }
+}
+
+// opt-in append-only record of every solution a run accepts, one json line each, shared by all
+// workers. buffered like FailureSink, but flush also syncs the file to disk, so records that were
+// flushed survive a crash
+#[derive(Debug)]
+pub struct AuditLog {
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl AuditLog {
+    // appends to the file at path. records are never rewritten
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open audit log {:?}: {}", path, e))?;
+        Ok(Self {
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub fn record(&self, record: &AuditRecord) -> Result<()> {
+        writeln!(self.writer.lock().unwrap(), "{}", jsonify(record))
+            .map_err(|e| format!("Failed to write audit log: {}", e))
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        let mut writer = self.writer.lock().unwrap();
+        writer
+            .flush()
+            .and_then(|_| writer.get_ref().sync_data())
+            .map_err(|e| format!("Failed to flush audit log: {}", e))
+    }
+}
+
+// records an accepted solution if the run keeps an audit log
+pub(super) fn record_audit(
+    audit_log: &Option<Arc<AuditLog>>,
+    job: &Job,
+    nonce: u64,
+    solution: &Solution,
+    timestamp: u64,
+) {
+    if let Some(audit_log) = audit_log {
+        // a failing log shouldn't stop the run
+        if let Err(e) = AuditRecord::new(job, nonce, solution, timestamp)
+            .and_then(|record| audit_log.record(&record))
+        {
+            println!("{}", e);
+        }
+    }
+}
+
+pub fn read_audit_log(path: &Path) -> Result<Vec<AuditRecord>> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read audit log {:?}: {}", path, e))?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| dejsonify(line).map_err(|e| format!("Invalid audit record {:?}: {}", line, e)))
+        .collect()
+}
@@ -0,0 +1,73 @@
+use super::Job;
+use crate::future_utils::time;
+use serde::Serialize;
+use tig_worker::{compute_solution, Solution};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BackendComparison {
+    pub num_nonces: u64,
+    pub native_ms: u64,
+    pub wasm_ms: u64,
+    // nonces where the backends returned different solutions, or only one found a solution
+    pub divergent_nonces: Vec<u64>,
+}
+
+impl BackendComparison {
+    // native throughput relative to wasm, i.e. how many times faster native runs. None if either
+    // run was too fast to measure
+    pub fn throughput_ratio(&self) -> Option<f64> {
+        if self.native_ms == 0 || self.wasm_ms == 0 {
+            None
+        } else {
+            Some(self.wasm_ms as f64 / self.native_ms as f64)
+        }
+    }
+
+    // native solutions are only safe to ship if they are exactly what the wasm produces, as the
+    // protocol verifies submissions by re-running the wasm
+    pub fn solutions_match(&self) -> bool {
+        self.divergent_nonces.is_empty()
+    }
+}
+
+// runs both backends of the same algorithm over the same nonces, one backend after the other so
+// their timings don't interfere
+pub fn compare_backends(
+    nonces: &[u64],
+    native: impl Fn(u64) -> Option<Solution>,
+    wasm: impl Fn(u64) -> Option<Solution>,
+) -> BackendComparison {
+    let start = time();
+    let native_solutions: Vec<Option<Solution>> = nonces.iter().map(|&n| native(n)).collect();
+    let native_ms = time() - start;
+    let start = time();
+    let wasm_solutions: Vec<Option<Solution>> = nonces.iter().map(|&n| wasm(n)).collect();
+    let wasm_ms = time() - start;
+    BackendComparison {
+        num_nonces: nonces.len() as u64,
+        native_ms,
+        wasm_ms,
+        divergent_nonces: nonces
+            .iter()
+            .zip(native_solutions.iter().zip(wasm_solutions.iter()))
+            .filter(|(_, (native, wasm))| native != wasm)
+            .map(|(&nonce, _)| nonce)
+            .collect(),
+    }
+}
+
+// the wasm backend of compare_backends for a job. failed computations count as no solution
+pub fn wasm_backend<'a>(job: &'a Job, wasm: &'a [u8]) -> impl Fn(u64) -> Option<Solution> + 'a {
+    move |nonce| {
+        compute_solution(
+            &job.settings,
+            nonce,
+            wasm,
+            job.wasm_vm_config.max_memory,
+            job.wasm_vm_config.max_fuel,
+        )
+        .ok()
+        .flatten()
+        .map(|solution_data| solution_data.solution)
+    }
+}
@@ -0,0 +1,110 @@
+use super::{
+    difficulty_surface::{execute_cell, SurfaceCell},
+    Job, Result,
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::BenchmarkSettings;
+
+// throughput and solve rate of an algorithm at a fixed config. saved from a run on one commit and
+// checked against runs on later commits, so ci can reject a change that slows the algorithm down
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BenchmarkBaseline {
+    pub challenge_id: String,
+    pub algorithm_id: String,
+    pub difficulty: Vec<i32>,
+    pub num_workers: u32,
+    pub num_attempts: u64,
+    pub num_solutions: u64,
+    pub elapsed_ms: u64,
+}
+
+impl BenchmarkBaseline {
+    pub fn from_cell(settings: &BenchmarkSettings, num_workers: u32, cell: &SurfaceCell) -> Self {
+        Self {
+            challenge_id: settings.challenge_id.clone(),
+            algorithm_id: settings.algorithm_id.clone(),
+            difficulty: cell.difficulty.clone(),
+            num_workers,
+            num_attempts: cell.num_attempts,
+            num_solutions: cell.num_solutions,
+            elapsed_ms: cell.elapsed_ms,
+        }
+    }
+
+    pub fn nonces_per_second(&self) -> f64 {
+        if self.elapsed_ms == 0 {
+            0.0
+        } else {
+            self.num_attempts as f64 * 1000.0 / self.elapsed_ms as f64
+        }
+    }
+
+    pub fn solve_rate(&self) -> f64 {
+        if self.num_attempts == 0 {
+            0.0
+        } else {
+            self.num_solutions as f64 / self.num_attempts as f64
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, jsonify(self)).map_err(|e| format!("Failed to write baseline: {}", e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let baseline =
+            fs::read_to_string(path).map_err(|e| format!("Failed to read baseline: {}", e))?;
+        dejsonify(&baseline).map_err(|e| format!("Failed to parse baseline: {}", e))
+    }
+}
+
+// fails if current's throughput is more than tolerance, e.g. 0.1 for 10%, below the baseline's.
+// runs are only comparable at the same config
+pub fn check_regression(
+    current: &BenchmarkBaseline,
+    baseline: &BenchmarkBaseline,
+    tolerance: f64,
+) -> Result<()> {
+    if (
+        &current.challenge_id,
+        &current.algorithm_id,
+        &current.difficulty,
+        current.num_workers,
+    ) != (
+        &baseline.challenge_id,
+        &baseline.algorithm_id,
+        &baseline.difficulty,
+        baseline.num_workers,
+    ) {
+        return Err(format!(
+            "Run of {} at difficulty {:?} on {} workers is not comparable to baseline of {} at \
+             difficulty {:?} on {} workers",
+            current.algorithm_id,
+            current.difficulty,
+            current.num_workers,
+            baseline.algorithm_id,
+            baseline.difficulty,
+            baseline.num_workers
+        ));
+    }
+    let min_nonces_per_second = baseline.nonces_per_second() * (1.0 - tolerance);
+    if current.nonces_per_second() < min_nonces_per_second {
+        return Err(format!(
+            "Throughput regressed: {:.2} nonces/s is below {:.2} nonces/s, the baseline's {:.2} \
+             nonces/s less {}%",
+            current.nonces_per_second(),
+            min_nonces_per_second,
+            baseline.nonces_per_second(),
+            tolerance * 100.0
+        ));
+    }
+    Ok(())
+}
+
+// runs the job for ms and records its throughput and solve rate
+pub async fn execute(job: &Job, wasm: &Vec<u8>, num_workers: u32, ms: u32) -> BenchmarkBaseline {
+    let cell = execute_cell(job, wasm, num_workers, ms, None).await;
+    BenchmarkBaseline::from_cell(&job.settings, num_workers, &cell)
+}
@@ -0,0 +1,76 @@
+use serde::Serialize;
+use std::fs;
+
+const ALGORITHMS: [(&str, bool); 11] = [
+    ("c001_a001", cfg!(feature = "c001_a001")),
+    ("c001_a005", cfg!(feature = "c001_a005")),
+    ("c001_a011", cfg!(feature = "c001_a011")),
+    ("c001_a012", cfg!(feature = "c001_a012")),
+    ("c001_a018", cfg!(feature = "c001_a018")),
+    ("c001_a023", cfg!(feature = "c001_a023")),
+    ("c002_a001", cfg!(feature = "c002_a001")),
+    ("c003_a001", cfg!(feature = "c003_a001")),
+    ("c003_a007", cfg!(feature = "c003_a007")),
+    ("c003_a019", cfg!(feature = "c003_a019")),
+    ("c004_a014", cfg!(feature = "c004_a014")),
+];
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Capabilities {
+    pub enabled_algorithms: Vec<String>,
+    pub backends: Vec<String>,
+    pub cpu_features: Vec<String>,
+    pub num_cores: usize,
+    pub total_memory_bytes: Option<u64>,
+}
+
+pub fn capabilities() -> Capabilities {
+    let mut backends = vec!["cpu".to_string()];
+    #[cfg(feature = "cuda")]
+    {
+        let num_devices = cudarc::driver::CudaDevice::count().unwrap_or(0);
+        backends.extend((0..num_devices).map(|i| format!("cuda:{}", i)));
+    }
+    Capabilities {
+        enabled_algorithms: ALGORITHMS
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(id, _)| id.to_string())
+            .collect(),
+        backends,
+        cpu_features: cpu_features(),
+        num_cores: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        total_memory_bytes: total_memory_bytes(),
+    }
+}
+
+fn cpu_features() -> Vec<String> {
+    #[allow(unused_mut)]
+    let mut features = Vec::new();
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("sse4.2") {
+            features.push("sse4.2".to_string());
+        }
+        if is_x86_feature_detected!("avx2") {
+            features.push("avx2".to_string());
+        }
+        if is_x86_feature_detected!("fma") {
+            features.push("fma".to_string());
+        }
+        if is_x86_feature_detected!("avx512f") {
+            features.push("avx512f".to_string());
+        }
+    }
+    features
+}
+
+fn total_memory_bytes() -> Option<u64> {
+    // only available on linux. reports None elsewhere
+    let meminfo = fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo.lines().find(|l| l.starts_with("MemTotal:"))?;
+    let kb = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(kb * 1024)
+}
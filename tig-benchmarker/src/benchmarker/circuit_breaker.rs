@@ -0,0 +1,67 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+const DEFAULT_MAX_ERROR_RATE: f64 = 0.99;
+const DEFAULT_MIN_SAMPLES: u64 = 500;
+
+// shared by all workers of a run. trips once more than max_error_rate of at least min_samples
+// nonces errored, e.g. because the wasm is broken, so the run can abort instead of burning through
+// nonces
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    max_error_rate: f64,
+    min_samples: u64,
+    num_samples: AtomicU64,
+    num_errors: AtomicU64,
+    tripped: AtomicBool,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_ERROR_RATE, DEFAULT_MIN_SAMPLES)
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(max_error_rate: f64, min_samples: u64) -> Self {
+        Self {
+            max_error_rate,
+            min_samples,
+            num_samples: AtomicU64::new(0),
+            num_errors: AtomicU64::new(0),
+            tripped: AtomicBool::new(false),
+        }
+    }
+
+    // returns whether the breaker has tripped
+    pub fn record(&self, is_error: bool) -> bool {
+        let num_samples = self.num_samples.fetch_add(1, Ordering::Relaxed) + 1;
+        let num_errors = if is_error {
+            self.num_errors.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.num_errors.load(Ordering::Relaxed)
+        };
+        if num_samples >= self.min_samples
+            && num_errors as f64 / num_samples as f64 > self.max_error_rate
+        {
+            self.tripped.store(true, Ordering::Relaxed);
+        }
+        self.is_tripped()
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        self.tripped.load(Ordering::Relaxed)
+    }
+
+    pub fn num_errors(&self) -> u64 {
+        self.num_errors.load(Ordering::Relaxed)
+    }
+
+    pub fn error(&self) -> String {
+        format!(
+            "Circuit breaker tripped: {} of {} nonces errored (max error rate {})",
+            self.num_errors.load(Ordering::Relaxed),
+            self.num_samples.load(Ordering::Relaxed),
+            self.max_error_rate
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use serde::Serialize;
+use std::ops::Range;
+
+// which part of an intended nonce range was attempted, e.g. by all shards of a distributed run
+// combined. gaps are the ranges no shard attempted
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CoverageReport {
+    pub range: Range<u64>,
+    // disjoint and sorted ascending, as are gaps
+    pub covered: Vec<Range<u64>>,
+    pub gaps: Vec<Range<u64>>,
+    // attempted nonces outside of range, e.g. from a shard configured with the wrong offset
+    pub num_outside: u64,
+}
+
+impl CoverageReport {
+    pub fn is_complete(&self) -> bool {
+        self.gaps.is_empty()
+    }
+
+    pub fn num_covered(&self) -> u64 {
+        self.covered.iter().map(|x| x.end - x.start).sum()
+    }
+}
+
+// attempted nonces may come in any order and contain duplicates, e.g. where shards overlap
+pub fn coverage(range: Range<u64>, attempted: impl IntoIterator<Item = u64>) -> CoverageReport {
+    let mut nonces: Vec<u64> = attempted.into_iter().collect();
+    nonces.sort_unstable();
+    nonces.dedup();
+    let mut num_outside = 0;
+    let mut covered: Vec<Range<u64>> = Vec::new();
+    for nonce in nonces {
+        if !range.contains(&nonce) {
+            num_outside += 1;
+            continue;
+        }
+        match covered.last_mut() {
+            Some(last) if last.end == nonce => last.end += 1,
+            _ => covered.push(nonce..nonce + 1),
+        }
+    }
+    let mut gaps = Vec::new();
+    let mut start = range.start;
+    for interval in covered.iter() {
+        if interval.start > start {
+            gaps.push(start..interval.start);
+        }
+        start = interval.end;
+    }
+    if start < range.end {
+        gaps.push(start..range.end);
+    }
+    CoverageReport {
+        range,
+        covered,
+        gaps,
+        num_outside,
+    }
+}
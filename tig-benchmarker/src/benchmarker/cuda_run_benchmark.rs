@@ -1,17 +1,60 @@
-use super::{Job, NonceIterator};
+use super::{
+    accept_if::AcceptIf,
+    audit_log::{record_audit, AuditLog},
+    circuit_breaker::CircuitBreaker,
+    clock::{system_clock, Clock},
+    duty_cycle::DutyCycle,
+    failure_sink::{Failure, FailureSink},
+    instance_dedup::InstanceDedup,
+    instance_pipeline::{GenerationMode, InstanceSource},
+    live_instances::LiveInstanceLimit,
+    marginal_rate::MarginalRateFloor,
+    max_run_duration::MaxRunDuration,
+    run_warnings::{RunWarning, RunWarnings},
+    schedule_log::ScheduleRecorder,
+    solve_outcome::OutcomeCounts,
+    solve_rate_target::SolveRateTarget,
+    staggered_start::StaggeredStart,
+    stats::StatsAccumulator,
+    timing_trace::{TimingTrace, TraceOutcome},
+    Job, NonceIterator,
+};
 use crate::future_utils;
+use anyhow::anyhow;
 use cudarc::driver::*;
 use cudarc::nvrtc::{compile_ptx, Ptx};
-use future_utils::{spawn, time, yield_now, Mutex};
+use future_utils::{sleep, spawn, yield_now, Mutex};
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use tig_algorithms::{c001, c002, c003, c004, CudaKernel};
-use tig_challenges::ChallengeTrait;
-use tig_worker::{compute_solution, verify_solution, SolutionData};
+use tig_challenges::{ChallengeTrait, SolveOutcome};
+use tig_worker::{
+    calc_version_pin, compute_outcome_for_instance, generate_serialized_instance_with_options,
+    generate_vector_search_instance, generate_vehicle_routing_instance, instance_fingerprint,
+    repair_solution, search_space_log2, trivial_solution, verify_solution_with_options,
+    verify_vector_search_solution, BenchmarkSettings, Solution, SolutionData,
+};
 
 static PTX_CACHE: OnceCell<Mutex<HashMap<String, Ptx>>> = OnceCell::new();
 
+// solvers are moved across spawned tasks. fails to compile if a challenge's types or the solver
+// signature stop being Send + Sync, so a new algorithm can't introduce a data race through them
+const fn _assert_send_sync<C: Send + Sync, S: Send + Sync>()
+where
+    tig_challenges::SolveChallengeFn<C, S>: Send + Sync,
+{
+}
+const _: () = {
+    _assert_send_sync::<tig_challenges::c001::Challenge, tig_challenges::c001::Solution>();
+    _assert_send_sync::<tig_challenges::c002::Challenge, tig_challenges::c002::Solution>();
+    _assert_send_sync::<tig_challenges::c003::Challenge, tig_challenges::c003::Solution>();
+    _assert_send_sync::<tig_challenges::c004::Challenge, tig_challenges::c004::Solution>();
+};
+
 pub async fn get_or_compile_cuda(
     key: &String,
     kernel: &Option<CudaKernel>,
@@ -55,36 +98,147 @@ pub async fn get_or_compile_cuda(
         .collect()
 }
 
+// the optional parts of a run. the default pulls instances on each worker and records nothing
+// beyond the solutions
+#[derive(Default)]
+pub struct ExecuteOptions {
+    pub generation_mode: GenerationMode,
+    // caps the instances alive across all workers, queued or being solved
+    pub live_instance_limit: Option<Arc<LiveInstanceLimit>>,
+    pub solve_rate_target: Option<Arc<SolveRateTarget>>,
+    pub fallback_solutions: Option<Arc<Mutex<Vec<(u64, Solution)>>>>,
+    pub outcome_counts: Option<Arc<OutcomeCounts>>,
+    pub staggered_start: Option<StaggeredStart>,
+    pub timing_trace: Option<Arc<TimingTrace>>,
+    pub stats: Option<Arc<Mutex<StatsAccumulator>>>,
+    pub duty_cycle: Option<Arc<DutyCycle>>,
+    pub accept_if: Option<Arc<AcceptIf>>,
+    pub instance_dedup: Option<Arc<InstanceDedup>>,
+    pub failure_sink: Option<Arc<FailureSink>>,
+    pub schedule_recorder: Option<Arc<ScheduleRecorder>>,
+    pub run_warnings: Option<Arc<RunWarnings>>,
+    pub marginal_rate_floor: Option<Arc<MarginalRateFloor>>,
+    pub max_run_duration: Option<MaxRunDuration>,
+    // None is the system clock
+    pub clock: Option<Arc<dyn Clock>>,
+    pub audit_log: Option<Arc<AuditLog>>,
+}
+
+// each nonce_iter's instances are generated as options.generation_mode says, e.g. by a producer
+// task ahead of its worker
 pub async fn execute(
     nonce_iters: Vec<Arc<Mutex<NonceIterator>>>,
     job: &Job,
     wasm: &Vec<u8>,
     solutions_data: Arc<Mutex<Vec<SolutionData>>>,
-    solutions_count: Arc<Mutex<u32>>,
+    solutions_count: Arc<AtomicU64>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    options: ExecuteOptions,
 ) {
-    for nonce_iter in nonce_iters {
+    let ExecuteOptions {
+        generation_mode,
+        live_instance_limit,
+        solve_rate_target,
+        fallback_solutions,
+        outcome_counts,
+        staggered_start,
+        timing_trace,
+        stats,
+        duty_cycle,
+        accept_if,
+        instance_dedup,
+        failure_sink,
+        schedule_recorder,
+        run_warnings,
+        marginal_rate_floor,
+        max_run_duration,
+        clock,
+        audit_log,
+    } = options;
+    let clock = clock.unwrap_or_else(system_clock);
+    let version_pin = calc_version_pin(wasm);
+    let start_delays = staggered_start.map(|x| x.delays(nonce_iters.len()));
+    for (i, nonce_iter) in nonce_iters.into_iter().enumerate() {
+        let source = {
+            let settings = job.settings.clone();
+            let instance_options = job.instance_options.clone();
+            let clock = clock.clone();
+            InstanceSource::with_limit(
+                generation_mode,
+                nonce_iter.clone(),
+                live_instance_limit.clone(),
+                // timed where it's generated, as that's off the worker when pipelined
+                move |nonce| {
+                    let start = clock.now();
+                    let serialized_challenge = generate_serialized_instance_with_options(
+                        &settings,
+                        nonce,
+                        &instance_options,
+                    );
+                    (serialized_challenge, clock.now() - start)
+                },
+            )
+        };
         let job = job.clone();
         let wasm = wasm.clone();
+        let version_pin = version_pin.clone();
+        let circuit_breaker = circuit_breaker.clone();
+        let solve_rate_target = solve_rate_target.clone();
+        let fallback_solutions = fallback_solutions.clone();
+        let outcome_counts = outcome_counts.clone();
+        let timing_trace = timing_trace.clone();
+        let stats = stats.clone();
+        let duty_cycle = duty_cycle.clone();
+        let accept_if = accept_if.clone();
+        let instance_dedup = instance_dedup.clone();
+        let failure_sink = failure_sink.clone();
+        let audit_log = audit_log.clone();
+        let schedule_recorder = schedule_recorder.clone();
+        let run_warnings = run_warnings.clone();
+        let marginal_rate_floor = marginal_rate_floor.clone();
+        let max_run_duration = max_run_duration.clone();
+        let clock = clock.clone();
         let solutions_data = solutions_data.clone();
         let solutions_count = solutions_count.clone();
+        let start_delay = start_delays.as_ref().map_or(0, |x| x[i]);
         spawn(async move {
-            let mut last_yield = time();
+            if start_delay > 0 {
+                sleep(start_delay).await;
+            }
+            let mut last_yield = clock.now();
             let dev = CudaDevice::new(0).expect("Failed to create CudaDevice");
             let mut challenge_cuda_funcs: Option<HashMap<&'static str, CudaFunction>> = None;
             let mut algorithm_cuda_funcs: Option<HashMap<&'static str, CudaFunction>> = None;
+            let mut work_start = None;
             loop {
-                match {
-                    let mut nonce_iter = (*nonce_iter).lock().await;
-                    (*nonce_iter).next()
-                } {
+                // a run over its max duration takes no more nonces
+                if max_run_duration.as_ref().is_some_and(|x| x.is_exceeded()) {
+                    (*nonce_iter).lock().await.empty();
+                }
+                match source.next_live().await {
                     None => break,
-                    Some(nonce) => {
-                        let now = time();
+                    // _permit keeps the instance counted as live until the nonce is done
+                    Some((nonce, (serialized_challenge, generation_ms), _permit)) => {
+                        if let Some(schedule_recorder) = schedule_recorder.as_ref() {
+                            schedule_recorder.dispatched(i, nonce);
+                        }
+                        // rests for the previous nonce
+                        if let (Some(duty_cycle), Some(work_start)) =
+                            (duty_cycle.as_ref(), work_start)
+                        {
+                            let rest_ms = duty_cycle.rest_ms(clock.now() - work_start);
+                            if rest_ms > 0 {
+                                sleep(rest_ms as u32).await;
+                            }
+                        }
+                        work_start = Some(clock.now());
+                        let now = clock.now();
                         if now - last_yield > 25 {
                             yield_now().await;
                             last_yield = now;
                         }
-                        let seeds = job.settings.calc_seeds(nonce);
+                        let start = clock.now();
+                        let seeds = job.instance_options.calc_seeds(&job.settings, nonce);
                         let skip = match job.settings.challenge_id.as_str() {
                             "c001" => {
                                 type CudaSolveChallengeFn =
@@ -3138,7 +3292,13 @@ pub async fn execute(
                                             _ => true,
                                         }
                                     }
-                                    None => false,
+                                    None => {
+                                        record_backend_fallback(
+                                            &run_warnings,
+                                            &job.settings.algorithm_id,
+                                        );
+                                        false
+                                    }
                                 }
                             }
                             "c002" => {
@@ -6174,14 +6334,21 @@ pub async fn execute(
                                                 .await,
                                             )
                                             .clone();
-                                        let challenge =
-                                            tig_challenges::c002::Challenge::cuda_generate_instance_from_vec(
+                                        let challenge = match job.instance_options.dataset {
+                                            // dataset instances are only generated on the cpu
+                                            Some(_) => generate_vehicle_routing_instance(
+                                                &job.settings,
+                                                nonce,
+                                                &job.instance_options,
+                                            ),
+                                            None => tig_challenges::c002::Challenge::cuda_generate_instance_from_vec(
                                                 seeds,
                                                 &job.settings.difficulty,
                                                 &dev,
                                                 challenge_cuda_funcs,
-                                            )
-                                            .unwrap();
+                                            ),
+                                        }
+                                        .unwrap();
                                         match cuda_solve_challenge(
                                             &challenge,
                                             &dev,
@@ -6193,7 +6360,13 @@ pub async fn execute(
                                             _ => true,
                                         }
                                     }
-                                    None => false,
+                                    None => {
+                                        record_backend_fallback(
+                                            &run_warnings,
+                                            &job.settings.algorithm_id,
+                                        );
+                                        false
+                                    }
                                 }
                             }
                             "c003" => {
@@ -9248,7 +9421,13 @@ pub async fn execute(
                                             _ => true,
                                         }
                                     }
-                                    None => false,
+                                    None => {
+                                        record_backend_fallback(
+                                            &run_warnings,
+                                            &job.settings.algorithm_id,
+                                        );
+                                        false
+                                    }
                                 }
                             }
                             "c004" => {
@@ -12284,47 +12463,187 @@ pub async fn execute(
                                                 .await,
                                             )
                                             .clone();
-                                        let challenge =
-                                            tig_challenges::c004::Challenge::cuda_generate_instance_from_vec(
+                                        let challenge = match (
+                                            &job.instance_options.quantization_levels,
+                                            &job.instance_options.dataset,
+                                        ) {
+                                            (None, None) => tig_challenges::c004::Challenge::cuda_generate_instance_from_vec(
                                                 seeds,
                                                 &job.settings.difficulty,
                                                 &dev,
                                                 challenge_cuda_funcs,
-                                            )
-                                            .unwrap();
+                                            ),
+                                            // quantized and dataset instances are only generated on
+                                            // the cpu
+                                            _ => generate_vector_search_instance(
+                                                &job.settings,
+                                                nonce,
+                                                &job.instance_options,
+                                            ),
+                                        }
+                                        .unwrap();
                                         match cuda_solve_challenge(
                                             &challenge,
                                             &dev,
                                             algorithm_cuda_funcs,
                                         ) {
-                                            Ok(Some(solution)) => {
-                                                challenge.verify_solution(&solution).is_err()
-                                            }
+                                            Ok(Some(solution)) => verify_vector_search_solution(
+                                                &challenge,
+                                                &solution,
+                                                &job.instance_options,
+                                            )
+                                            .is_err(),
                                             _ => true,
                                         }
                                     }
-                                    None => false,
+                                    None => {
+                                        record_backend_fallback(
+                                            &run_warnings,
+                                            &job.settings.algorithm_id,
+                                        );
+                                        false
+                                    }
                                 }
                             }
                             _ => panic!("Unknown challenge id: {}", job.settings.challenge_id),
                         };
                         if skip {
+                            // native solvers return an Option, so can't prove anything
+                            if let Some(outcome_counts) = outcome_counts.as_ref() {
+                                outcome_counts.record(&SolveOutcome::<()>::GaveUp);
+                            }
+                            // the native check generates and solves in one go
+                            record_trace(
+                                &timing_trace,
+                                &schedule_recorder,
+                                i,
+                                nonce,
+                                0,
+                                clock.now() - start,
+                                TraceOutcome::GaveUp,
+                            );
+                            record_failure(&failure_sink, nonce, TraceOutcome::GaveUp, None);
+                            record_fallback(&fallback_solutions, &job, nonce, None).await;
+                            if should_stop(&solve_rate_target, &marginal_rate_floor, false) {
+                                (*nonce_iter).lock().await.empty();
+                                break;
+                            }
                             continue;
                         }
-                        if let Ok(Some(solution_data)) = compute_solution(
-                            &job.settings,
-                            nonce,
-                            wasm.as_slice(),
-                            job.wasm_vm_config.max_memory,
-                            job.wasm_vm_config.max_fuel,
-                        ) {
-                            if verify_solution(&job.settings, nonce, &solution_data.solution)
-                                .is_ok()
+                        let start = clock.now();
+                        // a repeat of an instance already attempted is skipped, not solved again
+                        if let (Some(instance_dedup), Ok(instance)) =
+                            (instance_dedup.as_ref(), serialized_challenge.as_ref())
+                        {
+                            if instance_fingerprint(&job.settings.challenge_id, instance)
+                                .is_ok_and(|fingerprint| instance_dedup.is_repeat(fingerprint))
                             {
-                                {
-                                    let mut solutions_count = (*solutions_count).lock().await;
-                                    *solutions_count += 1;
-                                }
+                                record_trace(
+                                    &timing_trace,
+                                    &schedule_recorder,
+                                    i,
+                                    nonce,
+                                    generation_ms,
+                                    0,
+                                    TraceOutcome::Duplicate,
+                                );
+                                record_failure(
+                                    &failure_sink,
+                                    nonce,
+                                    TraceOutcome::Duplicate,
+                                    None,
+                                );
+                                continue;
+                            }
+                        }
+                        let result = match serialized_challenge.as_ref() {
+                            Ok(serialized_challenge) => compute_outcome_for_instance(
+                                nonce,
+                                serialized_challenge,
+                                wasm.as_slice(),
+                                job.wasm_vm_config.max_memory,
+                                job.wasm_vm_config.max_fuel,
+                            ),
+                            Err(e) => Err(anyhow!("{}", e)),
+                        };
+                        let solve_ms = clock.now() - start;
+                        let mut trace_outcome = match &result {
+                            // until verified
+                            Ok(SolveOutcome::Solved(_)) => TraceOutcome::Invalid,
+                            Ok(SolveOutcome::ProvedNoSolution) => TraceOutcome::ProvedNoSolution,
+                            Ok(SolveOutcome::GaveUp) => TraceOutcome::GaveUp,
+                            Err(_) => TraceOutcome::Error,
+                        };
+                        let mut error = result.as_ref().err().map(|e| e.to_string());
+                        if circuit_breaker.record(result.is_err()) {
+                            record_trace(
+                                &timing_trace,
+                                &schedule_recorder,
+                                i,
+                                nonce,
+                                generation_ms,
+                                solve_ms,
+                                trace_outcome,
+                            );
+                            record_failure(&failure_sink, nonce, trace_outcome, error);
+                            (*nonce_iter).lock().await.empty();
+                            break;
+                        }
+                        if let (Some(outcome_counts), Ok(outcome)) =
+                            (outcome_counts.as_ref(), result.as_ref())
+                        {
+                            outcome_counts.record(outcome);
+                            // the solve can't be interrupted, so a late solution is still kept
+                            if job.timeout_ms.is_some_and(|x| solve_ms > x) {
+                                outcome_counts.record_timed_out();
+                            }
+                        }
+                        let mut solved = false;
+                        let mut invalid_solution = None;
+                        if let Ok(SolveOutcome::Solved(mut solution_data)) = result {
+                            let verification = verify_solution_with_options(
+                                &job.settings,
+                                nonce,
+                                &solution_data.solution,
+                                &job.instance_options,
+                            );
+                            let verified = verification.is_ok();
+                            error = verification.err().map(|e| e.to_string());
+                            if !verified {
+                                invalid_solution = Some(solution_data.solution.clone());
+                            }
+                            // a verified solution only counts if it also clears the accept_if bar
+                            let accepted = verified
+                                && accept_if.as_ref().is_none_or(|accept_if| {
+                                    serialized_challenge.as_ref().is_ok_and(|instance| {
+                                        accept_if.accepts(
+                                            &job.settings.challenge_id,
+                                            instance,
+                                            &solution_data.solution,
+                                        )
+                                    })
+                                });
+                            if verified && !accepted {
+                                trace_outcome = TraceOutcome::Rejected;
+                            }
+                            if accepted {
+                                solved = true;
+                                trace_outcome = TraceOutcome::Solved;
+                                solutions_count.fetch_add(1, Ordering::Relaxed);
+                                record_audit(
+                                    &audit_log,
+                                    &job,
+                                    nonce,
+                                    &solution_data.solution,
+                                    clock.now(),
+                                );
+                                record_solved_difficulty(
+                                    &stats,
+                                    &job.settings,
+                                    serialized_challenge.as_ref().ok(),
+                                )
+                                .await;
+                                job.metadata_config.apply(&mut solution_data, &version_pin);
                                 if solution_data.calc_solution_signature()
                                     <= job.solution_signature_threshold
                                 {
@@ -12333,9 +12652,131 @@ pub async fn execute(
                                 }
                             }
                         }
+                        record_trace(
+                            &timing_trace,
+                            &schedule_recorder,
+                            i,
+                            nonce,
+                            generation_ms,
+                            solve_ms,
+                            trace_outcome,
+                        );
+                        if !solved {
+                            record_failure(&failure_sink, nonce, trace_outcome, error);
+                            record_fallback(
+                                &fallback_solutions,
+                                &job,
+                                nonce,
+                                invalid_solution.as_ref(),
+                            )
+                            .await;
+                        }
+                        if should_stop(&solve_rate_target, &marginal_rate_floor, solved) {
+                            (*nonce_iter).lock().await.empty();
+                            break;
+                        }
                     }
                 }
             }
         });
     }
 }
+
+// records the attempt with each stop condition of the run, returning whether any is met
+fn should_stop(
+    solve_rate_target: &Option<Arc<SolveRateTarget>>,
+    marginal_rate_floor: &Option<Arc<MarginalRateFloor>>,
+    solved: bool,
+) -> bool {
+    let target_reached = solve_rate_target.as_ref().is_some_and(|t| t.record(solved));
+    let below_floor = marginal_rate_floor.as_ref().is_some_and(|f| f.record(solved));
+    target_reached || below_floor
+}
+
+// every nonce a worker takes ends here, whatever its outcome
+fn record_trace(
+    timing_trace: &Option<Arc<TimingTrace>>,
+    schedule_recorder: &Option<Arc<ScheduleRecorder>>,
+    worker: usize,
+    nonce: u64,
+    generation_ms: u64,
+    solve_ms: u64,
+    outcome: TraceOutcome,
+) {
+    if let Some(schedule_recorder) = schedule_recorder {
+        schedule_recorder.completed(worker, nonce, outcome);
+    }
+    if let Some(timing_trace) = timing_trace {
+        // a failing trace shouldn't stop the run
+        if let Err(e) = timing_trace.record(nonce, generation_ms, solve_ms, outcome) {
+            println!("{}", e);
+        }
+    }
+}
+
+fn record_failure(
+    failure_sink: &Option<Arc<FailureSink>>,
+    nonce: u64,
+    outcome: TraceOutcome,
+    error: Option<String>,
+) {
+    if let Some(failure_sink) = failure_sink {
+        // a failing sink shouldn't stop the run
+        if let Err(e) = failure_sink.record(&Failure {
+            nonce,
+            outcome,
+            error,
+        }) {
+            println!("{}", e);
+        }
+    }
+}
+
+// the algorithm's nonces skip the cuda check and only run on wasm
+fn record_backend_fallback(run_warnings: &Option<Arc<RunWarnings>>, algorithm_id: &String) {
+    if let Some(run_warnings) = run_warnings {
+        run_warnings.record(RunWarning::BackendFallback {
+            algorithm_id: algorithm_id.clone(),
+            requested: "cuda".to_string(),
+            used: "wasm".to_string(),
+        });
+    }
+}
+
+// tracks the hardest instance solved during the run, normalized to compare across challenges
+async fn record_solved_difficulty(
+    stats: &Option<Arc<Mutex<StatsAccumulator>>>,
+    settings: &BenchmarkSettings,
+    serialized_challenge: Option<&Vec<u8>>,
+) {
+    if let (Some(stats), Some(serialized_challenge)) = (stats, serialized_challenge) {
+        if let Ok(difficulty) = search_space_log2(&settings.challenge_id, serialized_challenge) {
+            (*stats).lock().await.record_solved_difficulty(difficulty);
+        }
+    }
+}
+
+// records the repair of the solver's invalid solution, or else the challenge's trivial solution,
+// for a nonce the solver found nothing for. kept apart from solutions_data as the protocol only
+// accepts solutions it can reproduce with the algorithm's wasm
+async fn record_fallback(
+    fallback_solutions: &Option<Arc<Mutex<Vec<(u64, Solution)>>>>,
+    job: &Job,
+    nonce: u64,
+    invalid_solution: Option<&Solution>,
+) {
+    if let Some(fallback_solutions) = fallback_solutions {
+        let repaired = invalid_solution.and_then(|solution| {
+            repair_solution(&job.settings, nonce, solution, &job.instance_options)
+                .ok()
+                .flatten()
+        });
+        if let Some(solution) = repaired.or_else(|| {
+            trivial_solution(&job.settings, nonce, &job.instance_options)
+                .ok()
+                .flatten()
+        }) {
+            (*fallback_solutions).lock().await.push((nonce, solution));
+        }
+    }
+}
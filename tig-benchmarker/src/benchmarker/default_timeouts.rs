@@ -0,0 +1,59 @@
+use super::Job;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// per-nonce solve timeout of a job that sets none, by challenge id. challenges differ by orders of
+// magnitude, e.g. checking a small formula against routing hundreds of nodes, so one global
+// default is either too tight for some or too loose for others. the defaults are generous: fuel
+// still bounds a solve, the timeout only flags solves that are slow for other reasons
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DefaultTimeouts {
+    pub per_challenge: HashMap<String, u64>,
+    // for challenges missing from per_challenge
+    pub fallback_ms: u64,
+}
+
+impl Default for DefaultTimeouts {
+    fn default() -> Self {
+        Self {
+            per_challenge: HashMap::from([
+                // satisfiability
+                ("c001".to_string(), 10_000),
+                // vehicle_routing
+                ("c002".to_string(), 60_000),
+                // knapsack
+                ("c003".to_string(), 20_000),
+                // vector_search
+                ("c004".to_string(), 30_000),
+            ]),
+            fallback_ms: 60_000,
+        }
+    }
+}
+
+impl DefaultTimeouts {
+    pub fn set(&mut self, challenge_id: String, timeout_ms: u64) {
+        self.per_challenge.insert(challenge_id, timeout_ms);
+    }
+
+    pub fn default_ms(&self, challenge_id: &str) -> u64 {
+        self.per_challenge
+            .get(challenge_id)
+            .copied()
+            .unwrap_or(self.fallback_ms)
+    }
+
+    // the job's own timeout if it sets one, otherwise its challenge's default
+    pub fn timeout_ms(&self, job: &Job) -> u64 {
+        job.timeout_ms
+            .unwrap_or_else(|| self.default_ms(&job.settings.challenge_id))
+    }
+
+    // a recompute job must reproduce every sampled nonce as it was first solved, so it keeps the
+    // timeout it came with
+    pub fn apply(&self, job: &mut Job) {
+        if job.sampled_nonces.is_none() {
+            job.timeout_ms = Some(self.timeout_ms(job));
+        }
+    }
+}
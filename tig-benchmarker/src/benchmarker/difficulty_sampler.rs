@@ -95,7 +95,7 @@ impl DifficultySampler {
         self.update_distributions();
     }
 
-    pub fn update_with_solutions(&mut self, difficulty: &Vec<i32>, num_solutions: u32) {
+    pub fn update_with_solutions(&mut self, difficulty: &Vec<i32>, num_solutions: u64) {
         let (x, y) = (
             (difficulty[0] - self.min_difficulty[0]) as usize,
             (difficulty[1] - self.min_difficulty[1]) as usize,
@@ -0,0 +1,69 @@
+use super::{
+    difficulty_surface::{execute_cell, SurfaceCell},
+    Job,
+};
+
+#[derive(Debug, Clone)]
+pub struct ScheduleContext<'a> {
+    pub phase: u32,
+    pub previous: Option<&'a SurfaceCell>,
+}
+
+pub trait DifficultySchedule {
+    fn next_difficulty(&mut self, ctx: &ScheduleContext) -> Vec<i32>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ConstantSchedule {
+    pub difficulty: Vec<i32>,
+}
+
+impl DifficultySchedule for ConstantSchedule {
+    fn next_difficulty(&mut self, _ctx: &ScheduleContext) -> Vec<i32> {
+        self.difficulty.clone()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LinearRampSchedule {
+    pub start: Vec<i32>,
+    pub step: Vec<i32>,
+    pub end: Vec<i32>,
+}
+
+impl DifficultySchedule for LinearRampSchedule {
+    fn next_difficulty(&mut self, ctx: &ScheduleContext) -> Vec<i32> {
+        (0..self.start.len())
+            .map(|i| {
+                let d = self.start[i] + self.step[i] * ctx.phase as i32;
+                if self.step[i] >= 0 {
+                    d.min(self.end[i])
+                } else {
+                    d.max(self.end[i])
+                }
+            })
+            .collect()
+    }
+}
+
+// runs num_phases phases, consulting the schedule for the difficulty before each phase
+pub async fn execute(
+    job: &Job,
+    wasm: &Vec<u8>,
+    num_workers: u32,
+    schedule: &mut impl DifficultySchedule,
+    num_phases: u32,
+    ms_per_phase: u32,
+) -> Vec<SurfaceCell> {
+    let mut phases: Vec<SurfaceCell> = Vec::new();
+    for phase in 0..num_phases {
+        let difficulty = schedule.next_difficulty(&ScheduleContext {
+            phase,
+            previous: phases.last(),
+        });
+        let mut job = job.clone();
+        job.settings.difficulty = difficulty;
+        phases.push(execute_cell(&job, wasm, num_workers, ms_per_phase, None).await);
+    }
+    phases
+}
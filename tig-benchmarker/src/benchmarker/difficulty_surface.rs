@@ -0,0 +1,155 @@
+use super::{
+    circuit_breaker::CircuitBreaker,
+    run_benchmark::{self, ExecuteOptions},
+    solve_rate_target::SolveRateTarget,
+    Job, NonceIterator,
+};
+use crate::future_utils::{sleep, time, Mutex};
+use serde::Serialize;
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use tig_worker::{calc_version_pin, SolutionData, VersionPin};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SurfaceCell {
+    pub difficulty: Vec<i32>,
+    pub num_attempts: u64,
+    pub num_solutions: u64,
+    pub elapsed_ms: u64,
+    pub version_pin: VersionPin,
+}
+
+impl SurfaceCell {
+    pub fn solve_rate(&self) -> f64 {
+        if self.num_attempts == 0 {
+            0.0
+        } else {
+            self.num_solutions as f64 / self.num_attempts as f64
+        }
+    }
+
+    pub fn ms_per_solution(&self) -> Option<f64> {
+        if self.num_solutions == 0 {
+            None
+        } else {
+            Some(self.elapsed_ms as f64 / self.num_solutions as f64)
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct DifficultySurface {
+    pub x_values: Vec<i32>,
+    pub y_values: Vec<i32>,
+    // cells[i][j] is the cell for difficulty [x_values[i], y_values[j]]
+    pub cells: Vec<Vec<SurfaceCell>>,
+}
+
+impl DifficultySurface {
+    pub fn to_csv(&self) -> String {
+        let mut csv =
+            "x,y,num_attempts,num_solutions,elapsed_ms,solve_rate,ms_per_solution\n".to_string();
+        for row in self.cells.iter() {
+            for cell in row.iter() {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    cell.difficulty[0],
+                    cell.difficulty[1],
+                    cell.num_attempts,
+                    cell.num_solutions,
+                    cell.elapsed_ms,
+                    cell.solve_rate(),
+                    cell.ms_per_solution()
+                        .map(|x| x.to_string())
+                        .unwrap_or_default()
+                ));
+            }
+        }
+        csv
+    }
+}
+
+// runs the job at every difficulty in the grid x_values * y_values for ms_per_cell each
+pub async fn execute(
+    job: &Job,
+    wasm: &Vec<u8>,
+    num_workers: u32,
+    x_values: Vec<i32>,
+    y_values: Vec<i32>,
+    ms_per_cell: u32,
+) -> DifficultySurface {
+    let mut cells = Vec::new();
+    for &x in x_values.iter() {
+        let mut row = Vec::new();
+        for &y in y_values.iter() {
+            let mut job = job.clone();
+            job.settings.difficulty = vec![x, y];
+            row.push(execute_cell(&job, wasm, num_workers, ms_per_cell, None).await);
+        }
+        cells.push(row);
+    }
+    DifficultySurface {
+        x_values,
+        y_values,
+        cells,
+    }
+}
+
+pub(super) async fn execute_cell(
+    job: &Job,
+    wasm: &Vec<u8>,
+    num_workers: u32,
+    ms_per_cell: u32,
+    solve_rate_target: Option<Arc<SolveRateTarget>>,
+) -> SurfaceCell {
+    let nonce_iters: Vec<Arc<Mutex<NonceIterator>>> = (0..num_workers)
+        .into_iter()
+        .map(|x| {
+            Arc::new(Mutex::new(NonceIterator::from_u64(
+                u64::MAX / num_workers as u64 * x as u64,
+            )))
+        })
+        .collect();
+    let solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
+    let solutions_count = Arc::new(AtomicU64::new(0));
+    let start = time();
+    run_benchmark::execute(
+        nonce_iters.iter().cloned().collect(),
+        job,
+        wasm,
+        solutions_data,
+        solutions_count.clone(),
+        Arc::new(CircuitBreaker::default()),
+        ExecuteOptions {
+            solve_rate_target: solve_rate_target.clone(),
+            ..Default::default()
+        },
+    )
+    .await;
+    match solve_rate_target {
+        // ms_per_cell is an upper bound when running to a target
+        Some(solve_rate_target) => {
+            while !solve_rate_target.is_reached() && time() - start < ms_per_cell as u64 {
+                sleep(100).await;
+            }
+        }
+        None => sleep(ms_per_cell).await,
+    }
+    let mut num_attempts = 0;
+    for nonce_iter in nonce_iters.iter() {
+        let mut nonce_iter = (*nonce_iter).lock().await;
+        nonce_iter.empty();
+        num_attempts += nonce_iter.attempts();
+    }
+    let elapsed_ms = time() - start;
+    let num_solutions = solutions_count.load(Ordering::Relaxed);
+    SurfaceCell {
+        difficulty: job.settings.difficulty.clone(),
+        num_attempts,
+        num_solutions,
+        elapsed_ms,
+        version_pin: calc_version_pin(wasm),
+    }
+}
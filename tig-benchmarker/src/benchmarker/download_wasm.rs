@@ -3,6 +3,7 @@ use crate::future_utils::Mutex;
 use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use tig_utils::get;
+use tig_worker::check_wasm;
 
 static CACHE: OnceCell<Mutex<HashMap<String, Vec<u8>>>> = OnceCell::new();
 
@@ -17,6 +18,13 @@ pub async fn execute(job: &Job) -> Result<Vec<u8>> {
         let wasm = get::<Vec<u8>>(&job.download_url, None)
             .await
             .map_err(|e| format!("Failed to download wasm from {}: {:?}", job.download_url, e))?;
+        // a corrupt download isn't cached, so the next attempt downloads it again
+        check_wasm(&wasm).map_err(|e| {
+            format!(
+                "Downloaded wasm from {} is invalid: {}",
+                job.download_url, e
+            )
+        })?;
         (*cache).insert(job.settings.algorithm_id.clone(), wasm.clone());
         Ok(wasm)
     }
@@ -0,0 +1,87 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc, Mutex,
+};
+
+// set while workers should slow down, e.g. by whatever watches the machine's temperature
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleSignal(Arc<AtomicBool>);
+
+impl ThrottleSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&self, should_throttle: bool) {
+        self.0.store(should_throttle, Ordering::Relaxed);
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Totals {
+    work_ms: u64,
+    rest_ms: u64,
+    // fractions of a millisecond not rested yet
+    owed_ms: f64,
+}
+
+// shared by all workers of a run. limits the fraction of time workers spend solving, so a sustained
+// run on e.g. a laptop doesn't heat it into thermal throttling, which would distort the results.
+// after each nonce a worker rests long enough that it works duty_cycle of the time. with a signal,
+// workers only rest while it is set
+#[derive(Debug)]
+pub struct DutyCycle {
+    duty_cycle: f64,
+    signal: Option<ThrottleSignal>,
+    totals: Mutex<Totals>,
+}
+
+impl DutyCycle {
+    // duty_cycle is clamped to [0.01, 1]
+    pub fn new(duty_cycle: f64, signal: Option<ThrottleSignal>) -> Self {
+        Self {
+            duty_cycle: duty_cycle.clamp(0.01, 1.0),
+            signal,
+            totals: Mutex::new(Totals::default()),
+        }
+    }
+
+    pub fn duty_cycle(&self) -> f64 {
+        self.duty_cycle
+    }
+
+    pub fn is_throttling(&self) -> bool {
+        self.signal.as_ref().map_or(true, |x| x.is_set())
+    }
+
+    // records work_ms spent on a nonce and returns how long to rest before the next one
+    pub fn rest_ms(&self, work_ms: u64) -> u64 {
+        let mut totals = self.totals.lock().unwrap();
+        totals.work_ms += work_ms;
+        if self.is_throttling() {
+            totals.owed_ms += work_ms as f64 * (1.0 - self.duty_cycle) / self.duty_cycle;
+        }
+        let rest_ms = totals.owed_ms.floor() as u64;
+        totals.owed_ms -= rest_ms as f64;
+        totals.rest_ms += rest_ms;
+        rest_ms
+    }
+
+    // fraction of the time workers spent working rather than resting. 1 if they never rested
+    pub fn applied(&self) -> f64 {
+        let totals = self.totals.lock().unwrap();
+        if totals.work_ms + totals.rest_ms == 0 {
+            1.0
+        } else {
+            totals.work_ms as f64 / (totals.work_ms + totals.rest_ms) as f64
+        }
+    }
+
+    pub fn reset(&self) {
+        *self.totals.lock().unwrap() = Totals::default();
+    }
+}
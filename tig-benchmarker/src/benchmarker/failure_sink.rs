@@ -0,0 +1,72 @@
+use super::{reproduction::ReproductionNonces, timing_trace::TraceOutcome, Result};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Mutex,
+};
+use tig_utils::{dejsonify, jsonify};
+
+// a nonce that didn't produce a solution. error is set when the outcome has one to explain it, e.g.
+// why the solution didn't verify
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub nonce: u64,
+    pub outcome: TraceOutcome,
+    pub error: Option<String>,
+}
+
+// opt-in record of every nonce of a run that wasn't solved, one json line each, shared by all
+// workers. buffered like TimingTrace. read it back with read_failures to replay exactly those nonces
+pub struct FailureSink {
+    writer: Mutex<BufWriter<Box<dyn Write + Send>>>,
+}
+
+impl FailureSink {
+    // appends to the file at path, so failures of several runs can be collected
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open failure sink {:?}: {}", path, e))?;
+        Ok(Self::new(Box::new(file)))
+    }
+
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            writer: Mutex::new(BufWriter::new(writer)),
+        }
+    }
+
+    pub fn record(&self, failure: &Failure) -> Result<()> {
+        writeln!(self.writer.lock().unwrap(), "{}", jsonify(failure))
+            .map_err(|e| format!("Failed to write failure sink: {}", e))
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.writer
+            .lock()
+            .unwrap()
+            .flush()
+            .map_err(|e| format!("Failed to flush failure sink: {}", e))
+    }
+}
+
+pub fn read_failures(path: &Path) -> Result<Vec<Failure>> {
+    fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read failure sink {:?}: {}", path, e))?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| dejsonify(line).map_err(|e| format!("Invalid failure {:?}: {}", line, e)))
+        .collect()
+}
+
+// the failed nonces in ascending order, for a ReproductionLog that retries exactly those
+pub fn failed_nonces(failures: &[Failure]) -> ReproductionNonces {
+    let mut nonces: Vec<u64> = failures.iter().map(|failure| failure.nonce).collect();
+    nonces.sort_unstable();
+    nonces.dedup();
+    ReproductionNonces::List(nonces)
+}
@@ -0,0 +1,90 @@
+use crate::future_utils::time;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+// handed to a solve when it starts. solves poll is_canceled and give up on their nonce once set
+#[derive(Debug, Clone)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(AtomicBool::new(false)))
+    }
+
+    pub(crate) fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_canceled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+struct InFlightSolve {
+    start: u64,
+    seq: u64,
+    canceled: Arc<AtomicBool>,
+}
+
+// shared by all workers of a run. tracks the nonces currently being solved so a controller can
+// abandon the longest running ones that drag out the tail of a run
+#[derive(Default)]
+pub struct InFlightTracker {
+    // seq breaks ties between solves that started in the same millisecond
+    solves: Mutex<(u64, HashMap<u64, InFlightSolve>)>,
+}
+
+impl InFlightTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&self, nonce: u64) -> CancelToken {
+        let canceled = Arc::new(AtomicBool::new(false));
+        let mut solves = self.solves.lock().unwrap();
+        let seq = solves.0;
+        solves.0 += 1;
+        solves.1.insert(
+            nonce,
+            InFlightSolve {
+                start: time(),
+                seq,
+                canceled: canceled.clone(),
+            },
+        );
+        CancelToken(canceled)
+    }
+
+    pub fn finish(&self, nonce: u64) {
+        self.solves.lock().unwrap().1.remove(&nonce);
+    }
+
+    pub fn num_in_flight(&self) -> usize {
+        self.solves.lock().unwrap().1.len()
+    }
+
+    // signals the n longest running solves to stop and returns their nonces, longest first. solves
+    // that were already canceled are not counted again
+    pub fn cancel_slowest(&self, n: usize) -> Vec<u64> {
+        let solves = self.solves.lock().unwrap();
+        let mut running: Vec<(&u64, &InFlightSolve)> = solves
+            .1
+            .iter()
+            .filter(|(_, solve)| !solve.canceled.load(Ordering::Relaxed))
+            .collect();
+        running.sort_by_key(|(_, solve)| (solve.start, solve.seq));
+        running
+            .into_iter()
+            .take(n)
+            .map(|(&nonce, solve)| {
+                solve.canceled.store(true, Ordering::Relaxed);
+                nonce
+            })
+            .collect()
+    }
+}
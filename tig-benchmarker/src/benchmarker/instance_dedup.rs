@@ -0,0 +1,35 @@
+use std::{
+    collections::HashSet,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+// opt-in, shared by all workers of a run. remembers the fingerprint of every instance generated,
+// see tig_worker::instance_fingerprint, so a nonce whose instance repeats an earlier one is skipped
+// instead of solved. a skipped nonce still counts as attempted, but can never be a solution
+#[derive(Debug, Default)]
+pub struct InstanceDedup {
+    seen: Mutex<HashSet<String>>,
+    num_skipped: AtomicU64,
+}
+
+impl InstanceDedup {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // records the fingerprint, returning whether it was seen before
+    pub fn is_repeat(&self, fingerprint: String) -> bool {
+        let is_repeat = !self.seen.lock().unwrap().insert(fingerprint);
+        if is_repeat {
+            self.num_skipped.fetch_add(1, Ordering::Relaxed);
+        }
+        is_repeat
+    }
+
+    pub fn num_skipped(&self) -> u64 {
+        self.num_skipped.load(Ordering::Relaxed)
+    }
+}
@@ -0,0 +1,246 @@
+use super::live_instances::{LiveInstanceLimit, LivePermit};
+use crate::future_utils::{spawn, yield_now, Mutex};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    ops::Range,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+// how instances reach a worker's solver. generating right before solving keeps the instance in the
+// cache of the thread that solves it, pre-generating on a separate task overlaps generation with
+// solving. which is faster depends on the challenge and the hardware. both solve the same nonces
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub enum GenerationMode {
+    #[default]
+    Inline,
+    Pipelined {
+        queue_depth: usize,
+    },
+}
+
+// a nonce iterator whose already generated instances are dropped once it's cancelled, e.g. a
+// NonceIterator emptied to stop its run. one that merely runs out still has them solved
+pub trait Cancellable {
+    fn is_cancelled(&self) -> bool;
+}
+
+impl Cancellable for Range<u64> {
+    fn is_cancelled(&self) -> bool {
+        false
+    }
+}
+
+// bounded queue of pre-generated instances between a producer task and the solver tasks. the
+// producer waits while the queue is full, so generation never runs more than depth nonces ahead
+pub struct InstanceQueue<T> {
+    depth: usize,
+    queue: Mutex<VecDeque<(u64, T)>>,
+    closed: AtomicBool,
+    cancelled: AtomicBool,
+}
+
+impl<T> InstanceQueue<T> {
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            queue: Mutex::new(VecDeque::new()),
+            closed: AtomicBool::new(false),
+            cancelled: AtomicBool::new(false),
+        }
+    }
+
+    // drops the instance if the queue is cancelled
+    pub async fn push(&self, nonce: u64, instance: T) {
+        let mut item = Some((nonce, instance));
+        loop {
+            if self.is_cancelled() {
+                return;
+            }
+            {
+                let mut queue = self.queue.lock().await;
+                if queue.len() < self.depth || self.is_closed() {
+                    queue.push_back(item.take().unwrap());
+                    return;
+                }
+            }
+            yield_now().await;
+        }
+    }
+
+    // waits for the next instance. returns None once the queue is closed and drained, or cancelled
+    pub async fn pop(&self) -> Option<(u64, T)> {
+        loop {
+            if self.is_cancelled() {
+                return None;
+            }
+            {
+                let mut queue = self.queue.lock().await;
+                if let Some(item) = queue.pop_front() {
+                    return Some(item);
+                }
+                if self.is_closed() {
+                    return None;
+                }
+            }
+            yield_now().await;
+        }
+    }
+
+    pub fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    // drops the queued instances and any pushed after, so none of them are solved
+    pub async fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.close();
+        self.queue.lock().await.clear();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// generates an instance for every nonce taken from nonce_iter until it runs out, then closes the
+// queue
+pub fn spawn_producer<T, I>(
+    queue: Arc<InstanceQueue<T>>,
+    nonce_iter: Arc<Mutex<I>>,
+    generate: impl Fn(u64) -> T + Send + 'static,
+) where
+    T: Send + 'static,
+    I: Iterator<Item = u64> + Send + 'static,
+{
+    spawn(async move {
+        loop {
+            let nonce = match (*nonce_iter).lock().await.next() {
+                Some(nonce) => nonce,
+                None => break,
+            };
+            queue.push(nonce, generate(nonce)).await;
+        }
+        queue.close();
+    });
+}
+
+// like spawn_producer, but the producer takes a permit of limit before generating each instance and
+// queues the permit with it
+fn spawn_limited_producer<T, I>(
+    queue: Arc<InstanceQueue<(T, Option<LivePermit>)>>,
+    nonce_iter: Arc<Mutex<I>>,
+    limit: Option<Arc<LiveInstanceLimit>>,
+    generate: impl Fn(u64) -> T + Send + 'static,
+) where
+    T: Send + 'static,
+    I: Iterator<Item = u64> + Send + 'static,
+{
+    spawn(async move {
+        loop {
+            let permit = match limit.as_ref() {
+                Some(limit) => Some(limit.acquire().await),
+                None => None,
+            };
+            let nonce = match (*nonce_iter).lock().await.next() {
+                Some(nonce) => nonce,
+                None => break,
+            };
+            queue.push(nonce, (generate(nonce), permit)).await;
+        }
+        queue.close();
+    });
+}
+
+// a worker's instances in the order its solver takes them, generated as mode says
+pub enum InstanceSource<T, I> {
+    Inline {
+        nonce_iter: Arc<Mutex<I>>,
+        generate: Box<dyn Fn(u64) -> T + Send + Sync>,
+        limit: Option<Arc<LiveInstanceLimit>>,
+    },
+    Pipelined {
+        nonce_iter: Arc<Mutex<I>>,
+        queue: Arc<InstanceQueue<(T, Option<LivePermit>)>>,
+    },
+}
+
+impl<T, I> InstanceSource<T, I>
+where
+    T: Send + 'static,
+    I: Iterator<Item = u64> + Cancellable + Send + 'static,
+{
+    // spawns the producer task when pipelined
+    pub fn new(
+        mode: GenerationMode,
+        nonce_iter: Arc<Mutex<I>>,
+        generate: impl Fn(u64) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self::with_limit(mode, nonce_iter, None, generate)
+    }
+
+    // like new, but every instance holds a permit of limit from before it is generated until the
+    // solver drops it, see next_live. a limit shared by all workers caps their live instances
+    pub fn with_limit(
+        mode: GenerationMode,
+        nonce_iter: Arc<Mutex<I>>,
+        limit: Option<Arc<LiveInstanceLimit>>,
+        generate: impl Fn(u64) -> T + Send + Sync + 'static,
+    ) -> Self {
+        match mode {
+            GenerationMode::Inline => InstanceSource::Inline {
+                nonce_iter,
+                generate: Box::new(generate),
+                limit,
+            },
+            GenerationMode::Pipelined { queue_depth } => {
+                let queue = Arc::new(InstanceQueue::new(queue_depth));
+                spawn_limited_producer(queue.clone(), nonce_iter.clone(), limit, generate);
+                InstanceSource::Pipelined { nonce_iter, queue }
+            }
+        }
+    }
+
+    // returns None once nonce_iter runs out or is cancelled
+    pub async fn next(&self) -> Option<(u64, T)> {
+        self.next_live()
+            .await
+            .map(|(nonce, instance, _)| (nonce, instance))
+    }
+
+    // next with the instance's permit when limited. drop the permit once done with the instance
+    pub async fn next_live(&self) -> Option<(u64, T, Option<LivePermit>)> {
+        match self {
+            InstanceSource::Inline {
+                nonce_iter,
+                generate,
+                limit,
+            } => {
+                // lets other tasks run between nonces, as the queue does while waiting
+                yield_now().await;
+                let permit = match limit.as_ref() {
+                    Some(limit) => Some(limit.acquire().await),
+                    None => None,
+                };
+                let nonce = (*nonce_iter).lock().await.next()?;
+                Some((nonce, generate(nonce), permit))
+            }
+            InstanceSource::Pipelined { nonce_iter, queue } => {
+                let item = queue.pop().await;
+                // instances generated ahead of a cancel are dropped, not solved
+                if (*nonce_iter).lock().await.is_cancelled() {
+                    queue.cancel().await;
+                    return None;
+                }
+                item.map(|(nonce, (instance, permit))| (nonce, instance, permit))
+            }
+        }
+    }
+}
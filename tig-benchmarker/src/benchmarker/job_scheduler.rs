@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+// how compute is split across jobs that run at the same time
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum FairnessPolicy {
+    EqualNonces,
+    EqualTime,
+    // weights are aligned with jobs, e.g. each challenge's reward potential. jobs with a weight of
+    // 0 get no nonces
+    Weighted(Vec<f64>),
+}
+
+// dispatches nonces across jobs, each time to the job furthest below its share under the policy
+#[derive(Debug, Clone)]
+pub struct JobScheduler {
+    policy: FairnessPolicy,
+    next_nonces: Vec<u64>,
+    num_attempts: Vec<u64>,
+    elapsed_ms: Vec<u64>,
+}
+
+impl JobScheduler {
+    pub fn new(policy: FairnessPolicy, num_jobs: usize) -> Self {
+        Self {
+            policy,
+            next_nonces: vec![0; num_jobs],
+            num_attempts: vec![0; num_jobs],
+            elapsed_ms: vec![0; num_jobs],
+        }
+    }
+
+    // returns the index of the job and the nonce to attempt next. None if no job can be scheduled
+    pub fn dispatch(&mut self) -> Option<(usize, u64)> {
+        let job = (0..self.num_attempts.len())
+            .filter_map(|i| self.usage(i).map(|usage| (i, usage)))
+            .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            .map(|(i, _)| i)?;
+        let nonce = self.next_nonces[job];
+        self.next_nonces[job] += 1;
+        self.num_attempts[job] += 1;
+        Some((job, nonce))
+    }
+
+    // records how long an attempt dispatched to job took. only used by EqualTime
+    pub fn record_time(&mut self, job: usize, elapsed_ms: u64) {
+        self.elapsed_ms[job] += elapsed_ms;
+    }
+
+    pub fn num_attempts(&self) -> &[u64] {
+        &self.num_attempts
+    }
+
+    // compute used by a job relative to its share. None if the job gets no share
+    fn usage(&self, job: usize) -> Option<(f64, u64)> {
+        match &self.policy {
+            FairnessPolicy::EqualNonces => Some((self.num_attempts[job] as f64, 0)),
+            // attempts break ties until times are recorded
+            FairnessPolicy::EqualTime => {
+                Some((self.elapsed_ms[job] as f64, self.num_attempts[job]))
+            }
+            FairnessPolicy::Weighted(weights) => match weights.get(job) {
+                Some(&weight) if weight > 0.0 => Some((self.num_attempts[job] as f64 / weight, 0)),
+                _ => None,
+            },
+        }
+    }
+}
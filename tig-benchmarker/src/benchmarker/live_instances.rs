@@ -0,0 +1,71 @@
+use crate::future_utils::yield_now;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+// caps the generated instances alive at once across all workers of a run, counting the ones queued
+// by pre-generation as well as the ones being solved, so memory stays predictable on difficulties
+// with large instances. a permit is taken before generating an instance and released when it is
+// dropped after solving
+#[derive(Debug)]
+pub struct LiveInstanceLimit {
+    max_live_instances: usize,
+    live: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl LiveInstanceLimit {
+    pub fn new(max_live_instances: usize) -> Self {
+        Self {
+            max_live_instances: max_live_instances.max(1),
+            live: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        }
+    }
+
+    // waits until an instance may be generated
+    pub async fn acquire(self: &Arc<Self>) -> LivePermit {
+        loop {
+            if let Some(permit) = self.try_acquire() {
+                return permit;
+            }
+            yield_now().await;
+        }
+    }
+
+    pub fn try_acquire(self: &Arc<Self>) -> Option<LivePermit> {
+        self.live
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |live| {
+                (live < self.max_live_instances).then_some(live + 1)
+            })
+            .ok()
+            .map(|live| {
+                self.peak.fetch_max(live + 1, Ordering::Relaxed);
+                LivePermit {
+                    limit: self.clone(),
+                }
+            })
+    }
+
+    pub fn live(&self) -> usize {
+        self.live.load(Ordering::Acquire)
+    }
+
+    // most instances alive at once so far
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+}
+
+// one live instance. hold it for as long as the instance is
+#[derive(Debug)]
+pub struct LivePermit {
+    limit: Arc<LiveInstanceLimit>,
+}
+
+impl Drop for LivePermit {
+    fn drop(&mut self) {
+        self.limit.live.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+const DEFAULT_WINDOW: usize = 100;
+
+// shared by all workers of a run. stops the run once the solve rate over the last window attempts
+// falls below min_marginal_rate, as more nonces are then unlikely to add much. unlike
+// SolveRateTarget it only looks at recent attempts, so it catches a run whose rate is declining
+#[derive(Debug)]
+pub struct MarginalRateFloor {
+    min_marginal_rate: f64,
+    window: usize,
+    recent: Mutex<VecDeque<bool>>,
+    reached: AtomicBool,
+}
+
+impl MarginalRateFloor {
+    pub fn new(min_marginal_rate: f64) -> Self {
+        Self::with_window(min_marginal_rate, DEFAULT_WINDOW)
+    }
+
+    pub fn with_window(min_marginal_rate: f64, window: usize) -> Self {
+        let window = window.max(1);
+        Self {
+            min_marginal_rate,
+            window,
+            recent: Mutex::new(VecDeque::with_capacity(window + 1)),
+            reached: AtomicBool::new(false),
+        }
+    }
+
+    // returns whether the rate has fallen below the floor. never before window attempts
+    pub fn record(&self, solved: bool) -> bool {
+        let mut recent = self.recent.lock().unwrap();
+        recent.push_back(solved);
+        if recent.len() > self.window {
+            recent.pop_front();
+        }
+        if recent.len() == self.window && rate(&recent) < self.min_marginal_rate {
+            self.reached.store(true, Ordering::Relaxed);
+        }
+        self.is_reached()
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.reached.load(Ordering::Relaxed)
+    }
+
+    // solve rate over the last window attempts, or fewer if there haven't been as many
+    pub fn marginal_rate(&self) -> Option<f64> {
+        let recent = self.recent.lock().unwrap();
+        (!recent.is_empty()).then(|| rate(&recent))
+    }
+}
+
+fn rate(recent: &VecDeque<bool>) -> f64 {
+    recent.iter().filter(|&&solved| solved).count() as f64 / recent.len() as f64
+}
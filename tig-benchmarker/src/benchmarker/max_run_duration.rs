@@ -0,0 +1,56 @@
+use super::{
+    clock::{system_clock, Clock},
+    NonceIterator,
+};
+use crate::future_utils::Mutex;
+use std::sync::Arc;
+
+// hard cap on a run's wall clock time, as a safety net for unattended runs. unlike the run's other
+// stop conditions it doesn't depend on solutions or settings, so a run is bounded even if they
+// never trigger. counts from when it's created. workers take no more nonces once it's exceeded,
+// but finish the one they're on
+#[derive(Clone)]
+pub struct MaxRunDuration {
+    start: u64,
+    max_ms: u64,
+    clock: Arc<dyn Clock>,
+}
+
+impl MaxRunDuration {
+    pub fn new(max_ms: u64) -> Self {
+        Self::with_clock(max_ms, system_clock())
+    }
+
+    pub fn with_clock(max_ms: u64, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            start: clock.now(),
+            max_ms,
+            clock,
+        }
+    }
+
+    pub fn max_ms(&self) -> u64 {
+        self.max_ms
+    }
+
+    pub fn remaining_ms(&self) -> u64 {
+        self.max_ms
+            .saturating_sub(self.clock.now().saturating_sub(self.start))
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        self.remaining_ms() == 0
+    }
+
+    // empties every nonce iterator of the run once exceeded, so no worker takes another nonce.
+    // returns whether it did
+    pub async fn enforce(&self, nonce_iters: &[Arc<Mutex<NonceIterator>>]) -> bool {
+        if !self.is_exceeded() {
+            return false;
+        }
+        for nonce_iter in nonce_iters.iter() {
+            (*nonce_iter).lock().await.empty();
+        }
+        true
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use tig_worker::{SolutionData, VersionPin};
+
+// selects the optional metadata recorded with each solution, so memory constrained runs can keep
+// only what the protocol needs. nonce, runtime_signature, fuel_consumed and solution are always
+// recorded
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct MetadataConfig {
+    pub version_pin: bool,
+}
+
+impl Default for MetadataConfig {
+    fn default() -> Self {
+        Self { version_pin: true }
+    }
+}
+
+impl MetadataConfig {
+    // populates the enabled fields and leaves disabled ones empty
+    pub fn apply(&self, solution_data: &mut SolutionData, version_pin: &VersionPin) {
+        solution_data.version_pin = if self.version_pin {
+            Some(version_pin.clone())
+        } else {
+            None
+        };
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+use std::fmt::Write;
+
+// counters of the current benchmark, updated while it runs. exposed in prometheus text format for
+// operators scraping a benchmarker running as a service. they restart from 0 with every benchmark,
+// which prometheus treats as a counter reset
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Metrics {
+    pub challenge_id: String,
+    pub algorithm_id: String,
+    pub num_attempts: u64,
+    pub num_solutions: u64,
+    pub num_errors: u64,
+    pub elapsed_ms: u64,
+}
+
+impl Metrics {
+    pub fn solve_rate(&self) -> f64 {
+        if self.num_attempts == 0 {
+            0.0
+        } else {
+            self.num_solutions as f64 / self.num_attempts as f64
+        }
+    }
+
+    pub fn nonces_per_second(&self) -> f64 {
+        if self.elapsed_ms == 0 {
+            0.0
+        } else {
+            self.num_attempts as f64 * 1000.0 / self.elapsed_ms as f64
+        }
+    }
+
+    pub fn to_prometheus(&self) -> String {
+        let labels = format!(
+            "{{challenge_id=\"{}\",algorithm_id=\"{}\"}}",
+            self.challenge_id, self.algorithm_id
+        );
+        let metrics: [(&str, &str, &str, String); 5] = [
+            (
+                "tig_benchmarker_attempts_total",
+                "counter",
+                "Nonces attempted in the current benchmark",
+                self.num_attempts.to_string(),
+            ),
+            (
+                "tig_benchmarker_solutions_total",
+                "counter",
+                "Valid solutions found in the current benchmark",
+                self.num_solutions.to_string(),
+            ),
+            (
+                "tig_benchmarker_errors_total",
+                "counter",
+                "Nonces that errored in the current benchmark",
+                self.num_errors.to_string(),
+            ),
+            (
+                "tig_benchmarker_solve_rate",
+                "gauge",
+                "Fraction of attempted nonces solved",
+                self.solve_rate().to_string(),
+            ),
+            (
+                "tig_benchmarker_nonces_per_second",
+                "gauge",
+                "Nonces attempted per second",
+                self.nonces_per_second().to_string(),
+            ),
+        ];
+        let mut text = String::new();
+        for (name, metric_type, help, value) in metrics {
+            writeln!(text, "# HELP {} {}", name, help).unwrap();
+            writeln!(text, "# TYPE {} {}", name, metric_type).unwrap();
+            writeln!(text, "{}{} {}", name, labels, value).unwrap();
+        }
+        text
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Instant,
+};
+use tig_worker::Solution;
+
+// completions each backend needs before Auto trusts its throughput
+const MIN_SAMPLES: u64 = 5;
+// Auto keeps sending this share of nonces to the slower backend, so its throughput stays measured
+const MIN_SHARE: f64 = 0.05;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    Cpu,
+    Gpu,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum SplitPolicy {
+    // this fraction of nonces goes to the gpu
+    Fixed(f64),
+    // starts at this fraction, then follows the backends' observed throughput, so both finish their
+    // share at the same rate
+    Auto(f64),
+}
+
+#[derive(Debug, Default)]
+struct Counts {
+    dispatched: [u64; 2],
+    completed: [u64; 2],
+    busy_us: [u64; 2],
+}
+
+// decides which backend solves each nonce of a mixed run. shared by all workers of the run
+#[derive(Debug)]
+pub struct BackendSplit {
+    policy: SplitPolicy,
+    counts: Mutex<Counts>,
+}
+
+impl BackendSplit {
+    pub fn new(policy: SplitPolicy) -> Self {
+        Self {
+            policy,
+            counts: Mutex::new(Counts::default()),
+        }
+    }
+
+    // fraction of nonces currently meant for the gpu
+    pub fn gpu_fraction(&self) -> f64 {
+        gpu_fraction(self.policy, &self.counts.lock().unwrap())
+    }
+
+    // the backend for the next nonce, keeping the share dispatched to the gpu at gpu_fraction
+    pub fn dispatch(&self) -> Backend {
+        let mut counts = self.counts.lock().unwrap();
+        let total = counts.dispatched.iter().sum::<u64>() + 1;
+        let backend =
+            if (counts.dispatched[1] as f64) < gpu_fraction(self.policy, &counts) * total as f64 {
+                Backend::Gpu
+            } else {
+                Backend::Cpu
+            };
+        counts.dispatched[backend as usize] += 1;
+        backend
+    }
+
+    // records a nonce the backend finished in elapsed_us microseconds
+    pub fn record(&self, backend: Backend, elapsed_us: u64) {
+        let mut counts = self.counts.lock().unwrap();
+        counts.completed[backend as usize] += 1;
+        counts.busy_us[backend as usize] += elapsed_us;
+    }
+
+    pub fn num_dispatched(&self, backend: Backend) -> u64 {
+        self.counts.lock().unwrap().dispatched[backend as usize]
+    }
+}
+
+fn gpu_fraction(policy: SplitPolicy, counts: &Counts) -> f64 {
+    match policy {
+        SplitPolicy::Fixed(fraction) => fraction.clamp(0.0, 1.0),
+        SplitPolicy::Auto(initial) => {
+            if counts.completed.iter().any(|&x| x < MIN_SAMPLES) {
+                return initial.clamp(MIN_SHARE, 1.0 - MIN_SHARE);
+            }
+            // nonces per microsecond
+            let throughput =
+                |i: usize| counts.completed[i] as f64 / counts.busy_us[i].max(1) as f64;
+            let (cpu, gpu) = (throughput(0), throughput(1));
+            (gpu / (cpu + gpu)).clamp(MIN_SHARE, 1.0 - MIN_SHARE)
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct MixedResult {
+    pub nonce: u64,
+    pub backend: Backend,
+    pub solution: Option<Solution>,
+}
+
+// solves the nonces on num_workers threads, each nonce on the backend split picks for it. both
+// backends must run the same algorithm, as their solutions are submitted alike. results are in the
+// order of nonces. not available in the browser, which can't spawn threads
+pub fn execute_mixed(
+    nonces: &[u64],
+    num_workers: usize,
+    split: &BackendSplit,
+    cpu: impl Fn(u64) -> Option<Solution> + Sync,
+    gpu: impl Fn(u64) -> Option<Solution> + Sync,
+) -> Vec<MixedResult> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<MixedResult>>> = Mutex::new(vec![None; nonces.len()]);
+    thread::scope(|s| {
+        for _ in 0..num_workers.max(1) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let nonce = match nonces.get(i) {
+                    Some(&nonce) => nonce,
+                    None => break,
+                };
+                let backend = split.dispatch();
+                let start = Instant::now();
+                let solution = match backend {
+                    Backend::Cpu => cpu(nonce),
+                    Backend::Gpu => gpu(nonce),
+                };
+                split.record(backend, start.elapsed().as_micros() as u64);
+                results.lock().unwrap()[i] = Some(MixedResult {
+                    nonce,
+                    backend,
+                    solution,
+                });
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect()
+}
@@ -1,10 +1,54 @@
+pub mod accept_if;
+pub mod audit_log;
+pub mod backend_comparison;
+pub mod baseline;
+pub mod capabilities;
+pub mod circuit_breaker;
+pub mod clock;
+pub mod coverage;
+pub mod default_timeouts;
 mod difficulty_sampler;
+pub mod difficulty_schedule;
+pub mod difficulty_surface;
 pub mod download_wasm;
+pub mod duty_cycle;
+pub mod failure_sink;
 mod find_proof_to_submit;
+pub mod in_flight;
+pub mod instance_dedup;
+pub mod instance_pipeline;
+pub mod job_scheduler;
+pub mod live_instances;
+pub mod marginal_rate;
+pub mod max_run_duration;
+pub mod metadata_config;
+pub mod metrics;
+pub mod mixed_execution;
+pub mod nonce_offset;
+pub mod progress_throttle;
 mod query_data;
+pub mod race;
+pub mod reproduction;
+pub mod run_seed;
+pub mod run_warnings;
+pub mod sampling_policy;
+pub mod schedule_log;
 mod setup_job;
+#[cfg(feature = "standalone")]
+pub mod shutdown;
+pub mod solution_flusher;
+pub mod solve_outcome;
+pub mod solve_rate_target;
+pub mod staggered_start;
+pub mod stats;
+pub mod submission;
 mod submit_benchmark;
 mod submit_proof;
+pub mod timeout_scaling;
+pub mod timing_trace;
+pub mod top_k;
+pub mod verify_bundle;
+pub mod verify_tiers;
 
 #[cfg(not(feature = "cuda"))]
 pub mod run_benchmark;
@@ -13,15 +57,43 @@ pub mod run_benchmark;
 pub mod run_benchmark;
 
 use crate::future_utils::{sleep, spawn, time, Mutex};
+use accept_if::AcceptIf;
+use audit_log::AuditLog;
+use circuit_breaker::CircuitBreaker;
+use default_timeouts::DefaultTimeouts;
 use difficulty_sampler::DifficultySampler;
+use duty_cycle::DutyCycle;
+use instance_dedup::InstanceDedup;
+use instance_pipeline::{Cancellable, GenerationMode};
+use live_instances::LiveInstanceLimit;
+use marginal_rate::MarginalRateFloor;
+use max_run_duration::MaxRunDuration;
+use metadata_config::MetadataConfig;
+use metrics::Metrics;
+use nonce_offset::NonceOffset;
 use once_cell::sync::OnceCell;
+use progress_throttle::ProgressThrottle;
+use reproduction::{ReproductionLog, ReproductionNonces};
+use run_benchmark::ExecuteOptions;
+use run_seed::RunSeed;
+use run_warnings::{RunWarning, RunWarnings};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use stats::StatsAccumulator;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 use tig_api::Api;
 use tig_structs::{
     config::{MinMaxDifficulty, WasmVMConfig},
     core::*,
 };
+use tig_worker::{calc_version_pin, InstanceOptions};
+use timeout_scaling::TimeoutScaling;
 
 pub type Result<T> = std::result::Result<T, String>;
 
@@ -69,6 +141,20 @@ pub struct Job {
     pub solution_signature_threshold: u32,
     pub sampled_nonces: Option<Vec<u64>>,
     pub wasm_vm_config: WasmVMConfig,
+    #[serde(default)]
+    pub metadata_config: MetadataConfig,
+    // protocol limit on a solution's size. None if the protocol sets none
+    #[serde(default)]
+    pub max_solution_size: Option<usize>,
+    // wall clock budget of a nonce's solve, past which the nonce counts as timed out. its solution
+    // is still kept, a wasm solve can't be interrupted and the work is done. None if no timeout
+    // applies. setup_job fills it in from DefaultTimeouts unless the job sets one or is a recompute
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    // how the job's instances differ from the protocol's, e.g. salted for a local experiment. a
+    // job with other than the protocol's instances is never submitted
+    #[serde(default)]
+    pub instance_options: InstanceOptions,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -76,6 +162,7 @@ pub struct NonceIterator {
     nonces: Option<Vec<u64>>,
     current: u64,
     attempts: u64,
+    cancelled: bool,
 }
 
 impl NonceIterator {
@@ -84,6 +171,7 @@ impl NonceIterator {
             nonces: Some(nonces),
             current: 0,
             attempts: 0,
+            cancelled: false,
         }
     }
     pub fn from_u64(start: u64) -> Self {
@@ -91,6 +179,7 @@ impl NonceIterator {
             nonces: None,
             current: start,
             attempts: 0,
+            cancelled: false,
         }
     }
     pub fn attempts(&self) -> u64 {
@@ -99,11 +188,22 @@ impl NonceIterator {
     pub fn is_empty(&self) -> bool {
         self.nonces.as_ref().is_some_and(|x| x.is_empty()) || self.current == u64::MAX
     }
+    // the next nonce of an iterator from_u64, so it can be resumed from there. None once empty
+    pub fn cursor(&self) -> Option<u64> {
+        (self.nonces.is_none() && self.current < u64::MAX).then_some(self.current)
+    }
+    // stops the run of the iterator, see Cancellable
     pub fn empty(&mut self) {
         if let Some(nonces) = self.nonces.as_mut() {
             nonces.clear();
         }
         self.current = u64::MAX;
+        self.cancelled = true;
+    }
+}
+impl Cancellable for NonceIterator {
+    fn is_cancelled(&self) -> bool {
+        self.cancelled
     }
 }
 impl Iterator for NonceIterator {
@@ -138,8 +238,47 @@ pub struct State {
     pub timer: Option<Timer>,
     pub query_data: QueryData,
     pub selected_algorithms: HashMap<String, String>,
+    pub timeout_scalings: HashMap<String, TimeoutScaling>,
+    pub default_timeouts: DefaultTimeouts,
+    pub metadata_config: MetadataConfig,
+    pub progress_throttle: ProgressThrottle,
+    pub metrics: Metrics,
+    pub generation_mode: GenerationMode,
+    // cap on instances alive at once when generation is pipelined. None leaves it to queue_depth
+    pub max_live_instances: Option<usize>,
+    pub instance_dedup: bool,
+    // stops a run once its solve rate over recent nonces falls below this, see MarginalRateFloor
+    pub min_marginal_rate: Option<f64>,
+    // hard cap on a run's duration, whatever its other settings. see MaxRunDuration
+    pub max_run_duration_ms: Option<u64>,
+    pub nonce_offset: NonceOffset,
+    // seeds every run with this instead of a fresh run seed, reproducing the run it came from
+    pub fixed_run_seed: Option<RunSeed>,
+    // the current run's seed, which its offset and sampling derive from. see RunSeed
+    pub run_seed: Option<RunSeed>,
+    // the offset the current run's nonces started at, for reproducing an Entropy run
+    pub run_nonce_offset: Option<u64>,
+    // instance options of every job benchmarked, recomputes always use the protocol's instances
+    pub instance_options: InstanceOptions,
+    // salts every job's instances with its run's seed salt, see RunSeed::salt
+    pub salt_from_run_seed: bool,
+    // where each run's ReproductionLog is written. None writes none
+    pub reproduction_log_path: Option<PathBuf>,
     pub job: Option<Job>,
+    // advisories of the current run, e.g. a backend it fell back from. none of them stop it
+    pub warnings: Vec<RunWarning>,
+    // whether warnings are also printed as they're recorded
+    pub stream_warnings: bool,
     pub submission_errors: HashMap<String, String>,
+    pub version_pin: Option<VersionPin>,
+    #[serde(skip_serializing)]
+    pub duty_cycle: Option<Arc<DutyCycle>>,
+    #[serde(skip_serializing)]
+    pub accept_if: Option<Arc<AcceptIf>>,
+    #[serde(skip_serializing)]
+    pub run_warnings: Option<Arc<RunWarnings>>,
+    #[serde(skip_serializing)]
+    pub audit_log: Option<Arc<AuditLog>>,
     #[serde(skip_serializing)]
     pub difficulty_samplers: HashMap<String, DifficultySampler>,
 }
@@ -170,12 +309,50 @@ async fn update_status(status: &str) {
     }
 }
 
+// logs a recompute's sampled nonces, or each worker's nonces from its start up to where its
+// iterator has got to
+async fn write_reproduction_log(
+    path: &Path,
+    job: &Job,
+    nonce_starts: &[u64],
+    nonce_iters: &[Arc<Mutex<NonceIterator>>],
+) {
+    let nonces = match &job.sampled_nonces {
+        Some(nonces) => ReproductionNonces::List(nonces.clone()),
+        None => {
+            let mut ranges = Vec::new();
+            for (&start, nonce_iter) in nonce_starts.iter().zip(nonce_iters) {
+                let end = (*nonce_iter).lock().await.cursor().unwrap_or(start);
+                ranges.push((start, end));
+            }
+            ReproductionNonces::Ranges(ranges)
+        }
+    };
+    let log = ReproductionLog::new(job.settings.clone(), job.instance_options.clone(), nonces);
+    if let Err(e) = log.write(path) {
+        update_status(&e).await;
+    }
+}
+
 async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
-    {
+    let run_seed = {
         let mut state = (*state()).lock().await;
         state.job = None;
         state.timer = None;
-    }
+        state.run_nonce_offset = None;
+        let run_seed = state
+            .fixed_run_seed
+            .unwrap_or_else(|| RunSeed::from_entropy(time()));
+        state.run_seed = Some(run_seed);
+        state.warnings.clear();
+        state.run_warnings = Some(Arc::new(RunWarnings::new(state.stream_warnings)));
+        run_seed
+    };
+    update_status(&format!(
+        "Run seed {} (pass it to --run-seed to reproduce this run)",
+        run_seed
+    ))
+    .await;
     update_status("Querying latest data").await;
     // retain only benchmarks that are within the lifespan period
     // preserves solution_meta_data and solution_data
@@ -264,23 +441,72 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
     ))
     .await;
     let wasm = download_wasm::execute(&job).await?;
+    (*state()).lock().await.version_pin = Some(calc_version_pin(&wasm));
 
     // variables that are shared by workers
+    let nonce_starts = match &job.sampled_nonces {
+        Some(_) => Vec::new(),
+        None => {
+            let offset = {
+                let mut state = (*state()).lock().await;
+                let offset = state
+                    .nonce_offset
+                    .resolve_with_seed(u64::MAX / num_workers as u64, run_seed);
+                state.run_nonce_offset = Some(offset);
+                offset
+            };
+            update_status(&format!("Starting nonces at offset {}", offset)).await;
+            NonceOffset::starts(offset, num_workers)
+        }
+    };
     let nonce_iters = match &job.sampled_nonces {
         Some(nonces) => vec![Arc::new(Mutex::new(NonceIterator::from_vec(
             nonces.clone(),
         )))],
-        None => (0..num_workers)
-            .into_iter()
-            .map(|x| {
-                Arc::new(Mutex::new(NonceIterator::from_u64(
-                    u64::MAX / num_workers as u64 * x as u64,
-                )))
-            })
+        None => nonce_starts
+            .iter()
+            .map(|&start| Arc::new(Mutex::new(NonceIterator::from_u64(start))))
             .collect(),
     };
+    let reproduction_log_path = (*state()).lock().await.reproduction_log_path.clone();
+    if let Some(path) = reproduction_log_path.as_ref() {
+        write_reproduction_log(path, &job, &nonce_starts, &nonce_iters).await;
+    }
     let solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
-    let solutions_count = Arc::new(Mutex::new(0u32));
+    let solutions_count = Arc::new(AtomicU64::new(0));
+    let circuit_breaker = Arc::new(CircuitBreaker::default());
+    let stats = Arc::new(Mutex::new(StatsAccumulator::new()));
+    let (
+        duty_cycle,
+        accept_if,
+        generation_mode,
+        live_instance_limit,
+        instance_dedup,
+        run_warnings,
+        marginal_rate_floor,
+        max_run_duration,
+        audit_log,
+    ) = {
+        let state = (*state()).lock().await;
+        (
+            state.duty_cycle.clone(),
+            state.accept_if.clone(),
+            state.generation_mode,
+            state
+                .max_live_instances
+                .map(|x| Arc::new(LiveInstanceLimit::new(x))),
+            state.instance_dedup.then(|| Arc::new(InstanceDedup::new())),
+            state.run_warnings.clone(),
+            state
+                .min_marginal_rate
+                .map(|x| Arc::new(MarginalRateFloor::new(x))),
+            state.max_run_duration_ms.map(MaxRunDuration::new),
+            state.audit_log.clone(),
+        )
+    };
+    if let Some(duty_cycle) = duty_cycle.as_ref() {
+        duty_cycle.reset();
+    }
     update_status("Starting benchmark").await;
     run_benchmark::execute(
         nonce_iters.iter().cloned().collect(),
@@ -288,12 +514,29 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
         &wasm,
         solutions_data.clone(),
         solutions_count.clone(),
+        circuit_breaker.clone(),
+        ExecuteOptions {
+            generation_mode,
+            live_instance_limit,
+            stats: Some(stats.clone()),
+            duty_cycle: duty_cycle.clone(),
+            accept_if,
+            instance_dedup: instance_dedup.clone(),
+            run_warnings: run_warnings.clone(),
+            marginal_rate_floor: marginal_rate_floor.clone(),
+            max_run_duration: max_run_duration.clone(),
+            audit_log: audit_log.clone(),
+            ..Default::default()
+        },
     )
     .await;
-    {
+    let mut progress_throttle = {
         let mut state = state().lock().await;
         (*state).timer = Some(Timer::new(ms_per_benchmark as u64));
-    }
+        state.progress_throttle
+    };
+    progress_throttle.reset();
+    let start = time();
     loop {
         {
             // transfers solutions computed by workers to benchmark state
@@ -306,34 +549,99 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
                 num_attempts += nonce_iter.attempts();
                 finished &= nonce_iter.is_empty();
             }
-            update_status(&format!(
-                "Computed {} solutions out of {} instances",
-                num_solutions, num_attempts
-            ))
-            .await;
+            if progress_throttle.should_emit(time(), num_attempts) {
+                update_status(&format!(
+                    "Computed {} solutions out of {} instances",
+                    num_solutions, num_attempts
+                ))
+                .await;
+                if let Some(path) = reproduction_log_path.as_ref() {
+                    write_reproduction_log(path, &job, &nonce_starts, &nonce_iters).await;
+                }
+            }
             let State {
                 status,
                 timer: time_left,
+                metrics,
+                warnings,
                 ..
             } = &mut (*state().lock().await);
+            *metrics = Metrics {
+                challenge_id: job.settings.challenge_id.clone(),
+                algorithm_id: job.settings.algorithm_id.clone(),
+                num_attempts,
+                num_solutions,
+                num_errors: circuit_breaker.num_errors(),
+                elapsed_ms: time() - start,
+            };
+            if let Some(run_warnings) = run_warnings.as_ref() {
+                *warnings = run_warnings.warnings();
+            }
             if time_left.as_mut().unwrap().update().finished()
-                || (finished && num_solutions == (num_attempts as u32)) // nonce_iter is only empty if recomputing
+                || (finished && num_solutions == num_attempts) // nonce_iter is only empty if recomputing
                 || *status == Status::Stopping
+                || circuit_breaker.is_tripped()
+                || marginal_rate_floor.as_ref().is_some_and(|x| x.is_reached())
+                || max_run_duration.as_ref().is_some_and(|x| x.is_exceeded())
             {
                 break;
             }
         }
         sleep(200).await;
     }
+    if let Some(path) = reproduction_log_path.as_ref() {
+        write_reproduction_log(path, &job, &nonce_starts, &nonce_iters).await;
+    }
     for nonce_iter in nonce_iters {
         (*(*nonce_iter).lock().await).empty();
     }
+    if let Some(Err(e)) = audit_log.as_ref().map(|x| x.flush()) {
+        update_status(&e).await;
+    }
+    if let Some(run_warnings) = run_warnings.as_ref() {
+        (*state()).lock().await.warnings = run_warnings.warnings();
+    }
+    if circuit_breaker.is_tripped() {
+        return Err(circuit_breaker.error());
+    }
+    if let Some(duty_cycle) = duty_cycle.as_ref() {
+        update_status(&format!(
+            "Applied a duty cycle of {:.0}%",
+            duty_cycle.applied() * 100.0
+        ))
+        .await;
+    }
+    if let Some(marginal_rate) = marginal_rate_floor
+        .as_ref()
+        .filter(|x| x.is_reached())
+        .and_then(|x| x.marginal_rate())
+    {
+        update_status(&format!(
+            "Stopped early. Solve rate over recent instances fell to {:.1}%",
+            marginal_rate * 100.0
+        ))
+        .await;
+    }
+    if let Some(max_run_duration) = max_run_duration.as_ref().filter(|x| x.is_exceeded()) {
+        update_status(&format!(
+            "Stopped at the max run duration of {}ms",
+            max_run_duration.max_ms()
+        ))
+        .await;
+    }
+    if let Some(instance_dedup) = instance_dedup.as_ref() {
+        update_status(&format!(
+            "Skipped {} repeated instances",
+            instance_dedup.num_skipped()
+        ))
+        .await;
+    }
 
     // transfers solutions computed by workers to benchmark state
     let num_solutions =
         drain_solutions(&job.benchmark_id, &mut *(*solutions_data).lock().await).await;
     if let Some(sampled_nonces) = job.sampled_nonces.as_ref() {
-        if num_solutions != sampled_nonces.len() as u32 {
+        if num_solutions != sampled_nonces.len() as u64 {
             let mut state = (*state()).lock().await;
             (*state)
                 .query_data
@@ -357,7 +665,7 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
     } else {
         update_status("Updating difficulty sampler with solutions").await;
         {
-            let num_solutions = *solutions_count.lock().await;
+            let num_solutions = solutions_count.load(Ordering::Relaxed);
             let mut state = state().lock().await;
             state
                 .difficulty_samplers
@@ -365,9 +673,40 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
                 .unwrap()
                 .update_with_solutions(&job.settings.difficulty, num_solutions);
         }
+        let stats = {
+            let mut stats = (*stats).lock().await;
+            // workers only record solved difficulties
+            stats.num_attempts = (*state()).lock().await.metrics.num_attempts;
+            stats.num_solutions = solutions_count.load(Ordering::Relaxed);
+            stats.clone()
+        };
+        if let Some(solve_rate) = stats.solve_rate() {
+            let (lower, upper) = stats.solve_rate_interval(1.96);
+            update_status(&format!(
+                "Solve rate of {:.1}% over {} instances, 95% CI {:.1}% to {:.1}%",
+                solve_rate * 100.0,
+                stats.num_attempts,
+                lower * 100.0,
+                upper * 100.0
+            ))
+            .await;
+        }
+        if let Some(difficulty) = stats.max_solved_difficulty {
+            update_status(&format!(
+                "Hardest instance solved has a search space of 2^{:.1}",
+                difficulty
+            ))
+            .await;
+        }
 
         if num_solutions == 0 {
             update_status("Finished. No solutions to submit").await;
+        } else if !job.instance_options.is_protocol() {
+            update_status(&format!(
+                "Finished. Not submitting {} solutions to instances the protocol can't verify",
+                num_solutions
+            ))
+            .await;
         } else {
             update_status(&format!("Finished. Submitting {} solutions", num_solutions,)).await;
             let benchmark_id = match submit_benchmark::execute(&job).await {
@@ -396,8 +735,26 @@ async fn run_once(num_workers: u32, ms_per_benchmark: u32) -> Result<()> {
     Ok(())
 }
 
-pub async fn drain_solutions(benchmark_id: &String, solutions_data: &mut Vec<SolutionData>) -> u32 {
+pub async fn drain_solutions(benchmark_id: &String, solutions_data: &mut Vec<SolutionData>) -> u64 {
     let mut state = (*state()).lock().await;
+    if let Some(version_pin) = state
+        .version_pin
+        .as_ref()
+        .filter(|_| state.metadata_config.version_pin)
+    {
+        for d in solutions_data.iter() {
+            if let Some(drift) = d.version_drift(version_pin) {
+                let warning = RunWarning::VersionDrift {
+                    nonce: d.nonce,
+                    drift,
+                };
+                match state.run_warnings.as_ref() {
+                    Some(run_warnings) => run_warnings.record(warning),
+                    None => println!("Warning: {}", warning),
+                }
+            }
+        }
+    }
     let QueryData {
         benchmarks, proofs, ..
     } = &mut (*state).query_data;
@@ -413,7 +770,7 @@ pub async fn drain_solutions(benchmark_id: &String, solutions_data: &mut Vec<Sol
         }
         let to_update = proof.solutions_data.as_mut().unwrap();
         to_update.extend(solutions_data.drain(..));
-        to_update.len() as u32
+        to_update.len() as u64
     } else {
         0
     }
@@ -461,6 +818,98 @@ pub async fn select_algorithm(challenge_name: String, algorithm_name: String) {
         .insert(challenge_name, algorithm_name);
 }
 
+pub async fn set_timeout_scaling(challenge_name: String, timeout_scaling: TimeoutScaling) {
+    let mut state = (*state()).lock().await;
+    state
+        .timeout_scalings
+        .insert(challenge_name, timeout_scaling);
+}
+
+pub async fn set_default_timeout(challenge_id: String, timeout_ms: u64) {
+    let mut state = (*state()).lock().await;
+    state.default_timeouts.set(challenge_id, timeout_ms);
+}
+
+pub async fn set_metadata_config(metadata_config: MetadataConfig) {
+    let mut state = (*state()).lock().await;
+    state.metadata_config = metadata_config;
+}
+
+pub async fn set_progress_throttle(progress_throttle: ProgressThrottle) {
+    let mut state = (*state()).lock().await;
+    state.progress_throttle = progress_throttle;
+}
+
+pub async fn set_duty_cycle(duty_cycle: Option<Arc<DutyCycle>>) {
+    let mut state = (*state()).lock().await;
+    state.duty_cycle = duty_cycle;
+}
+
+pub async fn set_generation_mode(generation_mode: GenerationMode) {
+    let mut state = (*state()).lock().await;
+    state.generation_mode = generation_mode;
+}
+
+pub async fn set_max_live_instances(max_live_instances: Option<usize>) {
+    let mut state = (*state()).lock().await;
+    state.max_live_instances = max_live_instances;
+}
+
+pub async fn set_accept_if(accept_if: Option<Arc<AcceptIf>>) {
+    let mut state = (*state()).lock().await;
+    state.accept_if = accept_if;
+}
+
+pub async fn set_instance_dedup(instance_dedup: bool) {
+    let mut state = (*state()).lock().await;
+    state.instance_dedup = instance_dedup;
+}
+
+pub async fn set_min_marginal_rate(min_marginal_rate: Option<f64>) {
+    let mut state = (*state()).lock().await;
+    state.min_marginal_rate = min_marginal_rate;
+}
+
+pub async fn set_max_run_duration(max_run_duration_ms: Option<u64>) {
+    let mut state = (*state()).lock().await;
+    state.max_run_duration_ms = max_run_duration_ms;
+}
+
+pub async fn set_audit_log(audit_log: Option<Arc<AuditLog>>) {
+    let mut state = (*state()).lock().await;
+    state.audit_log = audit_log;
+}
+
+pub async fn set_stream_warnings(stream_warnings: bool) {
+    let mut state = (*state()).lock().await;
+    state.stream_warnings = stream_warnings;
+}
+
+pub async fn set_nonce_offset(nonce_offset: NonceOffset) {
+    let mut state = (*state()).lock().await;
+    state.nonce_offset = nonce_offset;
+}
+
+pub async fn set_run_seed(run_seed: Option<RunSeed>) {
+    let mut state = (*state()).lock().await;
+    state.fixed_run_seed = run_seed;
+}
+
+pub async fn set_instance_options(instance_options: InstanceOptions) {
+    let mut state = (*state()).lock().await;
+    state.instance_options = instance_options;
+}
+
+pub async fn set_salt_from_run_seed(salt_from_run_seed: bool) {
+    let mut state = (*state()).lock().await;
+    state.salt_from_run_seed = salt_from_run_seed;
+}
+
+pub async fn set_reproduction_log_path(reproduction_log_path: Option<PathBuf>) {
+    let mut state = (*state()).lock().await;
+    state.reproduction_log_path = reproduction_log_path;
+}
+
 pub async fn setup(api_url: String, api_key: String, player_id: String) {
     API.get_or_init(|| Api::new(api_url, api_key));
     PLAYER_ID.get_or_init(|| player_id);
@@ -481,8 +930,32 @@ pub async fn setup(api_url: String, api_key: String, player_id: String) {
             query_data,
             difficulty_samplers,
             selected_algorithms: HashMap::new(),
+            timeout_scalings: HashMap::new(),
+            default_timeouts: DefaultTimeouts::default(),
+            metadata_config: MetadataConfig::default(),
+            progress_throttle: ProgressThrottle::default(),
+            metrics: Metrics::default(),
+            generation_mode: GenerationMode::default(),
+            max_live_instances: None,
+            instance_dedup: false,
+            min_marginal_rate: None,
+            max_run_duration_ms: None,
+            nonce_offset: NonceOffset::default(),
+            fixed_run_seed: None,
+            run_seed: None,
+            run_nonce_offset: None,
+            instance_options: InstanceOptions::default(),
+            salt_from_run_seed: false,
+            reproduction_log_path: None,
             job: None,
+            warnings: Vec::new(),
+            stream_warnings: true,
             submission_errors: HashMap::new(),
+            version_pin: None,
+            duty_cycle: None,
+            accept_if: None,
+            run_warnings: None,
+            audit_log: None,
         })
     });
 }
@@ -0,0 +1,55 @@
+use super::run_seed::RunSeed;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::hash_map::RandomState,
+    hash::{BuildHasher, Hash, Hasher},
+};
+
+// where a run's workers start iterating nonces. Fixed starts every run at the same nonce, so a
+// fleet that keeps restarting keeps re-solving the same early nonces. Entropy draws a fresh offset
+// for every run; the drawn offset is recorded in the state, and passing it back as Fixed reproduces
+// the run
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum NonceOffset {
+    Fixed(u64),
+    Entropy,
+}
+
+impl Default for NonceOffset {
+    fn default() -> Self {
+        Self::Fixed(0)
+    }
+}
+
+impl NonceOffset {
+    // offset of the first nonce of a run, below limit. Entropy is seeded with now and the hash keys
+    // of the process, which differ between processes and between calls
+    pub fn resolve(&self, limit: u64, now: u64) -> u64 {
+        match self {
+            Self::Fixed(offset) => offset % limit.max(1),
+            Self::Entropy => {
+                let mut hasher = RandomState::new().build_hasher();
+                now.hash(&mut hasher);
+                StdRng::seed_from_u64(hasher.finish()).gen_range(0..limit.max(1))
+            }
+        }
+    }
+
+    // like resolve, but Entropy draws from the run seed, so the same seed gives the same offset
+    pub fn resolve_with_seed(&self, limit: u64, run_seed: RunSeed) -> u64 {
+        match self {
+            Self::Fixed(offset) => offset % limit.max(1),
+            Self::Entropy => run_seed.nonce_offset(limit),
+        }
+    }
+
+    // the first nonce of each of num_workers workers. the nonces are split into num_workers equal
+    // regions and every worker starts offset into its own region
+    pub fn starts(offset: u64, num_workers: u32) -> Vec<u64> {
+        let region = u64::MAX / num_workers.max(1) as u64;
+        (0..num_workers as u64)
+            .map(|x| region * x + offset)
+            .collect()
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+// limits how often progress is emitted, e.g. with a fast solver the attempt count changes on every
+// poll and printing each update floods the log. updates between emissions are coalesced: they are
+// skipped and the next emission reports the latest numbers. a limit of 0 is disabled, and with
+// both limits set an emission has to respect both
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProgressThrottle {
+    pub min_interval_ms: u64,
+    pub min_attempts: u64,
+    // time and attempt count of the last emission
+    #[serde(skip)]
+    last_emitted: Option<(u64, u64)>,
+}
+
+impl ProgressThrottle {
+    pub fn new(min_interval_ms: u64, min_attempts: u64) -> Self {
+        Self {
+            min_interval_ms,
+            min_attempts,
+            last_emitted: None,
+        }
+    }
+
+    // whether progress at time now_ms and num_attempts should be emitted. the first update always
+    // is. returning true counts as an emission
+    pub fn should_emit(&mut self, now_ms: u64, num_attempts: u64) -> bool {
+        let emit = match self.last_emitted {
+            None => true,
+            Some((last_ms, last_attempts)) => {
+                now_ms.saturating_sub(last_ms) >= self.min_interval_ms
+                    && num_attempts.saturating_sub(last_attempts) >= self.min_attempts
+            }
+        };
+        if emit {
+            self.last_emitted = Some((now_ms, num_attempts));
+        }
+        emit
+    }
+
+    // the next update is emitted regardless of the limits, e.g. when a new job starts counting
+    // attempts from 0
+    pub fn reset(&mut self) {
+        self.last_emitted = None;
+    }
+}
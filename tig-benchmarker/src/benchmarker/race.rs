@@ -0,0 +1,134 @@
+use super::in_flight::CancelToken;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+use tig_challenges::{
+    preprocessing::PreprocessingCache, ChallengeTrait, DifficultyTrait, SolutionTrait,
+};
+use tig_structs::config::WasmVMConfig;
+use tig_worker::{compute_solution, solution_qualifier, BenchmarkSettings, Solution};
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct RaceWinner {
+    pub algorithm_id: String,
+    pub solution: Solution,
+    pub qualifier: f64,
+}
+
+// runs the algorithms on the same nonce, at most max_concurrency at a time, and returns the solution
+// with the best qualifier, ties going to the algorithm listed first. seeds depend on the algorithm,
+// so each candidate solves and is verified under settings with its own algorithm_id. with a
+// target_qualifier, the first listed solution reaching it wins instead, so once one does the
+// candidates listed after it can only lose: those that haven't started are skipped and running
+// ones see their CancelToken canceled. either way the winner doesn't depend on which candidate
+// finishes first. not available in the browser, which can't spawn threads
+pub fn race_solve(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    algorithm_ids: &[String],
+    max_concurrency: usize,
+    target_qualifier: Option<f64>,
+    solve: impl Fn(&BenchmarkSettings, u64, &CancelToken) -> Option<Solution> + Sync,
+) -> Option<RaceWinner> {
+    let next = AtomicUsize::new(0);
+    let cancel_tokens: Vec<CancelToken> =
+        algorithm_ids.iter().map(|_| CancelToken::new()).collect();
+    // by position in algorithm_ids
+    let verified: Mutex<Vec<(usize, RaceWinner)>> = Mutex::new(Vec::new());
+    thread::scope(|s| {
+        for _ in 0..max_concurrency.clamp(1, algorithm_ids.len().max(1)) {
+            s.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let algorithm_id = match algorithm_ids.get(i) {
+                    Some(algorithm_id) => algorithm_id,
+                    None => break,
+                };
+                if cancel_tokens[i].is_canceled() {
+                    continue;
+                }
+                let mut settings = settings.clone();
+                settings.algorithm_id = algorithm_id.clone();
+                let solution = match solve(&settings, nonce, &cancel_tokens[i]) {
+                    Some(solution) => solution,
+                    None => continue,
+                };
+                // fails if the solution doesn't verify
+                let qualifier = match solution_qualifier(&settings, nonce, &solution) {
+                    Ok(qualifier) => qualifier,
+                    Err(_) => continue,
+                };
+                if target_qualifier.is_some_and(|target| qualifier >= target) {
+                    cancel_tokens[i + 1..].iter().for_each(CancelToken::cancel);
+                }
+                verified.lock().unwrap().push((
+                    i,
+                    RaceWinner {
+                        algorithm_id: algorithm_id.clone(),
+                        solution,
+                        qualifier,
+                    },
+                ));
+            });
+        }
+    });
+    let mut verified = verified.into_inner().unwrap();
+    verified.sort_by_key(|(i, _)| *i);
+    let reached_target = verified.iter().position(|(_, candidate)| {
+        target_qualifier.is_some_and(|target| candidate.qualifier >= target)
+    });
+    let winner = match reached_target {
+        Some(position) => verified.swap_remove(position),
+        // max_by keeps the last of equal elements, so iterate in reverse to keep the first listed
+        None => verified
+            .into_iter()
+            .rev()
+            .max_by(|(_, a), (_, b)| a.qualifier.total_cmp(&b.qualifier))?,
+    };
+    Some(winner.1)
+}
+
+// solves with native solvers of challenge C, which get each candidate's instance, generated from
+// its own settings, and the cache shared by the race. solvers look up what they preprocess with
+// instance_key, so candidates solving the same instance reuse each other's preprocessing
+pub fn native_solver<'a, C, S, D, const N: usize>(
+    cache: &'a PreprocessingCache,
+    solve: impl Fn(&str, &C, &PreprocessingCache, &CancelToken) -> Option<S> + Sync + 'a,
+) -> impl Fn(&BenchmarkSettings, u64, &CancelToken) -> Option<Solution> + Sync + 'a
+where
+    C: ChallengeTrait<S, D, N>,
+    S: SolutionTrait,
+    D: DifficultyTrait<N>,
+{
+    move |settings, nonce, cancel_token| {
+        let challenge =
+            C::generate_instance_from_vec(settings.calc_seeds(nonce), &settings.difficulty).ok()?;
+        let solution = solve(&settings.algorithm_id, &challenge, cache, cancel_token)?;
+        serde_json::to_value(solution).ok()?.as_object().cloned()
+    }
+}
+
+// solves with each algorithm's wasm. a wasm run can't be interrupted, so canceling only skips
+// algorithms that haven't started, see race_solve
+pub fn wasm_solver<'a>(
+    wasms: &'a HashMap<String, Vec<u8>>,
+    wasm_vm_config: &'a WasmVMConfig,
+) -> impl Fn(&BenchmarkSettings, u64, &CancelToken) -> Option<Solution> + Sync + 'a {
+    move |settings, nonce, _| {
+        compute_solution(
+            settings,
+            nonce,
+            wasms.get(&settings.algorithm_id)?,
+            wasm_vm_config.max_memory,
+            wasm_vm_config.max_fuel,
+        )
+        .ok()
+        .flatten()
+        .map(|solution_data| solution_data.solution)
+    }
+}
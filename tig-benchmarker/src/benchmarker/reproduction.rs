@@ -0,0 +1,86 @@
+use super::Result;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use tig_structs::core::BenchmarkSettings;
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::InstanceOptions;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ReproductionNonces {
+    // start inclusive, end exclusive
+    Range { start: u64, end: u64 },
+    List(Vec<u64>),
+    // a start inclusive, end exclusive range per worker of a run
+    Ranges(Vec<(u64, u64)>),
+}
+
+impl ReproductionNonces {
+    pub fn to_vec(&self) -> Vec<u64> {
+        match self {
+            Self::Range { start, end } => (*start..*end).collect(),
+            Self::List(nonces) => nonces.clone(),
+            Self::Ranges(ranges) => ranges.iter().flat_map(|&(start, end)| start..end).collect(),
+        }
+    }
+}
+
+// everything needed to rerun the exact instances of a run, e.g. one that failed in CI. the
+// benchmarker writes it when the run starts, so it exists even if the run crashes, and rewrites it
+// as the run's nonces grow
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReproductionLog {
+    pub settings: BenchmarkSettings,
+    pub instance_options: InstanceOptions,
+    pub nonces: ReproductionNonces,
+    // instance generation is pinned to the tig-challenges version
+    pub challenges_version: String,
+}
+
+impl ReproductionLog {
+    pub fn new(
+        settings: BenchmarkSettings,
+        instance_options: InstanceOptions,
+        nonces: ReproductionNonces,
+    ) -> Self {
+        Self {
+            settings,
+            instance_options,
+            nonces,
+            challenges_version: tig_challenges::VERSION.to_string(),
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read reproduction log {:?}: {}", path, e))?;
+        dejsonify(&json).map_err(|e| format!("Invalid reproduction log {:?}: {}", path, e))
+    }
+
+    pub fn write(&self, path: &Path) -> Result<()> {
+        fs::write(path, jsonify(self))
+            .map_err(|e| format!("Failed to write reproduction log {:?}: {}", path, e))
+    }
+
+    pub fn seeds(&self, nonce: u64) -> [u64; 8] {
+        self.instance_options.calc_seeds(&self.settings, nonce)
+    }
+}
+
+// reruns a logged scenario, calling run with every logged nonce and its seeds in the logged order.
+// fails if the log was recorded with a different tig-challenges version, as the instances could
+// differ
+pub fn reproduce<T>(log: &ReproductionLog, run: impl Fn(u64, [u64; 8]) -> T) -> Result<Vec<T>> {
+    if log.challenges_version != tig_challenges::VERSION {
+        return Err(format!(
+            "Reproduction log was recorded with tig-challenges {}, but this is {}",
+            log.challenges_version,
+            tig_challenges::VERSION
+        ));
+    }
+    Ok(log
+        .nonces
+        .to_vec()
+        .into_iter()
+        .map(|nonce| run(nonce, log.seeds(nonce)))
+        .collect())
+}
@@ -0,0 +1,73 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    hash::{BuildHasher, Hash, Hasher},
+    str::FromStr,
+};
+use tig_worker::InstanceOptions;
+
+// the one number a run is derived from: where its workers start iterating nonces, the rng that
+// samples what it benchmarks, and the seed salt of runs that salt their instances. given the same
+// job, a run seed determines every instance of the run, so passing it back reproduces the run.
+// each use draws from its own stream, so one drawing more numbers doesn't shift another
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RunSeed(pub u64);
+
+impl RunSeed {
+    // a fresh seed, seeded with now and the hash keys of the process like NonceOffset::Entropy
+    pub fn from_entropy(now: u64) -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        now.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    fn stream(&self, tag: &[u8; 8]) -> StdRng {
+        StdRng::seed_from_u64(self.0 ^ u64::from_le_bytes(*tag))
+    }
+
+    // offset of the first nonce of the run, below limit
+    pub fn nonce_offset(&self, limit: u64) -> u64 {
+        self.stream(b"offset\0\0").gen_range(0..limit.max(1))
+    }
+
+    // for sampling the run's settings, e.g. its challenge and difficulty
+    pub fn sampling_rng(&self) -> StdRng {
+        self.stream(b"sampling")
+    }
+
+    // for runs that salt their instances, e.g. local experiments. see calc_seeds_with_salt
+    pub fn seed_salt(&self) -> String {
+        format!("{:016x}", self.stream(b"salt\0\0\0\0").gen::<u64>())
+    }
+
+    // the instance options salted with the run's seed salt instead of any salt they had
+    pub fn salt(&self, instance_options: &InstanceOptions) -> InstanceOptions {
+        InstanceOptions {
+            seed_salt: Some(self.seed_salt()),
+            ..instance_options.clone()
+        }
+    }
+}
+
+impl fmt::Display for RunSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+// hex with a 0x prefix, as displayed, or decimal
+impl FromStr for RunSeed {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => s.parse::<u64>(),
+        };
+        parsed
+            .map(RunSeed)
+            .map_err(|e| format!("Invalid run seed '{}': {}", s, e))
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt, sync::Mutex};
+
+// something the run adjusted or worked around that the user should know about, but that doesn't
+// stop the run
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum RunWarning {
+    // the algorithm has no `requested` solver compiled into this build, so its nonces run on `used`
+    BackendFallback {
+        algorithm_id: String,
+        requested: String,
+        used: String,
+    },
+    VersionDrift {
+        nonce: u64,
+        drift: String,
+    },
+}
+
+impl fmt::Display for RunWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RunWarning::BackendFallback {
+                algorithm_id,
+                requested,
+                used,
+            } => write!(
+                f,
+                "no {} solver for {} in this build, running on {} only",
+                requested, algorithm_id, used
+            ),
+            RunWarning::VersionDrift { nonce, drift } => {
+                write!(f, "version drift for nonce {}: {}", nonce, drift)
+            }
+        }
+    }
+}
+
+// collects the warnings of a run, shared by all its workers. a warning equal to one already
+// recorded is dropped, so a condition hit on every nonce shows up once. if streamed, each warning
+// is also printed as it's recorded
+#[derive(Debug, Default)]
+pub struct RunWarnings {
+    warnings: Mutex<Vec<RunWarning>>,
+    stream: bool,
+}
+
+impl RunWarnings {
+    pub fn new(stream: bool) -> Self {
+        Self {
+            warnings: Mutex::new(Vec::new()),
+            stream,
+        }
+    }
+
+    pub fn record(&self, warning: RunWarning) {
+        let mut warnings = self.warnings.lock().unwrap();
+        if warnings.contains(&warning) {
+            return;
+        }
+        if self.stream {
+            let message = format!("Warning: {}", warning);
+            println!("{}", message);
+            #[cfg(feature = "browser")]
+            web_sys::console::log_1(&message.into());
+        }
+        warnings.push(warning);
+    }
+
+    // in the order they were first recorded
+    pub fn warnings(&self) -> Vec<RunWarning> {
+        self.warnings.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,103 @@
+use super::{
+    difficulty_surface::{execute_cell, SurfaceCell},
+    Job,
+};
+use tig_worker::calc_version_pin;
+
+pub trait SamplingPolicy {
+    // splits budget across cells. the result is aligned with cells and sums to budget
+    fn allocate(&self, cells: &[SurfaceCell], budget: u64) -> Vec<u64>;
+}
+
+#[derive(Debug, Clone)]
+pub struct UniformPolicy;
+
+impl SamplingPolicy for UniformPolicy {
+    fn allocate(&self, cells: &[SurfaceCell], budget: u64) -> Vec<u64> {
+        allocate_by_weight(&vec![1.0; cells.len()], budget)
+    }
+}
+
+// weights each difficulty by the variance of its solved/unsolved outcome, so samples concentrate
+// where the solve rate is furthest from 0 and 1. min_weight keeps every difficulty sampled
+#[derive(Debug, Clone)]
+pub struct VariancePolicy {
+    pub min_weight: f64,
+}
+
+impl SamplingPolicy for VariancePolicy {
+    fn allocate(&self, cells: &[SurfaceCell], budget: u64) -> Vec<u64> {
+        let weights: Vec<f64> = cells
+            .iter()
+            .map(|cell| {
+                // laplace smoothing so unexplored difficulties start at p = 0.5
+                let p = (cell.num_solutions as f64 + 1.0) / (cell.num_attempts as f64 + 2.0);
+                (p * (1.0 - p)).max(self.min_weight)
+            })
+            .collect();
+        allocate_by_weight(&weights, budget)
+    }
+}
+
+fn allocate_by_weight(weights: &[f64], budget: u64) -> Vec<u64> {
+    let total_weight: f64 = weights.iter().sum();
+    if weights.is_empty() || total_weight <= 0.0 {
+        return vec![0; weights.len()];
+    }
+    let shares: Vec<f64> = weights
+        .iter()
+        .map(|w| w / total_weight * budget as f64)
+        .collect();
+    let mut allocation: Vec<u64> = shares.iter().map(|s| s.floor() as u64).collect();
+    // hand out what flooring left over to the largest remainders
+    let mut order: Vec<usize> = (0..shares.len()).collect();
+    order.sort_by(|&a, &b| {
+        let rem_a = shares[a] - shares[a].floor();
+        let rem_b = shares[b] - shares[b].floor();
+        rem_b.partial_cmp(&rem_a).unwrap()
+    });
+    let remaining = budget - allocation.iter().sum::<u64>();
+    for &i in order.iter().take(remaining as usize) {
+        allocation[i] += 1;
+    }
+    allocation
+}
+
+// explores the given difficulties over num_rounds rounds. each round ms_per_round is split across
+// the difficulties by the policy, using the outcomes observed so far
+pub async fn execute(
+    job: &Job,
+    wasm: &Vec<u8>,
+    num_workers: u32,
+    difficulties: Vec<Vec<i32>>,
+    policy: &impl SamplingPolicy,
+    num_rounds: u32,
+    ms_per_round: u32,
+) -> Vec<SurfaceCell> {
+    let version_pin = calc_version_pin(wasm);
+    let mut cells: Vec<SurfaceCell> = difficulties
+        .into_iter()
+        .map(|difficulty| SurfaceCell {
+            difficulty,
+            num_attempts: 0,
+            num_solutions: 0,
+            elapsed_ms: 0,
+            version_pin: version_pin.clone(),
+        })
+        .collect();
+    for _ in 0..num_rounds {
+        let allocation = policy.allocate(&cells, ms_per_round as u64);
+        for (cell, ms) in cells.iter_mut().zip(allocation) {
+            if ms == 0 {
+                continue;
+            }
+            let mut job = job.clone();
+            job.settings.difficulty = cell.difficulty.clone();
+            let result = execute_cell(&job, wasm, num_workers, ms as u32, None).await;
+            cell.num_attempts += result.num_attempts;
+            cell.num_solutions += result.num_solutions;
+            cell.elapsed_ms += result.elapsed_ms;
+        }
+    }
+    cells
+}
@@ -0,0 +1,113 @@
+use super::{timing_trace::TraceOutcome, NonceIterator, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path, sync::Mutex};
+use tig_utils::{dejsonify, jsonify};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum ScheduleEvent {
+    // worker took nonce from its nonce_iter
+    Dispatched {
+        worker: usize,
+        nonce: u64,
+    },
+    Completed {
+        worker: usize,
+        nonce: u64,
+        outcome: TraceOutcome,
+    },
+}
+
+// opt-in record of the global order in which a run's workers took and finished their nonces, for
+// debugging issues that depend on how workers interleave. shared by all workers of a run
+#[derive(Debug, Default)]
+pub struct ScheduleRecorder {
+    events: Mutex<Vec<ScheduleEvent>>,
+}
+
+impl ScheduleRecorder {
+    pub fn new() -> Self {
+        Self {
+            events: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn dispatched(&self, worker: usize, nonce: u64) {
+        self.push(ScheduleEvent::Dispatched { worker, nonce });
+    }
+
+    pub fn completed(&self, worker: usize, nonce: u64, outcome: TraceOutcome) {
+        self.push(ScheduleEvent::Completed {
+            worker,
+            nonce,
+            outcome,
+        });
+    }
+
+    // the events recorded so far
+    pub fn log(&self) -> ScheduleLog {
+        ScheduleLog {
+            events: self.events.lock().unwrap().clone(),
+        }
+    }
+
+    fn push(&self, event: ScheduleEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ScheduleLog {
+    pub events: Vec<ScheduleEvent>,
+}
+
+impl ScheduleLog {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, jsonify(self))
+            .map_err(|e| format!("Failed to write schedule log {:?}: {}", path, e))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read schedule log {:?}: {}", path, e))?;
+        dejsonify(&json).map_err(|e| format!("Invalid schedule log {:?}: {}", path, e))
+    }
+
+    // nonces in the order workers took them
+    pub fn dispatch_order(&self) -> Vec<u64> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                ScheduleEvent::Dispatched { nonce, .. } => Some(*nonce),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // nonces in the order workers finished them
+    pub fn completion_order(&self) -> Vec<u64> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                ScheduleEvent::Completed { nonce, .. } => Some(*nonce),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub fn outcomes(&self) -> HashMap<u64, TraceOutcome> {
+        self.events
+            .iter()
+            .filter_map(|event| match event {
+                ScheduleEvent::Completed { nonce, outcome, .. } => Some((*nonce, *outcome)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    // the recorded nonces in dispatch order from a single iterator. a run given it as its only
+    // nonce_iter has one worker, so it replays the nonces serially in the recorded order
+    pub fn replay(&self) -> NonceIterator {
+        // from_vec pops from the back
+        NonceIterator::from_vec(self.dispatch_order().into_iter().rev().collect())
+    }
+}
@@ -7,15 +7,17 @@ use rand::{
 };
 use rand_distr::Distribution;
 use std::collections::HashMap;
-use tig_structs::core::*;
+use tig_structs::{config::MinMaxDifficulty, core::*};
+use tig_worker::InstanceOptions;
 
 pub async fn execute() -> Result<()> {
-    let job = if let Some(x) = find_settings_to_recompute().await? {
+    let mut job = if let Some(x) = find_settings_to_recompute().await? {
         x
     } else {
         pick_settings_to_benchmark().await?
     };
     let mut state = state().lock().await;
+    state.default_timeouts.apply(&mut job);
     (*state).job.replace(job.clone());
     let QueryData {
         latest_block,
@@ -51,6 +53,11 @@ pub async fn execute() -> Result<()> {
 }
 
 async fn find_settings_to_recompute() -> Result<Option<Job>> {
+    let State {
+        query_data,
+        metadata_config,
+        ..
+    } = &(*state().lock().await);
     let QueryData {
         latest_block,
         benchmarks,
@@ -58,7 +65,7 @@ async fn find_settings_to_recompute() -> Result<Option<Job>> {
         frauds,
         download_urls,
         ..
-    } = &state().lock().await.query_data;
+    } = query_data;
     for (benchmark_id, benchmark) in benchmarks.iter() {
         if !frauds.contains_key(benchmark_id)
             && !proofs.contains_key(benchmark_id)
@@ -77,6 +84,13 @@ async fn find_settings_to_recompute() -> Result<Option<Job>> {
                 solution_signature_threshold: u32::MAX, // is fine unless the player has committed fraud
                 sampled_nonces: Some(sampled_nonces),
                 wasm_vm_config: latest_block.config().wasm_vm.clone(),
+                metadata_config: metadata_config.clone(),
+                max_solution_size: latest_block
+                    .config()
+                    .benchmark_submissions
+                    .max_solution_size,
+                timeout_ms: None,
+                instance_options: InstanceOptions::default(),
             }));
         }
     }
@@ -87,7 +101,13 @@ async fn pick_settings_to_benchmark() -> Result<Job> {
     let State {
         query_data,
         selected_algorithms,
+        timeout_scalings,
+        default_timeouts,
+        metadata_config,
         difficulty_samplers,
+        run_seed,
+        instance_options,
+        salt_from_run_seed,
         ..
     } = &(*state().lock().await);
     let QueryData {
@@ -98,7 +118,10 @@ async fn pick_settings_to_benchmark() -> Result<Job> {
         algorithms_by_challenge,
         ..
     } = query_data;
-    let mut rng = StdRng::seed_from_u64(time() as u64);
+    let mut rng = match run_seed {
+        Some(run_seed) => run_seed.sampling_rng(),
+        None => StdRng::seed_from_u64(time() as u64),
+    };
     let challenge = pick_challenge(&mut rng, player_data, challenges, selected_algorithms)?;
     let selected_algorithm_id = get_algorithm_id(
         algorithms_by_challenge,
@@ -107,6 +130,18 @@ async fn pick_settings_to_benchmark() -> Result<Job> {
         &selected_algorithms[&challenge.details.name],
     )?;
     let difficulty = difficulty_samplers[&challenge.id].sample(&mut rng);
+    // left unset without a scaling, so the challenge's default applies
+    let timeout_ms = timeout_scalings
+        .get(&challenge.details.name)
+        .map(|timeout_scaling| {
+            let min_difficulty =
+                latest_block.config().difficulty.parameters[&challenge.id].min_difficulty();
+            timeout_scaling.timeout_ms(
+                &difficulty,
+                &min_difficulty,
+                default_timeouts.default_ms(&challenge.id),
+            )
+        });
     Ok(Job {
         benchmark_id: Alphanumeric.sample_string(&mut rng, 32),
         download_url: get_download_url(&selected_algorithm_id, download_urls)?,
@@ -120,6 +155,16 @@ async fn pick_settings_to_benchmark() -> Result<Job> {
         solution_signature_threshold: *challenge.block_data().solution_signature_threshold(),
         sampled_nonces: None,
         wasm_vm_config: latest_block.config().wasm_vm.clone(),
+        metadata_config: metadata_config.clone(),
+        max_solution_size: latest_block
+            .config()
+            .benchmark_submissions
+            .max_solution_size,
+        timeout_ms,
+        instance_options: match run_seed.filter(|_| *salt_from_run_seed) {
+            Some(run_seed) => run_seed.salt(instance_options),
+            None => instance_options.clone(),
+        },
     })
 }
 
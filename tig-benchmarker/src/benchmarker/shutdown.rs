@@ -0,0 +1,159 @@
+use super::{
+    solution_flusher::{SolutionFlusher, SolutionSubmitter},
+    Job, NonceIterator, Result,
+};
+use crate::future_utils::{sleep, spawn, time, Mutex};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::SolutionData;
+
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownSignal(Arc<AtomicBool>);
+
+impl ShutdownSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trigger(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// triggers signal on SIGTERM, e.g. from a process manager, or ctrl-c
+pub fn listen_for_shutdown(signal: ShutdownSignal) {
+    spawn(async move {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal as unix_signal, SignalKind};
+            let mut sigterm = unix_signal(SignalKind::terminate()).unwrap();
+            tokio::select! {
+                _ = sigterm.recv() => {}
+                _ = tokio::signal::ctrl_c() => {}
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+        signal.trigger();
+    });
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ShutdownCheckpoint {
+    pub benchmark_id: String,
+    pub num_attempts: u64,
+    pub num_flushed: u64,
+    // solutions that failed to submit, kept so they can be submitted after a restart
+    pub unsubmitted_solutions: Vec<SolutionData>,
+    // false if some worker was still solving after max_wait_ms
+    pub drained: bool,
+    // next nonce of each worker's nonce iterator by its index, so each shard of the run resumes
+    // where it stopped. empty for a run over sampled nonces
+    #[serde(default)]
+    pub cursors: BTreeMap<usize, u64>,
+}
+
+impl ShutdownCheckpoint {
+    pub fn load(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read checkpoint {:?}: {}", path, e))?;
+        dejsonify(&json).map_err(|e| format!("Invalid checkpoint {:?}: {}", path, e))
+    }
+
+    // where each shard of a run of benchmark_id starts, given where they would start afresh. a shard
+    // with a cursor resumes at it, the others start afresh, as do all shards of another benchmark
+    pub fn resume_starts(&self, benchmark_id: &str, starts: &[u64]) -> Vec<u64> {
+        starts
+            .iter()
+            .enumerate()
+            .map(|(i, &start)| match self.cursors.get(&i) {
+                Some(&cursor) if self.benchmark_id == benchmark_id => cursor,
+                _ => start,
+            })
+            .collect()
+    }
+}
+
+// empties every nonce iterator, returning the cursor of each that had one by its index. nonces
+// already taken are still being solved
+pub async fn stop_dispatch(nonce_iters: &[Arc<Mutex<NonceIterator>>]) -> BTreeMap<usize, u64> {
+    let mut cursors = BTreeMap::new();
+    for (i, nonce_iter) in nonce_iters.iter().enumerate() {
+        let mut nonce_iter = (*nonce_iter).lock().await;
+        if let Some(cursor) = nonce_iter.cursor() {
+            cursors.insert(i, cursor);
+        }
+        nonce_iter.empty();
+    }
+    cursors
+}
+
+// stops dispatch and waits up to max_wait_ms for workers to finish the nonce they are on. each
+// worker holds a clone of its nonce iterator until it exits, so the run is drained once the caller
+// holds the only references
+pub async fn drain_run(nonce_iters: &[Arc<Mutex<NonceIterator>>], max_wait_ms: u64) -> bool {
+    for nonce_iter in nonce_iters.iter() {
+        (*nonce_iter).lock().await.empty();
+    }
+    let start = time();
+    loop {
+        if nonce_iters.iter().all(|x| Arc::strong_count(x) == 1) {
+            return true;
+        }
+        if time() - start >= max_wait_ms {
+            return false;
+        }
+        sleep(10).await;
+    }
+}
+
+// drains the run, submits every solution found and writes a checkpoint to checkpoint_path before
+// returning, so a restart loses no work
+pub async fn shutdown(
+    job: &Job,
+    nonce_iters: &[Arc<Mutex<NonceIterator>>],
+    solutions_data: &Arc<Mutex<Vec<SolutionData>>>,
+    flusher: &mut SolutionFlusher,
+    submitter: &impl SolutionSubmitter,
+    checkpoint_path: &Path,
+    max_wait_ms: u64,
+) -> Result<ShutdownCheckpoint> {
+    let cursors = stop_dispatch(nonce_iters).await;
+    let drained = drain_run(nonce_iters, max_wait_ms).await;
+    flusher.push((*solutions_data).lock().await.drain(..));
+    if let Err(e) = flusher.flush(submitter, &job.benchmark_id).await {
+        println!(
+            "Error posting solutions data: {}. Keeping {} solutions in checkpoint",
+            e,
+            flusher.num_pending()
+        );
+    }
+    let mut num_attempts = 0;
+    for nonce_iter in nonce_iters.iter() {
+        num_attempts += (*nonce_iter).lock().await.attempts();
+    }
+    let checkpoint = ShutdownCheckpoint {
+        benchmark_id: job.benchmark_id.clone(),
+        num_attempts,
+        num_flushed: flusher.num_flushed(),
+        unsubmitted_solutions: flusher.pending().to_vec(),
+        drained,
+        cursors,
+    };
+    fs::write(checkpoint_path, jsonify(&checkpoint))
+        .map_err(|e| format!("Failed to write checkpoint: {}", e))?;
+    Ok(checkpoint)
+}
@@ -0,0 +1,89 @@
+use super::Result;
+use crate::future_utils::time;
+use tig_utils::{jsonify, post};
+use tig_worker::SolutionData;
+
+#[allow(async_fn_in_trait)]
+pub trait SolutionSubmitter {
+    async fn submit(&self, benchmark_id: &str, solutions_data: &[SolutionData]) -> Result<()>;
+}
+
+pub struct MasterSubmitter {
+    pub master_url: String,
+}
+
+impl SolutionSubmitter for MasterSubmitter {
+    async fn submit(&self, benchmark_id: &str, solutions_data: &[SolutionData]) -> Result<()> {
+        post::<String>(
+            &format!("{}/solutions_data/{}", self.master_url, benchmark_id),
+            &jsonify(&solutions_data),
+            Some(vec![(
+                "Content-Type".to_string(),
+                "application/json".to_string(),
+            )]),
+        )
+        .await
+        .map_err(|e| format!("Failed to post solutions data: {:?}", e))?;
+        Ok(())
+    }
+}
+
+// accumulates solutions and submits them once flush_count are pending or flush_interval_ms has
+// passed since the last flush. solutions are only dropped once a submission succeeds
+pub struct SolutionFlusher {
+    pending: Vec<SolutionData>,
+    flush_interval_ms: u64,
+    flush_count: usize,
+    last_flush: u64,
+    num_flushed: u64,
+}
+
+impl SolutionFlusher {
+    pub fn new(flush_interval_ms: u64, flush_count: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            flush_interval_ms,
+            flush_count,
+            last_flush: time(),
+            num_flushed: 0,
+        }
+    }
+
+    pub fn push(&mut self, solutions_data: impl IntoIterator<Item = SolutionData>) {
+        self.pending.extend(solutions_data);
+    }
+
+    pub fn num_pending(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn pending(&self) -> &[SolutionData] {
+        &self.pending
+    }
+
+    pub fn num_flushed(&self) -> u64 {
+        self.num_flushed
+    }
+
+    pub fn should_flush(&self) -> bool {
+        !self.pending.is_empty()
+            && (self.pending.len() >= self.flush_count
+                || time() - self.last_flush >= self.flush_interval_ms)
+    }
+
+    pub async fn flush(
+        &mut self,
+        submitter: &impl SolutionSubmitter,
+        benchmark_id: &str,
+    ) -> Result<usize> {
+        if self.pending.is_empty() {
+            return Ok(0);
+        }
+        submitter.submit(benchmark_id, &self.pending).await?;
+        let n = self.pending.len();
+        self.pending.clear();
+        self.last_flush = time();
+        self.num_flushed += n as u64;
+        Ok(n)
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use tig_challenges::SolveOutcome;
+
+// shared by all workers of a run. counts what the solver reported per nonce, so instances proved to
+// have no solution are told apart from ones the solver gave up on. solved counts what the solver
+// returned, not what verified, see solutions_count for that
+#[derive(Debug, Default)]
+pub struct OutcomeCounts {
+    num_solved: AtomicU64,
+    num_proved_no_solution: AtomicU64,
+    num_gave_up: AtomicU64,
+    num_timed_out: AtomicU64,
+}
+
+impl OutcomeCounts {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record<T>(&self, outcome: &SolveOutcome<T>) {
+        match outcome {
+            SolveOutcome::Solved(_) => &self.num_solved,
+            SolveOutcome::ProvedNoSolution => &self.num_proved_no_solution,
+            SolveOutcome::GaveUp => &self.num_gave_up,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+    }
+
+    // a solve that ran over the job's timeout. it's also counted by its outcome
+    pub fn record_timed_out(&self) {
+        self.num_timed_out.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn num_solved(&self) -> u64 {
+        self.num_solved.load(Ordering::Relaxed)
+    }
+
+    pub fn num_proved_no_solution(&self) -> u64 {
+        self.num_proved_no_solution.load(Ordering::Relaxed)
+    }
+
+    pub fn num_gave_up(&self) -> u64 {
+        self.num_gave_up.load(Ordering::Relaxed)
+    }
+
+    pub fn num_timed_out(&self) -> u64 {
+        self.num_timed_out.load(Ordering::Relaxed)
+    }
+
+    pub fn num_attempts(&self) -> u64 {
+        self.num_solved() + self.num_proved_no_solution() + self.num_gave_up()
+    }
+}
@@ -0,0 +1,103 @@
+use super::{
+    difficulty_surface::{execute_cell, SurfaceCell},
+    stats::wilson_interval,
+    Job,
+};
+use std::sync::{
+    atomic::{AtomicBool, AtomicU64, Ordering},
+    Arc,
+};
+
+const DEFAULT_Z: f64 = 1.96;
+const DEFAULT_MIN_SAMPLES: u64 = 30;
+
+// shared by all workers of a run. sequential sampling: the run stops once the wilson interval of the
+// solve rate is narrower than max_ci_width, instead of after a fixed number of nonces
+#[derive(Debug)]
+pub struct SolveRateTarget {
+    max_ci_width: f64,
+    z: f64,
+    min_samples: u64,
+    num_attempts: AtomicU64,
+    num_solutions: AtomicU64,
+    reached: AtomicBool,
+}
+
+impl SolveRateTarget {
+    // 95% interval
+    pub fn new(max_ci_width: f64) -> Self {
+        Self::with_z(max_ci_width, DEFAULT_Z, DEFAULT_MIN_SAMPLES)
+    }
+
+    pub fn with_z(max_ci_width: f64, z: f64, min_samples: u64) -> Self {
+        Self {
+            max_ci_width,
+            z,
+            min_samples,
+            num_attempts: AtomicU64::new(0),
+            num_solutions: AtomicU64::new(0),
+            reached: AtomicBool::new(false),
+        }
+    }
+
+    // returns whether the target has been reached
+    pub fn record(&self, solved: bool) -> bool {
+        let num_attempts = self.num_attempts.fetch_add(1, Ordering::Relaxed) + 1;
+        let num_solutions = if solved {
+            self.num_solutions.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.num_solutions.load(Ordering::Relaxed)
+        };
+        if num_attempts >= self.min_samples {
+            let (lower, upper) = wilson_interval(num_solutions, num_attempts, self.z);
+            if upper - lower <= self.max_ci_width {
+                self.reached.store(true, Ordering::Relaxed);
+            }
+        }
+        self.is_reached()
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.reached.load(Ordering::Relaxed)
+    }
+
+    pub fn num_attempts(&self) -> u64 {
+        self.num_attempts.load(Ordering::Relaxed)
+    }
+
+    pub fn solve_rate(&self) -> f64 {
+        let num_attempts = self.num_attempts();
+        if num_attempts == 0 {
+            0.0
+        } else {
+            self.num_solutions.load(Ordering::Relaxed) as f64 / num_attempts as f64
+        }
+    }
+
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        wilson_interval(
+            self.num_solutions.load(Ordering::Relaxed),
+            self.num_attempts(),
+            self.z,
+        )
+    }
+}
+
+// runs the job until the solve rate's 95% interval is narrower than max_ci_width, or max_ms has
+// passed, whichever comes first
+pub async fn execute(
+    job: &Job,
+    wasm: &Vec<u8>,
+    num_workers: u32,
+    max_ci_width: f64,
+    max_ms: u32,
+) -> SurfaceCell {
+    execute_cell(
+        job,
+        wasm,
+        num_workers,
+        max_ms,
+        Some(Arc::new(SolveRateTarget::new(max_ci_width))),
+    )
+    .await
+}
@@ -0,0 +1,34 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+// delays the start of each worker task so they don't all take their first nonce and generate their
+// first instance at the same instant. the delays are spread over max_delay_ms and seeded, so a run
+// ramps up the same way every time
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct StaggeredStart {
+    pub max_delay_ms: u32,
+    pub seed: u64,
+}
+
+impl StaggeredStart {
+    pub fn new(max_delay_ms: u32, seed: u64) -> Self {
+        Self { max_delay_ms, seed }
+    }
+
+    // start delay of each of num_tasks tasks. task i gets a jittered delay within the i-th of
+    // num_tasks equal slots of max_delay_ms, so no two tasks share a slot
+    pub fn delays(&self, num_tasks: usize) -> Vec<u32> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let slot_ms = self.max_delay_ms as u64 / num_tasks.max(1) as u64;
+        (0..num_tasks as u64)
+            .map(|i| {
+                let jitter = if slot_ms > 0 {
+                    rng.gen_range(0..slot_ms)
+                } else {
+                    0
+                };
+                (i * slot_ms + jitter) as u32
+            })
+            .collect()
+    }
+}
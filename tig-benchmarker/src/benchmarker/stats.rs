@@ -0,0 +1,140 @@
+use serde::Serialize;
+use tig_worker::{expected_solve_probability, BenchmarkSettings};
+
+// per-task stats that merge into a global report. only integer sums, maxima and a sorted sample
+// list are kept, so merging is commutative and associative and the result is independent of the
+// order in which tasks finish
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct StatsAccumulator {
+    pub num_attempts: u64,
+    pub num_solutions: u64,
+    pub total_fuel_consumed: u128,
+    // sizes of solutions as submitted, see SolutionData::solution_size
+    pub total_solution_size: u64,
+    pub max_solution_size: u64,
+    // search_space_log2 of the hardest instance solved, comparable across challenges. a guide for
+    // where to set the difficulty of later runs
+    pub max_solved_difficulty: Option<f64>,
+    // sorted ascending
+    fuel_samples: Vec<u64>,
+}
+
+impl StatsAccumulator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, fuel_consumed: u64, solved: bool) {
+        self.num_attempts += 1;
+        self.num_solutions += solved as u64;
+        self.total_fuel_consumed += fuel_consumed as u128;
+        let idx = self.fuel_samples.partition_point(|&x| x <= fuel_consumed);
+        self.fuel_samples.insert(idx, fuel_consumed);
+    }
+
+    // records the size of a solved attempt's solution. call once per solution
+    pub fn record_solution_size(&mut self, solution_size: usize) {
+        self.total_solution_size += solution_size as u64;
+        self.max_solution_size = self.max_solution_size.max(solution_size as u64);
+    }
+
+    // records the normalized difficulty of a solved attempt's instance, see
+    // tig_worker::search_space_log2
+    pub fn record_solved_difficulty(&mut self, difficulty: f64) {
+        self.max_solved_difficulty = Some(
+            self.max_solved_difficulty
+                .map_or(difficulty, |x| x.max(difficulty)),
+        );
+    }
+
+    pub fn merge(&mut self, other: &StatsAccumulator) {
+        self.num_attempts += other.num_attempts;
+        self.num_solutions += other.num_solutions;
+        self.total_fuel_consumed += other.total_fuel_consumed;
+        self.total_solution_size += other.total_solution_size;
+        self.max_solution_size = self.max_solution_size.max(other.max_solution_size);
+        if let Some(difficulty) = other.max_solved_difficulty {
+            self.record_solved_difficulty(difficulty);
+        }
+        let mut merged = Vec::with_capacity(self.fuel_samples.len() + other.fuel_samples.len());
+        let (mut i, mut j) = (0, 0);
+        while i < self.fuel_samples.len() && j < other.fuel_samples.len() {
+            if self.fuel_samples[i] <= other.fuel_samples[j] {
+                merged.push(self.fuel_samples[i]);
+                i += 1;
+            } else {
+                merged.push(other.fuel_samples[j]);
+                j += 1;
+            }
+        }
+        merged.extend_from_slice(&self.fuel_samples[i..]);
+        merged.extend_from_slice(&other.fuel_samples[j..]);
+        self.fuel_samples = merged;
+    }
+
+    pub fn solve_rate(&self) -> Option<f64> {
+        if self.num_attempts == 0 {
+            None
+        } else {
+            Some(self.num_solutions as f64 / self.num_attempts as f64)
+        }
+    }
+
+    // see wilson_interval. two algorithms' solve rates only differ significantly if their
+    // intervals don't overlap
+    pub fn solve_rate_interval(&self, z: f64) -> (f64, f64) {
+        wilson_interval(self.num_solutions, self.num_attempts, z)
+    }
+
+    pub fn mean_fuel_consumed(&self) -> Option<f64> {
+        if self.num_attempts == 0 {
+            None
+        } else {
+            Some(self.total_fuel_consumed as f64 / self.num_attempts as f64)
+        }
+    }
+
+    pub fn mean_solution_size(&self) -> Option<f64> {
+        if self.num_solutions == 0 {
+            None
+        } else {
+            Some(self.total_solution_size as f64 / self.num_solutions as f64)
+        }
+    }
+
+    // nearest-rank percentile, q in [0, 100]
+    pub fn fuel_percentile(&self, q: f64) -> Option<u64> {
+        if self.fuel_samples.is_empty() {
+            return None;
+        }
+        let rank = (q.clamp(0.0, 100.0) / 100.0 * self.fuel_samples.len() as f64).ceil() as usize;
+        Some(self.fuel_samples[rank.max(1) - 1])
+    }
+}
+
+// wilson score interval for a binomial proportion. unlike the normal approximation it stays within
+// [0, 1] and behaves for solve rates near 0 or 1. z = 1.96 gives a 95% interval
+pub fn wilson_interval(num_successes: u64, num_trials: u64, z: f64) -> (f64, f64) {
+    if num_trials == 0 {
+        return (0.0, 1.0);
+    }
+    let n = num_trials as f64;
+    let p = num_successes as f64 / n;
+    let z2 = z * z;
+    let denominator = 1.0 + z2 / n;
+    let center = (p + z2 / (2.0 * n)) / denominator;
+    let half_width = z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+    (
+        (center - half_width).max(0.0),
+        (center + half_width).min(1.0),
+    )
+}
+
+// nonces a run at the settings' difficulty is expected to take to find num_solutions, from
+// ChallengeTrait::expected_solve_probability. None where the challenge has no estimate
+pub fn expected_num_nonces(settings: &BenchmarkSettings, num_solutions: u64) -> Option<u64> {
+    match expected_solve_probability(settings) {
+        Ok(Some(p)) if p > 0.0 => Some((num_solutions as f64 / p).ceil() as u64),
+        _ => None,
+    }
+}
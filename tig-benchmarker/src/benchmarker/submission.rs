@@ -0,0 +1,133 @@
+use super::{Job, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt};
+use tig_structs::{
+    api::{SubmitBenchmarkReq, SubmitProofReq},
+    core::{SolutionData, SolutionMetaData},
+};
+
+// what the api client submits for a job. a benchmark job submits its solutions' meta data with one
+// of the solutions as a sample, a recompute job submits the solutions to the nonces the protocol
+// sampled as the benchmark's proof
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum SubmissionPayload {
+    Benchmark(Box<SubmitBenchmarkReq>),
+    Proof(SubmitProofReq),
+}
+
+// why a verified solution was left out of a submission
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ExclusionReason {
+    TooLarge {
+        solution_size: usize,
+        max_solution_size: usize,
+    },
+}
+
+impl fmt::Display for ExclusionReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExclusionReason::TooLarge {
+                solution_size,
+                max_solution_size,
+            } => write!(
+                f,
+                "solution size of {} bytes exceeds the max of {}",
+                solution_size, max_solution_size
+            ),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExcludedSolution {
+    pub nonce: u64,
+    pub reason: ExclusionReason,
+}
+
+fn exclusion_reason(job: &Job, solution_data: &SolutionData) -> Option<ExclusionReason> {
+    let max_solution_size = job.max_solution_size?;
+    let solution_size = solution_data.solution_size();
+    (solution_size > max_solution_size).then_some(ExclusionReason::TooLarge {
+        solution_size,
+        max_solution_size,
+    })
+}
+
+// the solutions as the protocol computes them, without the version pins and verification tokens the
+// benchmarker keeps for itself. the protocol compares submitted solutions to its own, so anything
+// submitted goes through here
+pub fn strip_local_metadata(solutions_data: &[SolutionData]) -> Vec<SolutionData> {
+    solutions_data
+        .iter()
+        .map(|d| SolutionData {
+            version_pin: None,
+            verification_token: None,
+            ..d.clone()
+        })
+        .collect()
+}
+
+// solutions are in the order they were collected. version pins and verification tokens are local
+// metadata and are not submitted. solutions the protocol would reject, e.g. over its size limit,
+// are left out of a benchmark and returned with the reason. a proof can't leave out a sampled
+// nonce, so one of those fails it instead
+pub fn to_submission(
+    job: &Job,
+    solutions_data: &[SolutionData],
+) -> Result<(SubmissionPayload, Vec<ExcludedSolution>)> {
+    let solutions_data = strip_local_metadata(solutions_data);
+    match job.sampled_nonces.as_ref() {
+        None => {
+            let mut included = Vec::new();
+            let mut excluded = Vec::new();
+            for d in solutions_data {
+                match exclusion_reason(job, &d) {
+                    Some(reason) => excluded.push(ExcludedSolution {
+                        nonce: d.nonce,
+                        reason,
+                    }),
+                    None => included.push(d),
+                }
+            }
+            let solution_data = included.first().cloned().ok_or_else(|| {
+                format!(
+                    "No solutions to submit for {} ({} excluded)",
+                    job.benchmark_id,
+                    excluded.len()
+                )
+            })?;
+            let req = SubmitBenchmarkReq {
+                settings: job.settings.clone(),
+                solutions_meta_data: included.into_iter().map(SolutionMetaData::from).collect(),
+                solution_data,
+                schema_version: None,
+            };
+            Ok((SubmissionPayload::Benchmark(Box::new(req)), excluded))
+        }
+        Some(sampled_nonces) => {
+            let mut by_nonce: HashMap<u64, SolutionData> =
+                solutions_data.into_iter().map(|d| (d.nonce, d)).collect();
+            let solutions_data = sampled_nonces
+                .iter()
+                .map(|nonce| {
+                    by_nonce
+                        .remove(nonce)
+                        .ok_or_else(|| format!("No solution for sampled nonce {}", nonce))
+                })
+                .collect::<Result<Vec<SolutionData>>>()?;
+            if let Some((nonce, reason)) = solutions_data
+                .iter()
+                .find_map(|d| exclusion_reason(job, d).map(|reason| (d.nonce, reason)))
+            {
+                return Err(format!("Can't submit sampled nonce {}: {}", nonce, reason));
+            }
+            let req = SubmitProofReq {
+                benchmark_id: job.benchmark_id.clone(),
+                solutions_data,
+                schema_version: None,
+            };
+            Ok((SubmissionPayload::Proof(req), Vec::new()))
+        }
+    }
+}
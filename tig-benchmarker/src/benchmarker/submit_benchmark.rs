@@ -1,6 +1,9 @@
-use super::{api, state, Job, QueryData, Result};
+use super::{
+    api, state,
+    submission::{to_submission, SubmissionPayload},
+    Job, QueryData, Result,
+};
 use crate::future_utils::sleep;
-use tig_api::SubmitBenchmarkReq;
 
 const MAX_RETRIES: u32 = 3;
 
@@ -15,13 +18,17 @@ pub async fn execute(job: &Job) -> Result<String> {
         let proof = proofs
             .get(&job.benchmark_id)
             .ok_or_else(|| format!("Job proof should exist"))?;
-        let settings = benchmark.settings.clone();
-        let solutions_meta_data = benchmark.solutions_meta_data.take().unwrap();
-        let solution_data = proof.solutions_data().first().unwrap().clone();
-        SubmitBenchmarkReq {
-            settings,
-            solutions_meta_data,
-            solution_data,
+        // the payload's meta data is rebuilt from the proof's solutions, in the same order
+        benchmark.solutions_meta_data.take();
+        let (payload, excluded) = to_submission(job, proof.solutions_data())?;
+        for x in excluded.iter() {
+            println!("Excluding nonce {} from submission: {}", x.nonce, x.reason);
+        }
+        match payload {
+            SubmissionPayload::Benchmark(req) => *req,
+            SubmissionPayload::Proof(_) => {
+                return Err(format!("Job {} is not a benchmark", job.benchmark_id))
+            }
         }
     };
     for attempt in 1..=MAX_RETRIES {
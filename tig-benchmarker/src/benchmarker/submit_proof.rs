@@ -1,4 +1,4 @@
-use super::{api, Result};
+use super::{api, submission::strip_local_metadata, Result};
 use crate::future_utils::sleep;
 use tig_api::SubmitProofReq;
 use tig_worker::SolutionData;
@@ -8,7 +8,8 @@ const MAX_RETRIES: u32 = 3;
 pub async fn execute(benchmark_id: String, solutions_data: Vec<SolutionData>) -> Result<()> {
     let req = SubmitProofReq {
         benchmark_id,
-        solutions_data,
+        solutions_data: strip_local_metadata(&solutions_data),
+        schema_version: None,
     };
     for attempt in 1..=MAX_RETRIES {
         println!("Submission attempt {} of {}", attempt, MAX_RETRIES);
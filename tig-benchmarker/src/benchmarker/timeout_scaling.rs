@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+// a fixed timeout is too short at high difficulty and wasteful at low. scaling it with difficulty
+// gives each nonce a proportionate budget without a blanket long timeout. the scaled timeout
+// replaces the challenge's default, see DefaultTimeouts
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum TimeoutScaling {
+    // the challenge's default timeout at every difficulty
+    Fixed,
+    // base_ms + sum of ms_per_step[i] * (difficulty[i] - min_difficulty[i])
+    Linear {
+        base_ms: u64,
+        ms_per_step: Vec<u64>,
+    },
+    // base_ms * product of growth_per_step[i] ^ (difficulty[i] - min_difficulty[i])
+    Exponential {
+        base_ms: u64,
+        growth_per_step: Vec<f64>,
+    },
+}
+
+impl TimeoutScaling {
+    pub fn timeout_ms(&self, difficulty: &[i32], min_difficulty: &[i32], default_ms: u64) -> u64 {
+        let steps = difficulty
+            .iter()
+            .zip(min_difficulty)
+            .map(|(d, min)| (d - min).max(0) as u64);
+        match self {
+            TimeoutScaling::Fixed => default_ms,
+            TimeoutScaling::Linear {
+                base_ms,
+                ms_per_step,
+            } => steps
+                .zip(ms_per_step)
+                .fold(*base_ms, |timeout_ms, (steps, per_step)| {
+                    timeout_ms.saturating_add(steps.saturating_mul(*per_step))
+                }),
+            TimeoutScaling::Exponential {
+                base_ms,
+                growth_per_step,
+            } => {
+                let timeout_ms = steps
+                    .zip(growth_per_step)
+                    .fold(*base_ms as f64, |timeout_ms, (steps, growth)| {
+                        timeout_ms * growth.powf(steps as f64)
+                    });
+                // casting saturates, including for inf
+                timeout_ms as u64
+            }
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use super::Result;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::OpenOptions,
+    io::{BufWriter, Write},
+    path::Path,
+    sync::Mutex,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum TraceOutcome {
+    Solved,
+    // the solver returned a solution that didn't verify
+    Invalid,
+    // the solution verified, but execute's accept_if rejected it
+    Rejected,
+    // skipped as a repeat of an instance already attempted, see InstanceDedup
+    Duplicate,
+    ProvedNoSolution,
+    GaveUp,
+    Error,
+}
+
+impl TraceOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TraceOutcome::Solved => "solved",
+            TraceOutcome::Invalid => "invalid",
+            TraceOutcome::Rejected => "rejected",
+            TraceOutcome::Duplicate => "duplicate",
+            TraceOutcome::ProvedNoSolution => "proved_no_solution",
+            TraceOutcome::GaveUp => "gave_up",
+            TraceOutcome::Error => "error",
+        }
+    }
+}
+
+// opt-in csv of per-nonce timings, shared by all workers of a run. rows are appended through a
+// buffer so workers don't wait on the disk, and reach the file when the buffer fills, on flush or
+// when the trace is dropped
+pub struct TimingTrace {
+    writer: Mutex<BufWriter<Box<dyn Write + Send>>>,
+}
+
+impl TimingTrace {
+    pub const HEADER: &'static str = "nonce,generation_ms,solve_ms,outcome";
+
+    // appends to the file at path, so a trace can span several runs. the header is only written to
+    // a new or empty file
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open timing trace {:?}: {}", path, e))?;
+        let is_empty = file
+            .metadata()
+            .map_err(|e| format!("Failed to read timing trace {:?}: {}", path, e))?
+            .len()
+            == 0;
+        let trace = Self::new(Box::new(file));
+        if is_empty {
+            trace.write_line(Self::HEADER)?;
+        }
+        Ok(trace)
+    }
+
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            writer: Mutex::new(BufWriter::new(writer)),
+        }
+    }
+
+    pub fn record(
+        &self,
+        nonce: u64,
+        generation_ms: u64,
+        solve_ms: u64,
+        outcome: TraceOutcome,
+    ) -> Result<()> {
+        self.write_line(&format!(
+            "{},{},{},{}",
+            nonce,
+            generation_ms,
+            solve_ms,
+            outcome.as_str()
+        ))
+    }
+
+    pub fn flush(&self) -> Result<()> {
+        self.writer
+            .lock()
+            .unwrap()
+            .flush()
+            .map_err(|e| format!("Failed to flush timing trace: {}", e))
+    }
+
+    fn write_line(&self, line: &str) -> Result<()> {
+        writeln!(self.writer.lock().unwrap(), "{}", line)
+            .map_err(|e| format!("Failed to write timing trace: {}", e))
+    }
+}
@@ -0,0 +1,25 @@
+use tig_worker::{solution_qualifier, BenchmarkSettings, SolutionData};
+
+// the k solutions with the highest ChallengeTrait::qualifier, best first, for when a block caps how
+// many solutions a player may submit. ties go to the lower nonce, so the selection doesn't depend
+// on the order solutions were collected in. solutions that don't verify are never selected
+pub fn select_top_k(
+    settings: &BenchmarkSettings,
+    solutions_data: &[SolutionData],
+    k: usize,
+) -> Vec<SolutionData> {
+    let mut ranked: Vec<(f64, &SolutionData)> = solutions_data
+        .iter()
+        .filter_map(|d| {
+            solution_qualifier(settings, d.nonce, &d.solution)
+                .ok()
+                .map(|qualifier| (qualifier, d))
+        })
+        .collect();
+    ranked.sort_by(|(qualifier_a, a), (qualifier_b, b)| {
+        qualifier_b
+            .total_cmp(qualifier_a)
+            .then(a.nonce.cmp(&b.nonce))
+    });
+    ranked.into_iter().take(k).map(|(_, d)| d.clone()).collect()
+}
@@ -0,0 +1,68 @@
+use super::Job;
+use serde::Serialize;
+use std::collections::HashSet;
+use tig_worker::{verify_solution_with_options, SolutionData};
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct BundleFailure {
+    pub nonce: u64,
+    pub reason: String,
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct BundleReport {
+    pub num_entries: u32,
+    pub passed_nonces: Vec<u64>,
+    pub failures: Vec<BundleFailure>,
+    // every passing solution is one qualifier, before the protocol caps a player's qualifiers at
+    // their cutoff
+    pub num_qualifiers: u32,
+}
+
+impl BundleReport {
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+// verifies every entry of a submission the way the protocol would and reports each failing nonce,
+// instead of stopping at the first failure
+pub fn verify_bundle(job: &Job, entries: &[(u64, SolutionData)]) -> BundleReport {
+    let mut report = BundleReport {
+        num_entries: entries.len() as u32,
+        ..Default::default()
+    };
+    let mut seen_nonces = HashSet::new();
+    for (nonce, solution_data) in entries.iter() {
+        let result = if solution_data.nonce != *nonce {
+            Err(format!("SolutionData is for nonce {}", solution_data.nonce))
+        } else if !seen_nonces.insert(*nonce) {
+            Err("Duplicate nonce".to_string())
+        } else if solution_data.calc_solution_signature() > job.solution_signature_threshold {
+            Err(format!(
+                "Solution signature {} exceeds threshold {}",
+                solution_data.calc_solution_signature(),
+                job.solution_signature_threshold
+            ))
+        } else {
+            verify_solution_with_options(
+                &job.settings,
+                *nonce,
+                &solution_data.solution,
+                &job.instance_options,
+            )
+            .map_err(|e| e.to_string())
+        };
+        match result {
+            Ok(()) => {
+                report.passed_nonces.push(*nonce);
+                report.num_qualifiers += 1;
+            }
+            Err(reason) => report.failures.push(BundleFailure {
+                nonce: *nonce,
+                reason,
+            }),
+        }
+    }
+    report
+}
@@ -0,0 +1,32 @@
+use super::{Job, Result};
+use serde::Serialize;
+use tig_worker::{highest_difficulty_satisfied, Solution};
+
+// the hardest of a list of difficulty thresholds a solution meets, e.g. for a verifier paying out
+// reward tiers
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub enum HighestTierSatisfied {
+    // not even the easiest threshold
+    None,
+    // index of the threshold and its difficulty
+    Tier { index: usize, difficulty: Vec<i32> },
+}
+
+// verifies a solution of the job's instance at the nonce against thresholds sorted from easiest to
+// hardest, without a full verification per threshold. see highest_difficulty_satisfied
+pub fn verify_solution_tiers(
+    job: &Job,
+    nonce: u64,
+    solution: &Solution,
+    thresholds: &[Vec<i32>],
+) -> Result<HighestTierSatisfied> {
+    let index = highest_difficulty_satisfied(&job.settings, nonce, solution, thresholds)
+        .map_err(|e| e.to_string())?;
+    Ok(match index {
+        Some(index) => HighestTierSatisfied::Tier {
+            index,
+            difficulty: thresholds[index].clone(),
+        },
+        None => HighestTierSatisfied::None,
+    })
+}
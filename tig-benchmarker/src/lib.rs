@@ -1,5 +1,58 @@
 mod benchmarker;
 mod future_utils;
+pub use benchmarker::accept_if::AcceptIf;
+pub use benchmarker::audit_log::{read_audit_log, AuditLog, AuditRecord};
+pub use benchmarker::backend_comparison::{compare_backends, wasm_backend, BackendComparison};
+pub use benchmarker::baseline::{check_regression, BenchmarkBaseline};
+pub use benchmarker::capabilities::{capabilities, Capabilities};
+pub use benchmarker::circuit_breaker::CircuitBreaker;
+pub use benchmarker::clock::{system_clock, Clock, MockClock, SystemClock};
+pub use benchmarker::coverage::{coverage, CoverageReport};
+pub use benchmarker::default_timeouts::DefaultTimeouts;
+pub use benchmarker::difficulty_surface::SurfaceCell;
+pub use benchmarker::duty_cycle::{DutyCycle, ThrottleSignal};
+pub use benchmarker::failure_sink::{failed_nonces, read_failures, Failure, FailureSink};
+pub use benchmarker::in_flight::{CancelToken, InFlightTracker};
+pub use benchmarker::instance_dedup::InstanceDedup;
+pub use benchmarker::instance_pipeline::{
+    spawn_producer, Cancellable, GenerationMode, InstanceQueue, InstanceSource,
+};
+pub use benchmarker::job_scheduler::{FairnessPolicy, JobScheduler};
+pub use benchmarker::live_instances::{LiveInstanceLimit, LivePermit};
+pub use benchmarker::marginal_rate::MarginalRateFloor;
+pub use benchmarker::max_run_duration::MaxRunDuration;
+pub use benchmarker::metadata_config::MetadataConfig;
+pub use benchmarker::metrics::Metrics;
+pub use benchmarker::mixed_execution::{
+    execute_mixed, Backend, BackendSplit, MixedResult, SplitPolicy,
+};
+pub use benchmarker::nonce_offset::NonceOffset;
+pub use benchmarker::progress_throttle::ProgressThrottle;
+pub use benchmarker::race::{native_solver, race_solve, wasm_solver, RaceWinner};
+pub use benchmarker::reproduction::{reproduce, ReproductionLog, ReproductionNonces};
+pub use benchmarker::run_benchmark;
+pub use benchmarker::run_seed::RunSeed;
+pub use benchmarker::run_warnings::{RunWarning, RunWarnings};
+pub use benchmarker::sampling_policy::{SamplingPolicy, UniformPolicy, VariancePolicy};
+pub use benchmarker::schedule_log::{ScheduleEvent, ScheduleLog, ScheduleRecorder};
+#[cfg(feature = "standalone")]
+pub use benchmarker::shutdown::{
+    drain_run, listen_for_shutdown, shutdown, stop_dispatch, ShutdownCheckpoint, ShutdownSignal,
+};
+pub use benchmarker::solution_flusher::{SolutionFlusher, SolutionSubmitter};
+pub use benchmarker::solve_outcome::OutcomeCounts;
+pub use benchmarker::solve_rate_target::SolveRateTarget;
+pub use benchmarker::staggered_start::StaggeredStart;
+pub use benchmarker::stats::{expected_num_nonces, wilson_interval, StatsAccumulator};
+pub use benchmarker::submission::{
+    strip_local_metadata, to_submission, ExcludedSolution, ExclusionReason, SubmissionPayload,
+};
+pub use benchmarker::timeout_scaling::TimeoutScaling;
+pub use benchmarker::timing_trace::{TimingTrace, TraceOutcome};
+pub use benchmarker::top_k::select_top_k;
+pub use benchmarker::verify_bundle::{verify_bundle, BundleFailure, BundleReport};
+pub use benchmarker::verify_tiers::{verify_solution_tiers, HighestTierSatisfied};
+pub use benchmarker::{Job, NonceIterator};
 
 #[cfg(feature = "browser")]
 mod exports {
@@ -12,6 +65,11 @@ mod exports {
         serde_wasm_bindgen::to_value(&state).unwrap()
     }
 
+    #[wasm_bindgen]
+    pub fn capabilities() -> JsValue {
+        serde_wasm_bindgen::to_value(&benchmarker::capabilities::capabilities()).unwrap()
+    }
+
     #[wasm_bindgen]
     pub async fn start(num_workers: u32, ms_per_benchmark: u32) {
         benchmarker::start(num_workers, ms_per_benchmark).await;
@@ -3,14 +3,42 @@
 
 mod benchmarker;
 mod future_utils;
-use benchmarker::{Job, NonceIterator};
-use clap::{value_parser, Arg, Command};
-use future_utils::{sleep, Mutex};
-use std::{collections::HashMap, fs, path::PathBuf, sync::Arc};
+use benchmarker::{
+    audit_log::AuditLog,
+    circuit_breaker::CircuitBreaker,
+    duty_cycle::{DutyCycle, ThrottleSignal},
+    failure_sink::FailureSink,
+    instance_pipeline::GenerationMode,
+    max_run_duration::MaxRunDuration,
+    nonce_offset::NonceOffset,
+    progress_throttle::ProgressThrottle,
+    run_benchmark::ExecuteOptions,
+    run_seed::RunSeed,
+    run_warnings::RunWarnings,
+    schedule_log::ScheduleRecorder,
+    shutdown::{listen_for_shutdown, shutdown, ShutdownCheckpoint, ShutdownSignal},
+    solution_flusher::{MasterSubmitter, SolutionFlusher},
+    staggered_start::StaggeredStart,
+    timing_trace::TimingTrace,
+    Job, NonceIterator,
+};
+use clap::{value_parser, Arg, ArgAction, Command};
+use future_utils::{sleep, spawn, time, Mutex};
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::{atomic::AtomicU64, Arc},
+};
+use tig_challenges::vector_search::MAX_QUANTIZATION_LEVELS;
 use tig_structs::core::*;
-use tig_utils::{dejsonify, get, jsonify, post};
+use tig_utils::{dejsonify, get, jsonify};
+use tig_worker::InstanceOptions;
 use warp::Filter;
 
+// how long a shutdown waits for workers to finish the nonce they are on
+const SHUTDOWN_MAX_WAIT_MS: u64 = 30000;
+
 fn cli() -> Command {
     Command::new("TIG Benchmarker")
         .about("Standalone benchmarker")
@@ -74,12 +102,198 @@ fn cli() -> Command {
                 .default_value("5000000")
                 .value_parser(value_parser!(u64)),
         )
+        .arg(
+            Arg::new("random-offset")
+                .long("random-offset")
+                .help("(Optional) Start each benchmark's nonces at a random offset so restarts don't re-solve the same nonces. The offset is printed. Ignored by slaves")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("flush")
+                .long("flush")
+                .help("(Optional) Set interval in milliseconds between slave submissions of solutions")
+                .default_value("0")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("batch")
+                .long("batch")
+                .help("(Optional) Set number of pending solutions that triggers a slave submission")
+                .default_value("100")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("stagger")
+                .long("stagger")
+                .help("(Optional) Spread the start of a slave's workers over this many milliseconds")
+                .default_value("0")
+                .value_parser(value_parser!(u32)),
+        )
+        .arg(
+            Arg::new("trace")
+                .long("trace")
+                .help("(Optional) Append per-nonce timings of a slave to this csv file")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("failures")
+                .long("failures")
+                .help("(Optional) Append the nonces a slave doesn't solve, with their outcome and error, to this file")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("audit")
+                .long("audit")
+                .help("(Optional) Append every accepted solution's nonce, qualifier, fingerprint and timestamp to this file, for an audit trail")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("schedule")
+                .long("schedule")
+                .help("(Optional) Write the order in which a slave's workers took and finished nonces to this json file when a job ends, for replaying it serially")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("progress")
+                .long("progress")
+                .help("(Optional) Set minimum milliseconds between progress updates")
+                .default_value("0")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("progress-attempts")
+                .long("progress-attempts")
+                .help("(Optional) Set minimum number of new attempts between progress updates")
+                .default_value("0")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("duty-cycle")
+                .long("duty-cycle")
+                .help("(Optional) Set fraction of the time workers spend solving, e.g. 0.7 to keep a laptop cool")
+                .default_value("1.0")
+                .value_parser(value_parser!(f64)),
+        )
+        .arg(
+            Arg::new("throttle-file")
+                .long("throttle-file")
+                .help("(Optional) Only apply the duty cycle while this file exists, e.g. created by a thermal monitor")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("pipeline-depth")
+                .long("pipeline-depth")
+                .help("(Optional) Pre-generate up to this many instances per worker on a separate task. 0 generates each instance right before solving it. Ignored by slaves")
+                .default_value("0")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("max-live-instances")
+                .long("max-live-instances")
+                .help("(Optional) Cap the pre-generated and in-flight instances of all workers at once, to bound memory on large instances. Only applies with --pipeline-depth. Ignored by slaves")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("dedup-instances")
+                .long("dedup-instances")
+                .help("(Optional) Skip nonces whose instance repeats one already attempted. Only applies when instances are generated right before solving. Ignored by slaves")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("min-marginal-rate")
+                .long("min-marginal-rate")
+                .help("(Optional) Stop a benchmark early once its solve rate over the last 100 nonces falls below this fraction. Ignored by slaves")
+                .value_parser(value_parser!(f64)),
+        )
+        .arg(
+            Arg::new("max-run-duration")
+                .long("max-run-duration")
+                .help("(Optional) Stop every benchmark after this many milliseconds, whatever its other settings, as a safety net for unattended runs")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("default-timeout")
+                .long("default-timeout")
+                .help("(Optional) Override a challenge's default solve timeout, e.g. c002=120000. A solve over its timeout counts as timed out, its solution is kept. Can be repeated. Ignored by slaves")
+                .value_parser(parse_default_timeout)
+                .action(ArgAction::Append),
+        )
+        .arg(
+            Arg::new("run-seed")
+                .long("run-seed")
+                .help("(Optional) Seed every benchmark with this run seed, as printed at the start of a run, to reproduce that run. Implies --random-offset. Ignored by slaves")
+                .value_parser(|s: &str| s.parse::<RunSeed>()),
+        )
+        .arg(
+            Arg::new("seed-salt")
+                .long("seed-salt")
+                .help("(Optional) Salt the seeds of every benchmark's instances, giving instances disjoint from the protocol's for independent experiments. Salted benchmarks are never submitted. Ignored by slaves")
+                .value_parser(value_parser!(String)),
+        )
+        .arg(
+            Arg::new("salt-from-run-seed")
+                .long("salt-from-run-seed")
+                .help("(Optional) Like --seed-salt, but with a salt drawn from each benchmark's run seed, so --run-seed also reproduces its instances. Ignored by slaves")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("seed-salt"),
+        )
+        .arg(
+            Arg::new("stretch-rounds")
+                .long("stretch-rounds")
+                .help("(Optional) Re-hash the seeds of every benchmark's instances this many rounds, for exploring how it changes their hardness. Stretched benchmarks are never submitted. Ignored by slaves")
+                .default_value("0")
+                .value_parser(value_parser!(u32)),
+        )
+        .arg(
+            Arg::new("quantization-levels")
+                .long("quantization-levels")
+                .help("(Optional) Snap every vector search benchmark's instances to this many levels per coordinate and verify them with integer distances. Quantized benchmarks are never submitted. Ignored by slaves")
+                .value_parser(value_parser!(u32).range(1..=MAX_QUANTIZATION_LEVELS as i64)),
+        )
+        .arg(
+            Arg::new("dataset")
+                .long("dataset")
+                .help("(Optional) Generate every vehicle routing and vector search benchmark's instances from the json dataset at this path, which slaves read from the same path. Dataset benchmarks are never submitted. Ignored by slaves")
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with("quantization-levels"),
+        )
+        .arg(
+            Arg::new("reproduction-log")
+                .long("reproduction-log")
+                .help("(Optional) Write the settings, instance options and nonces of each benchmark to this file as it runs, for rerunning its exact instances. Ignored by slaves")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("quiet-warnings")
+                .long("quiet-warnings")
+                .help("(Optional) Don't print warnings as they happen. The master still reports them in its state")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("checkpoint")
+                .long("checkpoint")
+                .help("(Optional) Set path of the checkpoint a slave writes when it shuts down")
+                .default_value("checkpoint.json")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("caps")
+                .long("caps")
+                .help("(Optional) Print the capabilities of this build and exit")
+                .action(ArgAction::SetTrue)
+                .exclusive(true),
+        )
 }
 
 #[tokio::main]
 async fn main() {
     let matches = cli().get_matches();
 
+    if matches.get_flag("caps") {
+        println!("{}", jsonify(&benchmarker::capabilities::capabilities()));
+        return;
+    }
+
     let algorithms_path = matches.get_one::<PathBuf>("ALGORITHMS_SELECTION").unwrap();
     let num_workers = *matches.get_one::<u32>("workers").unwrap();
     let port = *matches.get_one::<u16>("port").unwrap();
@@ -88,8 +302,68 @@ async fn main() {
     let api_key = matches.get_one::<String>("API_KEY").unwrap().clone();
     let player_id = matches.get_one::<String>("PLAYER_ID").unwrap().clone();
     let nonce_offset = matches.get_one::<u64>("offset").unwrap().clone();
+    let random_offset = matches.get_flag("random-offset");
+    let flush_interval = *matches.get_one::<u64>("flush").unwrap();
+    let flush_count = *matches.get_one::<usize>("batch").unwrap();
+    let stagger_ms = *matches.get_one::<u32>("stagger").unwrap();
+    let trace_path = matches.get_one::<PathBuf>("trace");
+    let failures_path = matches.get_one::<PathBuf>("failures");
+    let audit_log = matches
+        .get_one::<PathBuf>("audit")
+        .map(|path| Arc::new(AuditLog::open(path).unwrap()));
+    let schedule_path = matches.get_one::<PathBuf>("schedule");
+    let checkpoint_path = matches.get_one::<PathBuf>("checkpoint").unwrap();
+    let progress_throttle = ProgressThrottle::new(
+        *matches.get_one::<u64>("progress").unwrap(),
+        *matches.get_one::<u64>("progress-attempts").unwrap(),
+    );
+    let duty_cycle = *matches.get_one::<f64>("duty-cycle").unwrap();
+    let duty_cycle = (duty_cycle < 1.0).then(|| {
+        let signal = matches
+            .get_one::<PathBuf>("throttle-file")
+            .map(|path| watch_throttle_file(path.clone()));
+        Arc::new(DutyCycle::new(duty_cycle, signal))
+    });
+    let max_live_instances = matches.get_one::<usize>("max-live-instances").copied();
+    let generation_mode = match *matches.get_one::<usize>("pipeline-depth").unwrap() {
+        0 => GenerationMode::Inline,
+        queue_depth => GenerationMode::Pipelined { queue_depth },
+    };
+    let instance_dedup = matches.get_flag("dedup-instances");
+    let stream_warnings = !matches.get_flag("quiet-warnings");
+    let min_marginal_rate = matches.get_one::<f64>("min-marginal-rate").copied();
+    let max_run_duration_ms = matches.get_one::<u64>("max-run-duration").copied();
+    let run_seed = matches.get_one::<RunSeed>("run-seed").copied();
+    let salt_from_run_seed = matches.get_flag("salt-from-run-seed");
+    let instance_options = InstanceOptions {
+        seed_salt: matches.get_one::<String>("seed-salt").cloned(),
+        stretch_rounds: *matches.get_one::<u32>("stretch-rounds").unwrap(),
+        quantization_levels: matches.get_one::<u32>("quantization-levels").copied(),
+        dataset: matches.get_one::<PathBuf>("dataset").cloned(),
+    };
+    let reproduction_log_path = matches.get_one::<PathBuf>("reproduction-log").cloned();
+    let default_timeouts: Vec<(String, u64)> = matches
+        .get_many::<(String, u64)>("default-timeout")
+        .map_or_else(Vec::new, |x| x.cloned().collect());
     if let Some(master) = matches.get_one::<String>("master") {
-        slave_node(master, port, num_workers).await;
+        slave_node(
+            master,
+            port,
+            num_workers,
+            flush_interval,
+            flush_count,
+            stagger_ms,
+            trace_path,
+            failures_path,
+            schedule_path,
+            checkpoint_path,
+            progress_throttle,
+            duty_cycle,
+            stream_warnings,
+            max_run_duration_ms,
+            audit_log,
+        )
+        .await;
     } else {
         master_node(
             api_url,
@@ -100,19 +374,127 @@ async fn main() {
             algorithms_path,
             port,
             nonce_offset,
+            progress_throttle,
+            duty_cycle,
+            generation_mode,
+            max_live_instances,
+            instance_dedup,
+            random_offset,
+            stream_warnings,
+            min_marginal_rate,
+            max_run_duration_ms,
+            run_seed,
+            instance_options,
+            salt_from_run_seed,
+            reproduction_log_path,
+            default_timeouts,
+            audit_log,
         )
         .await
     }
 }
 
-async fn slave_node(master: &String, port: u16, num_workers: u32) {
+// overwrites the file with the schedule of the ending job
+fn save_schedule(path: Option<&PathBuf>, schedule_recorder: &Option<Arc<ScheduleRecorder>>) {
+    if let (Some(path), Some(schedule_recorder)) = (path, schedule_recorder) {
+        if let Err(e) = schedule_recorder.log().save(path) {
+            println!("{}", e);
+        }
+    }
+}
+
+// CHALLENGE_ID=MS of --default-timeout
+fn parse_default_timeout(s: &str) -> Result<(String, u64), String> {
+    let (challenge_id, timeout_ms) = s
+        .split_once('=')
+        .ok_or_else(|| format!("Expected CHALLENGE_ID=MS, got '{}'", s))?;
+    let timeout_ms = timeout_ms
+        .parse::<u64>()
+        .map_err(|e| format!("Invalid timeout '{}': {}", timeout_ms, e))?;
+    Ok((challenge_id.to_string(), timeout_ms))
+}
+
+// sets the signal while the file exists
+fn watch_throttle_file(path: PathBuf) -> ThrottleSignal {
+    let signal = ThrottleSignal::new();
+    let watched = signal.clone();
+    spawn(async move {
+        loop {
+            watched.set(path.exists());
+            sleep(1000).await;
+        }
+    });
+    signal
+}
+
+async fn slave_node(
+    master: &String,
+    port: u16,
+    num_workers: u32,
+    flush_interval: u64,
+    flush_count: usize,
+    stagger_ms: u32,
+    trace_path: Option<&PathBuf>,
+    failures_path: Option<&PathBuf>,
+    schedule_path: Option<&PathBuf>,
+    checkpoint_path: &PathBuf,
+    mut progress_throttle: ProgressThrottle,
+    duty_cycle: Option<Arc<DutyCycle>>,
+    stream_warnings: bool,
+    max_run_duration_ms: Option<u64>,
+    audit_log: Option<Arc<AuditLog>>,
+) {
     let master_url = format!("http://{}:{}", master, port);
+    let shutdown_signal = ShutdownSignal::new();
+    listen_for_shutdown(shutdown_signal.clone());
+    let submitter = MasterSubmitter {
+        master_url: master_url.clone(),
+    };
     let mut job: Option<Job> = None;
     let mut nonce_iters: Vec<Arc<Mutex<NonceIterator>>> = Vec::new();
     let mut solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
-    let mut solutions_count = Arc::new(Mutex::new(0u32));
-    let mut num_solutions = 0;
+    let mut solutions_count = Arc::new(AtomicU64::new(0));
+    let mut circuit_breaker = Arc::new(CircuitBreaker::default());
+    let mut flusher = SolutionFlusher::new(flush_interval, flush_count);
+    let timing_trace = trace_path.map(|path| Arc::new(TimingTrace::open(path).unwrap()));
+    let failure_sink = failures_path.map(|path| Arc::new(FailureSink::open(path).unwrap()));
+    let mut schedule_recorder = schedule_path.map(|_| Arc::new(ScheduleRecorder::new()));
+    let mut max_run_duration = None;
     loop {
+        if shutdown_signal.is_triggered() {
+            println!("Shutting down");
+            if let Some(Err(e)) = timing_trace.as_ref().map(|x| x.flush()) {
+                println!("{}", e);
+            }
+            if let Some(Err(e)) = failure_sink.as_ref().map(|x| x.flush()) {
+                println!("{}", e);
+            }
+            if let Some(Err(e)) = audit_log.as_ref().map(|x| x.flush()) {
+                println!("{}", e);
+            }
+            save_schedule(schedule_path, &schedule_recorder);
+            if let Some(job) = job.as_ref().filter(|x| x.sampled_nonces.is_none()) {
+                match shutdown(
+                    job,
+                    &nonce_iters,
+                    &solutions_data,
+                    &mut flusher,
+                    &submitter,
+                    checkpoint_path,
+                    SHUTDOWN_MAX_WAIT_MS,
+                )
+                .await
+                {
+                    Ok(checkpoint) => println!(
+                        "Wrote checkpoint to {:?}: {} solutions unsubmitted",
+                        checkpoint_path,
+                        checkpoint.unsubmitted_solutions.len()
+                    ),
+                    Err(e) => println!("Error shutting down: {}", e),
+                }
+            }
+            return;
+        }
         let next_job = match get::<String>(&format!("{}/job", master_url), None).await {
             Ok(resp) => dejsonify::<Option<Job>>(&resp).unwrap(),
             Err(e) => {
@@ -129,9 +511,38 @@ async fn slave_node(master: &String, port: u16, num_workers: u32) {
                 (*(*nonce_iter).lock().await).empty();
             }
             nonce_iters.clear();
+            if let Some(job) = job.as_ref() {
+                // last chance to submit solutions of the ending job
+                flusher.push(solutions_data.lock().await.drain(..));
+                if let Err(e) = flusher.flush(&submitter, &job.benchmark_id).await {
+                    println!(
+                        "Error posting solutions data: {}. Dropping {} solutions",
+                        e,
+                        flusher.num_pending()
+                    );
+                }
+            }
+            if job.is_some() {
+                save_schedule(schedule_path, &schedule_recorder);
+            }
+            if let Some(Err(e)) = audit_log.as_ref().map(|x| x.flush()) {
+                println!("{}", e);
+            }
+            schedule_recorder = schedule_path.map(|_| Arc::new(ScheduleRecorder::new()));
             solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
-            solutions_count = Arc::new(Mutex::new(0u32));
-            num_solutions = 0;
+            solutions_count = Arc::new(AtomicU64::new(0));
+            circuit_breaker = Arc::new(CircuitBreaker::default());
+            flusher = SolutionFlusher::new(flush_interval, flush_count);
+            progress_throttle.reset();
+            if let Some(duty_cycle) = duty_cycle.as_ref() {
+                if job.is_some() {
+                    println!(
+                        "Applied a duty cycle of {:.0}%",
+                        duty_cycle.applied() * 100.0
+                    );
+                }
+                duty_cycle.reset();
+            }
             if next_job
                 .as_ref()
                 .is_some_and(|x| x.sampled_nonces.is_none())
@@ -167,14 +578,23 @@ async fn slave_node(master: &String, port: u16, num_workers: u32) {
                 };
                 println!("Got nonce offset: {}", offset);
 
+                let starts: Vec<u64> = (0..num_workers)
+                    .map(|x| offset + u64::MAX / num_workers as u64 * x as u64)
+                    .collect();
+                // each worker resumes where it stopped if the slave was shut down during this job
+                let starts = match ShutdownCheckpoint::load(checkpoint_path) {
+                    Ok(checkpoint) if checkpoint.benchmark_id == job.benchmark_id => {
+                        println!("Resuming from checkpoint {:?}", checkpoint_path);
+                        checkpoint.resume_starts(&job.benchmark_id, &starts)
+                    }
+                    _ => starts,
+                };
+
                 // variables that are shared by workers
-                nonce_iters = (0..num_workers)
+                max_run_duration = max_run_duration_ms.map(MaxRunDuration::new);
+                nonce_iters = starts
                     .into_iter()
-                    .map(|x| {
-                        Arc::new(Mutex::new(NonceIterator::from_u64(
-                            offset + u64::MAX / num_workers as u64 * x as u64,
-                        )))
-                    })
+                    .map(|start| Arc::new(Mutex::new(NonceIterator::from_u64(start))))
                     .collect();
                 println!("Starting benchmark");
                 benchmarker::run_benchmark::execute(
@@ -183,6 +603,21 @@ async fn slave_node(master: &String, port: u16, num_workers: u32) {
                     &wasm,
                     solutions_data.clone(),
                     solutions_count.clone(),
+                    circuit_breaker.clone(),
+                    ExecuteOptions {
+                        // seeded with the offset, so slaves ramp up differently
+                        staggered_start: (stagger_ms > 0)
+                            .then(|| StaggeredStart::new(stagger_ms, offset)),
+                        timing_trace: timing_trace.clone(),
+                        duty_cycle: duty_cycle.clone(),
+                        failure_sink: failure_sink.clone(),
+                        schedule_recorder: schedule_recorder.clone(),
+                        // a slave has no report, so its warnings are only worth recording if printed
+                        run_warnings: stream_warnings.then(|| Arc::new(RunWarnings::new(true))),
+                        max_run_duration: max_run_duration.clone(),
+                        audit_log: audit_log.clone(),
+                        ..Default::default()
+                    },
                 )
                 .await;
             }
@@ -191,36 +626,45 @@ async fn slave_node(master: &String, port: u16, num_workers: u32) {
         }
         if job.as_ref().is_some_and(|x| x.sampled_nonces.is_none()) {
             let job = job.as_ref().unwrap();
-            let mut solutions_data = solutions_data.lock().await;
-            let n = solutions_data.len();
-            if n > 0 {
-                num_solutions += n as u32;
-                let data: Vec<SolutionData> = solutions_data.drain(..).collect();
-                println!("Posting {} solutions", n);
-                if let Err(e) = post::<String>(
-                    &format!("{}/solutions_data/{}", master_url, job.benchmark_id),
-                    &jsonify(&data),
-                    Some(vec![(
-                        "Content-Type".to_string(),
-                        "application/json".to_string(),
-                    )]),
-                )
-                .await
-                {
-                    println!("Error posting solutions data: {:?}", e);
+            flusher.push(solutions_data.lock().await.drain(..));
+            if flusher.should_flush() {
+                println!("Posting {} solutions", flusher.num_pending());
+                if let Err(e) = flusher.flush(&submitter, &job.benchmark_id).await {
+                    println!(
+                        "Error posting solutions data: {}. Retaining {} solutions",
+                        e,
+                        flusher.num_pending()
+                    );
                     sleep(5000).await;
                     continue;
                 }
             }
+            if circuit_breaker.is_tripped() {
+                println!("{}. Waiting for next job", circuit_breaker.error());
+                sleep(5000).await;
+                continue;
+            }
+            if let Some(x) = max_run_duration.as_ref() {
+                if x.enforce(&nonce_iters).await {
+                    println!(
+                        "Stopped at the max run duration of {}ms. Waiting for next job",
+                        x.max_ms()
+                    );
+                    max_run_duration = None;
+                }
+            }
             let mut num_attempts = 0;
             for nonce_iter in nonce_iters.iter().cloned() {
                 let nonce_iter = (*nonce_iter).lock().await;
                 num_attempts += nonce_iter.attempts();
             }
-            println!(
-                "Computed {} solutions out of {} instances",
-                num_solutions, num_attempts
-            );
+            if progress_throttle.should_emit(time(), num_attempts) {
+                println!(
+                    "Computed {} solutions out of {} instances",
+                    flusher.num_flushed() + flusher.num_pending() as u64,
+                    num_attempts
+                );
+            }
             sleep(100).await;
         } else {
             println!("No job, sleeping 100ms");
@@ -238,8 +682,43 @@ async fn master_node(
     algorithms_path: &PathBuf,
     port: u16,
     nonce_offset: u64,
+    progress_throttle: ProgressThrottle,
+    duty_cycle: Option<Arc<DutyCycle>>,
+    generation_mode: GenerationMode,
+    max_live_instances: Option<usize>,
+    instance_dedup: bool,
+    random_offset: bool,
+    stream_warnings: bool,
+    min_marginal_rate: Option<f64>,
+    max_run_duration_ms: Option<u64>,
+    run_seed: Option<RunSeed>,
+    instance_options: InstanceOptions,
+    salt_from_run_seed: bool,
+    reproduction_log_path: Option<PathBuf>,
+    default_timeouts: Vec<(String, u64)>,
+    audit_log: Option<Arc<AuditLog>>,
 ) {
     benchmarker::setup(api_url, api_key, player_id).await;
+    benchmarker::set_progress_throttle(progress_throttle).await;
+    benchmarker::set_duty_cycle(duty_cycle).await;
+    benchmarker::set_generation_mode(generation_mode).await;
+    benchmarker::set_max_live_instances(max_live_instances).await;
+    benchmarker::set_instance_dedup(instance_dedup).await;
+    benchmarker::set_stream_warnings(stream_warnings).await;
+    benchmarker::set_min_marginal_rate(min_marginal_rate).await;
+    benchmarker::set_max_run_duration(max_run_duration_ms).await;
+    benchmarker::set_run_seed(run_seed).await;
+    benchmarker::set_instance_options(instance_options).await;
+    benchmarker::set_salt_from_run_seed(salt_from_run_seed).await;
+    benchmarker::set_reproduction_log_path(reproduction_log_path).await;
+    benchmarker::set_audit_log(audit_log).await;
+    for (challenge_id, timeout_ms) in default_timeouts {
+        benchmarker::set_default_timeout(challenge_id, timeout_ms).await;
+    }
+    // the offset only derives from the run seed when it's drawn
+    if random_offset || run_seed.is_some() {
+        benchmarker::set_nonce_offset(NonceOffset::Entropy).await;
+    }
     benchmarker::start(num_workers, duration).await;
     future_utils::spawn(async move {
         let offsets = Arc::new(Mutex::new(HashMap::new()));
@@ -272,9 +751,17 @@ async fn master_node(
                     ))
                 },
             );
-        warp::serve(get_nonce_offset.or(get_job).or(post_solutions_data))
-            .run(([0, 0, 0, 0], port))
-            .await;
+        let routes = get_nonce_offset.or(get_job).or(post_solutions_data);
+        #[cfg(feature = "metrics")]
+        let routes = routes.or(warp::path("metrics").and(warp::get()).and_then(|| async {
+            let state = (*benchmarker::state()).lock().await;
+            Ok::<_, warp::Rejection>(warp::reply::with_header(
+                state.metrics.to_prometheus(),
+                "content-type",
+                "text/plain; version=0.0.4",
+            ))
+        }));
+        warp::serve(routes).run(([0, 0, 0, 0], port)).await;
     });
     loop {
         let selection = serde_json::from_str::<HashMap<String, String>>(
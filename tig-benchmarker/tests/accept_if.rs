@@ -0,0 +1,101 @@
+mod common;
+
+use tig_benchmarker::AcceptIf;
+use tig_challenges::vehicle_routing;
+use tig_challenges::vehicle_routing::{calc_baseline_routes, calc_routes_total_distance};
+use tig_utils::dejsonify;
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance, verify_solution, BenchmarkSettings,
+    Solution,
+};
+
+const NUM_NODES: usize = 20;
+
+fn settings() -> BenchmarkSettings {
+    // the baseline routes verify
+    common::settings("c002", vec![NUM_NODES as i32, 0])
+}
+
+fn total_distance(challenge: &vehicle_routing::Challenge, routes: &Vec<Vec<usize>>) -> i32 {
+    calc_routes_total_distance(
+        NUM_NODES,
+        challenge.max_capacity,
+        &challenge.demands,
+        &challenge.distance_matrix,
+        routes,
+    )
+    .unwrap()
+}
+
+// an instance per nonce with its baseline routes as the solver's solution
+fn solved_instances() -> Vec<(u64, Vec<u8>, Solution, i32)> {
+    (0..20)
+        .map(|nonce| {
+            let instance = generate_serialized_instance(&settings(), nonce).unwrap();
+            let challenge: vehicle_routing::Challenge = deserialize_instance(&instance).unwrap();
+            let routes = calc_baseline_routes(
+                NUM_NODES,
+                challenge.max_capacity,
+                &challenge.demands,
+                &challenge.distance_matrix,
+            )
+            .unwrap();
+            let distance = total_distance(&challenge, &routes);
+            let solution = dejsonify(&format!("{{\"routes\": {:?}}}", routes)).unwrap();
+            (nonce, instance, solution, distance)
+        })
+        .collect()
+}
+
+// mirrors execute: a solution is recorded if it verifies and accept_if accepts it
+fn recorded(accept_if: &AcceptIf, instances: &[(u64, Vec<u8>, Solution, i32)]) -> Vec<u64> {
+    instances
+        .iter()
+        .filter(|(nonce, instance, solution, _)| {
+            verify_solution(&settings(), *nonce, solution).is_ok()
+                && accept_if.accepts("c002", instance, solution)
+        })
+        .map(|(nonce, ..)| *nonce)
+        .collect()
+}
+
+#[test]
+fn test_rejected_solutions_are_not_recorded() {
+    let instances = solved_instances();
+    let mut distances: Vec<i32> = instances.iter().map(|x| x.3).collect();
+    distances.sort();
+    let max_distance = distances[distances.len() / 2];
+    let accept_if = AcceptIf::default().vehicle_routing(move |challenge, solution| {
+        total_distance(challenge, &solution.routes) < max_distance
+    });
+
+    let expected: Vec<u64> = instances
+        .iter()
+        .filter(|x| x.3 < max_distance)
+        .map(|x| x.0)
+        .collect();
+    assert!(!expected.is_empty());
+    assert!(expected.len() < instances.len());
+    for (nonce, _, solution, _) in instances.iter() {
+        // every solution verifies, only the short ones are recorded
+        assert!(verify_solution(&settings(), *nonce, solution).is_ok());
+    }
+    assert_eq!(recorded(&accept_if, &instances), expected);
+}
+
+#[test]
+fn test_accepts_without_predicate() {
+    let instances = solved_instances();
+    // a predicate for another challenge doesn't apply
+    let accept_if = AcceptIf::default().knapsack(|_, _| false);
+    assert_eq!(recorded(&accept_if, &instances).len(), instances.len());
+}
+
+#[test]
+fn test_rejects_undecodable_instance() {
+    let (_, instance, solution, _) = solved_instances().remove(0);
+    let accept_if = AcceptIf::default().vehicle_routing(|_, _| true);
+    assert!(accept_if.accepts("c002", &instance, &solution));
+    // the predicate can't be consulted
+    assert!(!accept_if.accepts("c002", &instance[..10], &solution));
+}
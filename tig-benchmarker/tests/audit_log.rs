@@ -0,0 +1,124 @@
+mod common;
+
+use std::{fs, path::PathBuf, sync::Arc, thread};
+use tig_algorithms::c003::c003_a001;
+use tig_benchmarker::{read_audit_log, AuditLog, AuditRecord, Clock, Job, MockClock};
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_structs::core::Solution;
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance, solution_hash, solution_qualifier,
+};
+
+const NUM_WORKERS: u64 = 2;
+const NONCES_PER_WORKER: u64 = 20;
+const START_MS: u64 = 1_700_000_000_000;
+
+fn log_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "tig_audit_log_{}_{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// the greedy solver only reaches a target above the baseline on some instances
+fn job() -> Job {
+    common::job(common::settings("c003", vec![50, 10]))
+}
+
+// solves a nonce like a worker of execute does, recording it in the log if accepted
+fn solve(log: &AuditLog, clock: &MockClock, nonce: u64) -> Option<Solution> {
+    let job = job();
+    let instance = generate_serialized_instance(&job.settings, nonce).unwrap();
+    let challenge: knapsack::Challenge = deserialize_instance(&instance).unwrap();
+    let solution = c003_a001::solve_challenge(&challenge).ok()??;
+    challenge.verify_solution(&solution).ok()?;
+    let solution: Solution =
+        serde_json::from_value(serde_json::to_value(&solution).unwrap()).unwrap();
+    clock.advance(1);
+    log.record(&AuditRecord::new(&job, nonce, &solution, clock.now()).unwrap())
+        .unwrap();
+    Some(solution)
+}
+
+#[test]
+fn test_one_record_per_accepted_solution() {
+    let path = log_path("accepted");
+    let log = Arc::new(AuditLog::open(&path).unwrap());
+    let clock = Arc::new(MockClock::new(START_MS));
+    // workers record concurrently, like the tasks of a run
+    let handles: Vec<_> = (0..NUM_WORKERS)
+        .map(|worker| {
+            let log = log.clone();
+            let clock = clock.clone();
+            thread::spawn(move || {
+                (0..NONCES_PER_WORKER)
+                    .filter_map(|i| {
+                        let nonce = worker * NONCES_PER_WORKER + i;
+                        solve(&log, &clock, nonce).map(|solution| (nonce, solution))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let mut accepted: Vec<(u64, Solution)> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    log.flush().unwrap();
+    accepted.sort_by_key(|(nonce, _)| *nonce);
+    assert!(!accepted.is_empty() && accepted.len() < (NUM_WORKERS * NONCES_PER_WORKER) as usize);
+
+    let mut records = read_audit_log(&path).unwrap();
+    assert_eq!(records.len(), accepted.len());
+    // stamped on the run's clock, which ticks once per accepted solution
+    for record in records.iter() {
+        assert!(record.timestamp > START_MS);
+        assert!(record.timestamp <= START_MS + accepted.len() as u64);
+    }
+
+    records.sort_by_key(|x| x.nonce);
+    let settings = job().settings;
+    for (record, (nonce, solution)) in records.iter().zip(accepted.iter()) {
+        assert_eq!(record.benchmark_id, "benchmark");
+        assert_eq!(record.nonce, *nonce);
+        assert_eq!(
+            record.qualifier,
+            solution_qualifier(&settings, *nonce, solution).unwrap()
+        );
+        assert!(record.qualifier >= 1.0);
+        assert_eq!(
+            record.fingerprint,
+            solution_hash(&settings, *nonce, solution).unwrap()
+        );
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_reopened_log_appends() {
+    let path = log_path("append");
+    let record = |nonce| AuditRecord {
+        benchmark_id: "benchmark".to_string(),
+        nonce,
+        qualifier: 1.25,
+        fingerprint: format!("{:032x}", nonce),
+        timestamp: START_MS + nonce,
+    };
+    for nonce in 0..2 {
+        let log = AuditLog::open(&path).unwrap();
+        log.record(&record(nonce)).unwrap();
+        log.flush().unwrap();
+    }
+    assert_eq!(read_audit_log(&path).unwrap(), vec![record(0), record(1)]);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_unverified_solution_is_not_audited() {
+    let mut solution = Solution::new();
+    solution.insert("items".to_string(), serde_json::json!([0, 0, 0]));
+    assert!(AuditRecord::new(&job(), 0, &solution, START_MS).is_err());
+}
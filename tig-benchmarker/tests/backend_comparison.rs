@@ -0,0 +1,43 @@
+use serde_json::json;
+use std::{thread, time::Duration};
+use tig_benchmarker::compare_backends;
+use tig_structs::core::Solution;
+
+// a trivial algorithm: solves even nonces with the nonce's square
+fn solve(nonce: u64) -> Option<Solution> {
+    (nonce & 1 == 0).then(|| json!({ "x": nonce * nonce }).as_object().unwrap().clone())
+}
+
+#[test]
+fn test_matching_backends() {
+    let nonces: Vec<u64> = (0..20).collect();
+    let comparison = compare_backends(
+        &nonces,
+        |nonce| {
+            thread::sleep(Duration::from_millis(2));
+            solve(nonce)
+        },
+        |nonce| {
+            // wasm runs the same algorithm slower
+            thread::sleep(Duration::from_millis(6));
+            solve(nonce)
+        },
+    );
+    assert_eq!(comparison.num_nonces, 20);
+    assert!(comparison.solutions_match());
+    let ratio = comparison.throughput_ratio().unwrap();
+    assert!(ratio > 1.5, "ratio {}", ratio);
+}
+
+#[test]
+fn test_divergent_backends() {
+    let nonces: Vec<u64> = (0..10).collect();
+    let comparison = compare_backends(&nonces, solve, |nonce| match nonce {
+        // a different solution, and a solution where native found none
+        4 => json!({ "x": 0 }).as_object().cloned(),
+        7 => json!({ "x": 49 }).as_object().cloned(),
+        _ => solve(nonce),
+    });
+    assert!(!comparison.solutions_match());
+    assert_eq!(comparison.divergent_nonces, vec![4, 7]);
+}
@@ -0,0 +1,54 @@
+use tig_benchmarker::{check_regression, BenchmarkBaseline};
+
+// a run of 4 workers on knapsack at a fixed difficulty
+fn run(num_attempts: u64, num_solutions: u64, elapsed_ms: u64) -> BenchmarkBaseline {
+    BenchmarkBaseline {
+        challenge_id: "c003".to_string(),
+        algorithm_id: "c003_a001".to_string(),
+        difficulty: vec![50, 10],
+        num_workers: 4,
+        num_attempts,
+        num_solutions,
+        elapsed_ms,
+    }
+}
+
+#[test]
+fn test_save_and_load() {
+    let path = std::env::temp_dir().join(format!("tig_baseline_{}.json", std::process::id()));
+    let baseline = run(2000, 500, 10000);
+    baseline.save(&path).unwrap();
+    assert_eq!(BenchmarkBaseline::load(&path).unwrap(), baseline);
+    std::fs::remove_file(&path).unwrap();
+    assert!(BenchmarkBaseline::load(&path).is_err());
+}
+
+#[test]
+fn test_regression_is_detected() {
+    let baseline = run(2000, 500, 10000);
+    assert_eq!(baseline.nonces_per_second(), 200.0);
+    assert_eq!(baseline.solve_rate(), 0.25);
+
+    // noise within the tolerance and improvements pass
+    assert!(check_regression(&run(1900, 480, 10000), &baseline, 0.1).is_ok());
+    assert!(check_regression(&run(3000, 700, 10000), &baseline, 0.1).is_ok());
+    // 30% fewer nonces in the same time
+    let err = check_regression(&run(1400, 350, 10000), &baseline, 0.1).unwrap_err();
+    assert!(err.contains("Throughput regressed"), "{}", err);
+    // the same nonces in a longer time
+    assert!(check_regression(&run(2000, 500, 15000), &baseline, 0.1).is_err());
+    // unless the tolerance allows it
+    assert!(check_regression(&run(1400, 350, 10000), &baseline, 0.5).is_ok());
+}
+
+#[test]
+fn test_different_config_is_not_comparable() {
+    let baseline = run(2000, 500, 10000);
+    let mut current = run(2000, 500, 10000);
+    current.num_workers = 8;
+    let err = check_regression(&current, &baseline, 0.1).unwrap_err();
+    assert!(err.contains("not comparable"), "{}", err);
+    let mut current = run(2000, 500, 10000);
+    current.difficulty = vec![60, 10];
+    assert!(check_regression(&current, &baseline, 0.1).is_err());
+}
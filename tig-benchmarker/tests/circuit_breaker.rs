@@ -0,0 +1,79 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::thread;
+use tig_benchmarker::CircuitBreaker;
+
+// mirrors the worker loop: stops taking nonces once the breaker trips
+fn run(
+    solver: fn(u64) -> Result<(), String>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    num_workers: u64,
+    nonces_per_worker: u64,
+) -> u64 {
+    let attempts = Arc::new(AtomicU64::new(0));
+    let handles: Vec<_> = (0..num_workers)
+        .map(|w| {
+            let circuit_breaker = circuit_breaker.clone();
+            let attempts = attempts.clone();
+            thread::spawn(move || {
+                for nonce in w * nonces_per_worker..(w + 1) * nonces_per_worker {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    if circuit_breaker.record(solver(nonce).is_err()) {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    attempts.load(Ordering::Relaxed)
+}
+
+#[test]
+fn test_breaker_trips_on_always_erroring_solver() {
+    let circuit_breaker = Arc::new(CircuitBreaker::new(0.9, 100));
+    let attempts = run(
+        |_| Err("trap".to_string()),
+        circuit_breaker.clone(),
+        4,
+        10_000,
+    );
+    assert!(circuit_breaker.is_tripped());
+    // aborted shortly after the minimum sample instead of running every nonce
+    assert!(attempts >= 100);
+    assert!(attempts < 200);
+    assert!(circuit_breaker.error().contains("Circuit breaker tripped"));
+}
+
+#[test]
+fn test_breaker_holds_below_threshold() {
+    let circuit_breaker = Arc::new(CircuitBreaker::new(0.9, 100));
+    // every other nonce errors
+    let attempts = run(
+        |nonce| {
+            if nonce % 2 == 0 {
+                Err("trap".to_string())
+            } else {
+                Ok(())
+            }
+        },
+        circuit_breaker.clone(),
+        4,
+        1_000,
+    );
+    assert!(!circuit_breaker.is_tripped());
+    assert_eq!(attempts, 4_000);
+}
+
+#[test]
+fn test_breaker_waits_for_min_samples() {
+    let circuit_breaker = CircuitBreaker::new(0.5, 10);
+    for _ in 0..9 {
+        assert!(!circuit_breaker.record(true));
+    }
+    assert!(circuit_breaker.record(true));
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use tig_benchmarker::{Clock, MaxRunDuration, MockClock};
+
+const MAX_RUN_MS: u64 = 300;
+
+#[test]
+fn test_mock_clock_only_moves_when_advanced() {
+    let clock = MockClock::new(1_000);
+    assert_eq!(clock.now(), 1_000);
+    assert_eq!(clock.now(), 1_000);
+    clock.advance(250);
+    assert_eq!(clock.now(), 1_250);
+}
+
+#[test]
+fn test_mock_clock_triggers_timeout() {
+    let clock = Arc::new(MockClock::new(0));
+    let max_run_duration = MaxRunDuration::with_clock(MAX_RUN_MS, clock.clone());
+    assert_eq!(max_run_duration.remaining_ms(), MAX_RUN_MS);
+
+    clock.advance(MAX_RUN_MS - 1);
+    assert!(!max_run_duration.is_exceeded());
+    assert_eq!(max_run_duration.remaining_ms(), 1);
+
+    clock.advance(1);
+    assert!(max_run_duration.is_exceeded());
+    assert_eq!(max_run_duration.remaining_ms(), 0);
+}
+
+#[cfg(all(feature = "standalone", test))]
+mod tests {
+    use super::*;
+    use tig_benchmarker::NonceIterator;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_enforce_at_mock_timeout() {
+        let clock = Arc::new(MockClock::new(0));
+        let max_run_duration = MaxRunDuration::with_clock(MAX_RUN_MS, clock.clone());
+        let nonce_iters = vec![Arc::new(Mutex::new(NonceIterator::from_u64(0)))];
+
+        clock.advance(MAX_RUN_MS - 1);
+        assert!(!max_run_duration.enforce(&nonce_iters).await);
+        assert!(!nonce_iters[0].lock().await.is_empty());
+
+        clock.advance(1);
+        assert!(max_run_duration.enforce(&nonce_iters).await);
+        assert!(nonce_iters[0].lock().await.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+// fixtures shared by the tests. every test file is its own crate and only uses some of them
+#![allow(dead_code)]
+
+use tig_benchmarker::{Job, MetadataConfig};
+use tig_structs::{
+    config::WasmVMConfig,
+    core::{BenchmarkSettings, SolutionData},
+};
+use tig_worker::InstanceOptions;
+
+// the wasm of c003_a001
+pub const KNAPSACK_WASM: &[u8] =
+    include_bytes!("../../../tig-algorithms/wasm/knapsack/dynamic.wasm");
+
+pub fn settings(challenge_id: &str, difficulty: Vec<i32>) -> BenchmarkSettings {
+    BenchmarkSettings {
+        player_id: "0x0000000000000000000000000000000000000000".to_string(),
+        block_id: "block".to_string(),
+        challenge_id: challenge_id.to_string(),
+        algorithm_id: format!("{}_a001", challenge_id),
+        difficulty,
+    }
+}
+
+// a job for settings with no sampled nonces, timeout or signature threshold, on the protocol's
+// instances
+pub fn job(settings: BenchmarkSettings) -> Job {
+    Job {
+        download_url: "".to_string(),
+        benchmark_id: "benchmark".to_string(),
+        settings,
+        solution_signature_threshold: u32::MAX,
+        sampled_nonces: None,
+        wasm_vm_config: WasmVMConfig {
+            max_memory: 1_000_000,
+            max_fuel: 1_000_000,
+        },
+        metadata_config: MetadataConfig::default(),
+        max_solution_size: None,
+        timeout_ms: None,
+        instance_options: InstanceOptions::default(),
+    }
+}
+
+// a job the knapsack wasm solves every nonce of within its limits, as any selection at least as
+// valuable as the greedy baseline is a solution
+pub fn knapsack_job() -> Job {
+    let mut job = job(settings("c003", vec![50, 0]));
+    job.wasm_vm_config.max_memory = 100_000_000;
+    job.wasm_vm_config.max_fuel = 10_000_000_000;
+    job
+}
+
+// runs the job through run_benchmark::execute on a worker per list of nonces, returning the
+// solutions it kept sorted by nonce
+#[cfg(feature = "standalone")]
+pub async fn execute(job: &Job, wasm: &[u8], worker_nonces: Vec<Vec<u64>>) -> Vec<SolutionData> {
+    use std::sync::{atomic::AtomicU64, Arc};
+    use tig_benchmarker::{
+        run_benchmark::{self, ExecuteOptions},
+        CircuitBreaker, NonceIterator,
+    };
+    use tokio::sync::Mutex;
+
+    let nonce_iters = worker_nonces
+        .into_iter()
+        .map(|nonces| Arc::new(Mutex::new(NonceIterator::from_vec(nonces))))
+        .collect();
+    let solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
+    run_benchmark::execute(
+        nonce_iters,
+        job,
+        &wasm.to_vec(),
+        solutions_data.clone(),
+        Arc::new(AtomicU64::new(0)),
+        Arc::new(CircuitBreaker::default()),
+        ExecuteOptions::default(),
+    )
+    .await;
+    // every worker holds solutions_data until it exits
+    while Arc::strong_count(&solutions_data) > 1 {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let mut solutions_data = solutions_data.lock().await.clone();
+    solutions_data.sort_by_key(|x| x.nonce);
+    solutions_data
+}
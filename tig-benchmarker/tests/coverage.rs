@@ -0,0 +1,41 @@
+use tig_benchmarker::{coverage, NonceIterator};
+
+// nonces a shard starting at start attempts before it is stopped
+fn shard(start: u64, num_attempts: usize) -> Vec<u64> {
+    NonceIterator::from_u64(start).take(num_attempts).collect()
+}
+
+#[test]
+fn test_gap_between_shards_is_reported() {
+    // the second shard starts at 150 instead of 100
+    let attempted = shard(0, 100).into_iter().chain(shard(150, 150));
+    let report = coverage(0..300, attempted);
+    assert_eq!(report.covered, vec![0..100, 150..300]);
+    assert_eq!(report.gaps, vec![100..150]);
+    assert_eq!(report.num_covered(), 250);
+    assert_eq!(report.num_outside, 0);
+    assert!(!report.is_complete());
+}
+
+#[test]
+fn test_overlapping_shards_cover_range() {
+    // shards report in any order and overlap by 10 nonces
+    let attempted = shard(90, 110).into_iter().chain(shard(0, 100));
+    let report = coverage(0..200, attempted);
+    assert_eq!(report.covered, vec![0..200]);
+    assert!(report.gaps.is_empty());
+    assert!(report.is_complete());
+}
+
+#[test]
+fn test_gaps_at_ends_and_nonces_outside_range() {
+    let attempted = shard(10, 20).into_iter().chain(shard(1000, 5));
+    let report = coverage(0..50, attempted);
+    assert_eq!(report.covered, vec![10..30]);
+    assert_eq!(report.gaps, vec![0..10, 30..50]);
+    assert_eq!(report.num_outside, 5);
+
+    let report = coverage(0..50, Vec::new());
+    assert!(report.covered.is_empty());
+    assert_eq!(report.gaps, vec![0..50]);
+}
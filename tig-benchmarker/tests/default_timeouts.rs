@@ -0,0 +1,64 @@
+mod common;
+
+use tig_benchmarker::{DefaultTimeouts, Job};
+
+fn job(challenge_id: &str, timeout_ms: Option<u64>) -> Job {
+    Job {
+        timeout_ms,
+        ..common::job(common::settings(challenge_id, vec![50, 300]))
+    }
+}
+
+#[test]
+fn test_default_applied_when_unspecified() {
+    let timeouts = DefaultTimeouts::default();
+    for challenge_id in ["c001", "c002", "c003", "c004"] {
+        let mut job = job(challenge_id, None);
+        timeouts.apply(&mut job);
+        assert_eq!(job.timeout_ms, Some(timeouts.default_ms(challenge_id)));
+    }
+    // a fast satisfiability check gets less time than a routing solve
+    assert!(timeouts.default_ms("c001") < timeouts.default_ms("c002"));
+    // challenges without an entry fall back
+    assert_eq!(timeouts.default_ms("c999"), timeouts.fallback_ms);
+}
+
+#[test]
+fn test_job_timeout_overrides_default() {
+    let timeouts = DefaultTimeouts::default();
+    let mut job = job("c002", Some(1234));
+    assert_eq!(timeouts.timeout_ms(&job), 1234);
+    timeouts.apply(&mut job);
+    assert_eq!(job.timeout_ms, Some(1234));
+}
+
+#[test]
+fn test_recompute_job_keeps_its_timeout() {
+    let timeouts = DefaultTimeouts::default();
+    let mut job = Job {
+        sampled_nonces: Some(vec![3, 7]),
+        ..job("c003", None)
+    };
+    timeouts.apply(&mut job);
+    assert_eq!(job.timeout_ms, None);
+}
+
+#[test]
+fn test_configured_default_replaces_builtin() {
+    let mut timeouts = DefaultTimeouts::default();
+    timeouts.set("c003".to_string(), 500);
+    assert_eq!(timeouts.timeout_ms(&job("c003", None)), 500);
+    assert_eq!(timeouts.timeout_ms(&job("c003", Some(700))), 700);
+    assert_eq!(
+        timeouts.timeout_ms(&job("c001", None)),
+        DefaultTimeouts::default().default_ms("c001")
+    );
+}
+
+#[test]
+fn test_unspecified_timeout_deserializes_as_none() {
+    let mut json = serde_json::to_value(job("c001", Some(5))).unwrap();
+    json.as_object_mut().unwrap().remove("timeout_ms");
+    let job: Job = serde_json::from_value(json).unwrap();
+    assert_eq!(job.timeout_ms, None);
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use tig_benchmarker::{DutyCycle, ThrottleSignal};
+
+// runs nonces taking work_ms each, returning the total time worked and rested
+fn run(duty_cycle: &DutyCycle, work_ms: &[u64]) -> (u64, u64) {
+    let rest_ms: u64 = work_ms.iter().map(|&x| duty_cycle.rest_ms(x)).sum();
+    (work_ms.iter().sum(), rest_ms)
+}
+
+#[test]
+fn test_duty_cycle_honored_when_signalled() {
+    let signal = ThrottleSignal::new();
+    let duty_cycle = DutyCycle::new(0.25, Some(signal.clone()));
+    let work_ms: Vec<u64> = (0..200).map(|i| 1 + i * 7 % 37).collect();
+
+    // full speed until the signal is set
+    assert!(!duty_cycle.is_throttling());
+    assert_eq!(run(&duty_cycle, &work_ms).1, 0);
+    assert_eq!(duty_cycle.applied(), 1.0);
+
+    signal.set(true);
+    duty_cycle.reset();
+    assert!(duty_cycle.is_throttling());
+    let (worked, rested) = run(&duty_cycle, &work_ms);
+    // working a quarter of the time means resting 3 times as long as working
+    assert!(rested.abs_diff(worked * 3) <= 1, "{} {}", worked, rested);
+    assert!((duty_cycle.applied() - 0.25).abs() < 1e-3);
+
+    signal.set(false);
+    assert_eq!(run(&duty_cycle, &work_ms).1, 0);
+    assert!(duty_cycle.applied() > 0.25);
+}
+
+#[test]
+fn test_periodic_duty_cycle() {
+    // without a signal, always throttles
+    let duty_cycle = DutyCycle::new(0.4, None);
+    assert!(duty_cycle.is_throttling());
+    // nonces shorter than the rest owed for them still add up to the duty cycle
+    let (worked, rested) = run(&duty_cycle, &[1; 1000]);
+    assert_eq!(worked, 1000);
+    assert!(rested.abs_diff(1500) <= 1, "{}", rested);
+    assert!((duty_cycle.applied() - 0.4).abs() < 1e-3);
+
+    // a duty cycle of 1 never rests
+    assert_eq!(run(&DutyCycle::new(1.0, None), &[100; 10]).1, 0);
+    // clamped, so workers still make progress
+    assert_eq!(DutyCycle::new(0.0, None).duty_cycle(), 0.01);
+}
+
+#[test]
+fn test_shared_by_workers() {
+    let duty_cycle = Arc::new(DutyCycle::new(0.5, None));
+    let rested: u64 = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| s.spawn(|| run(&duty_cycle, &[3; 250]).1))
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    });
+    assert_eq!(rested, 3000);
+    assert_eq!(duty_cycle.applied(), 0.5);
+}
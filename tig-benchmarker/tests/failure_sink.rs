@@ -0,0 +1,122 @@
+mod common;
+
+use std::{fs, path::PathBuf, sync::Arc, thread};
+use tig_algorithms::c003::c003_a001;
+use tig_benchmarker::{
+    failed_nonces, read_failures, reproduce, Failure, FailureSink, ReproductionLog, TraceOutcome,
+};
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance, BenchmarkSettings, InstanceOptions,
+};
+
+const NUM_WORKERS: u64 = 2;
+const NONCES_PER_WORKER: u64 = 20;
+
+fn sink_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "tig_failure_sink_{}_{}.jsonl",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+// the greedy solver only reaches a target above the baseline on some instances
+fn settings() -> BenchmarkSettings {
+    common::settings("c003", vec![50, 10])
+}
+
+// solves a nonce like a worker of execute does, recording it in the sink unless solved
+fn solve(sink: &FailureSink, nonce: u64) -> bool {
+    let instance = generate_serialized_instance(&settings(), nonce).unwrap();
+    let challenge: knapsack::Challenge = deserialize_instance(&instance).unwrap();
+    let (outcome, error) = match c003_a001::solve_challenge(&challenge) {
+        Ok(Some(solution)) => match challenge.verify_solution(&solution) {
+            Ok(()) => return true,
+            Err(e) => (TraceOutcome::Invalid, Some(e.to_string())),
+        },
+        Ok(None) => (TraceOutcome::GaveUp, None),
+        Err(e) => (TraceOutcome::Error, Some(e.to_string())),
+    };
+    sink.record(&Failure {
+        nonce,
+        outcome,
+        error,
+    })
+    .unwrap();
+    false
+}
+
+#[test]
+fn test_sink_holds_exactly_the_failed_nonces() {
+    let path = sink_path("failed");
+    let sink = Arc::new(FailureSink::open(&path).unwrap());
+    // workers record concurrently, like the tasks of a run
+    let handles: Vec<_> = (0..NUM_WORKERS)
+        .map(|worker| {
+            let sink = sink.clone();
+            thread::spawn(move || {
+                (0..NONCES_PER_WORKER)
+                    .map(|i| {
+                        let nonce = worker * NONCES_PER_WORKER + i;
+                        (nonce, solve(&sink, nonce))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    let results: Vec<(u64, bool)> = handles
+        .into_iter()
+        .flat_map(|handle| handle.join().unwrap())
+        .collect();
+    sink.flush().unwrap();
+
+    let mut failed: Vec<u64> = results
+        .iter()
+        .filter(|(_, solved)| !solved)
+        .map(|(nonce, _)| *nonce)
+        .collect();
+    failed.sort_unstable();
+    assert!(!failed.is_empty() && failed.len() < results.len());
+
+    let failures = read_failures(&path).unwrap();
+    assert_eq!(failures.len(), failed.len());
+    for failure in failures.iter() {
+        assert_ne!(failure.outcome, TraceOutcome::Solved);
+        if failure.outcome == TraceOutcome::Invalid {
+            assert!(failure.error.is_some());
+        }
+    }
+
+    // replays exactly the failed nonces
+    let log = ReproductionLog::new(
+        settings(),
+        InstanceOptions::default(),
+        failed_nonces(&failures),
+    );
+    let replayed = reproduce(&log, |nonce, _| nonce).unwrap();
+    assert_eq!(replayed, failed);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_reopened_sink_appends() {
+    let path = sink_path("append");
+    let failure = |nonce| Failure {
+        nonce,
+        outcome: TraceOutcome::Error,
+        // errors are free text, so may contain separators
+        error: Some("Failed, at line 1\nand 2".to_string()),
+    };
+    for nonce in 0..2 {
+        // dropping the sink flushes it
+        FailureSink::open(&path)
+            .unwrap()
+            .record(&failure(nonce))
+            .unwrap();
+    }
+    assert_eq!(read_failures(&path).unwrap(), vec![failure(0), failure(1)]);
+    fs::remove_file(&path).unwrap();
+}
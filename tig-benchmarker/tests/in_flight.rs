@@ -0,0 +1,49 @@
+use std::{
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+use tig_benchmarker::InFlightTracker;
+
+// solves poll their token every millisecond until done or canceled. returns whether it was canceled
+fn solve(tracker: Arc<InFlightTracker>, nonce: u64, duration_ms: u64) -> bool {
+    let token = tracker.start(nonce);
+    let start = Instant::now();
+    let mut canceled = false;
+    while start.elapsed() < Duration::from_millis(duration_ms) {
+        if token.is_canceled() {
+            canceled = true;
+            break;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    tracker.finish(nonce);
+    canceled
+}
+
+#[test]
+fn test_cancel_slowest() {
+    let tracker = Arc::new(InFlightTracker::new());
+    // started in nonce order, so the earliest are the longest running
+    let durations = [5_000, 5_000, 5_000, 300, 300];
+    let handles: Vec<_> = durations
+        .iter()
+        .enumerate()
+        .map(|(nonce, &duration_ms)| {
+            let tracker = tracker.clone();
+            let handle = thread::spawn(move || solve(tracker, nonce as u64, duration_ms));
+            thread::sleep(Duration::from_millis(10));
+            handle
+        })
+        .collect();
+    assert_eq!(tracker.num_in_flight(), 5);
+
+    assert_eq!(tracker.cancel_slowest(2), vec![0, 1]);
+    // already canceled solves are skipped
+    assert_eq!(tracker.cancel_slowest(1), vec![2]);
+
+    let canceled: Vec<bool> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(canceled, vec![true, true, true, false, false]);
+    assert_eq!(tracker.num_in_flight(), 0);
+    assert!(tracker.cancel_slowest(1).is_empty());
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use tig_benchmarker::InstanceDedup;
+use tig_worker::{generate_serialized_instance, instance_fingerprint, BenchmarkSettings};
+
+// a single clause over a single variable has only 8 possible instances, so nonces repeat them
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![1, 100])
+}
+
+fn fingerprint(nonce: u64) -> String {
+    let instance = generate_serialized_instance(&settings(), nonce).unwrap();
+    instance_fingerprint("c001", &instance).unwrap()
+}
+
+// the first nonce after 0 whose instance is a repeat of an earlier nonce's, and that earlier nonce
+fn repeated_nonces() -> (u64, u64) {
+    let fingerprints: Vec<String> = (0..20).map(fingerprint).collect();
+    (1..fingerprints.len())
+        .find_map(|b| {
+            (0..b)
+                .find(|&a| fingerprints[a] == fingerprints[b])
+                .map(|a| (a as u64, b as u64))
+        })
+        .expect("No repeated instance in 20 nonces")
+}
+
+#[test]
+fn test_repeated_instance_is_skipped() {
+    let (a, b) = repeated_nonces();
+    // the seeds differ, only the problem repeats
+    assert_ne!(
+        generate_serialized_instance(&settings(), a).unwrap(),
+        generate_serialized_instance(&settings(), b).unwrap()
+    );
+
+    let dedup = InstanceDedup::new();
+    assert!(!dedup.is_repeat(fingerprint(a)));
+    assert!(dedup.is_repeat(fingerprint(b)));
+    assert_eq!(dedup.num_skipped(), 1);
+}
+
+#[test]
+fn test_distinct_instances_are_not_skipped() {
+    let dedup = InstanceDedup::new();
+    let mut seen = Vec::new();
+    for nonce in 0..20 {
+        let fingerprint = fingerprint(nonce);
+        assert_eq!(
+            dedup.is_repeat(fingerprint.clone()),
+            seen.contains(&fingerprint)
+        );
+        seen.push(fingerprint);
+    }
+    assert_eq!(
+        dedup.num_skipped(),
+        20 - seen.iter().collect::<std::collections::HashSet<_>>().len() as u64
+    );
+}
+
+#[test]
+fn test_fingerprint_depends_on_the_problem() {
+    let instance = generate_serialized_instance(&settings(), 0).unwrap();
+    let other = BenchmarkSettings {
+        difficulty: vec![50, 300],
+        ..settings()
+    };
+    assert_ne!(
+        instance_fingerprint("c001", &instance).unwrap(),
+        instance_fingerprint("c001", &generate_serialized_instance(&other, 0).unwrap()).unwrap()
+    );
+}
@@ -0,0 +1,160 @@
+#![cfg(feature = "standalone")]
+
+mod common;
+
+use std::collections::HashMap;
+use tig_benchmarker::{verify_bundle, Job};
+use tig_structs::core::Solution;
+use tig_worker::{
+    generate_serialized_instance, generate_serialized_instance_with_options, verify_solution,
+    verify_solution_with_options, InstanceOptions,
+};
+
+const NUM_NONCES: u64 = 20;
+
+fn salted_job(seed_salt: Option<&str>) -> Job {
+    Job {
+        instance_options: InstanceOptions {
+            seed_salt: seed_salt.map(|x| x.to_string()),
+            ..Default::default()
+        },
+        ..common::knapsack_job()
+    }
+}
+
+fn stretched_job(stretch_rounds: u32) -> Job {
+    Job {
+        instance_options: InstanceOptions {
+            stretch_rounds,
+            ..Default::default()
+        },
+        ..common::knapsack_job()
+    }
+}
+
+async fn solutions(job: &Job) -> HashMap<u64, Solution> {
+    common::execute(
+        job,
+        common::KNAPSACK_WASM,
+        vec![
+            (0..NUM_NONCES / 2).collect(),
+            (NUM_NONCES / 2..NUM_NONCES).collect(),
+        ],
+    )
+    .await
+    .into_iter()
+    .map(|x| (x.nonce, x.solution))
+    .collect()
+}
+
+#[test]
+fn test_default_options_give_the_protocols_instances() {
+    let job = common::knapsack_job();
+    assert!(job.instance_options.is_protocol());
+    for nonce in 0..NUM_NONCES {
+        assert_eq!(
+            generate_serialized_instance_with_options(&job.settings, nonce, &job.instance_options)
+                .unwrap(),
+            generate_serialized_instance(&job.settings, nonce).unwrap()
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_salt_gives_a_disjoint_instance_family() {
+    let unsalted = salted_job(None);
+    let salted = [salted_job(Some("a")), salted_job(Some("b"))];
+    let protocol_solutions = solutions(&unsalted).await;
+    assert!(!protocol_solutions.is_empty());
+    for (nonce, solution) in protocol_solutions.iter() {
+        assert!(verify_solution(&unsalted.settings, *nonce, solution).is_ok());
+    }
+
+    let mut salted_solutions = Vec::new();
+    for job in salted.iter() {
+        assert!(!job.instance_options.is_protocol());
+        let solutions = solutions(job).await;
+        assert!(!solutions.is_empty());
+        let num_verified = |options: &InstanceOptions| {
+            solutions
+                .iter()
+                .filter(|&(&nonce, solution)| {
+                    verify_solution_with_options(&job.settings, nonce, solution, options).is_ok()
+                })
+                .count()
+        };
+        // every solution the run kept is to its own salted instance, which the protocol doesn't
+        // generate for the nonce
+        assert_eq!(num_verified(&job.instance_options), solutions.len());
+        assert!(num_verified(&InstanceOptions::default()) < solutions.len());
+        salted_solutions.push(solutions);
+    }
+    // the same nonces solved differently for each salt
+    let differs = |a: &HashMap<u64, Solution>, b: &HashMap<u64, Solution>| {
+        a.iter()
+            .any(|(nonce, solution)| b.get(nonce).is_some_and(|x| x != solution))
+    };
+    assert!(differs(&salted_solutions[0], &salted_solutions[1]));
+    assert!(differs(&salted_solutions[0], &protocol_solutions));
+    for nonce in 0..NUM_NONCES {
+        assert_ne!(
+            generate_serialized_instance_with_options(
+                &salted[0].settings,
+                nonce,
+                &salted[0].instance_options
+            )
+            .unwrap(),
+            generate_serialized_instance_with_options(
+                &salted[1].settings,
+                nonce,
+                &salted[1].instance_options
+            )
+            .unwrap()
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_bundle_verifies_salted_instances() {
+    let job = salted_job(Some("a"));
+    let entries: Vec<_> =
+        common::execute(&job, common::KNAPSACK_WASM, vec![(0..NUM_NONCES).collect()])
+            .await
+            .into_iter()
+            .map(|x| (x.nonce, x))
+            .collect();
+    assert!(!entries.is_empty());
+    assert!(verify_bundle(&job, &entries).is_valid());
+    assert!(!verify_bundle(&common::knapsack_job(), &entries).is_valid());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stretched_runs_are_deterministic() {
+    let stretched = stretched_job(3);
+    assert!(!stretched.instance_options.is_protocol());
+    assert!(stretched_job(0).instance_options.is_protocol());
+    let first = solutions(&stretched).await;
+    assert!(!first.is_empty());
+    assert_eq!(solutions(&stretched).await, first);
+    for (nonce, solution) in first.iter() {
+        assert!(verify_solution_with_options(
+            &stretched.settings,
+            *nonce,
+            solution,
+            &stretched.instance_options
+        )
+        .is_ok());
+    }
+
+    let unstretched = solutions(&stretched_job(0)).await;
+    assert_ne!(unstretched, first);
+    assert_ne!(solutions(&stretched_job(4)).await, first);
+    for nonce in 0..NUM_NONCES {
+        assert_eq!(
+            stretched
+                .instance_options
+                .calc_seeds(&stretched.settings, nonce),
+            stretched.settings.calc_stretched_seeds(nonce, 3)
+        );
+    }
+}
@@ -0,0 +1,328 @@
+#![cfg(feature = "standalone")]
+
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tig_algorithms::c003::c003_a001;
+use tig_benchmarker::{
+    run_benchmark::{self, ExecuteOptions},
+    spawn_producer, AcceptIf, CircuitBreaker, GenerationMode, InstanceQueue, InstanceSource, Job,
+    NonceIterator,
+};
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_utils::jsonify;
+use tig_worker::{deserialize_instance, generate_serialized_instance, SolutionData};
+use tokio::sync::Mutex;
+
+const NUM_NONCES: u64 = 40;
+const GENERATE_MS: u64 = 3;
+const SOLVE_MS: u64 = 3;
+const MODES: [GenerationMode; 3] = [
+    GenerationMode::Inline,
+    GenerationMode::Pipelined { queue_depth: 1 },
+    GenerationMode::Pipelined { queue_depth: 4 },
+];
+
+// (module
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0)
+//   (func (export "entry_point") (param i32 i32) (result i32) unreachable))
+// errors on every nonce
+#[rustfmt::skip]
+const TRAPPING_WASM: [u8; 77] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x1f, 0x03, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, // "init"
+    0x0b, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00, 0x01, // "entry_point"
+    0x0a, 0x0a, 0x02, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+    0x03, 0x00, 0x00, 0x0b, // entry_point
+];
+
+fn generate(nonce: u64) -> u64 {
+    thread::sleep(Duration::from_millis(GENERATE_MS));
+    nonce
+}
+
+fn solve(_instance: u64) {
+    thread::sleep(Duration::from_millis(SOLVE_MS));
+}
+
+// time the solver spends not solving, i.e. generating or waiting for an instance
+fn inline_idle_time() -> Duration {
+    let mut idle = Duration::ZERO;
+    for nonce in 0..NUM_NONCES {
+        let start = Instant::now();
+        let instance = generate(nonce);
+        idle += start.elapsed();
+        solve(instance);
+    }
+    idle
+}
+
+async fn pipelined_idle_time(queue_depth: usize) -> Duration {
+    let queue = Arc::new(InstanceQueue::new(queue_depth));
+    spawn_producer(queue.clone(), Arc::new(Mutex::new(0..NUM_NONCES)), generate);
+    let mut idle = Duration::ZERO;
+    let mut nonces = Vec::new();
+    loop {
+        let start = Instant::now();
+        let item = queue.pop().await;
+        idle += start.elapsed();
+        match item {
+            Some((nonce, instance)) => {
+                nonces.push(nonce);
+                solve(instance);
+            }
+            None => break,
+        }
+    }
+    assert_eq!(nonces, (0..NUM_NONCES).collect::<Vec<_>>());
+    idle
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pipelining_reduces_solver_idle_time() {
+    let inline = inline_idle_time();
+    let pipelined = pipelined_idle_time(4).await;
+    assert!(inline >= Duration::from_millis(NUM_NONCES * GENERATE_MS));
+    assert!(
+        pipelined < inline / 2,
+        "pipelined idle {:?} vs inline idle {:?}",
+        pipelined,
+        inline
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_producer_backpressure() {
+    let queue = Arc::new(InstanceQueue::new(2));
+    let nonce_iter = Arc::new(Mutex::new(0..100u64));
+    spawn_producer(queue.clone(), nonce_iter.clone(), |nonce| nonce);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    // 2 queued plus 1 waiting to be pushed
+    assert_eq!(nonce_iter.lock().await.start, 3);
+
+    assert_eq!(queue.pop().await, Some((0, 0)));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(nonce_iter.lock().await.start, 4);
+    assert!(!queue.is_closed());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_cancel_drops_queued_instances() {
+    let nonce_iter = Arc::new(Mutex::new(NonceIterator::from_u64(0)));
+    let source = InstanceSource::new(
+        GenerationMode::Pipelined { queue_depth: 8 },
+        nonce_iter.clone(),
+        |nonce| nonce,
+    );
+    assert_eq!(source.next().await, Some((0, 0)));
+    // lets the producer fill the queue
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(nonce_iter.lock().await.attempts() > 8);
+
+    nonce_iter.lock().await.empty();
+    let mut num_solved = 0;
+    while source.next().await.is_some() {
+        num_solved += 1;
+    }
+    assert_eq!(num_solved, 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_exhausted_nonces_are_still_solved() {
+    let nonce_iter = Arc::new(Mutex::new(NonceIterator::from_vec((0..5).collect())));
+    let source = InstanceSource::new(
+        GenerationMode::Pipelined { queue_depth: 8 },
+        nonce_iter.clone(),
+        |nonce| nonce,
+    );
+    // the producer takes every nonce before the first is solved
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(nonce_iter.lock().await.is_empty());
+    let mut solved = Vec::new();
+    while let Some((nonce, _)) = source.next().await {
+        solved.push(nonce);
+    }
+    solved.sort_unstable();
+    assert_eq!(solved, (0..5).collect::<Vec<_>>());
+}
+
+// solves nonces 0..40 on 2 workers, returning each nonce's verified solution if any
+async fn solve_all(mode: GenerationMode) -> Vec<(u64, Option<String>)> {
+    let settings = common::settings("c003", vec![50, 10]);
+    let handles: Vec<_> = [0..20u64, 20..40u64]
+        .into_iter()
+        .map(|nonces| {
+            let settings = settings.clone();
+            let source = InstanceSource::new(mode, Arc::new(Mutex::new(nonces)), move |nonce| {
+                generate_serialized_instance(&settings, nonce).unwrap()
+            });
+            tokio::spawn(async move {
+                let mut results = Vec::new();
+                while let Some((nonce, instance)) = source.next().await {
+                    let challenge: knapsack::Challenge = deserialize_instance(&instance).unwrap();
+                    let solution = c003_a001::solve_challenge(&challenge)
+                        .unwrap()
+                        .filter(|solution| challenge.verify_solution(solution).is_ok());
+                    results.push((nonce, solution.map(|solution| jsonify(&solution))));
+                }
+                results
+            })
+        })
+        .collect();
+    let mut results = Vec::new();
+    for handle in handles {
+        results.extend(handle.await.unwrap());
+    }
+    results.sort();
+    results
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_modes_are_equivalent() {
+    let inline = solve_all(GenerationMode::Inline).await;
+    assert_eq!(
+        inline.iter().map(|x| x.0).collect::<Vec<_>>(),
+        (0..40).collect::<Vec<_>>()
+    );
+    assert!(inline.iter().any(|x| x.1.is_some()));
+    for queue_depth in [1, 4] {
+        assert_eq!(
+            solve_all(GenerationMode::Pipelined { queue_depth }).await,
+            inline
+        );
+    }
+}
+
+fn knapsack_job() -> Job {
+    let mut job = common::job(common::settings("c003", vec![50, 10]));
+    job.wasm_vm_config.max_memory = 100_000_000;
+    job.wasm_vm_config.max_fuel = 10_000_000_000;
+    job
+}
+
+// runs each worker's nonces through run_benchmark::execute, returning the nonces of the
+// solutions it kept and how many it counted
+async fn execute(
+    mode: GenerationMode,
+    job: &Job,
+    wasm: &[u8],
+    worker_nonces: Vec<Vec<u64>>,
+    circuit_breaker: Arc<CircuitBreaker>,
+    accept_if: Option<Arc<AcceptIf>>,
+) -> (Vec<u64>, u64) {
+    let nonce_iters = worker_nonces
+        .into_iter()
+        .map(|nonces| Arc::new(Mutex::new(NonceIterator::from_vec(nonces))))
+        .collect();
+    let solutions_data = Arc::new(Mutex::new(Vec::<SolutionData>::new()));
+    let solutions_count = Arc::new(AtomicU64::new(0));
+    run_benchmark::execute(
+        nonce_iters,
+        job,
+        &wasm.to_vec(),
+        solutions_data.clone(),
+        solutions_count.clone(),
+        circuit_breaker,
+        ExecuteOptions {
+            generation_mode: mode,
+            accept_if,
+            ..Default::default()
+        },
+    )
+    .await;
+    // every worker holds solutions_data until it exits
+    while Arc::strong_count(&solutions_data) > 1 {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let mut nonces: Vec<u64> = solutions_data
+        .lock()
+        .await
+        .iter()
+        .map(|x| x.nonce)
+        .collect();
+    nonces.sort_unstable();
+    (nonces, solutions_count.load(Ordering::Relaxed))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_execute_modes_are_equivalent() {
+    let job = knapsack_job();
+    let worker_nonces = || vec![(0..20).collect(), (20..40).collect()];
+    // a quality bar only some of the verified solutions clear
+    let accept_if = Arc::new(
+        AcceptIf::default()
+            .knapsack(|_, solution: &knapsack::Solution| solution.items.len() % 2 == 0),
+    );
+    let mut verified = None;
+    let mut accepted = None;
+    for mode in MODES {
+        let all = execute(
+            mode,
+            &job,
+            common::KNAPSACK_WASM,
+            worker_nonces(),
+            Arc::new(CircuitBreaker::default()),
+            None,
+        )
+        .await;
+        let filtered = execute(
+            mode,
+            &job,
+            common::KNAPSACK_WASM,
+            worker_nonces(),
+            Arc::new(CircuitBreaker::default()),
+            Some(accept_if.clone()),
+        )
+        .await;
+        assert_eq!(all.0.len() as u64, all.1, "{:?}", mode);
+        assert_eq!(filtered.0.len() as u64, filtered.1, "{:?}", mode);
+        assert!(
+            filtered.0.iter().all(|nonce| all.0.contains(nonce)),
+            "{:?}",
+            mode
+        );
+        assert_eq!(verified.get_or_insert(all.clone()), &all, "{:?}", mode);
+        assert_eq!(
+            accepted.get_or_insert(filtered.clone()),
+            &filtered,
+            "{:?}",
+            mode
+        );
+    }
+    assert!(!verified.unwrap().0.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_circuit_breaker_trips_in_every_mode() {
+    let job = knapsack_job();
+    for mode in MODES {
+        let circuit_breaker = Arc::new(CircuitBreaker::new(0.5, 10));
+        let (nonces, num_solutions) = execute(
+            mode,
+            &job,
+            &TRAPPING_WASM,
+            vec![(0..NUM_NONCES).collect()],
+            circuit_breaker.clone(),
+            None,
+        )
+        .await;
+        assert!(nonces.is_empty() && num_solutions == 0, "{:?}", mode);
+        assert!(circuit_breaker.is_tripped(), "{:?}", mode);
+        // stops at the nonce that trips it, pre-generated instances are never solved
+        assert_eq!(circuit_breaker.num_errors(), 10, "{:?}", mode);
+    }
+}
@@ -0,0 +1,54 @@
+use tig_benchmarker::{FairnessPolicy, JobScheduler};
+
+const NUM_DISPATCHES: u64 = 4000;
+
+#[test]
+fn test_weighted_policy_allocates_proportionally() {
+    let weights = vec![3.0, 1.0, 0.5, 0.0];
+    let mut scheduler = JobScheduler::new(FairnessPolicy::Weighted(weights.clone()), 4);
+    for _ in 0..NUM_DISPATCHES {
+        scheduler.dispatch().unwrap();
+    }
+    let total_weight: f64 = weights.iter().sum();
+    for (weight, &num_attempts) in weights.iter().zip(scheduler.num_attempts()) {
+        let expected = weight / total_weight * NUM_DISPATCHES as f64;
+        assert!(
+            (num_attempts as f64 - expected).abs() <= 1.0,
+            "{} attempts, expected {}",
+            num_attempts,
+            expected
+        );
+    }
+    // a job without weight is never scheduled
+    assert_eq!(scheduler.num_attempts()[3], 0);
+}
+
+#[test]
+fn test_nonces_are_sequential_per_job() {
+    let mut scheduler = JobScheduler::new(FairnessPolicy::EqualNonces, 2);
+    let dispatched: Vec<(usize, u64)> = (0..6).map(|_| scheduler.dispatch().unwrap()).collect();
+    assert_eq!(
+        dispatched,
+        vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2), (1, 2)]
+    );
+}
+
+#[test]
+fn test_equal_time_policy() {
+    // job 0 takes 3x as long per attempt, so it gets a third of the attempts of job 1
+    let mut scheduler = JobScheduler::new(FairnessPolicy::EqualTime, 2);
+    for _ in 0..NUM_DISPATCHES {
+        let (job, _) = scheduler.dispatch().unwrap();
+        scheduler.record_time(job, if job == 0 { 3 } else { 1 });
+    }
+    let num_attempts = scheduler.num_attempts();
+    assert!((num_attempts[1] as f64 / num_attempts[0] as f64 - 3.0).abs() < 0.01);
+}
+
+#[test]
+fn test_no_schedulable_job() {
+    let mut scheduler = JobScheduler::new(FairnessPolicy::Weighted(vec![0.0]), 1);
+    assert_eq!(scheduler.dispatch(), None);
+    let mut scheduler = JobScheduler::new(FairnessPolicy::EqualNonces, 0);
+    assert_eq!(scheduler.dispatch(), None);
+}
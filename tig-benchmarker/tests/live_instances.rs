@@ -0,0 +1,94 @@
+use std::sync::Arc;
+use tig_benchmarker::LiveInstanceLimit;
+
+#[test]
+fn test_permits_are_capped_and_released_on_drop() {
+    let limit = Arc::new(LiveInstanceLimit::new(2));
+    let a = limit.try_acquire().unwrap();
+    let b = limit.try_acquire().unwrap();
+    assert!(limit.try_acquire().is_none());
+    assert_eq!(limit.live(), 2);
+    drop(a);
+    assert_eq!(limit.live(), 1);
+    let c = limit.try_acquire().unwrap();
+    assert!(limit.try_acquire().is_none());
+    drop((b, c));
+    assert_eq!(limit.live(), 0);
+    assert_eq!(limit.peak(), 2);
+
+    // a cap of 0 would never let an instance be generated
+    let limit = Arc::new(LiveInstanceLimit::new(0));
+    assert!(limit.try_acquire().is_some());
+}
+
+#[cfg(all(feature = "standalone", test))]
+mod tests {
+    use super::*;
+    use std::{thread, time::Duration};
+    use tig_benchmarker::{GenerationMode, InstanceSource};
+    use tokio::sync::Mutex;
+
+    const NUM_WORKERS: u64 = 4;
+    const NONCES_PER_WORKER: u64 = 15;
+    const MAX_LIVE_INSTANCES: usize = 3;
+
+    // every worker generates and solves concurrently, checking the cap whenever an instance is
+    // generated. returns the nonces solved
+    async fn run(mode: GenerationMode, limit: Arc<LiveInstanceLimit>) -> Vec<u64> {
+        let handles: Vec<_> = (0..NUM_WORKERS)
+            .map(|worker| {
+                let nonces = worker * NONCES_PER_WORKER..(worker + 1) * NONCES_PER_WORKER;
+                let generating_limit = limit.clone();
+                let source = InstanceSource::with_limit(
+                    mode,
+                    Arc::new(Mutex::new(nonces)),
+                    Some(limit.clone()),
+                    move |nonce| {
+                        assert!(generating_limit.live() <= MAX_LIVE_INSTANCES);
+                        thread::sleep(Duration::from_millis(1));
+                        nonce
+                    },
+                );
+                let limit = limit.clone();
+                tokio::spawn(async move {
+                    let mut solved = Vec::new();
+                    while let Some((nonce, instance, permit)) = source.next_live().await {
+                        assert!(permit.is_some());
+                        assert!(limit.live() <= MAX_LIVE_INSTANCES);
+                        tokio::time::sleep(Duration::from_millis(2)).await;
+                        solved.push(instance);
+                        assert_eq!(nonce, instance);
+                    }
+                    solved
+                })
+            })
+            .collect();
+        let mut solved = Vec::new();
+        for handle in handles {
+            solved.extend(handle.await.unwrap());
+        }
+        solved.sort_unstable();
+        solved
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_live_instances_never_exceed_cap() {
+        for mode in [
+            GenerationMode::Inline,
+            GenerationMode::Pipelined { queue_depth: 1 },
+            GenerationMode::Pipelined { queue_depth: 8 },
+        ] {
+            let limit = Arc::new(LiveInstanceLimit::new(MAX_LIVE_INSTANCES));
+            let solved = run(mode, limit.clone()).await;
+            assert_eq!(
+                solved,
+                (0..NUM_WORKERS * NONCES_PER_WORKER).collect::<Vec<_>>(),
+                "{:?}",
+                mode
+            );
+            // more workers than the cap keep it saturated
+            assert_eq!(limit.peak(), MAX_LIVE_INSTANCES, "{:?}", mode);
+            assert_eq!(limit.live(), 0, "{:?}", mode);
+        }
+    }
+}
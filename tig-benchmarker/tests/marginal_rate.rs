@@ -0,0 +1,66 @@
+use std::{sync::Arc, thread};
+use tig_benchmarker::MarginalRateFloor;
+
+// half the nonces solve at first, then a fifth, then none
+fn declining(nonce: u64) -> bool {
+    match nonce {
+        0..=299 => nonce % 10 < 5,
+        300..=599 => nonce % 10 < 2,
+        _ => false,
+    }
+}
+
+// mirrors the worker loop: stops taking nonces once the floor is reached
+fn run(solver: fn(u64) -> bool, floor: &MarginalRateFloor, max_nonces: u64) -> u64 {
+    for nonce in 0..max_nonces {
+        if floor.record(solver(nonce)) {
+            return nonce + 1;
+        }
+    }
+    max_nonces
+}
+
+#[test]
+fn test_declining_run_stops_below_floor() {
+    let floor = MarginalRateFloor::with_window(0.25, 100);
+    let attempts = run(declining, &floor, 1000);
+    // the window rate is 50% until nonce 300, and only falls below 25% once more than 83 of its
+    // nonces are past 300
+    assert!(floor.is_reached());
+    assert!((384..400).contains(&attempts), "attempts = {}", attempts);
+    assert!(floor.marginal_rate().unwrap() < 0.25);
+
+    // the overall rate is still well above the floor, so only the window catches the decline
+    let solved = (0..attempts).filter(|&nonce| declining(nonce)).count();
+    assert!(solved as f64 / attempts as f64 > 0.25);
+}
+
+#[test]
+fn test_steady_run_never_stops() {
+    let floor = MarginalRateFloor::with_window(0.25, 100);
+    assert_eq!(floor.marginal_rate(), None);
+    assert_eq!(run(|nonce| nonce % 10 < 3, &floor, 1000), 1000);
+    assert!(!floor.is_reached());
+    assert_eq!(floor.marginal_rate(), Some(0.3));
+
+    // no verdict before the window fills, even if nothing solves
+    let floor = MarginalRateFloor::with_window(0.25, 100);
+    assert_eq!(run(|_| false, &floor, 99), 99);
+    assert!(!floor.is_reached());
+    assert_eq!(run(|_| false, &floor, 1), 1);
+    assert!(floor.is_reached());
+}
+
+#[test]
+fn test_workers_stop_together() {
+    let floor = Arc::new(MarginalRateFloor::with_window(0.25, 50));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let floor = floor.clone();
+            thread::spawn(move || run(|nonce| nonce < 100, &floor, 10_000))
+        })
+        .collect();
+    let attempts: Vec<u64> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert!(floor.is_reached());
+    assert!(attempts.iter().all(|&x| x < 10_000));
+}
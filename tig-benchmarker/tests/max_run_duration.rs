@@ -0,0 +1,101 @@
+#![cfg(feature = "standalone")]
+
+use std::{sync::Arc, time::Instant};
+use tig_benchmarker::{MaxRunDuration, NonceIterator};
+use tokio::{
+    sync::Mutex,
+    time::{sleep, Duration},
+};
+
+const NUM_WORKERS: u64 = 4;
+const SOLVE_MS: u64 = 20;
+const MAX_RUN_MS: u64 = 300;
+// a worker may still be on its last nonce when the cap is hit, plus the controller's polling
+const TOLERANCE_MS: u128 = 2 * SOLVE_MS as u128 + 100;
+
+fn endless_nonce_iters() -> Vec<Arc<Mutex<NonceIterator>>> {
+    (0..NUM_WORKERS)
+        .map(|i| Arc::new(Mutex::new(NonceIterator::from_u64(i * 1_000_000))))
+        .collect()
+}
+
+// takes nonces like run_benchmark's workers until its nonce_iter is empty
+fn spawn_worker(
+    nonce_iter: Arc<Mutex<NonceIterator>>,
+    max_run_duration: Option<MaxRunDuration>,
+) -> tokio::task::JoinHandle<u64> {
+    tokio::spawn(async move {
+        let mut num_solved = 0;
+        loop {
+            let nonce = {
+                let mut nonce_iter = nonce_iter.lock().await;
+                if max_run_duration.as_ref().is_some_and(|x| x.is_exceeded()) {
+                    nonce_iter.empty();
+                }
+                nonce_iter.next()
+            };
+            match nonce {
+                None => return num_solved,
+                Some(_) => {
+                    sleep(Duration::from_millis(SOLVE_MS)).await;
+                    num_solved += 1;
+                }
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_workers_stop_at_max_run_duration() {
+    let start = Instant::now();
+    let max_run_duration = MaxRunDuration::new(MAX_RUN_MS);
+    let nonce_iters = endless_nonce_iters();
+    let handles: Vec<_> = nonce_iters
+        .iter()
+        .map(|x| spawn_worker(x.clone(), Some(max_run_duration.clone())))
+        .collect();
+    for handle in handles {
+        assert!(handle.await.unwrap() > 0);
+    }
+    let elapsed_ms = start.elapsed().as_millis();
+    // time() has ms resolution
+    assert!(elapsed_ms >= MAX_RUN_MS as u128 - 1);
+    assert!(elapsed_ms <= MAX_RUN_MS as u128 + TOLERANCE_MS);
+    assert!(max_run_duration.is_exceeded());
+    assert_eq!(max_run_duration.remaining_ms(), 0);
+    for nonce_iter in nonce_iters.iter() {
+        assert!(nonce_iter.lock().await.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_enforce_stops_workers_without_their_own_check() {
+    let start = Instant::now();
+    let max_run_duration = MaxRunDuration::new(MAX_RUN_MS);
+    assert!(!max_run_duration.is_exceeded());
+    assert!(max_run_duration.remaining_ms() <= MAX_RUN_MS);
+    let nonce_iters = endless_nonce_iters();
+    let handles: Vec<_> = nonce_iters
+        .iter()
+        .map(|x| spawn_worker(x.clone(), None))
+        .collect();
+    // polls like the run's controller loop
+    while !max_run_duration.enforce(&nonce_iters).await {
+        sleep(Duration::from_millis(50)).await;
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    let elapsed_ms = start.elapsed().as_millis();
+    // time() has ms resolution
+    assert!(elapsed_ms >= MAX_RUN_MS as u128 - 1);
+    assert!(elapsed_ms <= MAX_RUN_MS as u128 + TOLERANCE_MS);
+    let mut num_attempts = 0;
+    for nonce_iter in nonce_iters.iter() {
+        let nonce_iter = nonce_iter.lock().await;
+        assert!(nonce_iter.is_empty());
+        num_attempts += nonce_iter.attempts();
+    }
+    // roughly MAX_RUN_MS / SOLVE_MS nonces per worker, far from endless
+    assert!(num_attempts <= NUM_WORKERS * (MAX_RUN_MS / SOLVE_MS + 10));
+}
@@ -0,0 +1,57 @@
+use tig_benchmarker::MetadataConfig;
+use tig_structs::core::{SolutionData, VersionPin};
+use tig_utils::jsonify;
+
+fn new_solution_data() -> SolutionData {
+    SolutionData::new(7, 123, 1000, 2000, serde_json::Map::new()).unwrap()
+}
+
+fn version_pin() -> VersionPin {
+    VersionPin {
+        challenge_version: "0.0.1".to_string(),
+        algorithm_version: "abc".to_string(),
+    }
+}
+
+#[test]
+fn test_default_records_version_pin() {
+    let config = MetadataConfig::default();
+    assert!(config.version_pin);
+
+    let mut solution_data = new_solution_data();
+    config.apply(&mut solution_data, &version_pin());
+    assert_eq!(solution_data.version_pin, Some(version_pin()));
+    assert!(jsonify(&solution_data).contains("version_pin"));
+}
+
+#[test]
+fn test_disabled_version_pin_is_not_populated() {
+    let config = MetadataConfig { version_pin: false };
+
+    let mut solution_data = new_solution_data();
+    config.apply(&mut solution_data, &version_pin());
+    assert!(solution_data.version_pin.is_none());
+    assert!(!jsonify(&solution_data).contains("version_pin"));
+
+    // a pin left over from an earlier apply is dropped too
+    MetadataConfig::default().apply(&mut solution_data, &version_pin());
+    config.apply(&mut solution_data, &version_pin());
+    assert!(solution_data.version_pin.is_none());
+
+    // protocol fields are kept regardless
+    assert_eq!(solution_data.nonce, 7);
+    assert_eq!(solution_data.runtime_signature, 123);
+    assert_eq!(solution_data.fuel_consumed, 1000);
+    assert_eq!(
+        solution_data.calc_solution_signature(),
+        new_solution_data().calc_solution_signature()
+    );
+}
+
+#[test]
+fn test_missing_fields_use_defaults() {
+    let config: MetadataConfig = serde_json::from_str("{}").unwrap();
+    assert_eq!(config, MetadataConfig::default());
+    let config: MetadataConfig = serde_json::from_str(r#"{"version_pin":false}"#).unwrap();
+    assert!(!config.version_pin);
+}
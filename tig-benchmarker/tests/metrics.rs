@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::thread;
+use tig_benchmarker::{CircuitBreaker, Metrics};
+
+// a short run of 4 workers over 100 nonces each, where every 10th nonce errors and every 4th
+// nonce is solved
+fn short_run() -> Metrics {
+    let circuit_breaker = Arc::new(CircuitBreaker::new(0.9, 100));
+    let handles: Vec<_> = (0..4u64)
+        .map(|w| {
+            let circuit_breaker = circuit_breaker.clone();
+            thread::spawn(move || {
+                let mut num_solutions = 0;
+                for nonce in w * 100..(w + 1) * 100 {
+                    circuit_breaker.record(nonce % 10 == 0);
+                    if nonce % 4 == 0 {
+                        num_solutions += 1;
+                    }
+                }
+                num_solutions
+            })
+        })
+        .collect();
+    let num_solutions = handles.into_iter().map(|h| h.join().unwrap()).sum();
+    Metrics {
+        challenge_id: "c001".to_string(),
+        algorithm_id: "c001_a001".to_string(),
+        num_attempts: 400,
+        num_solutions,
+        num_errors: circuit_breaker.num_errors(),
+        elapsed_ms: 2000,
+    }
+}
+
+#[test]
+fn test_prometheus_text() {
+    let metrics = short_run();
+    assert_eq!(metrics.num_solutions, 100);
+    assert_eq!(metrics.num_errors, 40);
+    let text = metrics.to_prometheus();
+    let labels = "{challenge_id=\"c001\",algorithm_id=\"c001_a001\"}";
+    for (name, metric_type, value) in [
+        ("tig_benchmarker_attempts_total", "counter", "400"),
+        ("tig_benchmarker_solutions_total", "counter", "100"),
+        ("tig_benchmarker_errors_total", "counter", "40"),
+        ("tig_benchmarker_solve_rate", "gauge", "0.25"),
+        ("tig_benchmarker_nonces_per_second", "gauge", "200"),
+    ] {
+        assert!(text.contains(&format!("# HELP {} ", name)), "{}", text);
+        assert!(
+            text.contains(&format!("# TYPE {} {}\n", name, metric_type)),
+            "{}",
+            text
+        );
+        assert!(
+            text.contains(&format!("{}{} {}\n", name, labels, value)),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn test_no_attempts() {
+    // rates of a benchmark that hasn't started are 0 rather than NaN
+    let text = Metrics::default().to_prometheus();
+    assert!(text.contains("tig_benchmarker_solve_rate{challenge_id=\"\",algorithm_id=\"\"} 0\n"));
+    assert!(!text.contains("NaN"));
+}
@@ -0,0 +1,55 @@
+use serde_json::json;
+use std::{thread, time::Duration};
+use tig_benchmarker::{execute_mixed, Backend, BackendSplit, SplitPolicy};
+use tig_structs::core::Solution;
+
+// a trivial algorithm both backends run: solves even nonces with the nonce's square
+fn solve(nonce: u64) -> Option<Solution> {
+    (nonce & 1 == 0).then(|| json!({ "x": nonce * nonce }).as_object().unwrap().clone())
+}
+
+fn backend(ms: u64) -> impl Fn(u64) -> Option<Solution> + Sync {
+    move |nonce| {
+        thread::sleep(Duration::from_millis(ms));
+        solve(nonce)
+    }
+}
+
+#[test]
+fn test_fixed_split() {
+    let nonces: Vec<u64> = (0..200).collect();
+    let split = BackendSplit::new(SplitPolicy::Fixed(0.25));
+    let results = execute_mixed(&nonces, 4, &split, backend(0), backend(0));
+
+    // every nonce is recorded once, in order, with the same solution whichever backend ran it
+    assert_eq!(results.iter().map(|r| r.nonce).collect::<Vec<_>>(), nonces);
+    assert!(results.iter().all(|r| r.solution == solve(r.nonce)));
+    let num_gpu = results.iter().filter(|r| r.backend == Backend::Gpu).count() as u64;
+    assert_eq!(num_gpu, 50);
+    assert_eq!(split.num_dispatched(Backend::Gpu), 50);
+    assert_eq!(split.num_dispatched(Backend::Cpu), 150);
+
+    // all on one backend
+    for (fraction, expected) in [(0.0, Backend::Cpu), (1.0, Backend::Gpu)] {
+        let split = BackendSplit::new(SplitPolicy::Fixed(fraction));
+        let results = execute_mixed(&nonces[..20], 2, &split, backend(0), backend(0));
+        assert!(results.iter().all(|r| r.backend == expected));
+    }
+}
+
+#[test]
+fn test_auto_split_follows_throughput() {
+    let nonces: Vec<u64> = (0..300).collect();
+    let split = BackendSplit::new(SplitPolicy::Auto(0.5));
+    assert_eq!(split.gpu_fraction(), 0.5);
+    // the gpu solves a nonce 3 times faster than the cpu
+    let results = execute_mixed(&nonces, 4, &split, backend(6), backend(2));
+
+    assert_eq!(results.len(), nonces.len());
+    assert!(results.iter().all(|r| r.solution == solve(r.nonce)));
+    let fraction = split.gpu_fraction();
+    assert!((0.6..0.9).contains(&fraction), "fraction = {}", fraction);
+    let num_gpu = split.num_dispatched(Backend::Gpu);
+    assert!(num_gpu > 180, "num_gpu = {}", num_gpu);
+    assert_eq!(num_gpu + split.num_dispatched(Backend::Cpu), 300);
+}
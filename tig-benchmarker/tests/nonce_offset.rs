@@ -0,0 +1,41 @@
+use tig_benchmarker::{NonceIterator, NonceOffset};
+
+const NUM_WORKERS: u32 = 4;
+const NOW: u64 = 1_700_000_000_000;
+
+#[test]
+fn test_entropy_runs_start_at_different_offsets() {
+    let limit = u64::MAX / NUM_WORKERS as u64;
+    let first = NonceOffset::Entropy.resolve(limit, NOW);
+    let second = NonceOffset::Entropy.resolve(limit, NOW);
+    assert_ne!(first, second);
+    assert!(first < limit && second < limit);
+    assert_ne!(
+        NonceOffset::starts(first, NUM_WORKERS),
+        NonceOffset::starts(second, NUM_WORKERS)
+    );
+}
+
+#[test]
+fn test_recorded_offset_reproduces_the_run() {
+    let limit = u64::MAX / NUM_WORKERS as u64;
+    let recorded = NonceOffset::Entropy.resolve(limit, NOW);
+    let replayed = NonceOffset::Fixed(recorded).resolve(limit, NOW + 1000);
+    assert_eq!(replayed, recorded);
+
+    let starts = NonceOffset::starts(recorded, NUM_WORKERS);
+    assert_eq!(starts[0], recorded);
+    let nonces: Vec<u64> = NonceIterator::from_u64(starts[0]).take(3).collect();
+    assert_eq!(nonces, vec![recorded, recorded + 1, recorded + 2]);
+}
+
+#[test]
+fn test_default_offset_starts_at_zero() {
+    assert_eq!(NonceOffset::default().resolve(100, NOW), 0);
+    assert_eq!(NonceOffset::starts(0, NUM_WORKERS)[0], 0);
+    // each worker starts in its own region, below the next worker's
+    let starts = NonceOffset::starts(99, NUM_WORKERS);
+    assert!(starts
+        .windows(2)
+        .all(|x| x[1] - x[0] == u64::MAX / NUM_WORKERS as u64));
+}
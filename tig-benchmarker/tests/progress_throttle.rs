@@ -0,0 +1,54 @@
+use tig_benchmarker::ProgressThrottle;
+
+// progress polled every 10ms for 2s by a solver making 7 attempts per poll. returns the polls that
+// were emitted as (time, attempts)
+fn emissions(throttle: &mut ProgressThrottle) -> Vec<(u64, u64)> {
+    (0..200u64)
+        .map(|i| (i * 10, i * 7))
+        .filter(|&(now_ms, num_attempts)| throttle.should_emit(now_ms, num_attempts))
+        .collect()
+}
+
+#[test]
+fn test_disabled_throttle_emits_every_update() {
+    assert_eq!(emissions(&mut ProgressThrottle::default()).len(), 200);
+}
+
+#[test]
+fn test_emissions_respect_min_interval() {
+    let emitted = emissions(&mut ProgressThrottle::new(250, 0));
+    assert_eq!(emitted[0], (0, 0));
+    assert!(emitted.windows(2).all(|x| x[1].0 - x[0].0 >= 250));
+    // coalesced rather than dropped, the next poll past the interval is emitted
+    assert!(emitted.windows(2).all(|x| x[1].0 - x[0].0 < 250 + 10));
+    assert_eq!(emitted.len(), 8);
+}
+
+#[test]
+fn test_emissions_respect_min_attempts() {
+    let emitted = emissions(&mut ProgressThrottle::new(0, 100));
+    assert!(emitted.windows(2).all(|x| x[1].1 - x[0].1 >= 100));
+    assert!(emitted.windows(2).all(|x| x[1].1 - x[0].1 < 100 + 7));
+}
+
+#[test]
+fn test_emissions_respect_both_limits() {
+    let emitted = emissions(&mut ProgressThrottle::new(100, 300));
+    assert!(emitted
+        .windows(2)
+        .all(|x| x[1].0 - x[0].0 >= 100 && x[1].1 - x[0].1 >= 300));
+    // the attempt limit is the tighter one at 70 attempts per 100ms
+    assert!(emitted.windows(2).any(|x| x[1].0 - x[0].0 > 100));
+}
+
+#[test]
+fn test_reset_emits_next_update() {
+    let mut throttle = ProgressThrottle::new(1000, 0);
+    assert!(throttle.should_emit(0, 0));
+    assert!(!throttle.should_emit(500, 10));
+    throttle.reset();
+    // e.g. a new job whose attempts start from 0 again
+    assert!(throttle.should_emit(500, 0));
+    assert!(!throttle.should_emit(600, 5));
+    assert!(throttle.should_emit(1500, 5));
+}
@@ -0,0 +1,307 @@
+mod common;
+
+use serde_json::json;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tig_benchmarker::{native_solver, race_solve};
+use tig_challenges::{
+    knapsack,
+    preprocessing::{instance_key, PreprocessingCache},
+    satisfiability, ChallengeTrait,
+};
+use tig_structs::core::{BenchmarkSettings, Solution};
+
+const NUM_VARIABLES: usize = 10;
+
+fn settings() -> BenchmarkSettings {
+    BenchmarkSettings {
+        algorithm_id: "".to_string(),
+        ..common::settings("c001", vec![NUM_VARIABLES as i32, 200])
+    }
+}
+
+fn to_solution(variables: &[bool]) -> Solution {
+    let variables: Vec<u8> = variables.iter().map(|&v| v as u8).collect();
+    json!({ "variables": variables })
+        .as_object()
+        .unwrap()
+        .clone()
+}
+
+// strong algorithm: brute forces the instance, which is small enough to try every assignment
+fn brute_force(settings: &BenchmarkSettings, nonce: u64) -> Option<Solution> {
+    let challenge = satisfiability::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap();
+    (0..1u32 << NUM_VARIABLES)
+        .map(|bits| (0..NUM_VARIABLES).map(|i| bits & (1 << i) != 0).collect())
+        .find(|variables: &Vec<bool>| {
+            challenge
+                .verify_solution(&satisfiability::Solution {
+                    variables: variables.clone(),
+                })
+                .is_ok()
+        })
+        .map(|variables| to_solution(&variables))
+}
+
+fn algorithm_ids(ids: &[&str]) -> Vec<String> {
+    ids.iter().map(|x| x.to_string()).collect()
+}
+
+// nonce whose instances the strong algorithm solves under every algorithm id used below, and that
+// all false doesn't
+fn solvable_nonce() -> u64 {
+    (0..100)
+        .find(|&nonce| {
+            ["weak", "strong", "slow"].iter().all(|id| {
+                let mut settings = settings();
+                settings.algorithm_id = id.to_string();
+                brute_force(&settings, nonce).is_some()
+                    && brute_force(&settings, nonce) != Some(to_solution(&[false; NUM_VARIABLES]))
+            })
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_stronger_algorithm_wins() {
+    let nonce = solvable_nonce();
+    let slow_canceled = AtomicBool::new(false);
+    let winner = race_solve(
+        &settings(),
+        nonce,
+        &algorithm_ids(&["weak", "strong", "slow"]),
+        3,
+        Some(1.0),
+        |settings, nonce, cancel_token| match settings.algorithm_id.as_str() {
+            // answers first with a solution that doesn't verify
+            "weak" => Some(to_solution(&[false; NUM_VARIABLES])),
+            "strong" => {
+                thread::sleep(Duration::from_millis(20));
+                brute_force(settings, nonce)
+            }
+            // would also solve, but is canceled once strong reaches the target
+            _ => {
+                while !cancel_token.is_canceled() {
+                    thread::sleep(Duration::from_millis(1));
+                }
+                slow_canceled.store(true, Ordering::Relaxed);
+                None
+            }
+        },
+    )
+    .unwrap();
+    assert_eq!(winner.algorithm_id, "strong");
+    let mut settings = settings();
+    settings.algorithm_id = "strong".to_string();
+    assert_eq!(Some(winner.solution), brute_force(&settings, nonce));
+    assert!(slow_canceled.load(Ordering::Relaxed));
+}
+
+#[test]
+fn test_losers_that_have_not_started_are_skipped() {
+    let nonce = solvable_nonce();
+    let started = Mutex::new(Vec::new());
+    let winner = race_solve(
+        &settings(),
+        nonce,
+        &algorithm_ids(&["strong", "weak", "slow"]),
+        1,
+        Some(1.0),
+        |settings, nonce, _| {
+            started.lock().unwrap().push(settings.algorithm_id.clone());
+            brute_force(settings, nonce)
+        },
+    )
+    .unwrap();
+    assert_eq!(winner.algorithm_id, "strong");
+    assert_eq!(*started.lock().unwrap(), vec!["strong".to_string()]);
+}
+
+#[test]
+fn test_no_winner_when_nothing_verifies() {
+    let winner = race_solve(
+        &settings(),
+        solvable_nonce(),
+        &algorithm_ids(&["weak", "weak2"]),
+        2,
+        None,
+        |_, _, _| Some(to_solution(&[false; NUM_VARIABLES])),
+    );
+    assert!(winner.is_none());
+}
+
+const NUM_ITEMS: usize = 12;
+
+fn knapsack_settings() -> BenchmarkSettings {
+    BenchmarkSettings {
+        algorithm_id: "".to_string(),
+        ..common::settings("c003", vec![NUM_ITEMS as i32, 0])
+    }
+}
+
+fn knapsack_instance(settings: &BenchmarkSettings, nonce: u64) -> knapsack::Challenge {
+    knapsack::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap()
+}
+
+fn to_items(items: Vec<usize>) -> Solution {
+    json!({ "items": items }).as_object().unwrap().clone()
+}
+
+// weak algorithm: the greedy baseline, which just meets the min value
+fn greedy(settings: &BenchmarkSettings, nonce: u64) -> Vec<usize> {
+    let challenge = knapsack_instance(settings, nonce);
+    let mut items: Vec<usize> = (0..NUM_ITEMS).collect();
+    items.sort_by(|&a, &b| {
+        let ratio = |i: usize| challenge.values[i] as f64 / challenge.weights[i] as f64;
+        ratio(b).partial_cmp(&ratio(a)).unwrap()
+    });
+    let mut total_weight = 0;
+    items.retain(|&item| {
+        let fits = total_weight + challenge.weights[item] <= challenge.max_weight;
+        if fits {
+            total_weight += challenge.weights[item];
+        }
+        fits
+    });
+    items
+}
+
+// strong algorithm: the best of every subset of items
+fn optimal(settings: &BenchmarkSettings, nonce: u64) -> Vec<usize> {
+    let challenge = knapsack_instance(settings, nonce);
+    let sum = |bits: u32, of: &[u32]| {
+        (0..NUM_ITEMS)
+            .filter(|i| bits & (1 << i) != 0)
+            .map(|i| of[i])
+            .sum::<u32>()
+    };
+    let best = (0..1u32 << NUM_ITEMS)
+        .filter(|&bits| sum(bits, &challenge.weights) <= challenge.max_weight)
+        .max_by_key(|&bits| sum(bits, &challenge.values))
+        .unwrap();
+    (0..NUM_ITEMS).filter(|i| best & (1 << i) != 0).collect()
+}
+
+// nonce whose instance under the optimal algorithm's id is one greedy doesn't solve optimally
+fn improvable_nonce() -> u64 {
+    (0..100)
+        .find(|&nonce| {
+            let mut settings = knapsack_settings();
+            settings.algorithm_id = "optimal".to_string();
+            let values = knapsack_instance(&settings, nonce).values;
+            let value = |items: Vec<usize>| items.iter().map(|&i| values[i]).sum::<u32>();
+            value(optimal(&settings, nonce)) > value(greedy(&settings, nonce))
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_best_qualifier_wins_over_first_finished() {
+    let order = Mutex::new(Vec::new());
+    let winner = race_solve(
+        &knapsack_settings(),
+        improvable_nonce(),
+        &algorithm_ids(&["greedy", "optimal"]),
+        2,
+        None,
+        |settings, nonce, _| {
+            let solution = match settings.algorithm_id.as_str() {
+                "greedy" => greedy(settings, nonce),
+                _ => {
+                    thread::sleep(Duration::from_millis(20));
+                    optimal(settings, nonce)
+                }
+            };
+            order.lock().unwrap().push(settings.algorithm_id.clone());
+            Some(to_items(solution))
+        },
+    )
+    .unwrap();
+    // the stronger algorithm finished second, and still wins
+    assert_eq!(
+        *order.lock().unwrap(),
+        algorithm_ids(&["greedy", "optimal"])
+    );
+    assert_eq!(winner.algorithm_id, "optimal");
+    assert!(winner.qualifier > 1.0);
+}
+
+#[test]
+fn test_ties_go_to_the_first_listed() {
+    let order = Mutex::new(Vec::new());
+    // every satisfying assignment has a qualifier of 1, and the second listed finishes first
+    let winner = race_solve(
+        &settings(),
+        solvable_nonce(),
+        &algorithm_ids(&["slow", "strong"]),
+        2,
+        None,
+        |settings, nonce, _| {
+            if settings.algorithm_id == "slow" {
+                thread::sleep(Duration::from_millis(20));
+            }
+            let solution = brute_force(settings, nonce);
+            order.lock().unwrap().push(settings.algorithm_id.clone());
+            solution
+        },
+    )
+    .unwrap();
+    assert_eq!(*order.lock().unwrap(), algorithm_ids(&["strong", "slow"]));
+    assert_eq!(winner.algorithm_id, "slow");
+    assert_eq!(winner.qualifier, 1.0);
+}
+
+// literals of the clauses each variable is in
+fn clause_index(challenge: &satisfiability::Challenge) -> Vec<Vec<i32>> {
+    let mut index = vec![Vec::new(); challenge.difficulty.num_variables];
+    for clause in challenge.clauses.iter() {
+        for &literal in clause {
+            index[literal.unsigned_abs() as usize - 1].push(literal);
+        }
+    }
+    index
+}
+
+#[test]
+fn test_native_race_shares_preprocessing() {
+    let cache = PreprocessingCache::new(8);
+    // sets each variable to the polarity it appears with most
+    let solver = native_solver(
+        &cache,
+        |_, challenge: &satisfiability::Challenge, cache, _| {
+            let index = cache.get_or_insert_with(instance_key(challenge), "clause_index", || {
+                clause_index(challenge)
+            });
+            Some(satisfiability::Solution {
+                variables: index
+                    .iter()
+                    .map(|literals| {
+                        literals.iter().filter(|&&l| l > 0).count() * 2 > literals.len()
+                    })
+                    .collect(),
+            })
+        },
+    );
+    let nonce = solvable_nonce();
+    let ids = algorithm_ids(&["strong", "slow"]);
+    race_solve(&settings(), nonce, &ids, 2, None, &solver);
+    // seeds depend on the algorithm, so each candidate has its own instance
+    assert_eq!((cache.num_misses(), cache.num_hits()), (2, 0));
+    // solving the nonce again reuses both
+    race_solve(&settings(), nonce, &ids, 2, None, &solver);
+    assert_eq!((cache.num_misses(), cache.num_hits()), (2, 2));
+}
@@ -0,0 +1,137 @@
+mod common;
+
+use tig_benchmarker::{reproduce, ReproductionLog, ReproductionNonces};
+use tig_challenges::{satisfiability, ChallengeTrait};
+use tig_structs::core::BenchmarkSettings;
+use tig_worker::InstanceOptions;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![20, 150])
+}
+
+// stands in for a solver run: the instance and whether its trivial solution verifies
+fn salted(seed_salt: Option<&str>) -> InstanceOptions {
+    InstanceOptions {
+        seed_salt: seed_salt.map(|x| x.to_string()),
+        ..Default::default()
+    }
+}
+
+fn run(_nonce: u64, seeds: [u64; 8]) -> (String, bool) {
+    let challenge =
+        satisfiability::Challenge::generate_instance_from_vec(seeds, &settings().difficulty)
+            .unwrap();
+    (challenge.summary(), challenge.trivial_solution().is_some())
+}
+
+#[test]
+fn test_round_trip_reproduces_outcomes() {
+    for nonces in [
+        ReproductionNonces::Range { start: 5, end: 25 },
+        ReproductionNonces::List(vec![3, 99, 7, 7, 0]),
+        ReproductionNonces::Ranges(vec![(0, 4), (1000, 1003), (50, 50)]),
+    ] {
+        let log = ReproductionLog::new(settings(), salted(Some("ci")), nonces.clone());
+        let outcomes: Vec<(String, bool)> = nonces
+            .to_vec()
+            .into_iter()
+            .map(|nonce| run(nonce, settings().calc_seeds_with_salt(nonce, Some("ci"))))
+            .collect();
+
+        let json = serde_json::to_string(&log).unwrap();
+        let log: ReproductionLog = serde_json::from_str(&json).unwrap();
+        assert_eq!(log.nonces, nonces);
+        assert_eq!(reproduce(&log, run).unwrap(), outcomes);
+    }
+}
+
+#[test]
+fn test_salt_is_part_of_the_scenario() {
+    let nonces = ReproductionNonces::Range { start: 0, end: 10 };
+    let salted = ReproductionLog::new(settings(), salted(Some("ci")), nonces.clone());
+    let unsalted = ReproductionLog::new(settings(), salted(None), nonces);
+    assert_eq!(unsalted.seeds(4), settings().calc_seeds(4));
+    assert_ne!(
+        reproduce(&salted, run).unwrap(),
+        reproduce(&unsalted, run).unwrap()
+    );
+}
+
+#[test]
+fn test_version_mismatch() {
+    let mut log = ReproductionLog::new(
+        settings(),
+        InstanceOptions::default(),
+        ReproductionNonces::List(vec![1]),
+    );
+    log.challenges_version = "0.0.0-other".to_string();
+    let err = reproduce(&log, run).unwrap_err();
+    assert!(err.contains("0.0.0-other"));
+}
+
+#[cfg(feature = "standalone")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_reproduces_a_real_run() {
+    use std::{collections::HashMap, fs};
+    use tig_benchmarker::Job;
+    use tig_challenges::knapsack;
+
+    let job = Job {
+        instance_options: salted(Some("ci")),
+        ..common::knapsack_job()
+    };
+    // as a run of 2 workers logs its nonces
+    let ranges = vec![(0, 10), (u64::MAX / 2, u64::MAX / 2 + 10)];
+    let solutions: HashMap<u64, _> = common::execute(
+        &job,
+        common::KNAPSACK_WASM,
+        ranges
+            .iter()
+            .map(|&(start, end)| (start..end).collect())
+            .collect(),
+    )
+    .await
+    .into_iter()
+    .map(|x| (x.nonce, x.solution))
+    .collect();
+    assert!(!solutions.is_empty());
+    let path = std::env::temp_dir().join(format!("tig_reproduction_{}.json", std::process::id()));
+    ReproductionLog::new(
+        job.settings.clone(),
+        job.instance_options.clone(),
+        ReproductionNonces::Ranges(ranges),
+    )
+    .write(&path)
+    .unwrap();
+    let log = ReproductionLog::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // rerunning the logged scenario solves the same instances the same way
+    let rerun_job = Job {
+        settings: log.settings.clone(),
+        instance_options: log.instance_options.clone(),
+        ..common::knapsack_job()
+    };
+    let rerun: HashMap<u64, _> =
+        common::execute(&rerun_job, common::KNAPSACK_WASM, vec![log.nonces.to_vec()])
+            .await
+            .into_iter()
+            .map(|x| (x.nonce, x.solution))
+            .collect();
+    assert_eq!(rerun, solutions);
+
+    // and the reproduced seeds give the instances the run solved
+    let verified = reproduce(&log, |nonce, seeds| {
+        let challenge =
+            knapsack::Challenge::generate_instance_from_vec(seeds, &log.settings.difficulty)
+                .unwrap();
+        solutions.get(&nonce).map(|solution| {
+            let solution = knapsack::Solution::try_from(solution.clone()).unwrap();
+            challenge.verify_solution(&solution).is_ok()
+        })
+    })
+    .unwrap();
+    assert_eq!(verified.len(), 20);
+    assert_eq!(verified.iter().flatten().count(), solutions.len());
+    assert!(verified.into_iter().flatten().all(|x| x));
+}
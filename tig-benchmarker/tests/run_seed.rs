@@ -0,0 +1,85 @@
+mod common;
+
+use tig_benchmarker::{NonceOffset, RunSeed};
+use tig_worker::InstanceOptions;
+
+const NUM_WORKERS: u32 = 4;
+const NONCES_PER_WORKER: u64 = 5;
+
+// a run of the knapsack job at a sampled number of items, salted with the run's seed salt, with
+// every worker starting at its offset. returns each solution the run kept
+#[cfg(feature = "standalone")]
+async fn run(run_seed: RunSeed) -> Vec<(u64, tig_structs::core::Solution)> {
+    use rand::Rng;
+
+    let mut job = common::knapsack_job();
+    job.settings.difficulty[0] = run_seed.sampling_rng().gen_range(40..60);
+    job.instance_options = run_seed.salt(&InstanceOptions::default());
+    let offset = NonceOffset::Entropy.resolve_with_seed(u64::MAX / NUM_WORKERS as u64, run_seed);
+    let worker_nonces = NonceOffset::starts(offset, NUM_WORKERS)
+        .into_iter()
+        .map(|start| (start..start + NONCES_PER_WORKER).collect())
+        .collect();
+    common::execute(&job, common::KNAPSACK_WASM, worker_nonces)
+        .await
+        .into_iter()
+        .map(|x| (x.nonce, x.solution))
+        .collect()
+}
+
+#[cfg(feature = "standalone")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_same_run_seed_reproduces_the_run() {
+    let run_seed = RunSeed::from_entropy(1_700_000_000_000);
+    let outcomes = run(run_seed).await;
+    assert!(!outcomes.is_empty());
+    assert_eq!(run(run_seed).await, outcomes);
+
+    // as printed at the start of a run and passed back to --run-seed
+    let replayed: RunSeed = run_seed.to_string().parse().unwrap();
+    assert_eq!(replayed, run_seed);
+    assert_eq!(run(replayed).await, outcomes);
+}
+
+#[cfg(feature = "standalone")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_different_run_seeds_give_different_runs() {
+    assert_ne!(run(RunSeed(1)).await, run(RunSeed(2)).await);
+}
+
+#[test]
+fn test_run_seed_salts_instances() {
+    let options = InstanceOptions {
+        stretch_rounds: 2,
+        ..Default::default()
+    };
+    let salted = RunSeed(1).salt(&options);
+    assert_eq!(salted.seed_salt, Some(RunSeed(1).seed_salt()));
+    assert_eq!(salted.stretch_rounds, 2);
+    assert!(!RunSeed(1).salt(&InstanceOptions::default()).is_protocol());
+    assert_ne!(RunSeed(1).seed_salt(), RunSeed(2).seed_salt());
+    // the salt doesn't shift the run's other streams
+    let limit = u64::MAX / NUM_WORKERS as u64;
+    assert_eq!(
+        RunSeed(1).nonce_offset(limit),
+        NonceOffset::Entropy.resolve_with_seed(limit, RunSeed(1))
+    );
+}
+
+#[test]
+fn test_fixed_offset_ignores_run_seed() {
+    let limit = u64::MAX / NUM_WORKERS as u64;
+    assert_eq!(
+        NonceOffset::Fixed(42).resolve_with_seed(limit, RunSeed(1)),
+        NonceOffset::Fixed(42).resolve_with_seed(limit, RunSeed(2))
+    );
+    assert!(NonceOffset::Entropy.resolve_with_seed(limit, RunSeed(1)) < limit);
+}
+
+#[test]
+fn test_parse_run_seed() {
+    assert_eq!("0xff".parse::<RunSeed>().unwrap(), RunSeed(255));
+    assert_eq!("255".parse::<RunSeed>().unwrap(), RunSeed(255));
+    assert_eq!(RunSeed(255).to_string(), "0xff");
+    assert!("0xzz".parse::<RunSeed>().is_err());
+}
@@ -0,0 +1,55 @@
+use std::{sync::Arc, thread};
+use tig_benchmarker::{RunWarning, RunWarnings};
+use tig_utils::{dejsonify, jsonify};
+
+fn backend_fallback() -> RunWarning {
+    RunWarning::BackendFallback {
+        algorithm_id: "c003_a001".to_string(),
+        requested: "native".to_string(),
+        used: "wasm".to_string(),
+    }
+}
+
+#[test]
+fn test_backend_fallback_is_recorded_once() {
+    let run_warnings = Arc::new(RunWarnings::new(false));
+    // every worker falls back on every nonce
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let run_warnings = run_warnings.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    run_warnings.record(backend_fallback());
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(run_warnings.warnings(), vec![backend_fallback()]);
+    assert_eq!(
+        backend_fallback().to_string(),
+        "no native solver for c003_a001 in this build, running on wasm only"
+    );
+}
+
+#[test]
+fn test_warnings_keep_recorded_order() {
+    let run_warnings = RunWarnings::new(false);
+    assert!(run_warnings.warnings().is_empty());
+    let drift = |nonce| RunWarning::VersionDrift {
+        nonce,
+        drift: "wasm_hash: a != b".to_string(),
+    };
+    run_warnings.record(drift(3));
+    run_warnings.record(backend_fallback());
+    run_warnings.record(drift(3));
+    run_warnings.record(drift(1));
+    let warnings = run_warnings.warnings();
+    assert_eq!(warnings, vec![drift(3), backend_fallback(), drift(1)]);
+    assert_eq!(
+        dejsonify::<Vec<RunWarning>>(&jsonify(&warnings)).unwrap(),
+        warnings
+    );
+}
@@ -0,0 +1,54 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tig_benchmarker::{SamplingPolicy, SurfaceCell, UniformPolicy, VariancePolicy};
+use tig_structs::core::VersionPin;
+
+// solve probability drops from ~1 to ~0 around difficulty 10
+fn solve_probability(difficulty: i32) -> f64 {
+    1.0 / (1.0 + ((difficulty - 10) as f64 * 2.0).exp())
+}
+
+fn explore(policy: &impl SamplingPolicy) -> Vec<u64> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut cells: Vec<SurfaceCell> = (0..=20)
+        .map(|d| SurfaceCell {
+            difficulty: vec![d],
+            num_attempts: 0,
+            num_solutions: 0,
+            elapsed_ms: 0,
+            version_pin: VersionPin {
+                challenge_version: "0".to_string(),
+                algorithm_version: "0".to_string(),
+            },
+        })
+        .collect();
+    for _ in 0..20 {
+        let allocation = policy.allocate(&cells, 210);
+        assert_eq!(allocation.iter().sum::<u64>(), 210);
+        for (cell, num_samples) in cells.iter_mut().zip(allocation) {
+            let p = solve_probability(cell.difficulty[0]);
+            for _ in 0..num_samples {
+                cell.num_attempts += 1;
+                cell.num_solutions += (rng.gen::<f64>() < p) as u64;
+            }
+        }
+    }
+    cells.iter().map(|cell| cell.num_attempts).collect()
+}
+
+#[test]
+fn test_variance_policy_samples_near_cliff() {
+    let samples = explore(&VariancePolicy { min_weight: 0.01 });
+    let near_cliff: u64 = samples[8..=12].iter().sum();
+    let easy: u64 = samples[0..=4].iter().sum();
+    let hard: u64 = samples[16..=20].iter().sum();
+    assert!(near_cliff > 3 * easy);
+    assert!(near_cliff > 3 * hard);
+    // every difficulty still gets sampled
+    assert!(samples.iter().all(|&n| n > 0));
+}
+
+#[test]
+fn test_uniform_policy() {
+    let samples = explore(&UniformPolicy);
+    assert!(samples.iter().all(|&n| n == 200));
+}
@@ -0,0 +1,126 @@
+mod common;
+
+use std::{
+    collections::HashMap,
+    fs,
+    sync::{Arc, Mutex},
+    thread,
+};
+use tig_algorithms::c003::c003_a001;
+use tig_benchmarker::{NonceIterator, ScheduleEvent, ScheduleLog, ScheduleRecorder, TraceOutcome};
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_worker::{deserialize_instance, generate_serialized_instance, BenchmarkSettings};
+
+const NUM_WORKERS: usize = 4;
+const NUM_NONCES: u64 = 40;
+
+// the greedy solver only reaches a target above the baseline on some instances
+fn settings() -> BenchmarkSettings {
+    common::settings("c003", vec![50, 10])
+}
+
+fn solve(nonce: u64) -> TraceOutcome {
+    let instance = generate_serialized_instance(&settings(), nonce).unwrap();
+    let challenge: knapsack::Challenge = deserialize_instance(&instance).unwrap();
+    match c003_a001::solve_challenge(&challenge) {
+        Ok(Some(solution)) => match challenge.verify_solution(&solution) {
+            Ok(()) => TraceOutcome::Solved,
+            Err(_) => TraceOutcome::Invalid,
+        },
+        Ok(None) => TraceOutcome::GaveUp,
+        Err(_) => TraceOutcome::Error,
+    }
+}
+
+// workers share one nonce iterator and record like the workers of execute, so the recorded order
+// depends on how the threads interleave
+fn record_concurrent_run() -> ScheduleLog {
+    let nonce_iter = Arc::new(Mutex::new(NonceIterator::from_vec(
+        (0..NUM_NONCES).rev().collect(),
+    )));
+    let recorder = Arc::new(ScheduleRecorder::new());
+    let handles: Vec<_> = (0..NUM_WORKERS)
+        .map(|worker| {
+            let nonce_iter = nonce_iter.clone();
+            let recorder = recorder.clone();
+            thread::spawn(move || loop {
+                let nonce = match nonce_iter.lock().unwrap().next() {
+                    Some(nonce) => nonce,
+                    None => break,
+                };
+                recorder.dispatched(worker, nonce);
+                recorder.completed(worker, nonce, solve(nonce));
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    recorder.log()
+}
+
+#[test]
+fn test_replay_reproduces_outcomes_in_recorded_order() {
+    let log = record_concurrent_run();
+    assert_eq!(log.events.len(), 2 * NUM_NONCES as usize);
+    let mut dispatch_order = log.dispatch_order();
+    assert_eq!(log.completion_order().len(), NUM_NONCES as usize);
+    let outcomes = log.outcomes();
+    assert!(outcomes.values().any(|&x| x == TraceOutcome::Solved));
+    assert!(outcomes.values().any(|&x| x != TraceOutcome::Solved));
+
+    // a single worker replays the nonces serially in the recorded order
+    let replay_recorder = ScheduleRecorder::new();
+    let mut replayed = Vec::new();
+    for nonce in log.replay() {
+        replay_recorder.dispatched(0, nonce);
+        replay_recorder.completed(0, nonce, solve(nonce));
+        replayed.push(nonce);
+    }
+    let replay_log = replay_recorder.log();
+    assert_eq!(replayed, dispatch_order);
+    assert_eq!(replay_log.completion_order(), dispatch_order);
+    assert_eq!(replay_log.outcomes(), outcomes);
+
+    dispatch_order.sort_unstable();
+    assert_eq!(dispatch_order, (0..NUM_NONCES).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_log_round_trips_through_file() {
+    let path = std::env::temp_dir().join(format!("tig_schedule_log_{}.json", std::process::id()));
+    let log = ScheduleLog {
+        events: vec![
+            ScheduleEvent::Dispatched {
+                worker: 1,
+                nonce: 7,
+            },
+            ScheduleEvent::Dispatched {
+                worker: 0,
+                nonce: 3,
+            },
+            ScheduleEvent::Completed {
+                worker: 0,
+                nonce: 3,
+                outcome: TraceOutcome::GaveUp,
+            },
+            ScheduleEvent::Completed {
+                worker: 1,
+                nonce: 7,
+                outcome: TraceOutcome::Solved,
+            },
+        ],
+    };
+    log.save(&path).unwrap();
+    let loaded = ScheduleLog::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, log);
+    assert_eq!(loaded.dispatch_order(), vec![7, 3]);
+    assert_eq!(loaded.completion_order(), vec![3, 7]);
+    assert_eq!(
+        loaded.outcomes(),
+        HashMap::from([(3, TraceOutcome::GaveUp), (7, TraceOutcome::Solved)])
+    );
+    assert_eq!(loaded.replay().collect::<Vec<_>>(), vec![7, 3]);
+    assert!(ScheduleLog::load(&path).is_err());
+}
@@ -0,0 +1,205 @@
+#![cfg(feature = "standalone")]
+
+mod common;
+
+use std::{
+    cell::RefCell,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+use tig_benchmarker::{
+    shutdown, Job, NonceIterator, ShutdownCheckpoint, ShutdownSignal, SolutionFlusher,
+    SolutionSubmitter,
+};
+use tig_structs::core::{Solution, SolutionData};
+use tokio::sync::Mutex;
+
+const NUM_WORKERS: u64 = 3;
+const SOLVE_MS: u64 = 20;
+
+struct MockSubmitter {
+    fail: bool,
+    submitted: RefCell<Vec<u64>>,
+}
+
+impl SolutionSubmitter for MockSubmitter {
+    async fn submit(
+        &self,
+        _benchmark_id: &str,
+        solutions_data: &[SolutionData],
+    ) -> Result<(), String> {
+        if self.fail {
+            return Err("mock failure".to_string());
+        }
+        self.submitted
+            .borrow_mut()
+            .extend(solutions_data.iter().map(|d| d.nonce));
+        Ok(())
+    }
+}
+
+fn job() -> Job {
+    common::job(common::settings("c001", vec![50, 300]))
+}
+
+// stands in for run_benchmark::execute: every worker holds its nonce iterator until it exits and
+// finds a solution for every nonce
+fn spawn_workers(
+    nonce_iters: &[Arc<Mutex<NonceIterator>>],
+    solutions_data: &Arc<Mutex<Vec<SolutionData>>>,
+) {
+    for nonce_iter in nonce_iters.iter().cloned() {
+        let solutions_data = solutions_data.clone();
+        tokio::spawn(async move {
+            loop {
+                let nonce = match (*nonce_iter).lock().await.next() {
+                    Some(nonce) => nonce,
+                    None => break,
+                };
+                tokio::time::sleep(Duration::from_millis(SOLVE_MS)).await;
+                (*solutions_data)
+                    .lock()
+                    .await
+                    .push(SolutionData::new(nonce, 0, 1, 1, Solution::new()).unwrap());
+            }
+        });
+    }
+}
+
+async fn run_until_shutdown(
+    submitter: &MockSubmitter,
+    checkpoint_path: &Path,
+) -> (ShutdownCheckpoint, Vec<Arc<Mutex<NonceIterator>>>) {
+    let job = job();
+    let nonce_iters: Vec<Arc<Mutex<NonceIterator>>> = (0..NUM_WORKERS)
+        .map(|i| Arc::new(Mutex::new(NonceIterator::from_u64(i * 1000))))
+        .collect();
+    let solutions_data = Arc::new(Mutex::new(Vec::new()));
+    spawn_workers(&nonce_iters, &solutions_data);
+
+    let signal = ShutdownSignal::new();
+    let trigger = signal.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(5 * SOLVE_MS + SOLVE_MS / 2)).await;
+        trigger.trigger();
+    });
+    while !signal.is_triggered() {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    let mut flusher = SolutionFlusher::new(u64::MAX, usize::MAX);
+    let checkpoint = shutdown(
+        &job,
+        &nonce_iters,
+        &solutions_data,
+        &mut flusher,
+        submitter,
+        checkpoint_path,
+        10_000,
+    )
+    .await
+    .unwrap();
+    (checkpoint, nonce_iters)
+}
+
+fn checkpoint_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("tig_shutdown_{}_{}.json", name, std::process::id()))
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_shutdown_persists_solutions_and_checkpoint() {
+    let submitter = MockSubmitter {
+        fail: false,
+        submitted: RefCell::new(Vec::new()),
+    };
+    let path = checkpoint_path("ok");
+    let (checkpoint, nonce_iters) = run_until_shutdown(&submitter, &path).await;
+
+    // every worker finished its in-flight nonce and exited before shutdown returned
+    assert!(checkpoint.drained);
+    assert!(nonce_iters.iter().all(|x| Arc::strong_count(x) == 1));
+    // every attempted nonce was solved, and every solution was submitted
+    let submitted = submitter.submitted.borrow().clone();
+    assert!(checkpoint.num_attempts >= NUM_WORKERS * 5);
+    assert_eq!(submitted.len() as u64, checkpoint.num_attempts);
+    assert_eq!(checkpoint.num_flushed, checkpoint.num_attempts);
+    assert!(checkpoint.unsubmitted_solutions.is_empty());
+
+    let persisted: ShutdownCheckpoint =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(persisted, checkpoint);
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_unsubmitted_solutions_are_kept_in_checkpoint() {
+    let submitter = MockSubmitter {
+        fail: true,
+        submitted: RefCell::new(Vec::new()),
+    };
+    let path = checkpoint_path("failed");
+    let (checkpoint, _) = run_until_shutdown(&submitter, &path).await;
+
+    assert!(checkpoint.drained);
+    assert_eq!(checkpoint.num_flushed, 0);
+    assert_eq!(
+        checkpoint.unsubmitted_solutions.len() as u64,
+        checkpoint.num_attempts
+    );
+    let persisted: ShutdownCheckpoint =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(persisted, checkpoint);
+    fs::remove_file(&path).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_each_shard_resumes_at_its_cursor() {
+    let submitter = MockSubmitter {
+        fail: false,
+        submitted: RefCell::new(Vec::new()),
+    };
+    let path = checkpoint_path("cursors");
+    let (checkpoint, nonce_iters) = run_until_shutdown(&submitter, &path).await;
+
+    // each shard took its nonces in order from its own start
+    let starts: Vec<u64> = (0..NUM_WORKERS).map(|i| i * 1000).collect();
+    assert_eq!(checkpoint.cursors.len() as u64, NUM_WORKERS);
+    for (i, nonce_iter) in nonce_iters.iter().enumerate() {
+        let attempts = (*nonce_iter).lock().await.attempts();
+        assert_eq!(checkpoint.cursors[&i], starts[i] + attempts);
+    }
+
+    let persisted = ShutdownCheckpoint::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(persisted, checkpoint);
+    let resumed = persisted.resume_starts("benchmark", &starts);
+    assert_eq!(
+        resumed,
+        checkpoint.cursors.values().cloned().collect::<Vec<_>>()
+    );
+    for (i, &start) in resumed.iter().enumerate() {
+        let mut nonce_iter = NonceIterator::from_u64(start);
+        assert_eq!(nonce_iter.cursor(), Some(checkpoint.cursors[&i]));
+        assert_eq!(nonce_iter.next(), Some(checkpoint.cursors[&i]));
+    }
+    // another benchmark, or a shard without a cursor, starts afresh
+    assert_eq!(persisted.resume_starts("other", &starts), starts);
+    let mut starts_with_extra_shard = starts.clone();
+    starts_with_extra_shard.push(NUM_WORKERS * 1000);
+    assert_eq!(
+        persisted.resume_starts("benchmark", &starts_with_extra_shard)[NUM_WORKERS as usize],
+        NUM_WORKERS * 1000
+    );
+    assert!(ShutdownCheckpoint::load(&path).is_err());
+}
+
+#[test]
+fn test_checkpoint_without_cursors_still_loads() {
+    let checkpoint: ShutdownCheckpoint = serde_json::from_str(
+        r#"{"benchmark_id":"benchmark","num_attempts":3,"num_flushed":3,"unsubmitted_solutions":[],"drained":true}"#,
+    )
+    .unwrap();
+    assert!(checkpoint.cursors.is_empty());
+    assert_eq!(checkpoint.resume_starts("benchmark", &[5, 7]), vec![5, 7]);
+}
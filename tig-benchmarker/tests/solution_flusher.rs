@@ -0,0 +1,107 @@
+#![cfg(feature = "standalone")]
+
+use std::cell::RefCell;
+use tig_benchmarker::{SolutionFlusher, SolutionSubmitter};
+use tig_structs::core::{Solution, SolutionData};
+
+struct MockSubmitter {
+    fail: RefCell<bool>,
+    submitted: RefCell<Vec<(String, Vec<u64>)>>,
+}
+
+impl SolutionSubmitter for MockSubmitter {
+    async fn submit(
+        &self,
+        benchmark_id: &str,
+        solutions_data: &[SolutionData],
+    ) -> Result<(), String> {
+        if *self.fail.borrow() {
+            return Err("mock failure".to_string());
+        }
+        self.submitted.borrow_mut().push((
+            benchmark_id.to_string(),
+            solutions_data.iter().map(|d| d.nonce).collect(),
+        ));
+        Ok(())
+    }
+}
+
+fn solution_data(nonce: u64) -> SolutionData {
+    SolutionData {
+        nonce,
+        runtime_signature: 0,
+        fuel_consumed: 0,
+        solution: Solution::new(),
+        version_pin: None,
+        verification_token: None,
+    }
+}
+
+fn mock_submitter() -> MockSubmitter {
+    MockSubmitter {
+        fail: RefCell::new(false),
+        submitted: RefCell::new(Vec::new()),
+    }
+}
+
+#[tokio::test]
+async fn test_flushes_on_count() {
+    let submitter = mock_submitter();
+    let benchmark_id = "benchmark".to_string();
+    let mut flusher = SolutionFlusher::new(u64::MAX, 3);
+    for nonce in 0..7 {
+        flusher.push(vec![solution_data(nonce)]);
+        if flusher.should_flush() {
+            flusher.flush(&submitter, &benchmark_id).await.unwrap();
+        }
+    }
+    assert_eq!(
+        *submitter.submitted.borrow(),
+        vec![
+            (benchmark_id.clone(), vec![0, 1, 2]),
+            (benchmark_id.clone(), vec![3, 4, 5]),
+        ]
+    );
+    assert_eq!(flusher.num_flushed(), 6);
+    assert_eq!(flusher.num_pending(), 1);
+}
+
+#[tokio::test]
+async fn test_flushes_on_interval() {
+    let submitter = mock_submitter();
+    let benchmark_id = "benchmark".to_string();
+    let mut flusher = SolutionFlusher::new(50, usize::MAX);
+    flusher.push(vec![solution_data(0)]);
+    assert!(!flusher.should_flush());
+    tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+    assert!(flusher.should_flush());
+    assert_eq!(flusher.flush(&submitter, &benchmark_id).await, Ok(1));
+    flusher.push(vec![solution_data(1)]);
+    assert!(!flusher.should_flush());
+    tokio::time::sleep(std::time::Duration::from_millis(60)).await;
+    assert!(flusher.should_flush());
+    assert_eq!(flusher.flush(&submitter, &benchmark_id).await, Ok(1));
+    assert_eq!(submitter.submitted.borrow().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retains_solutions_on_failure() {
+    let submitter = mock_submitter();
+    let benchmark_id = "benchmark".to_string();
+    let mut flusher = SolutionFlusher::new(0, 1);
+    flusher.push(vec![solution_data(0), solution_data(1)]);
+    *submitter.fail.borrow_mut() = true;
+    assert!(flusher.flush(&submitter, &benchmark_id).await.is_err());
+    assert_eq!(flusher.num_pending(), 2);
+    assert_eq!(flusher.num_flushed(), 0);
+
+    flusher.push(vec![solution_data(2)]);
+    *submitter.fail.borrow_mut() = false;
+    assert_eq!(flusher.flush(&submitter, &benchmark_id).await, Ok(3));
+    assert_eq!(
+        *submitter.submitted.borrow(),
+        vec![(benchmark_id.clone(), vec![0, 1, 2])]
+    );
+    assert_eq!(flusher.num_pending(), 0);
+    assert_eq!(flusher.num_flushed(), 3);
+}
@@ -0,0 +1,51 @@
+mod common;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+};
+use tig_benchmarker::{BenchmarkBaseline, SurfaceCell};
+use tig_worker::calc_version_pin;
+
+const NUM_WORKERS: u64 = 4;
+const SOLUTIONS_PER_WORKER: u64 = 100;
+
+#[test]
+fn test_count_does_not_wrap_past_u32() {
+    // the shared count execute's workers increment, just short of the old u32 limit
+    let start = u32::MAX as u64 - 10;
+    let solutions_count = Arc::new(AtomicU64::new(start));
+    let handles: Vec<_> = (0..NUM_WORKERS)
+        .map(|_| {
+            let solutions_count = solutions_count.clone();
+            thread::spawn(move || {
+                for _ in 0..SOLUTIONS_PER_WORKER {
+                    solutions_count.fetch_add(1, Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let num_solutions = solutions_count.load(Ordering::Relaxed);
+    assert_eq!(num_solutions, start + NUM_WORKERS * SOLUTIONS_PER_WORKER);
+    assert!(num_solutions > u32::MAX as u64);
+
+    // and is reported as is
+    let cell = SurfaceCell {
+        difficulty: vec![50, 10],
+        num_attempts: num_solutions * 2,
+        num_solutions,
+        elapsed_ms: 1000,
+        version_pin: calc_version_pin(&[]),
+    };
+    assert_eq!(cell.solve_rate(), 0.5);
+    let settings = common::settings("c003", vec![50, 10]);
+    let baseline = BenchmarkBaseline::from_cell(&settings, NUM_WORKERS as u32, &cell);
+    assert_eq!(baseline.num_solutions, num_solutions);
+    assert_eq!(baseline.solve_rate(), 0.5);
+}
@@ -0,0 +1,68 @@
+use tig_benchmarker::OutcomeCounts;
+use tig_challenges::{satisfiability::*, ChallengeTrait, SolveOutcome};
+
+const MAX_VARIABLES: usize = 12;
+
+// complete solver: tries every assignment, so it proves unsatisfiability when none works. gives up
+// on instances too large to enumerate, like a solver running out of time
+fn solve_challenge(challenge: &Challenge) -> SolveOutcome<Solution> {
+    let num_variables = challenge.difficulty.num_variables;
+    if num_variables > MAX_VARIABLES {
+        return SolveOutcome::GaveUp;
+    }
+    for bits in 0..1u32 << num_variables {
+        let variables: Vec<bool> = (0..num_variables).map(|i| bits >> i & 1 == 1).collect();
+        let solution = Solution { variables };
+        if challenge.verify_solution(&solution).is_ok() {
+            return SolveOutcome::Solved(solution);
+        }
+    }
+    SolveOutcome::ProvedNoSolution
+}
+
+fn challenge(num_variables: usize, clauses: Vec<Vec<i32>>) -> Challenge {
+    Challenge {
+        seeds: [0; 8],
+        difficulty: Difficulty {
+            num_variables,
+            clauses_to_variables_percent: 0,
+        },
+        clauses,
+    }
+}
+
+#[test]
+fn test_proved_unsat_recorded_apart_from_gave_up() {
+    // x1 and not x1
+    let unsat = challenge(3, vec![vec![1, 1, 1], vec![-1, -1, -1]]);
+    let sat = challenge(3, vec![vec![1, 2, 3], vec![-1, 2, -3]]);
+    let too_large = challenge(MAX_VARIABLES + 1, vec![vec![1, 2, 3]]);
+
+    let counts = OutcomeCounts::new();
+    for challenge in [&unsat, &sat, &too_large, &unsat] {
+        counts.record(&solve_challenge(challenge));
+    }
+    assert_eq!(counts.num_proved_no_solution(), 2);
+    assert_eq!(counts.num_gave_up(), 1);
+    assert_eq!(counts.num_solved(), 1);
+    assert_eq!(counts.num_attempts(), 4);
+    // timing out is counted on top of the outcome
+    counts.record_timed_out();
+    assert_eq!(counts.num_timed_out(), 1);
+    assert_eq!(counts.num_attempts(), 4);
+
+    assert!(solve_challenge(&unsat).is_proved_no_solution());
+    let solution = solve_challenge(&sat).solution().unwrap();
+    assert!(sat.verify_solution(&solution).is_ok());
+}
+
+#[test]
+fn test_option_solvers_never_prove_no_solution() {
+    let counts = OutcomeCounts::new();
+    counts.record(&SolveOutcome::from(None::<Solution>));
+    counts.record(&SolveOutcome::from(Some(Solution { variables: vec![] })));
+    assert_eq!(counts.num_proved_no_solution(), 0);
+    assert_eq!(counts.num_gave_up(), 1);
+    assert_eq!(counts.num_solved(), 1);
+    assert!(SolveOutcome::<Solution>::GaveUp.solution().is_none());
+}
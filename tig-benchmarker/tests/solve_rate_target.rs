@@ -0,0 +1,98 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+use std::thread;
+use tig_benchmarker::{wilson_interval, SolveRateTarget};
+
+// mirrors the worker loop: stops taking nonces once the target is reached
+fn run(
+    solver: fn(u64) -> bool,
+    solve_rate_target: Arc<SolveRateTarget>,
+    num_workers: u64,
+    nonces_per_worker: u64,
+) -> u64 {
+    let attempts = Arc::new(AtomicU64::new(0));
+    let handles: Vec<_> = (0..num_workers)
+        .map(|w| {
+            let solve_rate_target = solve_rate_target.clone();
+            let attempts = attempts.clone();
+            thread::spawn(move || {
+                for nonce in w * nonces_per_worker..(w + 1) * nonces_per_worker {
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    if solve_rate_target.record(solver(nonce)) {
+                        break;
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    attempts.load(Ordering::Relaxed)
+}
+
+#[test]
+fn test_wilson_interval() {
+    assert_eq!(wilson_interval(0, 0, 1.96), (0.0, 1.0));
+    let (lower, upper) = wilson_interval(50, 100, 1.96);
+    assert!((lower - 0.4038).abs() < 1e-3);
+    assert!((upper - 0.5962).abs() < 1e-3);
+    // stays within [0, 1] at the extremes
+    let (lower, upper) = wilson_interval(0, 10, 1.96);
+    assert_eq!(lower, 0.0);
+    assert!(upper > 0.0 && upper < 1.0);
+    let (lower, upper) = wilson_interval(10, 10, 1.96);
+    assert!(lower > 0.0 && lower < 1.0);
+    assert_eq!(upper, 1.0);
+}
+
+#[test]
+fn test_run_stops_once_ci_width_is_met() {
+    let max_ci_width = 0.1;
+    // every other nonce is solved, so the interval narrows deterministically
+    let solver = |nonce: u64| nonce & 1 == 0;
+    let mut min_attempts = 1;
+    loop {
+        let (lower, upper) = wilson_interval(min_attempts / 2, min_attempts, 1.96);
+        if upper - lower <= max_ci_width {
+            break;
+        }
+        min_attempts += 1;
+    }
+
+    let solve_rate_target = Arc::new(SolveRateTarget::new(max_ci_width));
+    let attempts = run(solver, solve_rate_target.clone(), 1, 100_000);
+    assert!(solve_rate_target.is_reached());
+    // a single worker stops on the exact nonce that meets the target
+    assert!(attempts.abs_diff(min_attempts) <= 1);
+    let (lower, upper) = solve_rate_target.confidence_interval();
+    assert!(upper - lower <= max_ci_width);
+    assert!((solve_rate_target.solve_rate() - 0.5).abs() < 0.01);
+
+    // with several workers only the nonces in flight when the target is met are extra
+    let solve_rate_target = Arc::new(SolveRateTarget::new(max_ci_width));
+    let attempts = run(solver, solve_rate_target.clone(), 4, 100_000);
+    assert!(solve_rate_target.is_reached());
+    assert!(attempts >= min_attempts - 4);
+    assert!(attempts <= min_attempts + 8);
+}
+
+#[test]
+fn test_run_continues_until_ci_width_is_met() {
+    let solve_rate_target = Arc::new(SolveRateTarget::new(0.01));
+    let attempts = run(|nonce| nonce & 1 == 0, solve_rate_target.clone(), 4, 250);
+    assert!(!solve_rate_target.is_reached());
+    assert_eq!(attempts, 1_000);
+}
+
+#[test]
+fn test_target_waits_for_min_samples() {
+    // every nonce solved gives a narrow interval early on, but not before min_samples
+    let solve_rate_target = SolveRateTarget::with_z(0.5, 1.96, 10);
+    for _ in 0..9 {
+        assert!(!solve_rate_target.record(true));
+    }
+    assert!(solve_rate_target.record(true));
+}
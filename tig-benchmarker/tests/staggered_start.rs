@@ -0,0 +1,71 @@
+use tig_benchmarker::StaggeredStart;
+
+const NUM_TASKS: usize = 8;
+const MAX_DELAY_MS: u32 = 400;
+
+#[test]
+fn test_delays_are_bounded_spread_and_deterministic() {
+    let stagger = StaggeredStart::new(MAX_DELAY_MS, 42);
+    let delays = stagger.delays(NUM_TASKS);
+    assert_eq!(delays.len(), NUM_TASKS);
+    assert!(delays.iter().all(|&d| d < MAX_DELAY_MS));
+    // one task per slot of MAX_DELAY_MS / NUM_TASKS
+    let slot_ms = MAX_DELAY_MS / NUM_TASKS as u32;
+    for (i, &d) in delays.iter().enumerate() {
+        assert_eq!(d / slot_ms, i as u32);
+    }
+    assert_eq!(delays, stagger.delays(NUM_TASKS));
+    assert_ne!(
+        delays,
+        StaggeredStart::new(MAX_DELAY_MS, 43).delays(NUM_TASKS)
+    );
+}
+
+#[test]
+fn test_zero_max_delay_starts_all_tasks_at_once() {
+    assert_eq!(StaggeredStart::new(0, 42).delays(4), vec![0; 4]);
+    assert!(StaggeredStart::new(MAX_DELAY_MS, 42).delays(0).is_empty());
+}
+
+#[cfg(all(feature = "standalone", test))]
+mod tests {
+    use super::*;
+    use std::{sync::Arc, time::Instant};
+    use tig_benchmarker::NonceIterator;
+    use tokio::{sync::Mutex, time::Duration};
+
+    // mirrors how run_benchmark::execute starts its tasks: wait out the start delay, then take the
+    // first nonce from the task's iterator
+    async fn first_nonce_times(stagger: Option<StaggeredStart>) -> Vec<u128> {
+        let start = Instant::now();
+        let delays = stagger.map(|x| x.delays(NUM_TASKS));
+        let mut handles = Vec::new();
+        for i in 0..NUM_TASKS {
+            let nonce_iter = Arc::new(Mutex::new(NonceIterator::from_u64(i as u64 * 1000)));
+            let start_delay = delays.as_ref().map_or(0, |x| x[i]);
+            handles.push(tokio::spawn(async move {
+                if start_delay > 0 {
+                    tokio::time::sleep(Duration::from_millis(start_delay as u64)).await;
+                }
+                let _nonce = (*nonce_iter).lock().await.next().unwrap();
+                start.elapsed().as_millis()
+            }));
+        }
+        let mut times = Vec::new();
+        for handle in handles {
+            times.push(handle.await.unwrap());
+        }
+        times.sort_unstable();
+        times
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_staggered_tasks_take_first_nonce_at_different_times() {
+        let times = first_nonce_times(Some(StaggeredStart::new(MAX_DELAY_MS, 42))).await;
+        // spread over most of the window rather than all at once
+        assert!(times[NUM_TASKS - 1] - times[0] >= (MAX_DELAY_MS / 2) as u128);
+
+        let times = first_nonce_times(None).await;
+        assert!(times[NUM_TASKS - 1] - times[0] < (MAX_DELAY_MS / 2) as u128);
+    }
+}
@@ -0,0 +1,155 @@
+mod common;
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tig_benchmarker::{expected_num_nonces, StatsAccumulator};
+use tig_worker::{generate_serialized_instance, search_space_log2};
+
+fn task_stats(seed: u64) -> StatsAccumulator {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut stats = StatsAccumulator::new();
+    for _ in 0..rng.gen_range(10..100) {
+        stats.record(rng.gen_range(0..1_000_000), rng.gen_bool(0.3));
+    }
+    stats
+}
+
+fn merge_in_order(tasks: &[StatsAccumulator], order: &[usize]) -> StatsAccumulator {
+    let mut total = StatsAccumulator::new();
+    for &i in order {
+        total.merge(&tasks[i]);
+    }
+    total
+}
+
+#[test]
+fn test_merge_is_order_independent() {
+    let tasks: Vec<StatsAccumulator> = (0..8).map(task_stats).collect();
+    let forward = merge_in_order(&tasks, &[0, 1, 2, 3, 4, 5, 6, 7]);
+    let backward = merge_in_order(&tasks, &[7, 6, 5, 4, 3, 2, 1, 0]);
+    let shuffled = merge_in_order(&tasks, &[3, 0, 6, 1, 7, 4, 2, 5]);
+    assert_eq!(forward, backward);
+    assert_eq!(forward, shuffled);
+
+    // merging merged groups gives the same result as merging tasks one by one
+    let mut left = merge_in_order(&tasks, &[0, 1, 2, 3]);
+    let right = merge_in_order(&tasks, &[4, 5, 6, 7]);
+    left.merge(&right);
+    assert_eq!(forward, left);
+
+    assert_eq!(
+        forward.num_attempts,
+        tasks.iter().map(|t| t.num_attempts).sum::<u64>()
+    );
+    assert_eq!(
+        forward.num_solutions,
+        tasks.iter().map(|t| t.num_solutions).sum::<u64>()
+    );
+    assert_eq!(
+        forward.fuel_percentile(50.0),
+        shuffled.fuel_percentile(50.0)
+    );
+}
+
+#[test]
+fn test_fuel_percentile() {
+    let mut stats = StatsAccumulator::new();
+    assert_eq!(stats.fuel_percentile(50.0), None);
+    for fuel in [50, 10, 40, 20, 30] {
+        stats.record(fuel, false);
+    }
+    assert_eq!(stats.fuel_percentile(0.0), Some(10));
+    assert_eq!(stats.fuel_percentile(50.0), Some(30));
+    assert_eq!(stats.fuel_percentile(100.0), Some(50));
+    assert_eq!(stats.mean_fuel_consumed(), Some(30.0));
+}
+
+#[test]
+fn test_solution_size() {
+    let sizes = [[120, 80], [200, 40]];
+    let mut tasks: Vec<StatsAccumulator> = sizes
+        .iter()
+        .map(|task_sizes| {
+            let mut stats = StatsAccumulator::new();
+            stats.record(10, false);
+            for &size in task_sizes {
+                stats.record(10, true);
+                stats.record_solution_size(size);
+            }
+            stats
+        })
+        .collect();
+    assert_eq!(tasks[0].mean_solution_size(), Some(100.0));
+    let other = tasks.pop().unwrap();
+    let mut stats = tasks.pop().unwrap();
+    stats.merge(&other);
+    assert_eq!(stats.total_solution_size, 440);
+    assert_eq!(stats.max_solution_size, 200);
+    assert_eq!(stats.mean_solution_size(), Some(110.0));
+    assert_eq!(StatsAccumulator::new().mean_solution_size(), None);
+}
+
+#[test]
+fn test_max_solved_difficulty() {
+    let settings = |num_items: i32| common::settings("c003", vec![num_items, 0]);
+    // solutions to instances of varied difficulty, spread over tasks out of order
+    let num_items = [[60, 20, 45], [100, 30, 80], [25, 70, 50]];
+    let tasks: Vec<StatsAccumulator> = num_items
+        .iter()
+        .map(|task_num_items| {
+            let mut stats = StatsAccumulator::new();
+            for (nonce, &n) in task_num_items.iter().enumerate() {
+                let instance = generate_serialized_instance(&settings(n), nonce as u64).unwrap();
+                stats.record_solved_difficulty(search_space_log2("c003", &instance).unwrap());
+            }
+            stats
+        })
+        .collect();
+    assert_eq!(tasks[0].max_solved_difficulty, Some(60.0));
+    assert_eq!(tasks[2].max_solved_difficulty, Some(70.0));
+    let total = merge_in_order(&tasks, &[2, 0, 1]);
+    assert_eq!(total.max_solved_difficulty, Some(100.0));
+    assert_eq!(merge_in_order(&tasks, &[0, 1, 2]), total);
+    // an empty task doesn't lower it
+    let mut with_empty = total.clone();
+    with_empty.merge(&StatsAccumulator::new());
+    assert_eq!(with_empty.max_solved_difficulty, Some(100.0));
+    assert_eq!(StatsAccumulator::new().max_solved_difficulty, None);
+}
+
+#[test]
+fn test_expected_num_nonces() {
+    // harder runs need more nonces for the same number of solutions
+    let easy = expected_num_nonces(&common::settings("c001", vec![100, 400]), 10).unwrap();
+    let hard = expected_num_nonces(&common::settings("c001", vec![100, 450]), 10).unwrap();
+    assert!(easy >= 10);
+    assert!(hard > easy);
+    assert_eq!(
+        expected_num_nonces(&common::settings("c003", vec![50, 10]), 10),
+        None
+    );
+}
+
+#[test]
+fn test_solve_rate_interval() {
+    let mut stats = StatsAccumulator::new();
+    assert_eq!(stats.solve_rate(), None);
+    assert_eq!(stats.solve_rate_interval(1.96), (0.0, 1.0));
+
+    let mut last_width = 1.0;
+    for num_attempts in [10, 100, 1000, 10000] {
+        while stats.num_attempts < num_attempts {
+            // 3 in every 10 solved
+            stats.record(0, stats.num_attempts % 10 < 3);
+        }
+        let solve_rate = stats.solve_rate().unwrap();
+        assert_eq!(solve_rate, 0.3);
+        let (lower, upper) = stats.solve_rate_interval(1.96);
+        assert!(lower < solve_rate && solve_rate < upper);
+        assert!(upper - lower < last_width);
+        last_width = upper - lower;
+        // a wider confidence level widens the interval
+        let (wider_lower, wider_upper) = stats.solve_rate_interval(2.58);
+        assert!(wider_lower < lower && upper < wider_upper);
+    }
+    assert!(last_width < 0.02);
+}
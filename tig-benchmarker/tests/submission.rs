@@ -0,0 +1,173 @@
+mod common;
+
+use serde_json::json;
+use tig_benchmarker::{
+    strip_local_metadata, to_submission, ExcludedSolution, ExclusionReason, Job, SubmissionPayload,
+};
+use tig_structs::core::{Solution, SolutionData, SolutionMetaData, VersionPin};
+
+fn job(sampled_nonces: Option<Vec<u64>>) -> Job {
+    Job {
+        sampled_nonces,
+        ..common::job(common::settings("c003", vec![50, 10]))
+    }
+}
+
+fn solutions_data() -> Vec<SolutionData> {
+    [7u64, 3, 12, 5]
+        .iter()
+        .map(|&nonce| {
+            let solution: Solution = json!({ "items": [nonce, nonce + 1] })
+                .as_object()
+                .unwrap()
+                .clone();
+            let mut d = SolutionData::new(nonce, 123, 1000 + nonce, 2000, solution).unwrap();
+            d.version_pin = Some(VersionPin {
+                challenge_version: "0.1.0".to_string(),
+                algorithm_version: "hash".to_string(),
+            });
+            d.verification_token = Some("token".to_string());
+            d
+        })
+        .collect()
+}
+
+#[test]
+fn test_benchmark_payload() {
+    let job = job(None);
+    let solutions_data = solutions_data();
+    let (payload, excluded) = to_submission(&job, &solutions_data).unwrap();
+    assert!(excluded.is_empty());
+    let req = match payload {
+        SubmissionPayload::Benchmark(req) => req,
+        SubmissionPayload::Proof(_) => panic!("Expected a benchmark payload"),
+    };
+    assert_eq!(req.settings, job.settings);
+    // every solution, in the order collected
+    assert_eq!(
+        req.solutions_meta_data,
+        solutions_data
+            .iter()
+            .map(|d| SolutionMetaData::from(d.clone()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(req.solution_data.nonce, 7);
+    assert_eq!(req.solution_data.solution, solutions_data[0].solution);
+    assert!(req.solution_data.version_pin.is_none());
+    assert!(req.solution_data.verification_token.is_none());
+
+    assert!(to_submission(&job, &[]).is_err());
+}
+
+#[test]
+fn test_proof_payload() {
+    let job = job(Some(vec![12, 7]));
+    let req = match to_submission(&job, &solutions_data()).unwrap().0 {
+        SubmissionPayload::Proof(req) => req,
+        SubmissionPayload::Benchmark(_) => panic!("Expected a proof payload"),
+    };
+    assert_eq!(req.benchmark_id, job.benchmark_id);
+    // only the sampled nonces, in the order sampled
+    assert_eq!(
+        req.solutions_data
+            .iter()
+            .map(|d| d.nonce)
+            .collect::<Vec<_>>(),
+        vec![12, 7]
+    );
+    assert_eq!(req.solutions_data[0].fuel_consumed, 1012);
+    // exactly what the protocol computes, which has no local metadata
+    assert!(req
+        .solutions_data
+        .iter()
+        .all(|d| d.version_pin.is_none() && d.verification_token.is_none()));
+    assert_eq!(
+        req.solutions_data,
+        strip_local_metadata(&solutions_data())
+            .into_iter()
+            .filter(|d| [12, 7].contains(&d.nonce))
+            .rev()
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_missing_sampled_nonce() {
+    let err = to_submission(&job(Some(vec![12, 99])), &solutions_data()).unwrap_err();
+    assert!(err.contains("99"), "{}", err);
+}
+
+// a valid solution whose json is much larger than the others'
+fn with_large_solution(nonce: u64) -> Vec<SolutionData> {
+    let mut solutions_data = solutions_data();
+    let d = solutions_data
+        .iter_mut()
+        .find(|d| d.nonce == nonce)
+        .unwrap();
+    d.solution = json!({ "items": (0..100).collect::<Vec<u64>>() })
+        .as_object()
+        .unwrap()
+        .clone();
+    solutions_data
+}
+
+#[test]
+fn test_over_limit_solution_is_excluded() {
+    let mut job = job(None);
+    job.max_solution_size = Some(64);
+    let solutions_data = with_large_solution(12);
+    let solution_size = solutions_data[2].solution_size();
+    assert!(solution_size > 64);
+    let (payload, excluded) = to_submission(&job, &solutions_data).unwrap();
+    assert_eq!(
+        excluded,
+        vec![ExcludedSolution {
+            nonce: 12,
+            reason: ExclusionReason::TooLarge {
+                solution_size,
+                max_solution_size: 64,
+            },
+        }]
+    );
+    assert_eq!(
+        excluded[0].reason.to_string(),
+        format!(
+            "solution size of {} bytes exceeds the max of 64",
+            solution_size
+        )
+    );
+    let req = match payload {
+        SubmissionPayload::Benchmark(req) => req,
+        SubmissionPayload::Proof(_) => panic!("Expected a benchmark payload"),
+    };
+    // the rest are submitted in the order collected
+    assert_eq!(
+        req.solutions_meta_data
+            .iter()
+            .map(|d| d.nonce)
+            .collect::<Vec<_>>(),
+        vec![7, 3, 5]
+    );
+
+    // without a limit nothing is excluded
+    job.max_solution_size = None;
+    let (_, excluded) = to_submission(&job, &solutions_data).unwrap();
+    assert!(excluded.is_empty());
+
+    // nothing left to submit
+    job.max_solution_size = Some(1);
+    let err = to_submission(&job, &solutions_data).unwrap_err();
+    assert!(err.contains("4 excluded"), "{}", err);
+}
+
+#[test]
+fn test_over_limit_sampled_nonce_fails_proof() {
+    let mut job = job(Some(vec![12, 7]));
+    job.max_solution_size = Some(64);
+    let err = to_submission(&job, &with_large_solution(12)).unwrap_err();
+    assert!(err.contains("sampled nonce 12"), "{}", err);
+    assert!(err.contains("exceeds the max of 64"), "{}", err);
+
+    // an over-limit solution that wasn't sampled doesn't matter
+    assert!(to_submission(&job, &with_large_solution(3)).is_ok());
+}
@@ -0,0 +1,56 @@
+use tig_benchmarker::TimeoutScaling;
+
+const DEFAULT_MS: u64 = 20_000;
+
+#[test]
+fn test_linear_timeout_scaling() {
+    let scaling = TimeoutScaling::Linear {
+        base_ms: 1_000,
+        ms_per_step: vec![100, 1_000],
+    };
+    let min_difficulty = [50, 10];
+    assert_eq!(
+        scaling.timeout_ms(&[50, 10], &min_difficulty, DEFAULT_MS),
+        1_000
+    );
+    assert_eq!(
+        scaling.timeout_ms(&[60, 10], &min_difficulty, DEFAULT_MS),
+        2_000
+    );
+    assert_eq!(
+        scaling.timeout_ms(&[60, 15], &min_difficulty, DEFAULT_MS),
+        7_000
+    );
+    // below the minimum counts as the minimum
+    assert_eq!(
+        scaling.timeout_ms(&[40, 10], &min_difficulty, DEFAULT_MS),
+        1_000
+    );
+}
+
+#[test]
+fn test_exponential_timeout_scaling() {
+    let scaling = TimeoutScaling::Exponential {
+        base_ms: 1_000,
+        growth_per_step: vec![2.0, 1.0],
+    };
+    let min_difficulty = [50, 10];
+    let timeouts: Vec<u64> = (50..60)
+        .map(|x| scaling.timeout_ms(&[x, 10], &min_difficulty, DEFAULT_MS))
+        .collect();
+    assert_eq!(timeouts[0], 1_000);
+    assert_eq!(timeouts[3], 8_000);
+    assert!(timeouts.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(
+        scaling.timeout_ms(&[5000, 10], &min_difficulty, DEFAULT_MS),
+        u64::MAX
+    );
+}
+
+#[test]
+fn test_fixed_timeout_scaling() {
+    assert_eq!(
+        TimeoutScaling::Fixed.timeout_ms(&[60, 15], &[50, 10], DEFAULT_MS),
+        DEFAULT_MS
+    );
+}
@@ -0,0 +1,86 @@
+use std::{fs, path::PathBuf, sync::Arc, thread};
+use tig_benchmarker::{TimingTrace, TraceOutcome};
+
+const NUM_WORKERS: u64 = 4;
+const NONCES_PER_WORKER: u64 = 250;
+
+fn trace_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "tig_timing_trace_{}_{}.csv",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_file(&path);
+    path
+}
+
+fn rows(path: &PathBuf) -> Vec<Vec<String>> {
+    fs::read_to_string(path)
+        .unwrap()
+        .lines()
+        .map(|line| line.split(',').map(|x| x.to_string()).collect())
+        .collect()
+}
+
+#[test]
+fn test_one_row_per_processed_nonce() {
+    let path = trace_path("rows");
+    let trace = Arc::new(TimingTrace::open(&path).unwrap());
+    // workers record concurrently, like the tasks of a run
+    let handles: Vec<_> = (0..NUM_WORKERS)
+        .map(|worker| {
+            let trace = trace.clone();
+            thread::spawn(move || {
+                for i in 0..NONCES_PER_WORKER {
+                    let nonce = worker * 1000 + i;
+                    let outcome = if nonce & 1 == 0 {
+                        TraceOutcome::Solved
+                    } else {
+                        TraceOutcome::GaveUp
+                    };
+                    trace.record(nonce, i % 3, i % 7, outcome).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    trace.flush().unwrap();
+
+    let rows = rows(&path);
+    assert_eq!(rows[0].join(","), TimingTrace::HEADER);
+    let rows = &rows[1..];
+    assert_eq!(rows.len() as u64, NUM_WORKERS * NONCES_PER_WORKER);
+    let mut nonces: Vec<u64> = rows.iter().map(|row| row[0].parse().unwrap()).collect();
+    nonces.sort_unstable();
+    nonces.dedup();
+    assert_eq!(nonces.len() as u64, NUM_WORKERS * NONCES_PER_WORKER);
+    for row in rows {
+        assert_eq!(row.len(), 4);
+        let nonce: u64 = row[0].parse().unwrap();
+        let i = nonce % 1000;
+        assert_eq!(row[1], (i % 3).to_string());
+        assert_eq!(row[2], (i % 7).to_string());
+        let outcome = if nonce & 1 == 0 { "solved" } else { "gave_up" };
+        assert_eq!(row[3], outcome);
+    }
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_reopened_trace_appends_without_second_header() {
+    let path = trace_path("append");
+    for nonce in 0..2 {
+        // dropping the trace flushes it
+        let trace = TimingTrace::open(&path).unwrap();
+        trace
+            .record(nonce, 1, 2, TraceOutcome::ProvedNoSolution)
+            .unwrap();
+    }
+    let rows = rows(&path);
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1], vec!["0", "1", "2", "proved_no_solution"]);
+    assert_eq!(rows[2], vec!["1", "1", "2", "proved_no_solution"]);
+    fs::remove_file(&path).unwrap();
+}
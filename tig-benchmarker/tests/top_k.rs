@@ -0,0 +1,104 @@
+mod common;
+
+use tig_algorithms::c003::c003_a001;
+use tig_benchmarker::select_top_k;
+use tig_challenges::{knapsack, satisfiability, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{BenchmarkSettings, SolutionData};
+
+const NUM_NONCES: u64 = 12;
+
+fn solution_data(nonce: u64, solution: &impl serde::Serialize) -> SolutionData {
+    SolutionData::new(
+        nonce,
+        123,
+        1000,
+        2000,
+        dejsonify(&jsonify(solution)).unwrap(),
+    )
+    .unwrap()
+}
+
+fn knapsack_instance(settings: &BenchmarkSettings, nonce: u64) -> knapsack::Challenge {
+    knapsack::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_selects_top_k_by_qualifier() {
+    let settings = common::settings("c003", vec![30, 0]);
+    // the solver's solutions beat the baseline by varying amounts, the greedy ones just meet it
+    let mut solutions: Vec<(SolutionData, f64)> = (0..NUM_NONCES)
+        .map(|nonce| {
+            let challenge = knapsack_instance(&settings, nonce);
+            let solution = match nonce % 2 {
+                0 => c003_a001::solve_challenge(&challenge).unwrap().unwrap(),
+                _ => knapsack::greedy_warm_start(&challenge),
+            };
+            let total_value: u32 = solution.items.iter().map(|&i| challenge.values[i]).sum();
+            let qualifier = total_value as f64 / challenge.min_value as f64;
+            (solution_data(nonce, &solution), qualifier)
+        })
+        .collect();
+    // an empty knapsack never verifies, so is never selected however small k is
+    solutions.push((
+        solution_data(NUM_NONCES, &knapsack::Solution { items: Vec::new() }),
+        0.0,
+    ));
+    // collected out of order
+    solutions.reverse();
+
+    let mut expected = solutions.clone();
+    expected.retain(|(_, qualifier)| *qualifier >= 1.0);
+    expected.sort_by(|(a, qualifier_a), (b, qualifier_b)| {
+        qualifier_b
+            .partial_cmp(qualifier_a)
+            .unwrap()
+            .then(a.nonce.cmp(&b.nonce))
+    });
+    let expected: Vec<u64> = expected.iter().map(|(d, _)| d.nonce).collect();
+    assert_eq!(expected.len(), NUM_NONCES as usize);
+
+    let solutions_data: Vec<SolutionData> = solutions.into_iter().map(|(d, _)| d).collect();
+    for k in [0, 1, 5, NUM_NONCES as usize, NUM_NONCES as usize + 5] {
+        let selected: Vec<u64> = select_top_k(&settings, &solutions_data, k)
+            .iter()
+            .map(|d| d.nonce)
+            .collect();
+        assert_eq!(selected, expected[..k.min(expected.len())]);
+    }
+}
+
+#[test]
+fn test_ties_go_to_the_lower_nonce() {
+    // every valid satisfiability solution has the same qualifier
+    let settings = common::settings("c001", vec![10, 200]);
+    let mut solutions_data: Vec<SolutionData> = (0..NUM_NONCES)
+        .filter_map(|nonce| {
+            let challenge = satisfiability::Challenge::generate_instance_from_vec(
+                settings.calc_seeds(nonce),
+                &settings.difficulty,
+            )
+            .unwrap();
+            (0..1u32 << 10)
+                .map(|bits| satisfiability::Solution {
+                    variables: (0..10).map(|i| bits & (1 << i) != 0).collect(),
+                })
+                .find(|solution| challenge.verify_solution(solution).is_ok())
+                .map(|solution| solution_data(nonce, &solution))
+        })
+        .collect();
+    assert!(solutions_data.len() > 3);
+    let mut nonces: Vec<u64> = solutions_data.iter().map(|d| d.nonce).collect();
+    solutions_data.reverse();
+
+    let selected: Vec<u64> = select_top_k(&settings, &solutions_data, 3)
+        .iter()
+        .map(|d| d.nonce)
+        .collect();
+    nonces.truncate(3);
+    assert_eq!(selected, nonces);
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use serde_json::json;
+use tig_benchmarker::{verify_bundle, Job};
+use tig_challenges::{satisfiability, ChallengeTrait};
+use tig_structs::core::{Solution, SolutionData};
+
+const NUM_VARIABLES: usize = 10;
+
+fn job() -> Job {
+    common::job(common::settings("c001", vec![NUM_VARIABLES as i32, 200]))
+}
+
+// brute forces the nonce's instance, which is small enough to try every assignment
+fn solve(job: &Job, nonce: u64) -> Option<Vec<bool>> {
+    let challenge = satisfiability::Challenge::generate_instance_from_vec(
+        job.settings.calc_seeds(nonce),
+        &job.settings.difficulty,
+    )
+    .unwrap();
+    (0..1u32 << NUM_VARIABLES)
+        .map(|bits| (0..NUM_VARIABLES).map(|i| bits & (1 << i) != 0).collect())
+        .find(|variables: &Vec<bool>| {
+            challenge
+                .verify_solution(&satisfiability::Solution {
+                    variables: variables.clone(),
+                })
+                .is_ok()
+        })
+}
+
+fn solution_data(nonce: u64, variables: &[bool]) -> SolutionData {
+    let variables: Vec<u8> = variables.iter().map(|&v| v as u8).collect();
+    let solution: Solution = json!({ "variables": variables })
+        .as_object()
+        .unwrap()
+        .clone();
+    SolutionData::new(nonce, 123, 1000, 2000, solution).unwrap()
+}
+
+#[test]
+fn test_mostly_valid_bundle() {
+    let job = job();
+    let mut entries: Vec<(u64, SolutionData)> = (0..20)
+        .filter_map(|nonce| solve(&job, nonce).map(|v| (nonce, solution_data(nonce, &v))))
+        .take(8)
+        .collect();
+    assert_eq!(entries.len(), 8);
+
+    let report = verify_bundle(&job, &entries);
+    assert!(report.is_valid());
+    assert_eq!(report.num_entries, 8);
+    assert_eq!(report.num_qualifiers, 8);
+
+    // drop a variable from one solution, and give another the solution of a different nonce
+    let truncated = entries[2].0;
+    let variables = solve(&job, truncated).unwrap();
+    entries[2].1 = solution_data(truncated, &variables[1..]);
+    let mismatched = entries[5].0;
+    entries[5].1.nonce = entries[6].0;
+
+    let report = verify_bundle(&job, &entries);
+    assert!(!report.is_valid());
+    assert_eq!(report.num_entries, 8);
+    assert_eq!(report.num_qualifiers, 6);
+    let failed: Vec<u64> = report.failures.iter().map(|f| f.nonce).collect();
+    assert_eq!(failed, vec![truncated, mismatched]);
+    assert!(report.failures[0].reason.contains("number of variables"));
+    assert!(report.failures[1].reason.contains("nonce"));
+    for (nonce, _) in entries.iter() {
+        assert_ne!(report.passed_nonces.contains(nonce), failed.contains(nonce));
+    }
+}
+
+#[test]
+fn test_duplicate_and_signature_failures() {
+    let mut job = job();
+    let (nonce, variables) = (0..20)
+        .find_map(|nonce| solve(&job, nonce).map(|v| (nonce, v)))
+        .unwrap();
+    let entry = (nonce, solution_data(nonce, &variables));
+
+    let report = verify_bundle(&job, &[entry.clone(), entry.clone()]);
+    assert_eq!(report.passed_nonces, vec![nonce]);
+    assert_eq!(report.failures.len(), 1);
+    assert_eq!(report.failures[0].reason, "Duplicate nonce");
+
+    job.solution_signature_threshold = entry.1.calc_solution_signature() - 1;
+    let report = verify_bundle(&job, &[entry]);
+    assert_eq!(report.num_qualifiers, 0);
+    assert!(report.failures[0].reason.contains("threshold"));
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use serde_json::json;
+use tig_benchmarker::{verify_solution_tiers, HighestTierSatisfied, Job};
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_structs::core::Solution;
+
+const NUM_ITEMS: usize = 12;
+const NONCE: u64 = 7;
+
+fn job() -> Job {
+    common::job(common::settings("c003", vec![NUM_ITEMS as i32, 0]))
+}
+
+fn challenge(job: &Job) -> knapsack::Challenge {
+    knapsack::Challenge::generate_instance_from_vec(
+        job.settings.calc_seeds(NONCE),
+        &job.settings.difficulty,
+    )
+    .unwrap()
+}
+
+// the most valuable items that fit, small enough to try every subset
+fn best_items(challenge: &knapsack::Challenge) -> Vec<usize> {
+    (0u32..1 << NUM_ITEMS)
+        .map(|mask| (0..NUM_ITEMS).filter(|i| mask & (1 << i) != 0).collect())
+        .filter(|items: &Vec<usize>| {
+            items.iter().map(|&i| challenge.weights[i]).sum::<u32>() <= challenge.max_weight
+        })
+        .max_by_key(|items| items.iter().map(|&i| challenge.values[i]).sum::<u32>())
+        .unwrap()
+}
+
+fn solution(items: &[usize]) -> Solution {
+    serde_json::from_value(json!({ "items": items })).unwrap()
+}
+
+fn thresholds() -> Vec<Vec<i32>> {
+    (0..20).map(|x| vec![NUM_ITEMS as i32, x * 10]).collect()
+}
+
+#[test]
+fn test_highest_tier_matches_solution_strength() {
+    let job = job();
+    let challenge = challenge(&job);
+    let items = best_items(&challenge);
+    let total_value: u32 = items.iter().map(|&i| challenge.values[i]).sum();
+    let baseline_value =
+        knapsack::calc_baseline_value(&challenge.weights, &challenge.values, challenge.max_weight);
+
+    // the strongest better_than_baseline the solution reaches, checked tier by tier
+    let thresholds = thresholds();
+    let expected = thresholds
+        .iter()
+        .rposition(|t| knapsack::calc_min_value(baseline_value, t[1] as u32) <= total_value)
+        .unwrap();
+    assert_eq!(
+        verify_solution_tiers(&job, NONCE, &solution(&items), &thresholds).unwrap(),
+        HighestTierSatisfied::Tier {
+            index: expected,
+            difficulty: thresholds[expected].clone(),
+        }
+    );
+}
+
+#[test]
+fn test_no_tier_satisfied() {
+    let job = job();
+    assert_eq!(
+        verify_solution_tiers(&job, NONCE, &solution(&[]), &thresholds()).unwrap(),
+        HighestTierSatisfied::None
+    );
+    assert_eq!(
+        verify_solution_tiers(&job, NONCE, &solution(&[]), &[]).unwrap(),
+        HighestTierSatisfied::None
+    );
+}
+
+#[test]
+fn test_unsorted_thresholds_are_rejected() {
+    let job = job();
+    let items = best_items(&challenge(&job));
+    let mut thresholds = thresholds();
+    thresholds.reverse();
+    assert!(verify_solution_tiers(&job, NONCE, &solution(&items), &thresholds).is_err());
+}
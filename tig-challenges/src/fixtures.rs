@@ -0,0 +1,104 @@
+use crate::{knapsack, satisfiability, vector_search, vehicle_routing, ChallengeTrait};
+use anyhow::{anyhow, Result};
+
+pub const CHALLENGE_IDS: [&str; 4] = ["c001", "c002", "c003", "c004"];
+
+// a tiny hand written instance with a solution known to verify, for tests that need an instance of
+// a challenge without generating or solving one. small enough to check by hand
+pub enum Fixture {
+    Satisfiability(satisfiability::Challenge, satisfiability::Solution),
+    VehicleRouting(vehicle_routing::Challenge, vehicle_routing::Solution),
+    Knapsack(knapsack::Challenge, knapsack::Solution),
+    VectorSearch(vector_search::Challenge, vector_search::Solution),
+}
+
+impl Fixture {
+    // verifies the fixture's solution against its instance
+    pub fn verify(&self) -> Result<()> {
+        match self {
+            Fixture::Satisfiability(challenge, solution) => challenge.verify_solution(solution),
+            Fixture::VehicleRouting(challenge, solution) => challenge.verify_solution(solution),
+            Fixture::Knapsack(challenge, solution) => challenge.verify_solution(solution),
+            Fixture::VectorSearch(challenge, solution) => challenge.verify_solution(solution),
+        }
+    }
+}
+
+pub fn small_instance(challenge_id: &str) -> Result<Fixture> {
+    match challenge_id {
+        "c001" => Ok(Fixture::Satisfiability(
+            // (x1 or x2 or x3) and (!x1 or x2 or !x3) and (x1 or !x2 or x3)
+            satisfiability::Challenge {
+                seeds: [0; 8],
+                difficulty: satisfiability::Difficulty {
+                    num_variables: 3,
+                    clauses_to_variables_percent: 100,
+                },
+                clauses: vec![vec![1, 2, 3], vec![-1, 2, -3], vec![1, -2, 3]],
+            },
+            satisfiability::Solution {
+                variables: vec![true, true, false],
+            },
+        )),
+        "c002" => Ok(Fixture::VehicleRouting(
+            // the depot and 3 customers on the corners of a square with sides of 10
+            vehicle_routing::Challenge {
+                seeds: [0; 8],
+                difficulty: vehicle_routing::Difficulty {
+                    num_nodes: 4,
+                    better_than_baseline: 0,
+                },
+                demands: vec![0, 30, 40, 50],
+                distance_matrix: vec![
+                    vec![0, 10, 10, 14],
+                    vec![10, 0, 14, 10],
+                    vec![10, 14, 0, 10],
+                    vec![14, 10, 10, 0],
+                ],
+                max_total_distance: 60,
+                max_capacity: 100,
+            },
+            // distances 34 and 20
+            vehicle_routing::Solution {
+                routes: vec![vec![0, 1, 3, 0], vec![0, 2, 0]],
+            },
+        )),
+        "c003" => Ok(Fixture::Knapsack(
+            // items (weight, value): (2, 3), (3, 4), (4, 5), (5, 6). the optimum is 7
+            knapsack::Challenge {
+                seeds: [0; 8],
+                difficulty: knapsack::Difficulty {
+                    num_items: 4,
+                    better_than_baseline: 0,
+                },
+                weights: vec![2, 3, 4, 5],
+                values: vec![3, 4, 5, 6],
+                max_weight: 5,
+                min_value: 7,
+            },
+            knapsack::Solution { items: vec![0, 1] },
+        )),
+        "c004" => Ok(Fixture::VectorSearch(
+            // every query lies 0.1 from its nearest database vector
+            vector_search::Challenge {
+                seeds: [0; 8],
+                difficulty: vector_search::Difficulty {
+                    num_queries: 2,
+                    better_than_baseline: 0,
+                },
+                vector_database: vec![
+                    vec![0.0, 0.0],
+                    vec![1.0, 0.0],
+                    vec![0.0, 1.0],
+                    vec![1.0, 1.0],
+                ],
+                query_vectors: vec![vec![0.9, 1.0], vec![0.0, 0.1]],
+                max_distance: 0.2,
+            },
+            vector_search::Solution {
+                indexes: vec![3, 0],
+            },
+        )),
+        _ => Err(anyhow!("Unknown challenge id '{}'", challenge_id)),
+    }
+}
@@ -1,4 +1,4 @@
-use crate::RngArray;
+use crate::{pack_bits, unpack_bits, ChallengeTrait, DifficultyTrait, ProofBytes, RngArray};
 use anyhow::{anyhow, Result};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
@@ -36,7 +36,11 @@ pub struct Solution {
     pub items: Vec<usize>,
 }
 
-impl crate::SolutionTrait for Solution {}
+impl crate::SolutionTrait for Solution {
+    fn solution_len(&self) -> usize {
+        self.items.len()
+    }
+}
 
 impl TryFrom<Map<String, Value>> for Solution {
     type Error = serde_json::Error;
@@ -56,11 +60,18 @@ pub struct Challenge {
     pub min_value: u32,
 }
 
+// largest num_items * max_weight for which optimum runs the exact dynamic program
+const OPTIMUM_MAX_DP_SIZE: usize = 10_000_000;
+
 // TIG dev bounty available for a GPU optimisation for instance generation!
 #[cfg(feature = "cuda")]
 pub const KERNEL: Option<CudaKernel> = None;
 
 impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
+    // more items and a higher value target are both harder
+    const HARDER_WHEN_HIGHER: [bool; 2] = [true, true];
+    const DIFFICULTY_BOUNDS: [(i32, i32); 2] = [(1, i32::MAX), (0, i32::MAX)];
+
     #[cfg(feature = "cuda")]
     fn cuda_generate_instance(
         seeds: [u64; 8],
@@ -83,25 +94,8 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             .collect();
         let max_weight: u32 = weights.iter().sum::<u32>() / 2;
 
-        // Baseline greedy algorithm
-        let mut sorted_value_to_weight_ratio: Vec<usize> = (0..difficulty.num_items).collect();
-        sorted_value_to_weight_ratio.sort_by(|&a, &b| {
-            let ratio_a = values[a] as f64 / weights[a] as f64;
-            let ratio_b = values[b] as f64 / weights[b] as f64;
-            ratio_b.partial_cmp(&ratio_a).unwrap()
-        });
-
-        let mut total_weight = 0;
-        let mut min_value = 0;
-        for &item in &sorted_value_to_weight_ratio {
-            if total_weight + weights[item] > max_weight {
-                continue;
-            }
-            min_value += values[item];
-            total_weight += weights[item];
-        }
-        min_value = (min_value as f64 * (1.0 + difficulty.better_than_baseline as f64 / 1000.0))
-            .round() as u32;
+        let baseline_value = calc_baseline_value(&weights, &values, max_weight);
+        let min_value = calc_min_value(baseline_value, difficulty.better_than_baseline);
 
         Ok(Challenge {
             seeds,
@@ -114,6 +108,221 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
     }
 
     fn verify_solution(&self, solution: &Solution) -> Result<()> {
+        self.verify_solution_with_min_value(solution, self.min_value)
+    }
+
+    fn count_violations(&self, solution: &Solution) -> usize {
+        // every duplicate or out of bounds item, and each of the weight and value constraints
+        let mut selected_items = HashSet::new();
+        let mut num_violations = solution
+            .items
+            .iter()
+            .filter(|&&item| item >= self.weights.len() || !selected_items.insert(item))
+            .count();
+        let total_weight = selected_items
+            .iter()
+            .map(|&item| self.weights[item])
+            .sum::<u32>();
+        if total_weight > self.max_weight {
+            num_violations += 1;
+        }
+        let total_value = selected_items
+            .iter()
+            .map(|&item| self.values[item])
+            .sum::<u32>();
+        if total_value < self.min_value {
+            num_violations += 1;
+        }
+        num_violations
+    }
+
+    fn qualifier(&self, solution: &Solution) -> Result<f64> {
+        self.verify_solution(solution)?;
+        let total_value = solution
+            .items
+            .iter()
+            .map(|&item| self.values[item])
+            .sum::<u32>();
+        Ok(total_value as f64 / self.min_value.max(1) as f64)
+    }
+
+    // the knapsack's unused weight. unknown items are ignored
+    fn feasibility_margin(&self, solution: &Solution) -> f64 {
+        let total_weight = solution
+            .items
+            .iter()
+            .filter(|&&item| item < self.weights.len())
+            .map(|&item| self.weights[item] as f64)
+            .sum::<f64>();
+        1.0 - total_weight / self.max_weight.max(1) as f64
+    }
+
+    // one bit per item, set if selected. the encoding can't repeat an item or select an unknown
+    // one, so only a solution that verifies is encoded
+    fn minimal_proof(&self, solution: &Solution) -> Result<ProofBytes> {
+        self.verify_solution(solution)?;
+        let mut selected = vec![false; self.weights.len()];
+        for &item in solution.items.iter() {
+            selected[item] = true;
+        }
+        Ok(pack_bits(&selected))
+    }
+
+    // the selected items in any order. repeated and unknown items are kept, they change the meaning
+    fn essential_bytes(&self, solution: &Solution) -> Vec<u8> {
+        let mut items = solution.items.clone();
+        items.sort_unstable();
+        serde_json::to_vec(&items).expect("Failed to serialize items")
+    }
+
+    fn solution_from_proof(&self, proof: &[u8]) -> Result<Solution> {
+        let selected = unpack_bits(proof, self.weights.len())?;
+        Ok(Solution {
+            items: (0..selected.len()).filter(|&item| selected[item]).collect(),
+        })
+    }
+
+    fn verify_solution_at_difficulty(
+        &self,
+        solution: &Solution,
+        difficulty: &Difficulty,
+    ) -> Result<()> {
+        if difficulty.num_items != self.difficulty.num_items {
+            return Err(anyhow!(
+                "Difficulty num_items ({}) does not match instance num_items ({})",
+                difficulty.num_items,
+                self.difficulty.num_items
+            ));
+        }
+        let baseline_value = calc_baseline_value(&self.weights, &self.values, self.max_weight);
+        self.verify_solution_with_min_value(
+            solution,
+            calc_min_value(baseline_value, difficulty.better_than_baseline),
+        )
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "knapsack: {} items, max_weight={}, min_value={}, seeds={:?}",
+            self.weights.len(),
+            self.max_weight,
+            self.min_value,
+            self.seeds
+        )
+    }
+
+    fn explain(&self) -> String {
+        let num_items = self.weights.len();
+        [
+            format!("knapsack instance with seeds={:?}", self.seeds),
+            format!(
+                "{} items, each with a weight and a value (better_than_baseline={})",
+                num_items, self.difficulty.better_than_baseline
+            ),
+            format!(
+                "weights range from {} to {}, values from {} to {}",
+                self.weights.iter().min().unwrap_or(&0),
+                self.weights.iter().max().unwrap_or(&0),
+                self.values.iter().min().unwrap_or(&0),
+                self.values.iter().max().unwrap_or(&0)
+            ),
+            format!(
+                "a solution selects distinct items by index in 0..{}",
+                num_items
+            ),
+            format!(
+                "constraint: the selected items weigh at most max_weight={} in total",
+                self.max_weight
+            ),
+            format!(
+                "objective: maximise their total value, which must reach min_value={}",
+                self.min_value
+            ),
+        ]
+        .join("\n")
+    }
+
+    fn optimum(&self) -> Option<f64> {
+        let max_weight = self.max_weight as usize;
+        if self.weights.len() * (max_weight + 1) > OPTIMUM_MAX_DP_SIZE {
+            return None;
+        }
+        // best_values[w] is the best total value with total weight at most w
+        let mut best_values = vec![0u32; max_weight + 1];
+        for (&weight, &value) in self.weights.iter().zip(self.values.iter()) {
+            let weight = weight as usize;
+            for w in (weight..=max_weight).rev() {
+                best_values[w] = best_values[w].max(best_values[w - weight] + value);
+            }
+        }
+        Some(best_values[max_weight] as f64)
+    }
+
+    fn repair(&self, solution: &Solution) -> Option<Solution> {
+        // keeps the first of duplicate items and drops unknown ones, then drops the items worth
+        // least per unit of weight until the rest fit
+        let mut selected_items = HashSet::new();
+        let mut items: Vec<usize> = solution
+            .items
+            .iter()
+            .cloned()
+            .filter(|&item| item < self.weights.len() && selected_items.insert(item))
+            .collect();
+        let mut total_weight = items.iter().map(|&item| self.weights[item]).sum::<u32>();
+        while total_weight > self.max_weight {
+            let (idx, &item) = items.iter().enumerate().min_by(|(_, &a), (_, &b)| {
+                let ratio_a = self.values[a] as f64 / self.weights[a] as f64;
+                let ratio_b = self.values[b] as f64 / self.weights[b] as f64;
+                ratio_a.partial_cmp(&ratio_b).unwrap()
+            })?;
+            total_weight -= self.weights[item];
+            items.remove(idx);
+        }
+        let solution = Solution { items };
+        self.verify_solution(&solution).ok().map(|_| solution)
+    }
+
+    // even all items together fall short of min_value
+    fn infeasible_instance(seeds: [u64; 8], difficulty: &Difficulty) -> Option<Self> {
+        let mut challenge = Self::generate_instance(seeds, difficulty).ok()?;
+        challenge.min_value = challenge
+            .values
+            .iter()
+            .fold(0u32, |sum, &value| sum.saturating_add(value))
+            .checked_add(1)?;
+        Some(challenge)
+    }
+
+    fn trivial_solution(&self) -> Option<Solution> {
+        // the greedy packing reaches the baseline value, so verifies when the target is the baseline
+        [Solution { items: Vec::new() }, greedy_warm_start(self)]
+            .into_iter()
+            .find(|solution| self.verify_solution(solution).is_ok())
+    }
+
+    fn instances_equal(a: &Self, b: &Self) -> bool {
+        // solutions refer to items by index, so item order matters
+        a.difficulty.to_arr() == b.difficulty.to_arr()
+            && a.weights == b.weights
+            && a.values == b.values
+            && a.max_weight == b.max_weight
+            && a.min_value == b.min_value
+    }
+
+    fn max_solution_len(&self) -> usize {
+        // each item at most once
+        self.weights.len()
+    }
+
+    fn search_space_log2(&self) -> f64 {
+        // every subset of the items
+        self.weights.len() as f64
+    }
+}
+
+impl Challenge {
+    fn verify_solution_with_min_value(&self, solution: &Solution, min_value: u32) -> Result<()> {
+        self.check_solution_len(solution)?;
         let selected_items: HashSet<usize> = solution.items.iter().cloned().collect();
         if selected_items.len() != solution.items.len() {
             return Err(anyhow!("Duplicate items selected."));
@@ -140,14 +349,54 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             .iter()
             .map(|&item| self.values[item])
             .sum::<u32>();
-        if total_value < self.min_value {
+        if total_value < min_value {
             Err(anyhow!(
                 "Total value ({}) does not reach minimum value ({})",
                 total_value,
-                self.min_value
+                min_value
             ))
         } else {
             Ok(())
         }
     }
 }
+
+// the baseline's greedy packing, always within max_weight. a starting point for algorithms to
+// improve on, its total value is the instance's baseline value
+pub fn greedy_warm_start(challenge: &Challenge) -> Solution {
+    Solution {
+        items: calc_greedy_items(&challenge.weights, &challenge.values, challenge.max_weight),
+    }
+}
+
+pub fn calc_baseline_value(weights: &[u32], values: &[u32], max_weight: u32) -> u32 {
+    calc_greedy_items(weights, values, max_weight)
+        .iter()
+        .map(|&item| values[item])
+        .sum()
+}
+
+fn calc_greedy_items(weights: &[u32], values: &[u32], max_weight: u32) -> Vec<usize> {
+    // Baseline greedy algorithm
+    let mut sorted_value_to_weight_ratio: Vec<usize> = (0..weights.len()).collect();
+    sorted_value_to_weight_ratio.sort_by(|&a, &b| {
+        let ratio_a = values[a] as f64 / weights[a] as f64;
+        let ratio_b = values[b] as f64 / weights[b] as f64;
+        ratio_b.partial_cmp(&ratio_a).unwrap()
+    });
+
+    let mut total_weight = 0;
+    let mut items = Vec::new();
+    for &item in &sorted_value_to_weight_ratio {
+        if total_weight + weights[item] > max_weight {
+            continue;
+        }
+        items.push(item);
+        total_weight += weights[item];
+    }
+    items
+}
+
+pub fn calc_min_value(baseline_value: u32, better_than_baseline: u32) -> u32 {
+    (baseline_value as f64 * (1.0 + better_than_baseline as f64 / 1000.0)).round() as u32
+}
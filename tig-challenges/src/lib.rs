@@ -1,24 +1,82 @@
 use anyhow::{anyhow, Result};
 use rand::{rngs::StdRng, Rng, SeedableRng};
 use serde::de::DeserializeOwned;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::path::Path;
 
 #[cfg(feature = "cuda")]
 use cudarc::driver::*;
 #[cfg(feature = "cuda")]
 use std::{collections::HashMap, sync::Arc};
 
+// instance generation is pinned to the crate version. bump it whenever generation changes
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
 pub trait DifficultyTrait<const N: usize>: Serialize + DeserializeOwned {
     fn from_arr(arr: &[i32; N]) -> Self;
     fn to_arr(&self) -> [i32; N];
 }
-pub trait SolutionTrait: Serialize + DeserializeOwned {}
+pub trait SolutionTrait: Serialize + DeserializeOwned {
+    // length of the solution's vector, bounded by ChallengeTrait::max_solution_len
+    fn solution_len(&self) -> usize;
+}
 
 pub trait ChallengeTrait<T, U, const N: usize>: Serialize + DeserializeOwned
 where
     T: SolutionTrait,
     U: DifficultyTrait<N>,
 {
+    // per difficulty parameter, whether raising it makes instances harder
+    const HARDER_WHEN_HIGHER: [bool; N];
+    // inclusive range of each difficulty parameter that instances can be generated at, e.g. at least
+    // one variable. the protocol's own ranges are narrower
+    const DIFFICULTY_BOUNDS: [(i32, i32); N];
+    // partial order of difficulties by hardness. Greater if a is at least as hard as b in every
+    // parameter, None if a is harder in some parameters and easier in others
+    fn compare_difficulty(a: &U, b: &U) -> Option<Ordering> {
+        let (a, b) = (a.to_arr(), b.to_arr());
+        let mut ordering = Ordering::Equal;
+        for i in 0..N {
+            let param_ordering = if Self::HARDER_WHEN_HIGHER[i] {
+                a[i].cmp(&b[i])
+            } else {
+                b[i].cmp(&a[i])
+            };
+            match (ordering, param_ordering) {
+                (_, Ordering::Equal) => {}
+                (Ordering::Equal, _) => ordering = param_ordering,
+                _ if ordering != param_ordering => return None,
+                _ => {}
+            }
+        }
+        Some(ordering)
+    }
+    // difficulties one step from difficulty in a single parameter, harder first then easier for
+    // each parameter in order. steps out of DIFFICULTY_BOUNDS are left out. for hill climbing over
+    // the difficulty space. empty if difficulty doesn't have N parameters
+    fn difficulty_neighbors(difficulty: &[i32]) -> Vec<Vec<i32>> {
+        if difficulty.len() != N {
+            return Vec::new();
+        }
+        let mut neighbors = Vec::new();
+        for i in 0..N {
+            let (min, max) = Self::DIFFICULTY_BOUNDS[i];
+            let harder = if Self::HARDER_WHEN_HIGHER[i] { 1 } else { -1 };
+            for step in [harder, -harder] {
+                if let Some(value) = difficulty[i]
+                    .checked_add(step)
+                    .filter(|x| (min..=max).contains(x))
+                {
+                    let mut neighbor = difficulty.to_vec();
+                    neighbor[i] = value;
+                    neighbors.push(neighbor);
+                }
+            }
+        }
+        neighbors
+    }
+
     fn generate_instance(seeds: [u64; 8], difficulty: &U) -> Result<Self>;
     fn generate_instance_from_str(seeds: [u64; 8], difficulty: &str) -> Result<Self> {
         Self::generate_instance(seeds, &serde_json::from_str(difficulty)?)
@@ -71,16 +129,192 @@ where
         Self::cuda_generate_instance(seeds, &U::from_arr(difficulty), dev, funcs)
     }
 
+    // rough chance that a nonce at this difficulty yields a solution, for estimating how many nonces
+    // a run needs. None where no closed form or fitted estimate exists
+    fn expected_solve_probability(_difficulty: &U) -> Option<f64> {
+        None
+    }
+
+    // an instance generated like generate_instance's, then altered so no solution can verify, e.g. a
+    // formula with a contradiction. for testing how verifiers and solvers handle the negative path.
+    // None where the challenge has no cheap way to make one or the difficulty is too small
+    fn infeasible_instance(_seeds: [u64; 8], _difficulty: &U) -> Option<Self> {
+        None
+    }
+
     fn verify_solution(&self, solution: &T) -> Result<()>;
+    // verifies against the thresholds this instance would have at the given difficulty. a solution
+    // that satisfies a harder difficulty also satisfies any easier one
+    fn verify_solution_at_difficulty(&self, solution: &T, difficulty: &U) -> Result<()>;
+    // one line describing the instance for logs and error messages
+    fn summary(&self) -> String;
+    // several lines on what the instance asks of a solver: its defining parameters, the constraints
+    // a solution must meet and the objective, for developers new to the challenge
+    fn explain(&self) -> String;
+    // exact optimum of the instance's objective when it is cheap enough to compute at this instance
+    // size, otherwise None. decision challenges have no objective and always return None
+    fn optimum(&self) -> Option<f64>;
+    // a solution that needs no solver, e.g. an empty knapsack. only returned if it verifies, so it
+    // usually exists only at easy difficulties
+    fn trivial_solution(&self) -> Option<T>;
+    // deep comparison of the problems two instances pose, ignoring orderings the problem doesn't
+    // depend on, e.g. the order of clauses. seeds are not compared
+    fn instances_equal(a: &Self, b: &Self) -> bool;
+    // longest solution vector a valid solution can have at this instance's size. verification
+    // rejects longer solutions before looking at their entries
+    fn max_solution_len(&self) -> usize;
+    // approximate log2 of the number of candidate solutions the instance implies, ignoring its
+    // constraints. for normalizing difficulties across challenges and scaling timeouts
+    fn search_space_log2(&self) -> f64;
+    fn check_solution_len(&self, solution: &T) -> Result<()> {
+        if solution.solution_len() > self.max_solution_len() {
+            Err(anyhow!(
+                "Solution length ({}) exceeds max solution length ({})",
+                solution.solution_len(),
+                self.max_solution_len()
+            ))
+        } else {
+            Ok(())
+        }
+    }
+    // how far a solution is from valid, for watching a solver get closer: the number of constraints
+    // it violates, e.g. unsatisfied clauses. 0 exactly when verify_solution accepts it. challenges
+    // without countable constraints only tell valid from invalid, so return 1 if it is invalid
+    fn count_violations(&self, solution: &T) -> usize {
+        match self.verify_solution(solution) {
+            Ok(_) => 0,
+            Err(_) => 1,
+        }
+    }
+    // a valid solution close to an invalid one, e.g. a knapsack with items dropped until it fits.
+    // only returned if it verifies. challenges without a cheap repair return None
+    fn repair(&self, _solution: &T) -> Option<T> {
+        None
+    }
+    // how far a valid solution clears the instance's threshold, for ranking solutions when only
+    // some can be submitted. 1 just meets it, higher is better. decision challenges have no
+    // objective, so every valid solution is 1. fails if the solution doesn't verify
+    fn qualifier(&self, solution: &T) -> Result<f64> {
+        self.verify_solution(solution).map(|_| 1.0)
+    }
+    // fraction of the instance's capacity a solution leaves unused, 0 when it's used up. a valid
+    // solution with a large margin may be wasteful. negative when the solution is over capacity.
+    // challenges without a capacity constraint have no slack, so 0
+    fn feasibility_margin(&self, _solution: &T) -> f64 {
+        0.0
+    }
+    // verify_solution split over at most threads scoped threads, for large instances whose checks
+    // are independent, e.g. one per clause. gives exactly the result of verify_solution, including
+    // which failure is reported. not available in the browser, which can't spawn threads. challenges
+    // without independent checks verify serially
+    fn verify_solution_parallel(&self, solution: &T, _threads: usize) -> Result<()> {
+        self.verify_solution(solution)
+    }
     fn verify_solution_from_json(&self, solution: &str) -> Result<()> {
         let solution = serde_json::from_str(solution)
             .map_err(|e| anyhow!("Failed to parse solution: {}", e))?;
         self.verify_solution(&solution)
     }
+    // the smallest encoding of a solution for submitting it, leaving out what the verifier can
+    // recompute from the instance, e.g. the number of variables. fails if the encoding can't hold
+    // the solution as is, so a solution that doesn't verify never becomes a proof that does.
+    // defaults to the solution's json
+    fn minimal_proof(&self, solution: &T) -> Result<ProofBytes> {
+        serde_json::to_vec(solution).map_err(|e| anyhow!("Failed to serialize solution: {}", e))
+    }
+    // the bytes that define what a solution means for the instance, for hashing and dedup. solutions
+    // that differ only in what the verifier ignores or recomputes, e.g. the order of selected
+    // items, have the same essential bytes. unlike minimal_proof, defined for any solution.
+    // defaults to the solution's json
+    fn essential_bytes(&self, solution: &T) -> Vec<u8> {
+        serde_json::to_vec(solution).expect("Failed to serialize solution")
+    }
+    // rebuilds the solution from the instance and a minimal_proof. fails on malformed proofs
+    fn solution_from_proof(&self, proof: &[u8]) -> Result<T> {
+        serde_json::from_slice(proof).map_err(|e| anyhow!("Failed to parse proof: {}", e))
+    }
+    fn verify_proof(&self, proof: &[u8]) -> Result<()> {
+        self.verify_solution(&self.solution_from_proof(proof)?)
+    }
+}
+
+// a solution as encoded by ChallengeTrait::minimal_proof
+pub type ProofBytes = Vec<u8>;
+
+// signature of an algorithm's solve_challenge. the benchmarker calls solvers from spawned tasks, so
+// the challenge and solution types must stay Send + Sync
+pub type SolveChallengeFn<C, S> = fn(&C) -> Result<Option<S>>;
+
+// what a solver can tell about an instance. solve_challenge may return this instead of an Option to
+// report that it proved no solution exists, e.g. a complete SAT solver deriving UNSAT, as opposed
+// to giving up on the instance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SolveOutcome<T> {
+    Solved(T),
+    ProvedNoSolution,
+    GaveUp,
+}
+
+impl<T> From<Option<T>> for SolveOutcome<T> {
+    // a solver that returns an Option proves nothing when it returns None
+    fn from(solution: Option<T>) -> Self {
+        match solution {
+            Some(solution) => SolveOutcome::Solved(solution),
+            None => SolveOutcome::GaveUp,
+        }
+    }
 }
 
+impl<T> SolveOutcome<T> {
+    pub fn solution(self) -> Option<T> {
+        match self {
+            SolveOutcome::Solved(solution) => Some(solution),
+            _ => None,
+        }
+    }
+
+    pub fn is_proved_no_solution(&self) -> bool {
+        matches!(self, SolveOutcome::ProvedNoSolution)
+    }
+}
+
+// solution length the wasm entry point reports for SolveOutcome::ProvedNoSolution. a length of 0
+// means the solver gave up
+pub const PROVED_NO_SOLUTION_LEN: u32 = u32::MAX;
+
+// optional interface for anytime solvers that advance in steps. the solver's state can be saved
+// between steps and restored later, e.g. after a restart, so a long solve resumes instead of
+// starting over
+pub trait ResumableSolver<T: SolutionTrait>: Serialize + DeserializeOwned {
+    // advances the solve by one step. returns None while the solve is still running
+    fn step(&mut self) -> Result<Option<T>>;
+    fn save_state(&self) -> Result<Vec<u8>> {
+        serde_json::to_vec(self).map_err(|e| anyhow!("Failed to save solver state: {}", e))
+    }
+    fn restore_state(state: &[u8]) -> Result<Self> {
+        serde_json::from_slice(state).map_err(|e| anyhow!("Failed to restore solver state: {}", e))
+    }
+}
+
+// steps the solver until it finishes or max_steps is reached. returns None if paused
+pub fn run_resumable<T: SolutionTrait>(
+    solver: &mut impl ResumableSolver<T>,
+    max_steps: Option<u64>,
+) -> Result<Option<T>> {
+    let mut num_steps = 0;
+    while max_steps != Some(num_steps) {
+        if let Some(solution) = solver.step()? {
+            return Ok(Some(solution));
+        }
+        num_steps += 1;
+    }
+    Ok(None)
+}
+
+pub mod fixtures;
 pub mod knapsack;
 pub use knapsack as c003;
+pub mod preprocessing;
 pub mod satisfiability;
 pub use satisfiability as c001;
 pub mod vector_search;
@@ -94,6 +328,119 @@ pub struct CudaKernel {
     pub funcs: &'static [&'static str],
 }
 
+// reads an external dataset, e.g. a vehicle_routing::Dataset, from a json file
+pub fn load_dataset<D: DeserializeOwned>(path: &Path) -> Result<D> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read dataset {}: {}", path.display(), e))?;
+    serde_json::from_str(&contents)
+        .map_err(|e| anyhow!("Failed to parse dataset {}: {}", path.display(), e))
+}
+
+// indexes of amount distinct items out of len, in the order they were drawn. a partial fisher-yates
+// shuffle, so the draw only depends on the rngs
+pub fn sample_indexes(rngs: &mut RngArray, len: usize, amount: usize) -> Vec<usize> {
+    let mut indexes: Vec<usize> = (0..len).collect();
+    for i in 0..amount.min(len) {
+        let j = rngs.get_mut().gen_range(i..len);
+        indexes.swap(i, j);
+    }
+    indexes.truncate(amount);
+    indexes
+}
+
+// index of the first item failing is_ok, checking chunks of items on at most threads scoped threads.
+// each chunk reports its own first failure and the earliest wins, so the result is the one a serial
+// scan gives regardless of threads or which chunk finishes first
+pub fn find_first_failure<I: Sync>(
+    items: &[I],
+    threads: usize,
+    is_ok: impl Fn(&I) -> bool + Sync,
+) -> Option<usize> {
+    if items.is_empty() {
+        return None;
+    }
+    let chunk_size = items.len().div_ceil(threads.clamp(1, items.len()));
+    let is_ok = &is_ok;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = items
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .position(|item| !is_ok(item))
+                        .map(|idx| i * chunk_size + idx)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().unwrap())
+            .min()
+    })
+}
+
+// one bit per value, least significant bit first, for proofs of boolean vectors
+pub fn pack_bits(bits: &[bool]) -> ProofBytes {
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
+    for (i, &bit) in bits.iter().enumerate() {
+        if bit {
+            bytes[i / 8] |= 1 << (i % 8);
+        }
+    }
+    bytes
+}
+
+// the len values pack_bits encoded. strict, so a proof has exactly one encoding: the number of
+// bytes must match and the padding bits of the last byte must be 0
+pub fn unpack_bits(bytes: &[u8], len: usize) -> Result<Vec<bool>> {
+    if bytes.len() != len.div_ceil(8) {
+        return Err(anyhow!(
+            "Expected {} bytes for {} bits, got {}",
+            len.div_ceil(8),
+            len,
+            bytes.len()
+        ));
+    }
+    if !len.is_multiple_of(8) && bytes[len / 8] >> (len % 8) != 0 {
+        return Err(anyhow!("Padding bits are set"));
+    }
+    Ok((0..len)
+        .map(|i| bytes[i / 8] & (1 << (i % 8)) != 0)
+        .collect())
+}
+
+// a nonce's seeds feed independent rng streams, so the numbers one stream draws never shift another,
+// e.g. an algorithm drawing more numbers can't change the instance it is solving. Generation is the
+// protocol's stream and uses the seeds unchanged, so instances stay as they are. other streams are
+// derived from the seeds with a per-stream domain tag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RngStream {
+    Generation,
+    Solving,
+}
+
+impl RngStream {
+    pub fn seeds(&self, seeds: [u64; 8]) -> [u64; 8] {
+        match self {
+            Self::Generation => seeds,
+            Self::Solving => {
+                let tag = u64::from_le_bytes(*b"solving\0");
+                seeds.map(|seed| StdRng::seed_from_u64(seed ^ tag).gen())
+            }
+        }
+    }
+
+    // a single rng for the stream, e.g. for an algorithm's randomness
+    pub fn rng(&self, seeds: [u64; 8]) -> StdRng {
+        StdRng::seed_from_u64(self.seeds(seeds)[0])
+    }
+}
+
+// every instance owns its rngs, seeded from the seeds of its nonce. generation on parallel tasks
+// therefore shares no rng state and needs no lock, and an instance doesn't depend on which task
+// generated it or in what order
 pub struct RngArray {
     rngs: [StdRng; 8],
     index: u32,
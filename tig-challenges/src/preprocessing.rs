@@ -0,0 +1,132 @@
+use serde::Serialize;
+use std::{
+    any::{Any, TypeId},
+    collections::{hash_map::DefaultHasher, VecDeque},
+    hash::{Hash, Hasher},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
+
+type Key = (u64, &'static str, TypeId);
+type Entry = Arc<OnceLock<Arc<dyn Any + Send + Sync>>>;
+
+// structures a solver derives from an instance, e.g. an index of the clauses each variable is in,
+// shared by all solvers of the same instance, e.g. algorithms racing on a nonce. entries are keyed
+// by instance_key, a name agreed on by the solvers sharing it and the structure's type. at most capacity
+// entries are kept, the least recently used are evicted first
+pub struct PreprocessingCache {
+    capacity: usize,
+    // least recently used first
+    entries: Mutex<VecDeque<(Key, Entry)>>,
+    num_hits: AtomicU64,
+    num_misses: AtomicU64,
+}
+
+impl PreprocessingCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::new()),
+            num_hits: AtomicU64::new(0),
+            num_misses: AtomicU64::new(0),
+        }
+    }
+
+    // concurrent callers for the same entry wait for one of them to preprocess instead of each
+    // preprocessing the instance
+    pub fn get_or_insert_with<T: Any + Send + Sync>(
+        &self,
+        instance_key: u64,
+        name: &'static str,
+        preprocess: impl FnOnce() -> T,
+    ) -> Arc<T> {
+        if self.capacity == 0 {
+            self.num_misses.fetch_add(1, Ordering::Relaxed);
+            return Arc::new(preprocess());
+        }
+        let key = (instance_key, name, TypeId::of::<T>());
+        let entry = {
+            let mut entries = self.entries.lock().unwrap();
+            let entry = match entries.iter().position(|(k, _)| *k == key) {
+                Some(idx) => entries.remove(idx).unwrap(),
+                None => {
+                    if entries.len() == self.capacity {
+                        entries.pop_front();
+                    }
+                    (key, Entry::default())
+                }
+            };
+            let cell = entry.1.clone();
+            entries.push_back(entry);
+            cell
+        };
+        let mut is_miss = false;
+        let value = entry
+            .get_or_init(|| {
+                is_miss = true;
+                Arc::new(preprocess())
+            })
+            .clone();
+        if is_miss {
+            self.num_misses.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.num_hits.fetch_add(1, Ordering::Relaxed);
+        }
+        value.downcast::<T>().unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn num_hits(&self) -> u64 {
+        self.num_hits.load(Ordering::Relaxed)
+    }
+
+    pub fn num_misses(&self) -> u64 {
+        self.num_misses.load(Ordering::Relaxed)
+    }
+}
+
+// hash of an instance's content, leaving out the seeds it was generated from. seeds depend on the
+// settings, algorithm_id included, so the same instance can come from different seeds. costs a pass
+// over the instance, far less than preprocessing worth caching. only stable within a process
+pub fn instance_key(instance: &impl Serialize) -> u64 {
+    let mut content = serde_json::to_value(instance).expect("Failed to serialize instance");
+    if let Some(fields) = content.as_object_mut() {
+        fields.remove("seeds");
+    }
+    let mut hasher = DefaultHasher::new();
+    content.to_string().hash(&mut hasher);
+    hasher.finish()
+}
+
+static CACHE: OnceLock<PreprocessingCache> = OnceLock::new();
+
+// enables the process wide cache used by preprocessed. the first call decides the capacity
+pub fn enable_preprocessing_cache(capacity: usize) -> &'static PreprocessingCache {
+    CACHE.get_or_init(|| PreprocessingCache::new(capacity))
+}
+
+pub fn preprocessing_cache() -> Option<&'static PreprocessingCache> {
+    CACHE.get()
+}
+
+// for solvers. goes through the process wide cache if it is enabled, e.g. by a benchmarker racing
+// algorithms, and preprocesses directly otherwise, e.g. when running as wasm
+pub fn preprocessed<T: Any + Send + Sync>(
+    instance_key: u64,
+    name: &'static str,
+    preprocess: impl FnOnce() -> T,
+) -> Arc<T> {
+    match CACHE.get() {
+        Some(cache) => cache.get_or_insert_with(instance_key, name, preprocess),
+        None => Arc::new(preprocess()),
+    }
+}
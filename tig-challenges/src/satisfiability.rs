@@ -7,10 +7,14 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 use serde_json::{from_value, Map, Value};
+use std::io::Write;
 
 #[cfg(feature = "cuda")]
 use crate::CudaKernel;
-use crate::RngArray;
+use crate::{
+    find_first_failure, pack_bits, unpack_bits, ChallengeTrait, DifficultyTrait, ProofBytes,
+    RngArray,
+};
 #[cfg(feature = "cuda")]
 use cudarc::driver::*;
 #[cfg(feature = "cuda")]
@@ -44,7 +48,11 @@ pub struct Solution {
     pub variables: Vec<bool>,
 }
 
-impl crate::SolutionTrait for Solution {}
+impl crate::SolutionTrait for Solution {
+    fn solution_len(&self) -> usize {
+        self.variables.len()
+    }
+}
 
 impl TryFrom<Map<String, Value>> for Solution {
     type Error = serde_json::Error;
@@ -54,6 +62,12 @@ impl TryFrom<Map<String, Value>> for Solution {
     }
 }
 
+// clauses per variable at which random 3-SAT instances turn from mostly satisfiable to mostly not
+const SATISFIABILITY_THRESHOLD: f64 = 4.267;
+// width of the transition, fitted to the fraction of satisfiable instances at 100 variables
+const TRANSITION_WIDTH: f64 = 0.7;
+const REFERENCE_NUM_VARIABLES: f64 = 100.0;
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Challenge {
     pub seeds: [u64; 8],
@@ -66,6 +80,10 @@ pub struct Challenge {
 pub const KERNEL: Option<CudaKernel> = None;
 
 impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
+    // clauses are drawn at random, so more clauses per variable leaves fewer satisfying assignments
+    const HARDER_WHEN_HIGHER: [bool; 2] = [true, true];
+    const DIFFICULTY_BOUNDS: [(i32, i32); 2] = [(1, i32::MAX), (0, i32::MAX)];
+
     #[cfg(feature = "cuda")]
     fn cuda_generate_instance(
         seeds: [u64; 8],
@@ -117,7 +135,216 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
         })
     }
 
+    // the fraction of satisfiable instances, a logistic in the distance to the threshold. the
+    // transition sharpens as n^(2/3) with n variables. below the threshold more variables make
+    // instances more often satisfiable but harder to search, so the sharpening only applies above it
+    fn expected_solve_probability(difficulty: &Difficulty) -> Option<f64> {
+        let x = (difficulty.clauses_to_variables_percent as f64 / 100.0 - SATISFIABILITY_THRESHOLD)
+            / SATISFIABILITY_THRESHOLD;
+        let num_variables = if x > 0.0 {
+            difficulty.num_variables as f64
+        } else {
+            REFERENCE_NUM_VARIABLES
+        };
+        let scale = num_variables.powf(2.0 / 3.0) / TRANSITION_WIDTH;
+        Some(1.0 / (1.0 + (x * scale).exp()))
+    }
+
     fn verify_solution(&self, solution: &Solution) -> Result<()> {
+        self.check_num_variables(solution)?;
+        match self
+            .clauses
+            .iter()
+            .position(|clause| !is_satisfied(clause, &solution.variables))
+        {
+            Some(idx) => Err(anyhow!("Clause '{}' not satisfied", idx)),
+            None => Ok(()),
+        }
+    }
+
+    // clauses are checked independently, so they are split over the threads
+    fn verify_solution_parallel(&self, solution: &Solution, threads: usize) -> Result<()> {
+        self.check_num_variables(solution)?;
+        match find_first_failure(&self.clauses, threads, |clause| {
+            is_satisfied(clause, &solution.variables)
+        }) {
+            Some(idx) => Err(anyhow!("Clause '{}' not satisfied", idx)),
+            None => Ok(()),
+        }
+    }
+
+    fn count_violations(&self, solution: &Solution) -> usize {
+        // a solution with the wrong number of variables violates every clause
+        if solution.variables.len() != self.difficulty.num_variables {
+            return self.clauses.len().max(1);
+        }
+        self.clauses
+            .iter()
+            .filter(|clause| !is_satisfied(clause, &solution.variables))
+            .count()
+    }
+
+    // one bit per variable, the instance knows how many there are. padding hides extra variables,
+    // so only a solution that verifies is encoded
+    fn minimal_proof(&self, solution: &Solution) -> Result<ProofBytes> {
+        self.verify_solution(solution)?;
+        Ok(pack_bits(&solution.variables))
+    }
+
+    // the number of variables then a bit per variable. padding hides extra false variables, so
+    // without the count a solution and an invalid longer one would have the same bytes
+    fn essential_bytes(&self, solution: &Solution) -> Vec<u8> {
+        let mut bytes = (solution.variables.len() as u64).to_le_bytes().to_vec();
+        bytes.extend(pack_bits(&solution.variables));
+        bytes
+    }
+
+    fn solution_from_proof(&self, proof: &[u8]) -> Result<Solution> {
+        Ok(Solution {
+            variables: unpack_bits(proof, self.difficulty.num_variables)?,
+        })
+    }
+
+    fn verify_solution_at_difficulty(
+        &self,
+        solution: &Solution,
+        difficulty: &Difficulty,
+    ) -> Result<()> {
+        // both difficulty parameters determine the instance itself, so there is no weaker threshold
+        if difficulty.num_variables != self.difficulty.num_variables
+            || difficulty.clauses_to_variables_percent
+                != self.difficulty.clauses_to_variables_percent
+        {
+            return Err(anyhow!(
+                "Difficulty {:?} does not match instance difficulty {:?}",
+                difficulty,
+                self.difficulty
+            ));
+        }
+        self.verify_solution(solution)
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "satisfiability: {} variables, {} clauses, seeds={:?}",
+            self.difficulty.num_variables,
+            self.clauses.len(),
+            self.seeds
+        )
+    }
+
+    fn explain(&self) -> String {
+        let num_variables = self.difficulty.num_variables;
+        [
+            format!(
+                "satisfiability (3-SAT) instance with seeds={:?}",
+                self.seeds
+            ),
+            format!(
+                "{} boolean variables and {} clauses (clauses_to_variables_percent={})",
+                num_variables,
+                self.clauses.len(),
+                self.difficulty.clauses_to_variables_percent
+            ),
+            format!(
+                "each clause is a list of literals: k means variable k-1 is true, -k that it is false, for k in 1..={}",
+                num_variables
+            ),
+            "a clause is satisfied if any of its literals is".to_string(),
+            format!(
+                "a solution assigns all {} variables and must satisfy every clause",
+                num_variables
+            ),
+            "there is no objective: any satisfying assignment is valid".to_string(),
+        ]
+        .join("\n")
+    }
+
+    fn optimum(&self) -> Option<f64> {
+        None
+    }
+
+    // every assignment of the first 3 variables violates one of the 8 clauses over them
+    fn infeasible_instance(seeds: [u64; 8], difficulty: &Difficulty) -> Option<Self> {
+        if difficulty.num_variables < 3 {
+            return None;
+        }
+        let mut challenge = Self::generate_instance(seeds, difficulty).ok()?;
+        for signs in 0..8 {
+            challenge.clauses.push(
+                (0..3)
+                    .map(|i| {
+                        if signs & (1 << i) != 0 {
+                            i + 1
+                        } else {
+                            -(i + 1)
+                        }
+                    })
+                    .collect(),
+            );
+        }
+        Some(challenge)
+    }
+
+    fn trivial_solution(&self) -> Option<Solution> {
+        // all false or all true satisfies instances where every clause has a negative or a
+        // positive literal respectively
+        [false, true].into_iter().find_map(|value| {
+            let solution = Solution {
+                variables: vec![value; self.difficulty.num_variables],
+            };
+            self.verify_solution(&solution).ok().map(|_| solution)
+        })
+    }
+
+    fn instances_equal(a: &Self, b: &Self) -> bool {
+        // a formula is a set of clauses, each a set of literals
+        let normalized = |c: &Self| {
+            let mut clauses: Vec<Vec<i32>> = c
+                .clauses
+                .iter()
+                .map(|clause| {
+                    let mut clause = clause.clone();
+                    clause.sort_unstable();
+                    clause
+                })
+                .collect();
+            clauses.sort_unstable();
+            clauses
+        };
+        a.difficulty.to_arr() == b.difficulty.to_arr() && normalized(a) == normalized(b)
+    }
+
+    fn max_solution_len(&self) -> usize {
+        self.difficulty.num_variables
+    }
+
+    fn search_space_log2(&self) -> f64 {
+        // every assignment of the variables
+        self.difficulty.num_variables as f64
+    }
+}
+
+// optional interface for solvers that keep state between calls, e.g. learnt clauses. literals use
+// the same signed encoding as Challenge::clauses
+pub trait IncrementalSolver {
+    fn add_clause(&mut self, clause: &[i32]);
+    // solves the clauses added so far with every assumption literal forced true. returns the
+    // variable assignment, or None if unsatisfiable under the assumptions
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<Option<Vec<bool>>>;
+}
+
+fn is_satisfied(clause: &[i32], variables: &[bool]) -> bool {
+    clause.iter().any(|&literal| {
+        let var_idx = literal.unsigned_abs() as usize - 1;
+        let var_value = variables[var_idx];
+        (literal > 0 && var_value) || (literal < 0 && !var_value)
+    })
+}
+
+impl Challenge {
+    fn check_num_variables(&self, solution: &Solution) -> Result<()> {
+        self.check_solution_len(solution)?;
         if solution.variables.len() != self.difficulty.num_variables {
             return Err(anyhow!(
                 "Invalid number of variables. Expected: {}, Actual: {}",
@@ -125,18 +352,48 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
                 solution.variables.len()
             ));
         }
+        Ok(())
+    }
 
-        if let Some((idx, _)) = self.clauses.iter().enumerate().find(|(_, clause)| {
-            !clause.iter().any(|&literal| {
-                let var_idx = literal.abs() as usize - 1;
-                let var_value = solution.variables[var_idx];
-                (literal > 0 && var_value) || (literal < 0 && !var_value)
-            })
-        }) {
-            Err(anyhow!("Clause '{}' not satisfied", idx))
-        } else {
-            Ok(())
+    // loads the instance into an incremental solver and solves it without assumptions. one-shot
+    // algorithms keep implementing solve_challenge directly
+    pub fn solve_incremental(
+        &self,
+        solver: &mut impl IncrementalSolver,
+    ) -> Result<Option<Solution>> {
+        for clause in self.clauses.iter() {
+            solver.add_clause(clause);
+        }
+        Ok(solver
+            .solve_with_assumptions(&[])?
+            .map(|variables| Solution { variables }))
+    }
+
+    // the instance in DIMACS CNF format, for running external SAT solvers on it
+    pub fn to_standard_format(&self) -> String {
+        let mut buffer = Vec::new();
+        // writing to a Vec never fails and DIMACS is ascii
+        self.write_standard_format(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    // streams the DIMACS CNF format to writer line by line, so large instances are never held in
+    // memory as a whole. wrap unbuffered sinks like files in a BufWriter
+    pub fn write_standard_format(&self, mut writer: impl Write) -> Result<()> {
+        writeln!(
+            writer,
+            "p cnf {} {}",
+            self.difficulty.num_variables,
+            self.clauses.len()
+        )?;
+        for clause in self.clauses.iter() {
+            for literal in clause.iter() {
+                write!(writer, "{} ", literal)?;
+            }
+            writeln!(writer, "0")?;
         }
+        writer.flush()?;
+        Ok(())
     }
 }
 
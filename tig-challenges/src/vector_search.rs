@@ -1,4 +1,4 @@
-use crate::{ChallengeTrait, DifficultyTrait, RngArray, SolutionTrait};
+use crate::{sample_indexes, ChallengeTrait, DifficultyTrait, RngArray, SolutionTrait};
 use anyhow::{anyhow, Ok, Result};
 use rand::distributions::{Distribution, Uniform};
 use serde::{Deserialize, Serialize};
@@ -35,7 +35,11 @@ pub struct Solution {
     pub indexes: Vec<usize>,
 }
 
-impl SolutionTrait for Solution {}
+impl SolutionTrait for Solution {
+    fn solution_len(&self) -> usize {
+        self.indexes.len()
+    }
+}
 
 impl TryFrom<Map<String, Value>> for Solution {
     type Error = serde_json::Error;
@@ -54,6 +58,14 @@ pub struct Challenge {
     pub max_distance: f32,
 }
 
+// real world vectors to generate instances from instead of uniform random ones. every instance at a
+// nonce queries a random subset of the vectors against the rest. max_distance is still calibrated
+// on uniform random vectors, so pick better_than_baseline to suit the dataset
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Dataset {
+    pub vectors: Vec<Vec<f32>>,
+}
+
 pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
     a.iter()
         .zip(b)
@@ -62,11 +74,19 @@ pub fn euclidean_distance(a: &[f32], b: &[f32]) -> f32 {
         .sqrt()
 }
 
+// largest num_queries for which optimum runs an exhaustive search over the database
+const OPTIMUM_MAX_NUM_QUERIES: usize = 10;
+
 // TIG dev bounty available for a GPU optimisation for instance generation!
 #[cfg(feature = "cuda")]
 pub const KERNEL: Option<CudaKernel> = None;
 
 impl ChallengeTrait<Solution, Difficulty, 2> for Challenge {
+    // more queries and a tighter distance target are both harder
+    const HARDER_WHEN_HIGHER: [bool; 2] = [true, true];
+    // max_distance reaches 0 at 6000 better_than_baseline
+    const DIFFICULTY_BOUNDS: [(i32, i32); 2] = [(1, i32::MAX), (0, 5999)];
+
     #[cfg(feature = "cuda")]
     fn cuda_generate_instance(
         seeds: [u64; 8],
@@ -87,18 +107,252 @@ impl ChallengeTrait<Solution, Difficulty, 2> for Challenge {
         let query_vectors = (0..difficulty.num_queries)
             .map(|_| (0..250).map(|_| uniform.sample(rngs.get_mut())).collect())
             .collect();
-        let max_distance = 6.0 - (difficulty.better_than_baseline as f32) / 1000.0;
+        let max_distance = calc_max_distance(difficulty.better_than_baseline);
 
-        Ok(Self {
+        let challenge = Self {
             seeds,
             difficulty: difficulty.clone(),
             vector_database: search_vectors,
             query_vectors,
             max_distance,
-        })
+        };
+        challenge.check_finite()?;
+        Ok(challenge)
     }
 
     fn verify_solution(&self, solution: &Solution) -> Result<()> {
+        self.verify_solution_with_max_distance(solution, self.max_distance, euclidean_distance)
+    }
+
+    fn qualifier(&self, solution: &Solution) -> Result<f64> {
+        self.verify_solution(solution)?;
+        let avg_dist = self
+            .query_vectors
+            .iter()
+            .zip(solution.indexes.iter())
+            .map(|(query, &index)| euclidean_distance(query, &self.vector_database[index]))
+            .sum::<f32>()
+            / solution.indexes.len() as f32;
+        Ok(self.max_distance as f64 / (avg_dist as f64).max(f64::EPSILON))
+    }
+
+    fn verify_solution_at_difficulty(
+        &self,
+        solution: &Solution,
+        difficulty: &Difficulty,
+    ) -> Result<()> {
+        if difficulty.num_queries != self.difficulty.num_queries {
+            return Err(anyhow!(
+                "Difficulty num_queries ({}) does not match instance num_queries ({})",
+                difficulty.num_queries,
+                self.difficulty.num_queries
+            ));
+        }
+        self.verify_solution_with_max_distance(
+            solution,
+            calc_max_distance(difficulty.better_than_baseline),
+            euclidean_distance,
+        )
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "vector_search: {} queries, {} database vectors of dimension {}, max_distance={}, seeds={:?}",
+            self.query_vectors.len(),
+            self.vector_database.len(),
+            self.vector_database.first().map_or(0, |v| v.len()),
+            self.max_distance,
+            self.seeds
+        )
+    }
+
+    fn explain(&self) -> String {
+        let num_queries = self.query_vectors.len();
+        [
+            format!("vector_search instance with seeds={:?}", self.seeds),
+            format!(
+                "{} query vectors and {} database vectors of dimension {} (better_than_baseline={})",
+                num_queries,
+                self.vector_database.len(),
+                self.vector_database.first().map_or(0, |v| v.len()),
+                self.difficulty.better_than_baseline
+            ),
+            format!(
+                "a solution picks, for each of the {} queries in order, the index of a database vector",
+                num_queries
+            ),
+            "objective: minimise the average euclidean distance between each query and its pick"
+                .to_string(),
+            format!(
+                "constraint: the average distance must be at most max_distance={}",
+                self.max_distance
+            ),
+        ]
+        .join("\n")
+    }
+
+    fn optimum(&self) -> Option<f64> {
+        if self.query_vectors.len() > OPTIMUM_MAX_NUM_QUERIES {
+            return None;
+        }
+        // exhaustive nearest neighbour search for every query
+        let total_dist: f32 = self
+            .query_vectors
+            .iter()
+            .map(|query| {
+                self.vector_database
+                    .iter()
+                    .map(|search| euclidean_distance(query, search))
+                    .fold(f32::INFINITY, f32::min)
+            })
+            .sum();
+        Some((total_dist / self.query_vectors.len() as f32) as f64)
+    }
+
+    fn trivial_solution(&self) -> Option<Solution> {
+        // every query is matched to the first database vector
+        let solution = Solution {
+            indexes: vec![0; self.difficulty.num_queries as usize],
+        };
+        self.verify_solution(&solution).ok().map(|_| solution)
+    }
+
+    fn instances_equal(a: &Self, b: &Self) -> bool {
+        // solutions refer to database vectors by index, so order matters. floats are compared by
+        // bits, so NaNs compare equal to themselves and -0.0 differs from 0.0
+        let bits = |vectors: &Vec<Vec<f32>>| -> Vec<Vec<u32>> {
+            vectors
+                .iter()
+                .map(|v| v.iter().map(|x| x.to_bits()).collect())
+                .collect()
+        };
+        a.difficulty.to_arr() == b.difficulty.to_arr()
+            && a.max_distance.to_bits() == b.max_distance.to_bits()
+            && bits(&a.query_vectors) == bits(&b.query_vectors)
+            && bits(&a.vector_database) == bits(&b.vector_database)
+    }
+
+    fn max_solution_len(&self) -> usize {
+        self.difficulty.num_queries as usize
+    }
+
+    fn search_space_log2(&self) -> f64 {
+        // any database vector for every query
+        self.difficulty.num_queries as f64 * (self.vector_database.len() as f64).log2()
+    }
+}
+
+impl Challenge {
+    // draws the query vectors from the dataset at random, leaving the rest as the database in
+    // dataset order. the instance is deterministic given the dataset and the seeds of the nonce
+    pub fn generate_instance_from_dataset(
+        seeds: [u64; 8],
+        difficulty: &Difficulty,
+        dataset: &Dataset,
+    ) -> Result<Self> {
+        let num_queries = difficulty.num_queries as usize;
+        if num_queries >= dataset.vectors.len() {
+            return Err(anyhow!(
+                "Dataset has {} vectors, cannot generate an instance with {} queries",
+                dataset.vectors.len(),
+                num_queries
+            ));
+        }
+        let dimension = dataset.vectors[0].len();
+        if let Some(i) = dataset.vectors.iter().position(|v| v.len() != dimension) {
+            return Err(anyhow!(
+                "Dataset vector {} has dimension {}, expected {}",
+                i,
+                dataset.vectors[i].len(),
+                dimension
+            ));
+        }
+        let mut rngs = RngArray::new(seeds);
+        let query_indexes = sample_indexes(&mut rngs, dataset.vectors.len(), num_queries);
+        let mut is_query = vec![false; dataset.vectors.len()];
+        for &i in query_indexes.iter() {
+            is_query[i] = true;
+        }
+        let challenge = Self {
+            seeds,
+            difficulty: *difficulty,
+            vector_database: (0..dataset.vectors.len())
+                .filter(|&i| !is_query[i])
+                .map(|i| dataset.vectors[i].clone())
+                .collect(),
+            query_vectors: query_indexes
+                .iter()
+                .map(|&i| dataset.vectors[i].clone())
+                .collect(),
+            max_distance: calc_max_distance(difficulty.better_than_baseline),
+        };
+        challenge.check_finite()?;
+        Ok(challenge)
+    }
+
+    // generates the instance with every coordinate snapped to the grid k / levels. grid points are
+    // exact in f32, so distances can be computed from integers. such instances must be verified with
+    // verify_solution_quantized using the same levels
+    pub fn generate_quantized_instance(
+        seeds: [u64; 8],
+        difficulty: &Difficulty,
+        levels: u32,
+    ) -> Result<Self> {
+        if levels == 0 || levels > MAX_QUANTIZATION_LEVELS {
+            return Err(anyhow!(
+                "Quantization levels must be between 1 and {}",
+                MAX_QUANTIZATION_LEVELS
+            ));
+        }
+        let mut challenge = Self::generate_instance(seeds, difficulty)?;
+        for v in challenge
+            .vector_database
+            .iter_mut()
+            .chain(challenge.query_vectors.iter_mut())
+        {
+            for x in v.iter_mut() {
+                *x = quantize(*x, levels) as f32 / levels as f32;
+            }
+        }
+        Ok(challenge)
+    }
+
+    // non-finite values make every distance comparison false, so an instance containing them could
+    // accept any solution
+    pub fn check_finite(&self) -> Result<()> {
+        if !self.max_distance.is_finite() {
+            return Err(anyhow!("Non-finite max distance '{}'", self.max_distance));
+        }
+        for (name, vectors) in [
+            ("query vector", &self.query_vectors),
+            ("database vector", &self.vector_database),
+        ] {
+            if let Some(i) = vectors
+                .iter()
+                .position(|v| v.iter().any(|x| !x.is_finite()))
+            {
+                return Err(anyhow!("Non-finite value in {} {}", name, i));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn verify_solution_quantized(&self, solution: &Solution, levels: u32) -> Result<()> {
+        self.verify_solution_with_max_distance(solution, self.max_distance, |a, b| {
+            quantized_distance(a, b, levels)
+        })
+    }
+
+    fn verify_solution_with_max_distance(
+        &self,
+        solution: &Solution,
+        max_distance: f32,
+        distance: impl Fn(&[f32], &[f32]) -> f32,
+    ) -> Result<()> {
+        if !max_distance.is_finite() {
+            return Err(anyhow!("Non-finite max distance '{}'", max_distance));
+        }
+        self.check_solution_len(solution)?;
         if solution.indexes.len() != self.difficulty.num_queries as usize {
             return Err(anyhow!(
                 "Invalid number of indexes. Expected: {}, Actual: {}",
@@ -108,7 +362,12 @@ impl ChallengeTrait<Solution, Difficulty, 2> for Challenge {
         }
 
         let mut dists = Vec::new();
-        for (query, &search_index) in self.query_vectors.iter().zip(solution.indexes.iter()) {
+        for (i, (query, &search_index)) in self
+            .query_vectors
+            .iter()
+            .zip(solution.indexes.iter())
+            .enumerate()
+        {
             if search_index >= self.vector_database.len() {
                 return Err(anyhow!(
                     "Invalid index. Expected: less than {}, Actual: {}",
@@ -117,16 +376,51 @@ impl ChallengeTrait<Solution, Difficulty, 2> for Challenge {
                 ));
             }
             let search = &self.vector_database[search_index];
-            dists.push(euclidean_distance(query, search));
+            let dist = distance(query, search);
+            // checked per pair, since a NaN would otherwise slip through the average comparison
+            if !dist.is_finite() {
+                return Err(anyhow!(
+                    "Non-finite distance '{}' between query vector {} and database vector {}",
+                    dist,
+                    i,
+                    search_index
+                ));
+            }
+            dists.push(dist);
         }
         let avg_dist = dists.iter().sum::<f32>() / dists.len() as f32;
-        if avg_dist > self.max_distance {
+        if avg_dist > max_distance {
             return Err(anyhow!(
                 "Average query vector distance is '{}'. Max dist: '{}'",
                 avg_dist,
-                self.max_distance
+                max_distance
             ));
         }
         Ok(())
     }
 }
+
+// keeps k / levels exactly representable in f32 for coordinates in [0, 1]
+pub const MAX_QUANTIZATION_LEVELS: u32 = 1 << 24;
+
+pub fn quantize(x: f32, levels: u32) -> i64 {
+    (x as f64 * levels as f64).round() as i64
+}
+
+// euclidean distance computed from integer grid coordinates, so the result only depends on the
+// quantized inputs and not on the order of floating point operations
+pub fn quantized_distance(a: &[f32], b: &[f32], levels: u32) -> f32 {
+    let squared: i64 = a
+        .iter()
+        .zip(b)
+        .map(|(&x1, &x2)| {
+            let d = quantize(x1, levels) - quantize(x2, levels);
+            d * d
+        })
+        .sum();
+    ((squared as f64).sqrt() / levels as f64) as f32
+}
+
+pub fn calc_max_distance(better_than_baseline: u32) -> f32 {
+    6.0 - (better_than_baseline as f32) / 1000.0
+}
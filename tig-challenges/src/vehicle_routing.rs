@@ -2,10 +2,11 @@ use anyhow::{anyhow, Result};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 use serde_json::{from_value, Map, Value};
+use std::io::Write;
 
 #[cfg(feature = "cuda")]
 use crate::CudaKernel;
-use crate::RngArray;
+use crate::{sample_indexes, ChallengeTrait, DifficultyTrait, RngArray};
 #[cfg(feature = "cuda")]
 use cudarc::driver::*;
 #[cfg(feature = "cuda")]
@@ -35,7 +36,11 @@ pub struct Solution {
     pub routes: Vec<Vec<usize>>,
 }
 
-impl crate::SolutionTrait for Solution {}
+impl crate::SolutionTrait for Solution {
+    fn solution_len(&self) -> usize {
+        self.routes.len()
+    }
+}
 
 impl TryFrom<Map<String, Value>> for Solution {
     type Error = serde_json::Error;
@@ -55,11 +60,69 @@ pub struct Challenge {
     pub max_capacity: i32,
 }
 
+// real world nodes to generate instances from instead of uniform random ones. node 0 is the depot,
+// and every instance at a nonce visits a random subset of the other nodes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Dataset {
+    pub positions: Vec<(f64, f64)>,
+    pub demands: Vec<i32>,
+    pub max_capacity: i32,
+}
+
+impl Dataset {
+    // the baseline routes can only be built if every customer fits in a vehicle
+    fn check(&self, num_nodes: usize) -> Result<()> {
+        if self.max_capacity <= 0 {
+            return Err(anyhow!("Max capacity must be positive"));
+        }
+        if self.positions.len() != self.demands.len() {
+            return Err(anyhow!(
+                "Dataset has {} positions but {} demands",
+                self.positions.len(),
+                self.demands.len()
+            ));
+        }
+        if num_nodes < 2 || num_nodes > self.positions.len() {
+            return Err(anyhow!(
+                "Dataset has {} nodes, cannot generate an instance with {} nodes",
+                self.positions.len(),
+                num_nodes
+            ));
+        }
+        if let Some(i) = self
+            .positions
+            .iter()
+            .position(|p| !p.0.is_finite() || !p.1.is_finite())
+        {
+            return Err(anyhow!("Non-finite position of node {}", i));
+        }
+        if let Some(i) =
+            (1..self.demands.len()).find(|&i| !(0..=self.max_capacity).contains(&self.demands[i]))
+        {
+            return Err(anyhow!(
+                "Demand {} of node {} must be between 0 and max capacity {}",
+                self.demands[i],
+                i,
+                self.max_capacity
+            ));
+        }
+        Ok(())
+    }
+}
+
+// largest number of non-depot nodes for which optimum runs the exact subset dynamic program
+const OPTIMUM_MAX_NUM_CUSTOMERS: usize = 12;
+
 // TIG dev bounty available for a GPU optimisation for instance generation!
 #[cfg(feature = "cuda")]
 pub const KERNEL: Option<CudaKernel> = None;
 
 impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
+    // more nodes and a shorter distance target are both harder
+    const HARDER_WHEN_HIGHER: [bool; 2] = [true, true];
+    // a depot and a customer. at 1000 better_than_baseline the max total distance is 0
+    const DIFFICULTY_BOUNDS: [(i32, i32); 2] = [(2, i32::MAX), (0, 999)];
+
     #[cfg(feature = "cuda")]
     fn cuda_generate_instance(
         seeds: [u64; 8],
@@ -75,7 +138,6 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
         let mut rngs = RngArray::new(seeds);
 
         let num_nodes = difficulty.num_nodes;
-        let max_capacity = 100;
 
         let mut node_positions: Vec<(f64, f64)> = (0..num_nodes)
             .map(|_| {
@@ -92,6 +154,302 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             .collect();
         demands[0] = 0; // Depot demand is 0
 
+        Self::from_nodes(seeds, difficulty, node_positions, demands, 100)
+    }
+
+    fn verify_solution(&self, solution: &Solution) -> Result<()> {
+        self.verify_solution_with_max_total_distance(solution, self.max_total_distance)
+    }
+
+    fn count_violations(&self, solution: &Solution) -> usize {
+        // every malformed or over capacity route, every repeated or unknown node, every customer
+        // not visited, and the total distance constraint
+        let num_nodes = self.difficulty.num_nodes;
+        let mut num_violations = 0;
+        let mut visited = vec![false; num_nodes];
+        visited[0] = true;
+        let mut total_distance = 0;
+        for route in solution.routes.iter() {
+            if route.len() <= 2 || route[0] != 0 || route[route.len() - 1] != 0 {
+                num_violations += 1;
+                continue;
+            }
+            let mut demand = 0;
+            let mut current_node = 0;
+            for &node in &route[1..route.len() - 1] {
+                if node >= num_nodes || visited[node] {
+                    num_violations += 1;
+                    continue;
+                }
+                visited[node] = true;
+                demand += self.demands[node];
+                total_distance += self.distance_matrix[current_node][node];
+                current_node = node;
+            }
+            total_distance += self.distance_matrix[current_node][0];
+            if demand > self.max_capacity {
+                num_violations += 1;
+            }
+        }
+        num_violations += visited.iter().filter(|&&v| !v).count();
+        if total_distance > self.max_total_distance {
+            num_violations += 1;
+        }
+        num_violations
+    }
+
+    fn qualifier(&self, solution: &Solution) -> Result<f64> {
+        self.verify_solution(solution)?;
+        let total_distance = calc_routes_total_distance(
+            self.difficulty.num_nodes,
+            self.max_capacity,
+            &self.demands,
+            &self.distance_matrix,
+            &solution.routes,
+        )?;
+        Ok(self.max_total_distance as f64 / total_distance.max(1) as f64)
+    }
+
+    // the order of the routes doesn't matter, only which vehicle visits which nodes in what order
+    fn essential_bytes(&self, solution: &Solution) -> Vec<u8> {
+        let mut routes = solution.routes.clone();
+        routes.sort_unstable();
+        serde_json::to_vec(&routes).expect("Failed to serialize routes")
+    }
+
+    // the capacity the routes' vehicles leave unused, over all of them. unknown nodes are ignored
+    fn feasibility_margin(&self, solution: &Solution) -> f64 {
+        if solution.routes.is_empty() {
+            return 0.0;
+        }
+        let total_demand = solution
+            .routes
+            .iter()
+            .flatten()
+            .filter(|&&node| node < self.demands.len())
+            .map(|&node| self.demands[node] as f64)
+            .sum::<f64>();
+        let total_capacity = solution.routes.len() as f64 * self.max_capacity.max(1) as f64;
+        1.0 - total_demand / total_capacity
+    }
+
+    fn verify_solution_at_difficulty(
+        &self,
+        solution: &Solution,
+        difficulty: &Difficulty,
+    ) -> Result<()> {
+        if difficulty.num_nodes != self.difficulty.num_nodes {
+            return Err(anyhow!(
+                "Difficulty num_nodes ({}) does not match instance num_nodes ({})",
+                difficulty.num_nodes,
+                self.difficulty.num_nodes
+            ));
+        }
+        let baseline_routes = calc_baseline_routes(
+            self.difficulty.num_nodes,
+            self.max_capacity,
+            &self.demands,
+            &self.distance_matrix,
+        )?;
+        let baseline_routes_total_distance = calc_routes_total_distance(
+            self.difficulty.num_nodes,
+            self.max_capacity,
+            &self.demands,
+            &self.distance_matrix,
+            &baseline_routes,
+        )?;
+        self.verify_solution_with_max_total_distance(
+            solution,
+            calc_max_total_distance(
+                baseline_routes_total_distance,
+                difficulty.better_than_baseline,
+            ),
+        )
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "vehicle_routing: {} nodes, max_capacity={}, max_total_distance={}, seeds={:?}",
+            self.difficulty.num_nodes, self.max_capacity, self.max_total_distance, self.seeds
+        )
+    }
+
+    fn explain(&self) -> String {
+        let num_nodes = self.difficulty.num_nodes;
+        [
+            format!(
+                "vehicle_routing (capacitated vehicle routing) instance with seeds={:?}",
+                self.seeds
+            ),
+            format!(
+                "{} nodes: node 0 is the depot, nodes 1..{} are customers with demands (better_than_baseline={})",
+                num_nodes, num_nodes, self.difficulty.better_than_baseline
+            ),
+            format!(
+                "total demand is {}, and distances between nodes are given by a {}x{} matrix",
+                self.demands.iter().sum::<i32>(),
+                num_nodes,
+                num_nodes
+            ),
+            "a solution is a list of routes, each starting and ending at the depot".to_string(),
+            "constraint: every customer is visited by exactly one route".to_string(),
+            format!(
+                "constraint: each route's customers demand at most max_capacity={}",
+                self.max_capacity
+            ),
+            format!(
+                "objective: minimise the total distance of all routes, which must be at most max_total_distance={}",
+                self.max_total_distance
+            ),
+        ]
+        .join("\n")
+    }
+
+    fn optimum(&self) -> Option<f64> {
+        let num_customers = self.difficulty.num_nodes.saturating_sub(1);
+        if num_customers > OPTIMUM_MAX_NUM_CUSTOMERS {
+            return None;
+        }
+        let num_subsets = 1usize << num_customers;
+        let d = &self.distance_matrix;
+
+        // route_distances[s] is the shortest single route serving exactly the customers in s.
+        // customer i is node i + 1. computed with held-karp over open paths from the depot
+        let mut paths = vec![vec![i32::MAX; num_customers]; num_subsets];
+        for i in 0..num_customers {
+            paths[1 << i][i] = d[0][i + 1];
+        }
+        let mut route_distances = vec![i32::MAX; num_subsets];
+        route_distances[0] = 0;
+        for s in 1..num_subsets {
+            let demand: i32 = (0..num_customers)
+                .filter(|i| s & (1 << i) != 0)
+                .map(|i| self.demands[i + 1])
+                .sum();
+            if demand > self.max_capacity {
+                continue;
+            }
+            for last in 0..num_customers {
+                let dist = paths[s][last];
+                if dist == i32::MAX {
+                    continue;
+                }
+                route_distances[s] = route_distances[s].min(dist + d[last + 1][0]);
+                for next in (0..num_customers).filter(|next| s & (1 << next) == 0) {
+                    let t = s | (1 << next);
+                    paths[t][next] = paths[t][next].min(dist + d[last + 1][next + 1]);
+                }
+            }
+        }
+
+        // best[s] is the shortest set of routes serving exactly the customers in s. each
+        // partition is enumerated once by fixing the route containing the lowest customer
+        let mut best = vec![i32::MAX; num_subsets];
+        best[0] = 0;
+        for s in 1..num_subsets {
+            let lowest = s & s.wrapping_neg();
+            let rest = s ^ lowest;
+            let mut sub = rest;
+            loop {
+                let route = sub | lowest;
+                if route_distances[route] != i32::MAX && best[s ^ route] != i32::MAX {
+                    best[s] = best[s].min(route_distances[route] + best[s ^ route]);
+                }
+                if sub == 0 {
+                    break;
+                }
+                sub = (sub - 1) & rest;
+            }
+        }
+        match best[num_subsets - 1] {
+            i32::MAX => None,
+            dist => Some(dist as f64),
+        }
+    }
+
+    // every customer must be visited, but customer 1 demands more than a vehicle carries
+    fn infeasible_instance(seeds: [u64; 8], difficulty: &Difficulty) -> Option<Self> {
+        if difficulty.num_nodes < 2 {
+            return None;
+        }
+        let mut challenge = Self::generate_instance(seeds, difficulty).ok()?;
+        challenge.demands[1] = challenge.max_capacity + 1;
+        Some(challenge)
+    }
+
+    fn trivial_solution(&self) -> Option<Solution> {
+        // every customer gets its own route
+        let solution = Solution {
+            routes: (1..self.difficulty.num_nodes)
+                .map(|i| vec![0, i, 0])
+                .collect(),
+        };
+        self.verify_solution(&solution).ok().map(|_| solution)
+    }
+
+    fn instances_equal(a: &Self, b: &Self) -> bool {
+        // routes refer to nodes by index, so node order matters
+        a.difficulty.to_arr() == b.difficulty.to_arr()
+            && a.demands == b.demands
+            && a.distance_matrix == b.distance_matrix
+            && a.max_total_distance == b.max_total_distance
+            && a.max_capacity == b.max_capacity
+    }
+
+    fn max_solution_len(&self) -> usize {
+        // every route visits at least one customer, and no customer twice
+        self.difficulty.num_nodes - 1
+    }
+
+    fn search_space_log2(&self) -> f64 {
+        // every ordering of the customers, split into routes at any of the gaps between them
+        let num_customers = self.difficulty.num_nodes.saturating_sub(1);
+        let log2_orderings: f64 = (2..=num_customers).map(|k| (k as f64).log2()).sum();
+        log2_orderings + num_customers.saturating_sub(1) as f64
+    }
+}
+
+impl Challenge {
+    // generates the instance from the dataset's depot and num_nodes - 1 of its other nodes, drawn
+    // at random. the instance is deterministic given the dataset and the seeds of the nonce
+    pub fn generate_instance_from_dataset(
+        seeds: [u64; 8],
+        difficulty: &Difficulty,
+        dataset: &Dataset,
+    ) -> Result<Self> {
+        dataset.check(difficulty.num_nodes)?;
+        let mut rngs = RngArray::new(seeds);
+        let nodes: Vec<usize> = std::iter::once(0)
+            .chain(
+                sample_indexes(
+                    &mut rngs,
+                    dataset.positions.len() - 1,
+                    difficulty.num_nodes - 1,
+                )
+                .into_iter()
+                .map(|i| i + 1),
+            )
+            .collect();
+        let node_positions = nodes.iter().map(|&i| dataset.positions[i]).collect();
+        let mut demands: Vec<i32> = nodes.iter().map(|&i| dataset.demands[i]).collect();
+        demands[0] = 0; // Depot demand is 0
+        Self::from_nodes(
+            seeds,
+            difficulty,
+            node_positions,
+            demands,
+            dataset.max_capacity,
+        )
+    }
+
+    fn from_nodes(
+        seeds: [u64; 8],
+        difficulty: &Difficulty,
+        node_positions: Vec<(f64, f64)>,
+        demands: Vec<i32>,
+        max_capacity: i32,
+    ) -> Result<Self> {
+        let num_nodes = difficulty.num_nodes;
         let distance_matrix: Vec<Vec<i32>> = node_positions
             .iter()
             .map(|&from| {
@@ -115,13 +473,14 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             &distance_matrix,
             &baseline_routes,
         )?;
-        let max_total_distance = (baseline_routes_total_distance
-            * (1000 - difficulty.better_than_baseline as i32)
-            / 1000) as i32;
+        let max_total_distance = calc_max_total_distance(
+            baseline_routes_total_distance,
+            difficulty.better_than_baseline,
+        );
 
         Ok(Challenge {
             seeds,
-            difficulty: difficulty.clone(),
+            difficulty: *difficulty,
             demands,
             distance_matrix,
             max_total_distance,
@@ -129,7 +488,53 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
         })
     }
 
-    fn verify_solution(&self, solution: &Solution) -> Result<()> {
+    // the instance in TSPLIB CVRP format, for running external VRP solvers on it
+    pub fn to_standard_format(&self) -> String {
+        let mut buffer = Vec::new();
+        // writing to a Vec never fails and TSPLIB is ascii
+        self.write_standard_format(&mut buffer).unwrap();
+        String::from_utf8(buffer).unwrap()
+    }
+
+    // streams the TSPLIB CVRP format to writer row by row, so large instances are never held in
+    // memory as a whole. wrap unbuffered sinks like files in a BufWriter. TSPLIB nodes are numbered
+    // from 1, so node i is written as i + 1 and the depot as 1
+    pub fn write_standard_format(&self, mut writer: impl Write) -> Result<()> {
+        let num_nodes = self.difficulty.num_nodes;
+        writeln!(writer, "NAME : tig_vehicle_routing")?;
+        writeln!(writer, "TYPE : CVRP")?;
+        writeln!(writer, "DIMENSION : {}", num_nodes)?;
+        writeln!(writer, "EDGE_WEIGHT_TYPE : EXPLICIT")?;
+        writeln!(writer, "EDGE_WEIGHT_FORMAT : FULL_MATRIX")?;
+        writeln!(writer, "CAPACITY : {}", self.max_capacity)?;
+        writeln!(writer, "EDGE_WEIGHT_SECTION")?;
+        for row in self.distance_matrix.iter() {
+            for (j, distance) in row.iter().enumerate() {
+                if j > 0 {
+                    write!(writer, " ")?;
+                }
+                write!(writer, "{}", distance)?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "DEMAND_SECTION")?;
+        for (i, demand) in self.demands.iter().enumerate() {
+            writeln!(writer, "{} {}", i + 1, demand)?;
+        }
+        writeln!(writer, "DEPOT_SECTION")?;
+        writeln!(writer, "1")?;
+        writeln!(writer, "-1")?;
+        writeln!(writer, "EOF")?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn verify_solution_with_max_total_distance(
+        &self,
+        solution: &Solution,
+        max_total_distance: i32,
+    ) -> Result<()> {
+        self.check_solution_len(solution)?;
         let total_distance = calc_routes_total_distance(
             self.difficulty.num_nodes,
             self.max_capacity,
@@ -137,18 +542,25 @@ impl crate::ChallengeTrait<Solution, Difficulty, 2> for Challenge {
             &self.distance_matrix,
             &solution.routes,
         )?;
-        if total_distance <= self.max_total_distance {
+        if total_distance <= max_total_distance {
             Ok(())
         } else {
             Err(anyhow!(
                 "Total distance ({}) exceeds max total distance ({})",
                 total_distance,
-                self.max_total_distance
+                max_total_distance
             ))
         }
     }
 }
 
+pub fn calc_max_total_distance(
+    baseline_routes_total_distance: i32,
+    better_than_baseline: u32,
+) -> i32 {
+    baseline_routes_total_distance * (1000 - better_than_baseline as i32) / 1000
+}
+
 pub fn calc_baseline_routes(
     num_nodes: usize,
     max_capacity: i32,
@@ -0,0 +1,159 @@
+use tig_challenges::*;
+
+#[test]
+fn test_satisfiability() {
+    // x1, x2, x3, (x1 or x2), (x2 or x3)
+    let challenge = satisfiability::Challenge {
+        seeds: [0; 8],
+        difficulty: satisfiability::Difficulty {
+            num_variables: 3,
+            clauses_to_variables_percent: 167,
+        },
+        clauses: vec![
+            vec![1, 1, 1],
+            vec![2, 2, 2],
+            vec![3, 3, 3],
+            vec![1, 2, 2],
+            vec![2, 3, 3],
+        ],
+    };
+    // setting one more variable at a time satisfies more clauses
+    let counts: Vec<usize> = [
+        [false, false, false],
+        [true, false, false],
+        [true, true, false],
+        [true, true, true],
+    ]
+    .iter()
+    .map(|variables| {
+        challenge.count_violations(&satisfiability::Solution {
+            variables: variables.to_vec(),
+        })
+    })
+    .collect();
+    assert_eq!(counts, vec![5, 3, 1, 0]);
+    let solution = satisfiability::Solution {
+        variables: vec![true; 3],
+    };
+    assert!(challenge.verify_solution(&solution).is_ok());
+    // the wrong number of variables violates every clause
+    let solution = satisfiability::Solution {
+        variables: vec![true; 2],
+    };
+    assert_eq!(challenge.count_violations(&solution), 5);
+
+    // 0 exactly when the solution verifies
+    let challenge = satisfiability::Challenge::generate_instance(
+        [0; 8],
+        &satisfiability::Difficulty {
+            num_variables: 10,
+            clauses_to_variables_percent: 200,
+        },
+    )
+    .unwrap();
+    for mask in 0u32..(1 << 10) {
+        let solution = satisfiability::Solution {
+            variables: (0..10).map(|i| mask & (1 << i) != 0).collect(),
+        };
+        assert_eq!(
+            challenge.count_violations(&solution) == 0,
+            challenge.verify_solution(&solution).is_ok()
+        );
+    }
+}
+
+#[test]
+fn test_knapsack() {
+    let challenge = knapsack::Challenge {
+        seeds: [0; 8],
+        difficulty: knapsack::Difficulty {
+            num_items: 4,
+            better_than_baseline: 0,
+        },
+        weights: vec![5, 4, 3, 2],
+        values: vec![10, 8, 6, 1],
+        max_weight: 9,
+        min_value: 16,
+    };
+    let counts: Vec<usize> = [
+        // duplicate, out of bounds and too little value
+        vec![0, 0, 7],
+        // out of bounds and too little value
+        vec![0, 7],
+        // too heavy
+        vec![0, 1, 2],
+        vec![0, 1],
+    ]
+    .into_iter()
+    .map(|items| challenge.count_violations(&knapsack::Solution { items }))
+    .collect();
+    assert_eq!(counts, vec![3, 2, 1, 0]);
+    assert!(challenge
+        .verify_solution(&knapsack::Solution { items: vec![0, 1] })
+        .is_ok());
+}
+
+#[test]
+fn test_vehicle_routing() {
+    let difficulty = vehicle_routing::Difficulty {
+        num_nodes: 20,
+        better_than_baseline: 0,
+    };
+    let challenge = vehicle_routing::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    let baseline_routes = vehicle_routing::calc_baseline_routes(
+        difficulty.num_nodes,
+        challenge.max_capacity,
+        &challenge.demands,
+        &challenge.distance_matrix,
+    )
+    .unwrap();
+    assert!(baseline_routes.len() > 1);
+
+    // adding the baseline's routes one at a time visits more customers
+    let mut routes = Vec::new();
+    let mut counts = vec![challenge.count_violations(&vehicle_routing::Solution {
+        routes: routes.clone(),
+    })];
+    for route in baseline_routes.iter() {
+        routes.push(route.clone());
+        counts.push(challenge.count_violations(&vehicle_routing::Solution {
+            routes: routes.clone(),
+        }));
+    }
+    assert_eq!(counts[0], difficulty.num_nodes - 1);
+    assert!(counts.windows(2).all(|x| x[0] > x[1]), "{:?}", counts);
+    assert_eq!(*counts.last().unwrap(), 0);
+    assert!(challenge
+        .verify_solution(&vehicle_routing::Solution {
+            routes: routes.clone()
+        })
+        .is_ok());
+
+    // a repeated customer and a route that doesn't return to the depot
+    routes.push(vec![0, routes[1][1], 0]);
+    routes.push(vec![0, 1]);
+    assert_eq!(
+        challenge.count_violations(&vehicle_routing::Solution { routes }),
+        2
+    );
+}
+
+#[test]
+fn test_vector_search() {
+    // only tells valid from invalid
+    let challenge = vector_search::Challenge {
+        seeds: [0; 8],
+        difficulty: vector_search::Difficulty {
+            num_queries: 1,
+            better_than_baseline: 0,
+        },
+        vector_database: vec![vec![0.0, 0.0], vec![1.0, 1.0]],
+        query_vectors: vec![vec![0.1, 0.0]],
+        max_distance: 0.5,
+    };
+    let counts: Vec<usize> = [vec![], vec![5], vec![1], vec![0]]
+        .into_iter()
+        .map(|indexes| challenge.count_violations(&vector_search::Solution { indexes }))
+        .collect();
+    assert_eq!(counts, vec![1, 1, 1, 0]);
+}
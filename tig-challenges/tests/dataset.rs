@@ -0,0 +1,129 @@
+use std::path::Path;
+use tig_challenges::*;
+
+fn dataset<D: serde::de::DeserializeOwned>(name: &str) -> D {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("tests/datasets/{}.json", name));
+    load_dataset(&path).unwrap()
+}
+
+fn seeds(nonce: u64) -> [u64; 8] {
+    [nonce; 8]
+}
+
+#[test]
+fn test_vehicle_routing_from_dataset() {
+    let dataset: vehicle_routing::Dataset = dataset("vehicle_routing");
+    let difficulty = vehicle_routing::Difficulty {
+        num_nodes: 6,
+        better_than_baseline: 0,
+    };
+    let generate = |nonce| {
+        vehicle_routing::Challenge::generate_instance_from_dataset(
+            seeds(nonce),
+            &difficulty,
+            &dataset,
+        )
+        .unwrap()
+    };
+    let a = generate(0);
+    assert!(vehicle_routing::Challenge::instances_equal(
+        &a,
+        &generate(0)
+    ));
+    assert!((1..10).any(|nonce| !vehicle_routing::Challenge::instances_equal(&a, &generate(nonce))));
+
+    // the depot and distinct customers of the dataset
+    assert_eq!(a.max_capacity, 60);
+    assert_eq!(a.demands[0], 0);
+    let mut nodes = Vec::new();
+    for i in 1..difficulty.num_nodes {
+        let node = (1..dataset.positions.len())
+            .find(|&j| {
+                let (dx, dy) = (
+                    dataset.positions[j].0 - dataset.positions[0].0,
+                    dataset.positions[j].1 - dataset.positions[0].1,
+                );
+                dataset.demands[j] == a.demands[i]
+                    && dx.hypot(dy).round() as i32 == a.distance_matrix[0][i]
+            })
+            .unwrap();
+        assert!(!nodes.contains(&node));
+        nodes.push(node);
+    }
+    let baseline_routes = vehicle_routing::calc_baseline_routes(
+        difficulty.num_nodes,
+        a.max_capacity,
+        &a.demands,
+        &a.distance_matrix,
+    )
+    .unwrap();
+    assert!(a
+        .verify_solution(&vehicle_routing::Solution {
+            routes: baseline_routes
+        })
+        .is_ok());
+}
+
+#[test]
+fn test_vehicle_routing_rejects_invalid_dataset() {
+    let mut dataset: vehicle_routing::Dataset = dataset("vehicle_routing");
+    let difficulty = |num_nodes| vehicle_routing::Difficulty {
+        num_nodes,
+        better_than_baseline: 0,
+    };
+    assert!(vehicle_routing::Challenge::generate_instance_from_dataset(
+        seeds(0),
+        &difficulty(13),
+        &dataset
+    )
+    .is_err());
+    // a customer that fits in no vehicle
+    dataset.demands[3] = 61;
+    assert!(vehicle_routing::Challenge::generate_instance_from_dataset(
+        seeds(0),
+        &difficulty(6),
+        &dataset
+    )
+    .is_err());
+}
+
+#[test]
+fn test_vector_search_from_dataset() {
+    let dataset: vector_search::Dataset = dataset("vector_search");
+    let difficulty = vector_search::Difficulty {
+        num_queries: 3,
+        better_than_baseline: 0,
+    };
+    let generate = |nonce| {
+        vector_search::Challenge::generate_instance_from_dataset(
+            seeds(nonce),
+            &difficulty,
+            &dataset,
+        )
+        .unwrap()
+    };
+    let a = generate(0);
+    assert!(vector_search::Challenge::instances_equal(&a, &generate(0)));
+    assert!((1..10).any(|nonce| !vector_search::Challenge::instances_equal(&a, &generate(nonce))));
+
+    // every dataset vector is either a query or in the database
+    assert_eq!(a.query_vectors.len(), 3);
+    assert_eq!(a.vector_database.len(), 7);
+    for v in dataset.vectors.iter() {
+        assert_eq!(
+            a.query_vectors.contains(v) as u32 + a.vector_database.contains(v) as u32,
+            1
+        );
+    }
+
+    let difficulty = vector_search::Difficulty {
+        num_queries: 10,
+        better_than_baseline: 0,
+    };
+    assert!(vector_search::Challenge::generate_instance_from_dataset(
+        seeds(0),
+        &difficulty,
+        &dataset
+    )
+    .is_err());
+}
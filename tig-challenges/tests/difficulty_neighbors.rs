@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+use tig_challenges::*;
+
+// every neighbor is within bounds and one step from difficulty in exactly one parameter, and each
+// in-bounds step is there once
+fn check_neighbors<T, U, C>(difficulty: [i32; 2])
+where
+    T: SolutionTrait,
+    U: DifficultyTrait<2>,
+    C: ChallengeTrait<T, U, 2>,
+{
+    let neighbors = C::difficulty_neighbors(&difficulty);
+    for neighbor in neighbors.iter() {
+        assert_eq!(neighbor.len(), 2);
+        for (i, &value) in neighbor.iter().enumerate() {
+            let (min, max) = C::DIFFICULTY_BOUNDS[i];
+            assert!(min <= value && value <= max, "{:?}", neighbor);
+        }
+        let steps: Vec<i32> = (0..2).map(|i| neighbor[i] - difficulty[i]).collect();
+        assert_eq!(
+            steps.iter().map(|x| x.abs()).sum::<i32>(),
+            1,
+            "{:?}",
+            neighbor
+        );
+        // one step harder or easier
+        let ordering = C::compare_difficulty(
+            &U::from_arr(&neighbor.as_slice().try_into().unwrap()),
+            &U::from_arr(&difficulty),
+        );
+        assert!(matches!(
+            ordering,
+            Some(Ordering::Greater) | Some(Ordering::Less)
+        ));
+    }
+    let num_in_bounds = (0..2)
+        .map(|i| {
+            let (min, max) = C::DIFFICULTY_BOUNDS[i];
+            [difficulty[i] - 1, difficulty[i] + 1]
+                .iter()
+                .filter(|&&x| min <= x && x <= max)
+                .count()
+        })
+        .sum::<usize>();
+    assert_eq!(neighbors.len(), num_in_bounds);
+}
+
+#[test]
+fn test_satisfiability_neighbors() {
+    type C = satisfiability::Challenge;
+    check_neighbors::<satisfiability::Solution, satisfiability::Difficulty, C>([50, 300]);
+    assert_eq!(
+        C::difficulty_neighbors(&[50, 300]),
+        vec![vec![51, 300], vec![49, 300], vec![50, 301], vec![50, 299]]
+    );
+    // at the fewest variables and clauses only harder steps remain
+    check_neighbors::<satisfiability::Solution, satisfiability::Difficulty, C>([1, 0]);
+    assert_eq!(
+        C::difficulty_neighbors(&[1, 0]),
+        vec![vec![2, 0], vec![1, 1]]
+    );
+    // wrong number of parameters
+    assert!(C::difficulty_neighbors(&[50]).is_empty());
+}
+
+#[test]
+fn test_vehicle_routing_neighbors() {
+    type C = vehicle_routing::Challenge;
+    check_neighbors::<vehicle_routing::Solution, vehicle_routing::Difficulty, C>([40, 250]);
+    check_neighbors::<vehicle_routing::Solution, vehicle_routing::Difficulty, C>([2, 999]);
+    assert_eq!(
+        C::difficulty_neighbors(&[2, 999]),
+        vec![vec![3, 999], vec![2, 998]]
+    );
+}
+
+#[test]
+fn test_knapsack_neighbors() {
+    check_neighbors::<knapsack::Solution, knapsack::Difficulty, knapsack::Challenge>([50, 10]);
+    check_neighbors::<knapsack::Solution, knapsack::Difficulty, knapsack::Challenge>([1, 0]);
+}
+
+#[test]
+fn test_vector_search_neighbors() {
+    type C = vector_search::Challenge;
+    check_neighbors::<vector_search::Solution, vector_search::Difficulty, C>([10, 460]);
+    check_neighbors::<vector_search::Solution, vector_search::Difficulty, C>([1, 5999]);
+    assert_eq!(C::difficulty_neighbors(&[1, 5999]).len(), 2);
+}
@@ -0,0 +1,84 @@
+use std::cmp::Ordering;
+use tig_challenges::*;
+
+// checks the partial order against each parameter's hardness direction: moving one parameter in its
+// harder direction gives a harder difficulty, and moving two parameters in opposite directions gives
+// incomparable difficulties
+fn check_partial_order<T, U, C>(base: [i32; 2])
+where
+    T: SolutionTrait,
+    U: DifficultyTrait<2>,
+    C: ChallengeTrait<T, U, 2>,
+{
+    let harder = |i: usize, steps: i32| {
+        let mut arr = base;
+        arr[i] += if C::HARDER_WHEN_HIGHER[i] {
+            steps
+        } else {
+            -steps
+        };
+        U::from_arr(&arr)
+    };
+    let base = U::from_arr(&base);
+    assert_eq!(C::compare_difficulty(&base, &base), Some(Ordering::Equal));
+    for i in 0..2 {
+        assert_eq!(
+            C::compare_difficulty(&harder(i, 1), &base),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(
+            C::compare_difficulty(&base, &harder(i, 1)),
+            Some(Ordering::Less)
+        );
+    }
+    let mut both = base.to_arr();
+    for (i, param) in both.iter_mut().enumerate() {
+        *param = harder(i, 1).to_arr()[i];
+    }
+    assert_eq!(
+        C::compare_difficulty(&U::from_arr(&both), &base),
+        Some(Ordering::Greater)
+    );
+    let mut mixed = harder(0, 1).to_arr();
+    mixed[1] = harder(1, -1).to_arr()[1];
+    assert_eq!(C::compare_difficulty(&U::from_arr(&mixed), &base), None);
+    assert_eq!(C::compare_difficulty(&base, &U::from_arr(&mixed)), None);
+}
+
+#[test]
+fn test_satisfiability_difficulty_order() {
+    assert_eq!(satisfiability::Challenge::HARDER_WHEN_HIGHER, [true, true]);
+    check_partial_order::<_, _, satisfiability::Challenge>([50, 300]);
+    // a higher clause ratio is harder even with the same number of variables
+    assert_eq!(
+        satisfiability::Challenge::compare_difficulty(
+            &satisfiability::Difficulty {
+                num_variables: 50,
+                clauses_to_variables_percent: 420,
+            },
+            &satisfiability::Difficulty {
+                num_variables: 50,
+                clauses_to_variables_percent: 300,
+            },
+        ),
+        Some(Ordering::Greater)
+    );
+}
+
+#[test]
+fn test_vehicle_routing_difficulty_order() {
+    assert_eq!(vehicle_routing::Challenge::HARDER_WHEN_HIGHER, [true, true]);
+    check_partial_order::<_, _, vehicle_routing::Challenge>([40, 250]);
+}
+
+#[test]
+fn test_knapsack_difficulty_order() {
+    assert_eq!(knapsack::Challenge::HARDER_WHEN_HIGHER, [true, true]);
+    check_partial_order::<_, _, knapsack::Challenge>([50, 10]);
+}
+
+#[test]
+fn test_vector_search_difficulty_order() {
+    assert_eq!(vector_search::Challenge::HARDER_WHEN_HIGHER, [true, true]);
+    check_partial_order::<_, _, vector_search::Challenge>([40, 250]);
+}
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+use tig_challenges::*;
+
+fn satisfiability_grid() -> Vec<satisfiability::Difficulty> {
+    let mut difficulties = Vec::new();
+    for num_variables in [0, 10, 50, 100, 200, 1000, 10000] {
+        for clauses_to_variables_percent in (0..=800).step_by(25) {
+            difficulties.push(satisfiability::Difficulty {
+                num_variables,
+                clauses_to_variables_percent,
+            });
+        }
+    }
+    difficulties
+}
+
+#[test]
+fn test_satisfiability_in_range() {
+    for difficulty in satisfiability_grid() {
+        let p = satisfiability::Challenge::expected_solve_probability(&difficulty).unwrap();
+        assert!((0.0..=1.0).contains(&p), "{:?}: {}", difficulty, p);
+    }
+    let p = |num_variables, clauses_to_variables_percent| {
+        satisfiability::Challenge::expected_solve_probability(&satisfiability::Difficulty {
+            num_variables,
+            clauses_to_variables_percent,
+        })
+        .unwrap()
+    };
+    // mostly satisfiable well below the threshold, mostly not well above it
+    assert!(p(100, 300) > 0.9);
+    assert!(p(100, 550) < 0.1);
+    assert!((p(100, 427) - 0.5).abs() < 0.05);
+}
+
+#[test]
+fn test_satisfiability_decreasing_in_difficulty() {
+    let difficulties = satisfiability_grid();
+    for a in difficulties.iter() {
+        for b in difficulties.iter() {
+            if satisfiability::Challenge::compare_difficulty(a, b) == Some(Ordering::Greater) {
+                let p_a = satisfiability::Challenge::expected_solve_probability(a).unwrap();
+                let p_b = satisfiability::Challenge::expected_solve_probability(b).unwrap();
+                assert!(p_a <= p_b, "{:?}: {} > {:?}: {}", a, p_a, b, p_b);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_undefined_without_estimate() {
+    assert!(
+        vehicle_routing::Challenge::expected_solve_probability(&vehicle_routing::Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+        })
+        .is_none()
+    );
+    assert!(
+        knapsack::Challenge::expected_solve_probability(&knapsack::Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        })
+        .is_none()
+    );
+    assert!(
+        vector_search::Challenge::expected_solve_probability(&vector_search::Difficulty {
+            num_queries: 10,
+            better_than_baseline: 350,
+        })
+        .is_none()
+    );
+}
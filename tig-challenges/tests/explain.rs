@@ -0,0 +1,79 @@
+use tig_challenges::*;
+
+#[test]
+fn test_satisfiability_explain() {
+    let challenge = satisfiability::Challenge::generate_instance(
+        [1; 8],
+        &satisfiability::Difficulty {
+            num_variables: 300,
+            clauses_to_variables_percent: 425,
+        },
+    )
+    .unwrap();
+    let explanation = challenge.explain();
+    assert!(explanation.lines().count() > 1);
+    assert!(explanation.contains("300 boolean variables and 1275 clauses"));
+    assert!(explanation.contains("clauses_to_variables_percent=425"));
+    assert!(explanation.contains("satisfy every clause"));
+    assert!(explanation.contains("seeds=[1, 1, 1, 1, 1, 1, 1, 1]"));
+}
+
+#[test]
+fn test_vehicle_routing_explain() {
+    let challenge = vehicle_routing::Challenge::generate_instance(
+        [2; 8],
+        &vehicle_routing::Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 100,
+        },
+    )
+    .unwrap();
+    let explanation = challenge.explain();
+    assert!(explanation.contains("40 nodes"));
+    assert!(explanation.contains("better_than_baseline=100"));
+    assert!(explanation.contains(&format!("max_capacity={}", challenge.max_capacity)));
+    assert!(explanation.contains(&format!(
+        "max_total_distance={}",
+        challenge.max_total_distance
+    )));
+    assert!(explanation.contains(&format!(
+        "total demand is {}",
+        challenge.demands.iter().sum::<i32>()
+    )));
+}
+
+#[test]
+fn test_knapsack_explain() {
+    let challenge = knapsack::Challenge::generate_instance(
+        [3; 8],
+        &knapsack::Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        },
+    )
+    .unwrap();
+    let explanation = challenge.explain();
+    assert!(explanation.contains("50 items"));
+    assert!(explanation.contains("better_than_baseline=10"));
+    assert!(explanation.contains(&format!("max_weight={}", challenge.max_weight)));
+    assert!(explanation.contains(&format!("min_value={}", challenge.min_value)));
+}
+
+#[test]
+fn test_vector_search_explain() {
+    // built by hand since generation always creates the full database
+    let challenge = vector_search::Challenge {
+        seeds: [4; 8],
+        difficulty: vector_search::Difficulty {
+            num_queries: 2,
+            better_than_baseline: 7,
+        },
+        vector_database: vec![vec![0.0; 5]; 3],
+        query_vectors: vec![vec![1.0; 5]; 2],
+        max_distance: 6.0,
+    };
+    let explanation = challenge.explain();
+    assert!(explanation.contains("2 query vectors and 3 database vectors of dimension 5"));
+    assert!(explanation.contains("better_than_baseline=7"));
+    assert!(explanation.contains("max_distance=6"));
+}
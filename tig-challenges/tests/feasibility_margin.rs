@@ -0,0 +1,82 @@
+use tig_challenges::*;
+
+#[test]
+fn test_knapsack_feasibility_margin() {
+    let challenge = knapsack::Challenge {
+        seeds: [0; 8],
+        difficulty: knapsack::Difficulty {
+            num_items: 4,
+            better_than_baseline: 0,
+        },
+        weights: vec![2, 3, 4, 5],
+        values: vec![3, 4, 5, 6],
+        max_weight: 8,
+        min_value: 7,
+    };
+    // items 0 and 2 weigh 6 of 8
+    let solution = knapsack::Solution { items: vec![0, 2] };
+    assert!(challenge.verify_solution(&solution).is_ok());
+    assert_eq!(challenge.feasibility_margin(&solution), 0.25);
+
+    // items 1 and 3 fill the knapsack exactly
+    let solution = knapsack::Solution { items: vec![1, 3] };
+    assert!(challenge.verify_solution(&solution).is_ok());
+    assert_eq!(challenge.feasibility_margin(&solution), 0.0);
+
+    // 2 + 3 + 5 = 10 is over capacity
+    let solution = knapsack::Solution {
+        items: vec![0, 1, 3],
+    };
+    assert!(challenge.verify_solution(&solution).is_err());
+    assert_eq!(challenge.feasibility_margin(&solution), -0.25);
+}
+
+#[test]
+fn test_vehicle_routing_feasibility_margin() {
+    let challenge = vehicle_routing::Challenge {
+        seeds: [0; 8],
+        difficulty: vehicle_routing::Difficulty {
+            num_nodes: 4,
+            better_than_baseline: 0,
+        },
+        demands: vec![0, 30, 40, 50],
+        distance_matrix: vec![
+            vec![0, 1, 1, 1],
+            vec![1, 0, 1, 1],
+            vec![1, 1, 0, 1],
+            vec![1, 1, 1, 0],
+        ],
+        max_total_distance: 100,
+        max_capacity: 100,
+    };
+    // 2 vehicles carry 70 and 50 of 200
+    let solution = vehicle_routing::Solution {
+        routes: vec![vec![0, 1, 2, 0], vec![0, 3, 0]],
+    };
+    assert!(challenge.verify_solution(&solution).is_ok());
+    assert!((challenge.feasibility_margin(&solution) - 0.4).abs() < 1e-12);
+
+    // one vehicle carries 120 of 100
+    let solution = vehicle_routing::Solution {
+        routes: vec![vec![0, 1, 2, 3, 0]],
+    };
+    assert!(challenge.verify_solution(&solution).is_err());
+    assert!((challenge.feasibility_margin(&solution) + 0.2).abs() < 1e-12);
+}
+
+#[test]
+fn test_satisfiability_has_no_margin() {
+    let challenge = satisfiability::Challenge {
+        seeds: [0; 8],
+        difficulty: satisfiability::Difficulty {
+            num_variables: 3,
+            clauses_to_variables_percent: 100,
+        },
+        clauses: vec![vec![1, 2, 3]],
+    };
+    let solution = satisfiability::Solution {
+        variables: vec![true, false, false],
+    };
+    assert!(challenge.verify_solution(&solution).is_ok());
+    assert_eq!(challenge.feasibility_margin(&solution), 0.0);
+}
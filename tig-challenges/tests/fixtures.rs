@@ -0,0 +1,61 @@
+use tig_challenges::fixtures::{small_instance, Fixture, CHALLENGE_IDS};
+use tig_challenges::{knapsack, satisfiability, vector_search, vehicle_routing, ChallengeTrait};
+
+#[test]
+fn test_fixture_solutions_verify() {
+    for challenge_id in CHALLENGE_IDS {
+        let fixture = small_instance(challenge_id).unwrap();
+        assert!(
+            fixture.verify().is_ok(),
+            "{}: {:?}",
+            challenge_id,
+            fixture.verify()
+        );
+    }
+}
+
+#[test]
+fn test_fixtures_match_their_challenge() {
+    assert!(matches!(
+        small_instance("c001").unwrap(),
+        Fixture::Satisfiability(..)
+    ));
+    assert!(matches!(
+        small_instance("c002").unwrap(),
+        Fixture::VehicleRouting(..)
+    ));
+    assert!(matches!(
+        small_instance("c003").unwrap(),
+        Fixture::Knapsack(..)
+    ));
+    assert!(matches!(
+        small_instance("c004").unwrap(),
+        Fixture::VectorSearch(..)
+    ));
+    assert!(small_instance("c999").is_err());
+}
+
+#[test]
+fn test_fixtures_are_deterministic() {
+    for challenge_id in CHALLENGE_IDS {
+        let equal = match (
+            small_instance(challenge_id).unwrap(),
+            small_instance(challenge_id).unwrap(),
+        ) {
+            (Fixture::Satisfiability(a, _), Fixture::Satisfiability(b, _)) => {
+                satisfiability::Challenge::instances_equal(&a, &b)
+            }
+            (Fixture::VehicleRouting(a, _), Fixture::VehicleRouting(b, _)) => {
+                vehicle_routing::Challenge::instances_equal(&a, &b)
+            }
+            (Fixture::Knapsack(a, _), Fixture::Knapsack(b, _)) => {
+                knapsack::Challenge::instances_equal(&a, &b)
+            }
+            (Fixture::VectorSearch(a, _), Fixture::VectorSearch(b, _)) => {
+                vector_search::Challenge::instances_equal(&a, &b)
+            }
+            _ => false,
+        };
+        assert!(equal, "{}", challenge_id);
+    }
+}
@@ -0,0 +1,73 @@
+use tig_challenges::*;
+
+#[test]
+fn test_satisfiability_infeasible_instance() {
+    let difficulty = satisfiability::Difficulty {
+        num_variables: 12,
+        clauses_to_variables_percent: 300,
+    };
+    let challenge = satisfiability::Challenge::infeasible_instance([0; 8], &difficulty).unwrap();
+    assert!(challenge.trivial_solution().is_none());
+    // no assignment of the 12 variables verifies
+    for mask in 0u32..(1 << 12) {
+        let solution = satisfiability::Solution {
+            variables: (0..12).map(|i| mask & (1 << i) != 0).collect(),
+        };
+        assert!(challenge.verify_solution(&solution).is_err());
+    }
+
+    let difficulty = satisfiability::Difficulty {
+        num_variables: 2,
+        clauses_to_variables_percent: 300,
+    };
+    assert!(satisfiability::Challenge::infeasible_instance([0; 8], &difficulty).is_none());
+}
+
+#[test]
+fn test_knapsack_infeasible_instance() {
+    let difficulty = knapsack::Difficulty {
+        num_items: 50,
+        better_than_baseline: 10,
+    };
+    let challenge = knapsack::Challenge::infeasible_instance([0; 8], &difficulty).unwrap();
+    assert!(challenge.trivial_solution().is_none());
+    for solution in [
+        knapsack::Solution { items: Vec::new() },
+        knapsack::Solution {
+            items: (0..50).collect(),
+        },
+        knapsack::greedy_warm_start(&challenge),
+    ] {
+        assert!(challenge.verify_solution(&solution).is_err());
+    }
+}
+
+#[test]
+fn test_vehicle_routing_infeasible_instance() {
+    let difficulty = vehicle_routing::Difficulty {
+        num_nodes: 40,
+        better_than_baseline: 0,
+    };
+    let mut challenge =
+        vehicle_routing::Challenge::infeasible_instance([0; 8], &difficulty).unwrap();
+    assert!(challenge.trivial_solution().is_none());
+    // not even one route per customer with no distance limit fits customer 1
+    challenge.max_total_distance = i32::MAX;
+    let one_per_customer = vehicle_routing::Solution {
+        routes: (1..40).map(|node| vec![0, node, 0]).collect(),
+    };
+    assert!(challenge.verify_solution(&one_per_customer).is_err());
+    let skip_customer_1 = vehicle_routing::Solution {
+        routes: (2..40).map(|node| vec![0, node, 0]).collect(),
+    };
+    assert!(challenge.verify_solution(&skip_customer_1).is_err());
+}
+
+#[test]
+fn test_vector_search_has_no_infeasible_instance() {
+    let difficulty = vector_search::Difficulty {
+        num_queries: 3,
+        better_than_baseline: 0,
+    };
+    assert!(vector_search::Challenge::infeasible_instance([0; 8], &difficulty).is_none());
+}
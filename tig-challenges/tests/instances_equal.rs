@@ -0,0 +1,70 @@
+use tig_challenges::*;
+
+fn check<T, U, C>(difficulty: [i32; 2])
+where
+    T: SolutionTrait,
+    U: DifficultyTrait<2>,
+    C: ChallengeTrait<T, U, 2>,
+{
+    let a = C::generate_instance_from_arr([1; 8], &difficulty).unwrap();
+    let b = C::generate_instance_from_arr([1; 8], &difficulty).unwrap();
+    let c = C::generate_instance_from_arr([2; 8], &difficulty).unwrap();
+    assert!(C::instances_equal(&a, &a));
+    assert!(C::instances_equal(&a, &b));
+    assert!(C::instances_equal(&b, &a));
+    assert!(!C::instances_equal(&a, &c));
+    assert!(!C::instances_equal(&c, &a));
+}
+
+#[test]
+fn test_satisfiability_instances_equal() {
+    check::<_, _, satisfiability::Challenge>([50, 300]);
+
+    // clause order and literal order within a clause don't change the formula
+    let difficulty = satisfiability::Difficulty {
+        num_variables: 3,
+        clauses_to_variables_percent: 67,
+    };
+    let a = satisfiability::Challenge {
+        seeds: [1; 8],
+        difficulty,
+        clauses: vec![vec![1, -2, 3], vec![-1, 2, -3]],
+    };
+    let b = satisfiability::Challenge {
+        seeds: [2; 8],
+        difficulty,
+        clauses: vec![vec![2, -3, -1], vec![3, 1, -2]],
+    };
+    assert!(satisfiability::Challenge::instances_equal(&a, &b));
+    let c = satisfiability::Challenge {
+        seeds: [1; 8],
+        difficulty,
+        clauses: vec![vec![1, -2, 3], vec![-1, 2, 3]],
+    };
+    assert!(!satisfiability::Challenge::instances_equal(&a, &c));
+}
+
+#[test]
+fn test_vehicle_routing_instances_equal() {
+    check::<_, _, vehicle_routing::Challenge>([40, 250]);
+}
+
+#[test]
+fn test_knapsack_instances_equal() {
+    check::<_, _, knapsack::Challenge>([50, 10]);
+
+    // item order matters as solutions refer to items by index
+    let a = knapsack::Challenge::generate_instance_from_arr([1; 8], &[50, 10]).unwrap();
+    let mut b = knapsack::Challenge::generate_instance_from_arr([1; 8], &[50, 10]).unwrap();
+    let j = (1..b.weights.len())
+        .find(|&j| (b.weights[j], b.values[j]) != (b.weights[0], b.values[0]))
+        .unwrap();
+    b.weights.swap(0, j);
+    b.values.swap(0, j);
+    assert!(!knapsack::Challenge::instances_equal(&a, &b));
+}
+
+#[test]
+fn test_vector_search_instances_equal() {
+    check::<_, _, vector_search::Challenge>([10, 250]);
+}
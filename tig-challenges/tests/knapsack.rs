@@ -0,0 +1,189 @@
+use tig_challenges::fixtures::{small_instance, Fixture};
+use tig_challenges::{knapsack::*, ChallengeTrait};
+
+fn best_solution(challenge: &Challenge) -> Solution {
+    let num_items = challenge.weights.len();
+    let mut best_items = Vec::new();
+    let mut best_value = 0;
+    for mask in 0u32..(1 << num_items) {
+        let items: Vec<usize> = (0..num_items).filter(|i| mask & (1 << i) != 0).collect();
+        let weight: u32 = items.iter().map(|&i| challenge.weights[i]).sum();
+        let value: u32 = items.iter().map(|&i| challenge.values[i]).sum();
+        if weight <= challenge.max_weight && value > best_value {
+            best_items = items;
+            best_value = value;
+        }
+    }
+    Solution { items: best_items }
+}
+
+#[test]
+fn test_verify_solution_at_difficulty() {
+    let difficulty = Difficulty {
+        num_items: 12,
+        better_than_baseline: 0,
+    };
+    let challenge = Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    let solution = best_solution(&challenge);
+    let total_value: u32 = solution.items.iter().map(|&i| challenge.values[i]).sum();
+    let baseline_value =
+        calc_baseline_value(&challenge.weights, &challenge.values, challenge.max_weight);
+
+    // the best solution is at least as strong as the baseline
+    assert!(challenge
+        .verify_solution_at_difficulty(&solution, &difficulty)
+        .is_ok());
+
+    // a difficulty the solution exceeds is accepted
+    let weaker = Difficulty {
+        num_items: 12,
+        better_than_baseline: ((total_value as f64 / baseline_value as f64 - 1.0) * 1000.0).floor()
+            as u32,
+    };
+    assert!(challenge
+        .verify_solution_at_difficulty(&solution, &weaker)
+        .is_ok());
+
+    // a difficulty beyond what the solution achieves is rejected
+    let stronger = Difficulty {
+        num_items: 12,
+        better_than_baseline: weaker.better_than_baseline + 100,
+    };
+    assert!(challenge
+        .verify_solution_at_difficulty(&solution, &stronger)
+        .is_err());
+
+    // a difficulty describing a different instance size is rejected
+    let mismatched = Difficulty {
+        num_items: 13,
+        better_than_baseline: 0,
+    };
+    assert!(challenge
+        .verify_solution_at_difficulty(&solution, &mismatched)
+        .is_err());
+}
+
+#[test]
+fn test_optimum() {
+    // items (weight, value): (2, 3), (3, 4), (4, 5), (5, 6) with capacity 5.
+    // feasible candidates are {0, 1} = 7, {3} = 6, {2} = 5, so the optimum is 7
+    let challenge = Challenge {
+        seeds: [0; 8],
+        difficulty: Difficulty {
+            num_items: 4,
+            better_than_baseline: 0,
+        },
+        weights: vec![2, 3, 4, 5],
+        values: vec![3, 4, 5, 6],
+        max_weight: 5,
+        min_value: 0,
+    };
+    assert_eq!(challenge.optimum(), Some(7.0));
+
+    // agrees with brute force on a generated instance
+    let challenge = Challenge::generate_instance(
+        [0; 8],
+        &Difficulty {
+            num_items: 12,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap();
+    let solution = best_solution(&challenge);
+    let total_value: u32 = solution.items.iter().map(|&i| challenge.values[i]).sum();
+    assert_eq!(challenge.optimum(), Some(total_value as f64));
+}
+
+#[test]
+fn test_greedy_warm_start() {
+    for seed in 0..10 {
+        let difficulty = Difficulty {
+            num_items: 50,
+            better_than_baseline: 0,
+        };
+        let challenge = Challenge::generate_instance([seed; 8], &difficulty).unwrap();
+        let solution = greedy_warm_start(&challenge);
+        let total_weight: u32 = solution.items.iter().map(|&i| challenge.weights[i]).sum();
+        let total_value: u32 = solution.items.iter().map(|&i| challenge.values[i]).sum();
+        assert!(total_weight <= challenge.max_weight);
+        assert_eq!(
+            total_value,
+            calc_baseline_value(&challenge.weights, &challenge.values, challenge.max_weight)
+        );
+        // reaching the baseline is enough when the target is the baseline
+        assert!(challenge.verify_solution(&solution).is_ok());
+        assert_eq!(challenge.trivial_solution().unwrap().items, solution.items);
+
+        // still a valid packing when the target is beyond it
+        let harder = Challenge::generate_instance(
+            [seed; 8],
+            &Difficulty {
+                num_items: 50,
+                better_than_baseline: 10,
+            },
+        )
+        .unwrap();
+        assert_eq!(greedy_warm_start(&harder).items, solution.items);
+        assert_eq!(harder.count_violations(&solution), 1);
+    }
+}
+
+#[test]
+fn test_repair() {
+    // items (weight, value): (2, 3), (3, 4), (4, 5), (5, 6) with capacity 5
+    let challenge = match small_instance("c003").unwrap() {
+        Fixture::Knapsack(challenge, _) => challenge,
+        _ => unreachable!(),
+    };
+    // weighs 9, so drops item 2, which is worth least per unit of weight
+    let over_capacity = Solution {
+        items: vec![0, 1, 2],
+    };
+    assert!(challenge.verify_solution(&over_capacity).is_err());
+    let repaired = challenge.repair(&over_capacity).unwrap();
+    assert_eq!(repaired.items, vec![0, 1]);
+    assert!(challenge.verify_solution(&repaired).is_ok());
+
+    // duplicate and unknown items are dropped too
+    let repaired = challenge
+        .repair(&Solution {
+            items: vec![1, 0, 1, 9],
+        })
+        .unwrap();
+    assert_eq!(repaired.items, vec![1, 0]);
+
+    // dropping items can't reach the value target
+    assert!(challenge.repair(&Solution { items: vec![3, 2] }).is_none());
+}
+
+#[test]
+fn test_repair_over_capacity_generated() {
+    let challenge = Challenge::generate_instance(
+        [0; 8],
+        &Difficulty {
+            num_items: 50,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap();
+    // a near miss: the greedy packing plus the left out item worth least per unit of weight
+    let mut items = greedy_warm_start(&challenge).items;
+    let ratio = |item: usize| challenge.values[item] as f64 / challenge.weights[item] as f64;
+    let extra = (0..50)
+        .filter(|item| !items.contains(item))
+        .min_by(|&a, &b| ratio(a).partial_cmp(&ratio(b)).unwrap())
+        .unwrap();
+    items.push(extra);
+    let over_capacity = Solution { items };
+    let total_weight: u32 = over_capacity
+        .items
+        .iter()
+        .map(|&i| challenge.weights[i])
+        .sum();
+    assert!(total_weight > challenge.max_weight);
+
+    let repaired = challenge.repair(&over_capacity).unwrap();
+    let total_weight: u32 = repaired.items.iter().map(|&i| challenge.weights[i]).sum();
+    assert!(total_weight <= challenge.max_weight);
+    assert!(challenge.verify_solution(&repaired).is_ok());
+}
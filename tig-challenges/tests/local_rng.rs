@@ -0,0 +1,60 @@
+use rand::Rng;
+use std::{sync::Arc, thread};
+use tig_challenges::*;
+
+const NUM_NONCES: u64 = 64;
+const NUM_THREADS: u64 = 8;
+
+fn seeds(nonce: u64) -> [u64; 8] {
+    let mut seeds = [0x9e3779b97f4a7c15, 1, 2, 3, 4, 5, 6, 7];
+    for seed in seeds.iter_mut() {
+        *seed ^= nonce;
+    }
+    seeds
+}
+
+fn generate(nonce: u64) -> String {
+    let challenge = knapsack::Challenge::generate_instance(
+        seeds(nonce),
+        &knapsack::Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        },
+    )
+    .unwrap();
+    serde_json::to_string(&challenge).unwrap()
+}
+
+#[test]
+fn test_parallel_generation_matches_sequential() {
+    let sequential: Vec<String> = (0..NUM_NONCES).map(generate).collect();
+    let sequential = Arc::new(sequential);
+
+    // each thread walks its own interleaved slice of nonces in reverse, so instances are generated
+    // concurrently and in a different order than sequentially. nothing is shared between threads
+    // except the expected results
+    let handles: Vec<_> = (0..NUM_THREADS)
+        .map(|t| {
+            let sequential = sequential.clone();
+            thread::spawn(move || {
+                for nonce in (0..NUM_NONCES).rev().filter(|n| n % NUM_THREADS == t) {
+                    assert_eq!(generate(nonce), sequential[nonce as usize]);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_rng_array_is_local_to_instance() {
+    // drawing from one RngArray doesn't advance another with the same seeds
+    let mut a = RngArray::new(seeds(1));
+    let mut b = RngArray::new(seeds(1));
+    let first: Vec<u32> = (0..16).map(|_| a.get_mut().gen()).collect();
+    let _: Vec<u32> = (0..16).map(|_| a.get_mut().gen()).collect();
+    let from_b: Vec<u32> = (0..16).map(|_| b.get_mut().gen()).collect();
+    assert_eq!(first, from_b);
+}
@@ -0,0 +1,104 @@
+use tig_challenges::*;
+
+fn assert_too_long<
+    T: SolutionTrait,
+    U: DifficultyTrait<N>,
+    C: ChallengeTrait<T, U, N>,
+    const N: usize,
+>(
+    challenge: &C,
+    solution: &T,
+    difficulty: &U,
+) {
+    assert_eq!(solution.solution_len(), challenge.max_solution_len() + 1);
+    for result in [
+        challenge.verify_solution(solution),
+        challenge.verify_solution_at_difficulty(solution, difficulty),
+    ] {
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("exceeds max solution length"), "{}", err);
+    }
+}
+
+#[test]
+fn test_satisfiability_rejects_too_long_solution() {
+    let difficulty = satisfiability::Difficulty {
+        num_variables: 50,
+        clauses_to_variables_percent: 300,
+    };
+    let challenge = satisfiability::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    assert_eq!(challenge.max_solution_len(), 50);
+    let solution = satisfiability::Solution {
+        variables: vec![true; 51],
+    };
+    assert_too_long(&challenge, &solution, &difficulty);
+}
+
+#[test]
+fn test_knapsack_rejects_too_long_solution() {
+    let difficulty = knapsack::Difficulty {
+        num_items: 50,
+        better_than_baseline: 10,
+    };
+    let challenge = knapsack::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    assert_eq!(challenge.max_solution_len(), 50);
+    // checked before the duplicate check
+    let solution = knapsack::Solution { items: vec![0; 51] };
+    assert_too_long(&challenge, &solution, &difficulty);
+}
+
+#[test]
+fn test_vehicle_routing_rejects_too_long_solution() {
+    let difficulty = vehicle_routing::Difficulty {
+        num_nodes: 40,
+        better_than_baseline: 0,
+    };
+    let challenge = vehicle_routing::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    assert_eq!(challenge.max_solution_len(), 39);
+    // the baseline the instance's threshold comes from fits within the bound
+    let baseline_routes = vehicle_routing::calc_baseline_routes(
+        40,
+        challenge.max_capacity,
+        &challenge.demands,
+        &challenge.distance_matrix,
+    )
+    .unwrap();
+    assert!(baseline_routes.len() <= challenge.max_solution_len());
+    let solution = vehicle_routing::Solution {
+        routes: vec![vec![0, 1, 0]; 40],
+    };
+    assert_too_long(&challenge, &solution, &difficulty);
+}
+
+#[test]
+fn test_vector_search_rejects_too_long_solution() {
+    let difficulty = vector_search::Difficulty {
+        num_queries: 10,
+        better_than_baseline: 0,
+    };
+    let challenge = vector_search::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    assert_eq!(challenge.max_solution_len(), 10);
+    let solution = vector_search::Solution {
+        indexes: vec![0; 11],
+    };
+    assert_too_long(&challenge, &solution, &difficulty);
+}
+
+#[test]
+fn test_solutions_within_max_len_are_checked_as_before() {
+    let difficulty = knapsack::Difficulty {
+        num_items: 50,
+        better_than_baseline: 10,
+    };
+    let mut challenge = knapsack::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    challenge.min_value = 0;
+    let solution = knapsack::Solution {
+        items: (0..challenge.max_solution_len()).collect(),
+    };
+    assert!(challenge.check_solution_len(&solution).is_ok());
+    let err = challenge
+        .verify_solution(&solution)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("exceeded max weight"), "{}", err);
+}
@@ -0,0 +1,180 @@
+use tig_challenges::fixtures::{small_instance, Fixture, CHALLENGE_IDS};
+use tig_challenges::{
+    knapsack, pack_bits, satisfiability, unpack_bits, ChallengeTrait, DifficultyTrait,
+};
+
+// the minimal proof, and the solution as json for comparing sizes
+fn proof_and_json(fixture: &Fixture) -> (Vec<u8>, Vec<u8>) {
+    match fixture {
+        Fixture::Satisfiability(challenge, solution) => (
+            challenge.minimal_proof(solution).unwrap(),
+            serde_json::to_vec(solution).unwrap(),
+        ),
+        Fixture::VehicleRouting(challenge, solution) => (
+            challenge.minimal_proof(solution).unwrap(),
+            serde_json::to_vec(solution).unwrap(),
+        ),
+        Fixture::Knapsack(challenge, solution) => (
+            challenge.minimal_proof(solution).unwrap(),
+            serde_json::to_vec(solution).unwrap(),
+        ),
+        Fixture::VectorSearch(challenge, solution) => (
+            challenge.minimal_proof(solution).unwrap(),
+            serde_json::to_vec(solution).unwrap(),
+        ),
+    }
+}
+
+fn verify_proof(fixture: &Fixture, proof: &[u8]) -> anyhow::Result<()> {
+    match fixture {
+        Fixture::Satisfiability(challenge, _) => challenge.verify_proof(proof),
+        Fixture::VehicleRouting(challenge, _) => challenge.verify_proof(proof),
+        Fixture::Knapsack(challenge, _) => challenge.verify_proof(proof),
+        Fixture::VectorSearch(challenge, _) => challenge.verify_proof(proof),
+    }
+}
+
+#[test]
+fn test_minimal_proofs_verify() {
+    for challenge_id in CHALLENGE_IDS {
+        let fixture = small_instance(challenge_id).unwrap();
+        let (proof, json) = proof_and_json(&fixture);
+        assert!(
+            verify_proof(&fixture, &proof).is_ok(),
+            "{}: {:?}",
+            challenge_id,
+            verify_proof(&fixture, &proof)
+        );
+        assert!(proof.len() <= json.len(), "{}", challenge_id);
+    }
+}
+
+#[test]
+fn test_bit_packed_proofs_are_smaller() {
+    for challenge_id in ["c001", "c003"] {
+        let (proof, json) = proof_and_json(&small_instance(challenge_id).unwrap());
+        // 3 variables or 4 items fit in a byte
+        assert_eq!(proof.len(), 1, "{}", challenge_id);
+        assert!(proof.len() < json.len(), "{}", challenge_id);
+    }
+
+    // no clauses, so any assignment verifies
+    let difficulty = satisfiability::Difficulty::from_arr(&[50, 0]);
+    let challenge = satisfiability::Challenge::generate_instance([0; 8], &difficulty).unwrap();
+    let solution = satisfiability::Solution {
+        variables: vec![true; 50],
+    };
+    let proof = challenge.minimal_proof(&solution).unwrap();
+    assert_eq!(proof.len(), 7);
+    assert_eq!(
+        challenge.solution_from_proof(&proof).unwrap().variables,
+        solution.variables
+    );
+    assert!(proof.len() * 10 < serde_json::to_vec(&solution).unwrap().len());
+}
+
+#[test]
+fn test_tampered_proofs_are_rejected() {
+    for challenge_id in CHALLENGE_IDS {
+        let fixture = small_instance(challenge_id).unwrap();
+        let (proof, _) = proof_and_json(&fixture);
+        assert!(
+            verify_proof(&fixture, &proof[..proof.len() - 1]).is_err(),
+            "{}",
+            challenge_id
+        );
+        let mut extended = proof.clone();
+        extended.push(0);
+        assert!(
+            verify_proof(&fixture, &extended).is_err(),
+            "{}",
+            challenge_id
+        );
+    }
+
+    let fixture = small_instance("c001").unwrap();
+    let (proof, _) = proof_and_json(&fixture);
+    // the fixture has 3 variables, so the other 5 bits are padding
+    assert!(verify_proof(&fixture, &[proof[0] | 1 << 3]).is_err());
+    // flipping the first variable of [t, t, f] breaks the clause [1, -2, 3]
+    assert!(verify_proof(&fixture, &[proof[0] ^ 1]).is_err());
+
+    let fixture = small_instance("c003").unwrap();
+    let (proof, _) = proof_and_json(&fixture);
+    // adding any item to [0, 1] exceeds the max weight of 5
+    for item in 2..4 {
+        assert!(verify_proof(&fixture, &[proof[0] | 1 << item]).is_err());
+    }
+    // dropping an item falls below the min value of 7
+    for item in 0..2 {
+        assert!(verify_proof(&fixture, &[proof[0] & !(1 << item)]).is_err());
+    }
+}
+
+#[test]
+fn test_knapsack_proof_requires_valid_solution() {
+    let Fixture::Knapsack(challenge, solution) = small_instance("c003").unwrap() else {
+        unreachable!()
+    };
+    assert_eq!(solution.items, vec![0, 1]);
+    let proof = |items: Vec<usize>| challenge.minimal_proof(&knapsack::Solution { items });
+    assert!(proof(vec![0, 1]).is_ok());
+    // a bit per item can't repeat one, so [0, 1, 1] would encode as the valid [0, 1]
+    assert!(challenge
+        .verify_solution(&knapsack::Solution {
+            items: vec![0, 1, 1]
+        })
+        .is_err());
+    assert!(proof(vec![0, 1, 1]).is_err());
+    // nor select an unknown one
+    assert!(proof(vec![0, 1, 4]).is_err());
+    // and a selection that fits still has to reach the min value
+    assert!(proof(vec![0]).is_err());
+}
+
+#[test]
+fn test_satisfiability_proof_requires_valid_solution() {
+    let Fixture::Satisfiability(challenge, solution) = small_instance("c001").unwrap() else {
+        unreachable!()
+    };
+    let proof =
+        |variables: Vec<bool>| challenge.minimal_proof(&satisfiability::Solution { variables });
+    assert!(proof(solution.variables.clone()).is_ok());
+    // an extra false variable fits in the padding of the byte, so [t, t, f, f] would encode as
+    // the valid [t, t, f]
+    let mut oversized = solution.variables.clone();
+    oversized.push(false);
+    assert!(challenge
+        .verify_solution(&satisfiability::Solution {
+            variables: oversized.clone()
+        })
+        .is_err());
+    assert!(proof(oversized).is_err());
+    assert!(proof(solution.variables[..2].to_vec()).is_err());
+}
+
+#[test]
+fn test_pack_bits_round_trip() {
+    for len in [0usize, 1, 7, 8, 9, 20] {
+        let bits: Vec<bool> = (0..len).map(|i| i % 3 == 0).collect();
+        let bytes = pack_bits(&bits);
+        assert_eq!(bytes.len(), len.div_ceil(8));
+        assert_eq!(unpack_bits(&bytes, len).unwrap(), bits);
+    }
+    assert!(unpack_bits(&[0], 0).is_err());
+    assert!(unpack_bits(&[0b1000_0000], 7).is_err());
+    assert!(unpack_bits(&[0b0100_0000], 7).is_ok());
+}
+
+#[test]
+fn test_satisfiability_essential_bytes_count_variables() {
+    let Fixture::Satisfiability(challenge, solution) = small_instance("c001").unwrap() else {
+        unreachable!()
+    };
+    let mut padded = solution.variables.clone();
+    padded.push(false);
+    assert_ne!(
+        challenge.essential_bytes(&satisfiability::Solution { variables: padded }),
+        challenge.essential_bytes(&solution)
+    );
+}
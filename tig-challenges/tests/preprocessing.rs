@@ -0,0 +1,151 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+use tig_challenges::{preprocessing::*, satisfiability::*, ChallengeTrait};
+
+fn instance(seed: u64) -> Challenge {
+    Challenge::generate_instance(
+        [seed; 8],
+        &Difficulty {
+            num_variables: 50,
+            clauses_to_variables_percent: 300,
+        },
+    )
+    .unwrap()
+}
+
+// literals of the clauses each variable is in
+fn clause_index(challenge: &Challenge) -> Vec<Vec<i32>> {
+    let mut index = vec![Vec::new(); challenge.difficulty.num_variables];
+    for clause in challenge.clauses.iter() {
+        for &literal in clause {
+            index[literal.unsigned_abs() as usize - 1].push(literal);
+        }
+    }
+    index
+}
+
+fn cached_clause_index(challenge: &Challenge) -> Arc<Vec<Vec<i32>>> {
+    preprocessed(instance_key(challenge), "clause_index", || {
+        clause_index(challenge)
+    })
+}
+
+// sets each variable to the polarity it appears with most
+fn majority_solver(seed: u64) -> (Solution, Arc<Vec<Vec<i32>>>) {
+    let challenge = instance(seed);
+    let index = cached_clause_index(&challenge);
+    let variables = index
+        .iter()
+        .map(|literals| literals.iter().filter(|&&l| l > 0).count() * 2 > literals.len())
+        .collect();
+    (Solution { variables }, index)
+}
+
+// sets each variable to the polarity of its first appearance
+fn first_literal_solver(seed: u64) -> (Solution, Arc<Vec<Vec<i32>>>) {
+    let challenge = instance(seed);
+    let index = cached_clause_index(&challenge);
+    let variables = index
+        .iter()
+        .map(|literals| literals.first().is_some_and(|&l| l > 0))
+        .collect();
+    (Solution { variables }, index)
+}
+
+#[test]
+fn test_second_algorithm_reuses_preprocessing() {
+    // the only test using the process wide cache
+    let cache = enable_preprocessing_cache(8);
+    // each algorithm generates the nonce's instance itself
+    let (_, first_index) = majority_solver(0);
+    assert_eq!((cache.num_misses(), cache.num_hits()), (1, 0));
+    // a separately generated copy of the instance has the same content
+    let (_, second_index) = first_literal_solver(0);
+    assert_eq!((cache.num_misses(), cache.num_hits()), (1, 1));
+    assert!(Arc::ptr_eq(&first_index, &second_index));
+    assert_eq!(*first_index, clause_index(&instance(0)));
+
+    // another nonce's instance is preprocessed again
+    let (_, other_index) = first_literal_solver(1);
+    assert_eq!((cache.num_misses(), cache.num_hits()), (2, 1));
+    assert!(!Arc::ptr_eq(&first_index, &other_index));
+}
+
+#[test]
+fn test_cache_is_bounded() {
+    let cache = PreprocessingCache::new(2);
+    let num_builds = AtomicUsize::new(0);
+    let get = |key: u64| {
+        cache.get_or_insert_with(key, "test", || num_builds.fetch_add(1, Ordering::Relaxed))
+    };
+    get(0);
+    get(1);
+    // 0 is now the most recently used, so 1 is evicted for 2
+    get(0);
+    get(2);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(num_builds.load(Ordering::Relaxed), 3);
+    get(0);
+    get(2);
+    assert_eq!(num_builds.load(Ordering::Relaxed), 3);
+    get(1);
+    assert_eq!(num_builds.load(Ordering::Relaxed), 4);
+    assert_eq!(cache.len(), 2);
+}
+
+#[test]
+fn test_entries_are_keyed_by_name_and_type() {
+    let cache = PreprocessingCache::new(8);
+    assert_eq!(*cache.get_or_insert_with(0, "a", || 1u32), 1);
+    assert_eq!(*cache.get_or_insert_with(0, "b", || 2u32), 2);
+    assert_eq!(*cache.get_or_insert_with(0, "a", || 3u64), 3);
+    assert_eq!(*cache.get_or_insert_with(0, "a", || 4u32), 1);
+    assert_eq!((cache.num_misses(), cache.num_hits()), (3, 1));
+}
+
+#[test]
+fn test_concurrent_solvers_preprocess_once() {
+    let cache = PreprocessingCache::new(8);
+    let challenge = instance(2);
+    let key = instance_key(&challenge);
+    let num_builds = AtomicUsize::new(0);
+    let indexes: Vec<Arc<Vec<Vec<i32>>>> = std::thread::scope(|s| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                s.spawn(|| {
+                    cache.get_or_insert_with(key, "clause_index", || {
+                        num_builds.fetch_add(1, Ordering::Relaxed);
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                        clause_index(&challenge)
+                    })
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+    assert_eq!(num_builds.load(Ordering::Relaxed), 1);
+    assert!(indexes.iter().all(|x| Arc::ptr_eq(x, &indexes[0])));
+}
+
+#[test]
+fn test_instance_key_ignores_seeds() {
+    let challenge = instance(3);
+    let mut json = serde_json::to_value(&challenge).unwrap();
+    json["seeds"] = serde_json::to_value([4u64; 8]).unwrap();
+    let reseeded: Challenge = serde_json::from_value(json).unwrap();
+    assert_eq!(instance_key(&reseeded), instance_key(&challenge));
+    assert_ne!(instance_key(&instance(4)), instance_key(&challenge));
+}
+
+#[test]
+fn test_zero_capacity_disables_caching() {
+    let cache = PreprocessingCache::new(0);
+    let num_builds = AtomicUsize::new(0);
+    for _ in 0..3 {
+        cache.get_or_insert_with(0, "test", || num_builds.fetch_add(1, Ordering::Relaxed));
+    }
+    assert_eq!(num_builds.load(Ordering::Relaxed), 3);
+    assert!(cache.is_empty());
+}
@@ -0,0 +1,46 @@
+use tig_challenges::fixtures::{small_instance, Fixture};
+use tig_challenges::{knapsack, ChallengeTrait};
+
+fn qualifier(challenge_id: &str) -> f64 {
+    match small_instance(challenge_id).unwrap() {
+        Fixture::Satisfiability(challenge, solution) => challenge.qualifier(&solution),
+        Fixture::VehicleRouting(challenge, solution) => challenge.qualifier(&solution),
+        Fixture::Knapsack(challenge, solution) => challenge.qualifier(&solution),
+        Fixture::VectorSearch(challenge, solution) => challenge.qualifier(&solution),
+    }
+    .unwrap()
+}
+
+#[test]
+fn test_fixture_qualifiers() {
+    // satisfiability has no objective
+    assert_eq!(qualifier("c001"), 1.0);
+    // 54 of a max 60
+    assert!((qualifier("c002") - 60.0 / 54.0).abs() < 1e-9);
+    // exactly the min value
+    assert_eq!(qualifier("c003"), 1.0);
+    // 0.1 on average with a max of 0.2
+    assert!((qualifier("c004") - 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_invalid_solution_has_no_qualifier() {
+    let challenge = match small_instance("c003").unwrap() {
+        Fixture::Knapsack(challenge, _) => challenge,
+        _ => unreachable!(),
+    };
+    assert!(challenge
+        .qualifier(&knapsack::Solution { items: vec![0] })
+        .is_err());
+    // the same packing qualifies higher against a lower min value
+    let challenge = knapsack::Challenge {
+        min_value: 5,
+        ..challenge
+    };
+    assert_eq!(
+        challenge
+            .qualifier(&knapsack::Solution { items: vec![0, 1] })
+            .unwrap(),
+        7.0 / 5.0
+    );
+}
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tig_challenges::{knapsack::*, run_resumable, ChallengeTrait, ResumableSolver};
+
+// exhaustive knapsack search that checks one subset per step
+#[derive(Serialize, Deserialize)]
+struct ExhaustiveSolver {
+    weights: Vec<u32>,
+    values: Vec<u32>,
+    max_weight: u32,
+    next_mask: u64,
+    best_mask: u64,
+    best_value: u32,
+}
+
+impl ExhaustiveSolver {
+    fn new(challenge: &Challenge) -> Self {
+        Self {
+            weights: challenge.weights.clone(),
+            values: challenge.values.clone(),
+            max_weight: challenge.max_weight,
+            next_mask: 0,
+            best_mask: 0,
+            best_value: 0,
+        }
+    }
+}
+
+impl ResumableSolver<Solution> for ExhaustiveSolver {
+    fn step(&mut self) -> Result<Option<Solution>> {
+        let num_items = self.weights.len();
+        if self.next_mask == 1 << num_items {
+            return Ok(Some(Solution {
+                items: (0..num_items)
+                    .filter(|i| self.best_mask & (1 << i) != 0)
+                    .collect(),
+            }));
+        }
+        let mask = self.next_mask;
+        let items = (0..num_items).filter(|i| mask & (1 << i) != 0);
+        let weight: u32 = items.clone().map(|i| self.weights[i]).sum();
+        let value: u32 = items.map(|i| self.values[i]).sum();
+        if weight <= self.max_weight && value > self.best_value {
+            self.best_mask = mask;
+            self.best_value = value;
+        }
+        self.next_mask += 1;
+        Ok(None)
+    }
+}
+
+#[test]
+fn test_resumed_solve_matches_uninterrupted_solve() {
+    let challenge = Challenge::generate_instance(
+        [0; 8],
+        &Difficulty {
+            num_items: 10,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap();
+
+    let uninterrupted = run_resumable(&mut ExhaustiveSolver::new(&challenge), None)
+        .unwrap()
+        .unwrap();
+
+    // pause part way, checkpoint, and resume from a fresh solver restored from the checkpoint
+    let mut solver = ExhaustiveSolver::new(&challenge);
+    assert!(run_resumable(&mut solver, Some(300)).unwrap().is_none());
+    let checkpoint = solver.save_state().unwrap();
+    drop(solver);
+    let mut solver = ExhaustiveSolver::restore_state(&checkpoint).unwrap();
+    assert_eq!(solver.next_mask, 300);
+    let resumed = run_resumable(&mut solver, None).unwrap().unwrap();
+
+    assert_eq!(resumed.items, uninterrupted.items);
+    assert!(challenge.verify_solution(&resumed).is_ok());
+    assert_eq!(challenge.optimum(), Some(solver.best_value as f64));
+}
+
+#[test]
+fn test_restore_rejects_corrupt_state() {
+    assert!(ExhaustiveSolver::restore_state(b"not a checkpoint").is_err());
+}
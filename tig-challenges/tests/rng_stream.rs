@@ -0,0 +1,42 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tig_challenges::*;
+
+const SEEDS: [u64; 8] = [11, 12, 13, 14, 15, 16, 17, 18];
+
+fn generate() -> String {
+    let challenge = satisfiability::Challenge::generate_instance(
+        RngStream::Generation.seeds(SEEDS),
+        &satisfiability::Difficulty::from_arr(&[50, 300]),
+    )
+    .unwrap();
+    serde_json::to_string(&challenge).unwrap()
+}
+
+#[test]
+fn test_solver_draws_do_not_alter_instance() {
+    let expected = generate();
+    for num_draws in [0, 1, 100, 10000] {
+        let mut rng = RngStream::Solving.rng(SEEDS);
+        for _ in 0..num_draws {
+            let _: u64 = rng.gen();
+        }
+        assert_eq!(generate(), expected, "num_draws = {}", num_draws);
+    }
+}
+
+#[test]
+fn test_streams_are_independent() {
+    // generation keeps the protocol's seeds, so instances are unchanged
+    assert_eq!(RngStream::Generation.seeds(SEEDS), SEEDS);
+
+    let solving = RngStream::Solving.seeds(SEEDS);
+    assert!(solving.iter().all(|seed| !SEEDS.contains(seed)));
+    assert_eq!(RngStream::Solving.seeds(SEEDS), solving);
+
+    // the numbers an algorithm seeded from the raw seeds would draw are not the solving stream's
+    let draws = |mut rng: StdRng| (0..16).map(|_| rng.gen::<u64>()).collect::<Vec<_>>();
+    let generation_draws = draws(StdRng::seed_from_u64(SEEDS[0]));
+    let solving_draws = draws(RngStream::Solving.rng(SEEDS));
+    assert!(solving_draws.iter().all(|x| !generation_draws.contains(x)));
+    assert_eq!(solving_draws, draws(RngStream::Solving.rng(SEEDS)));
+}
@@ -0,0 +1,124 @@
+use anyhow::Result;
+use tig_challenges::{find_first_failure, satisfiability::*, ChallengeTrait, DifficultyTrait};
+
+// exhaustive solver that keeps its clauses between calls
+struct BruteForceSolver {
+    num_variables: usize,
+    clauses: Vec<Vec<i32>>,
+}
+
+impl IncrementalSolver for BruteForceSolver {
+    fn add_clause(&mut self, clause: &[i32]) {
+        self.clauses.push(clause.to_vec());
+    }
+
+    fn solve_with_assumptions(&mut self, assumptions: &[i32]) -> Result<Option<Vec<bool>>> {
+        let is_true = |variables: &Vec<bool>, literal: i32| {
+            variables[literal.unsigned_abs() as usize - 1] == (literal > 0)
+        };
+        for mask in 0u32..(1 << self.num_variables) {
+            let variables: Vec<bool> = (0..self.num_variables)
+                .map(|i| mask & (1 << i) != 0)
+                .collect();
+            if assumptions.iter().all(|&l| is_true(&variables, l))
+                && self
+                    .clauses
+                    .iter()
+                    .all(|clause| clause.iter().any(|&l| is_true(&variables, l)))
+            {
+                return Ok(Some(variables));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[test]
+fn test_incremental_solver() {
+    // (x1 or x2) and (not x1 or x3) and (not x2 or not x3)
+    let challenge = Challenge {
+        seeds: [0; 8],
+        difficulty: Difficulty {
+            num_variables: 3,
+            clauses_to_variables_percent: 100,
+        },
+        clauses: vec![vec![1, 2, 2], vec![-1, 3, 3], vec![-2, -3, -3]],
+    };
+    let mut solver = BruteForceSolver {
+        num_variables: 3,
+        clauses: Vec::new(),
+    };
+
+    let solution = challenge.solve_incremental(&mut solver).unwrap().unwrap();
+    assert!(challenge.verify_solution(&solution).is_ok());
+
+    // the loaded clauses are reused across calls with different assumptions
+    let variables = solver.solve_with_assumptions(&[1]).unwrap().unwrap();
+    assert_eq!(variables, vec![true, false, true]);
+    assert!(challenge.verify_solution(&Solution { variables }).is_ok());
+    let variables = solver.solve_with_assumptions(&[-1]).unwrap().unwrap();
+    assert_eq!(variables, vec![false, true, false]);
+    assert!(challenge.verify_solution(&Solution { variables }).is_ok());
+    assert_eq!(solver.solve_with_assumptions(&[1, 2]).unwrap(), None);
+
+    // adding clauses narrows the solutions of later calls
+    solver.add_clause(&[1]);
+    assert_eq!(
+        solver.solve_with_assumptions(&[]).unwrap(),
+        Some(vec![true, false, true])
+    );
+    solver.add_clause(&[-3]);
+    assert_eq!(solver.solve_with_assumptions(&[]).unwrap(), None);
+}
+
+#[test]
+fn test_parallel_verification_matches_serial() {
+    let difficulty = Difficulty::from_arr(&[20000, 420]);
+    let mut challenge = Challenge::generate_instance([7; 8], &difficulty).unwrap();
+    let all_false = Solution {
+        variables: vec![false; 20000],
+    };
+    let alternating = Solution {
+        variables: (0..20000).map(|i| i % 2 == 0).collect(),
+    };
+    let too_short = Solution {
+        variables: vec![false; 10],
+    };
+    for solution in [&all_false, &alternating, &too_short] {
+        let serial = challenge
+            .verify_solution(solution)
+            .map_err(|e| e.to_string());
+        assert!(serial.is_err());
+        for threads in [0, 1, 2, 3, 8, 64] {
+            let parallel = challenge
+                .verify_solution_parallel(solution, threads)
+                .map_err(|e| e.to_string());
+            assert_eq!(parallel, serial, "threads = {}", threads);
+        }
+    }
+
+    // only keep the clauses all false satisfies
+    challenge
+        .clauses
+        .retain(|clause| clause.iter().any(|&literal| literal < 0));
+    assert!(challenge.verify_solution(&all_false).is_ok());
+    for threads in [1, 4, 64] {
+        assert!(challenge
+            .verify_solution_parallel(&all_false, threads)
+            .is_ok());
+    }
+}
+
+#[test]
+fn test_find_first_failure() {
+    let items: Vec<u32> = (0..100).collect();
+    for threads in [0, 1, 3, 7, 100, 1000] {
+        assert_eq!(
+            find_first_failure(&items, threads, |&x| x % 40 != 39),
+            Some(39)
+        );
+        assert_eq!(find_first_failure(&items, threads, |&x| x != 99), Some(99));
+        assert_eq!(find_first_failure(&items, threads, |_| true), None);
+    }
+    assert_eq!(find_first_failure(&[] as &[u32], 4, |_| false), None);
+}
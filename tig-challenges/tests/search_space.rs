@@ -0,0 +1,79 @@
+use tig_challenges::*;
+
+fn satisfiability_log2(num_variables: usize) -> f64 {
+    satisfiability::Challenge::generate_instance(
+        [0; 8],
+        &satisfiability::Difficulty {
+            num_variables,
+            clauses_to_variables_percent: 300,
+        },
+    )
+    .unwrap()
+    .search_space_log2()
+}
+
+fn knapsack_log2(num_items: usize) -> f64 {
+    knapsack::Challenge::generate_instance(
+        [0; 8],
+        &knapsack::Difficulty {
+            num_items,
+            better_than_baseline: 10,
+        },
+    )
+    .unwrap()
+    .search_space_log2()
+}
+
+fn vehicle_routing_log2(num_nodes: usize) -> f64 {
+    vehicle_routing::Challenge::generate_instance(
+        [0; 8],
+        &vehicle_routing::Difficulty {
+            num_nodes,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap()
+    .search_space_log2()
+}
+
+fn vector_search_log2(num_queries: u32) -> f64 {
+    vector_search::Challenge::generate_instance(
+        [0; 8],
+        &vector_search::Difficulty {
+            num_queries,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap()
+    .search_space_log2()
+}
+
+#[test]
+fn test_binary_choice_search_spaces_grow_linearly() {
+    // one bit per variable or item
+    assert_eq!(satisfiability_log2(50), 50.0);
+    assert_eq!(satisfiability_log2(100), 100.0);
+    assert_eq!(knapsack_log2(50), 50.0);
+    assert_eq!(knapsack_log2(100), 100.0);
+}
+
+#[test]
+fn test_vehicle_routing_search_space_grows_faster_than_linearly() {
+    let (a, b, c) = (
+        vehicle_routing_log2(20),
+        vehicle_routing_log2(40),
+        vehicle_routing_log2(80),
+    );
+    assert!(a > 0.0 && a < b && b < c);
+    // log2 of a factorial, so doubling the nodes more than doubles it
+    assert!(b > 2.0 * a);
+    assert!(c > 2.0 * b);
+}
+
+#[test]
+fn test_vector_search_search_space_grows_with_queries() {
+    let (a, b) = (vector_search_log2(10), vector_search_log2(20));
+    assert!(a > 0.0);
+    // the database size doesn't depend on num_queries
+    assert!((b - 2.0 * a).abs() < 1e-9);
+}
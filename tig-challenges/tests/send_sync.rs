@@ -0,0 +1,39 @@
+use tig_challenges::*;
+
+// compile-time check: fails to build if any challenge's types or solver signature stop being
+// Send + Sync
+fn assert_send_sync<C: Send + Sync, S: Send + Sync, D: Send + Sync>()
+where
+    SolveChallengeFn<C, S>: Send + Sync,
+{
+}
+
+#[test]
+fn test_challenge_types_are_send_sync() {
+    assert_send_sync::<c001::Challenge, c001::Solution, c001::Difficulty>();
+    assert_send_sync::<c002::Challenge, c002::Solution, c002::Difficulty>();
+    assert_send_sync::<c003::Challenge, c003::Solution, c003::Difficulty>();
+    assert_send_sync::<c004::Challenge, c004::Solution, c004::Difficulty>();
+}
+
+#[test]
+fn test_solver_fn_moves_across_threads() {
+    fn solve_challenge(challenge: &c003::Challenge) -> anyhow::Result<Option<c003::Solution>> {
+        Ok(Some(c003::Solution {
+            items: (0..challenge.weights.len()).take(1).collect(),
+        }))
+    }
+    let solver = solve_challenge as SolveChallengeFn<c003::Challenge, c003::Solution>;
+    let challenge = c003::Challenge::generate_instance(
+        [0; 8],
+        &c003::Difficulty {
+            num_items: 10,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap();
+    let solution = std::thread::spawn(move || solver(&challenge).unwrap().unwrap())
+        .join()
+        .unwrap();
+    assert_eq!(solution.items, vec![0]);
+}
@@ -0,0 +1,111 @@
+use std::io::{BufWriter, Write};
+use tig_challenges::*;
+
+// a sink that only accepts a few bytes per write, like a pipe, to check nothing relies on whole
+// lines being written at once
+struct ChunkedSink(Vec<u8>);
+
+impl Write for ChunkedSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(3);
+        self.0.extend_from_slice(&buf[..n]);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_satisfiability_streamed_format_matches_string() {
+    let challenge = satisfiability::Challenge {
+        seeds: [0; 8],
+        difficulty: satisfiability::Difficulty {
+            num_variables: 3,
+            clauses_to_variables_percent: 67,
+        },
+        clauses: vec![vec![1, -2, 3], vec![-1, 2, -3]],
+    };
+    let string = challenge.to_standard_format();
+    assert_eq!(string, "p cnf 3 2\n1 -2 3 0\n-1 2 -3 0\n");
+
+    let mut sink = ChunkedSink(Vec::new());
+    challenge.write_standard_format(&mut sink).unwrap();
+    assert_eq!(String::from_utf8(sink.0).unwrap(), string);
+
+    let challenge = satisfiability::Challenge::generate_instance(
+        [7; 8],
+        &satisfiability::Difficulty {
+            num_variables: 50,
+            clauses_to_variables_percent: 420,
+        },
+    )
+    .unwrap();
+    let mut buffer = Vec::new();
+    challenge
+        .write_standard_format(BufWriter::new(&mut buffer))
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        challenge.to_standard_format()
+    );
+}
+
+#[test]
+fn test_vehicle_routing_streamed_format_matches_string() {
+    let challenge = vehicle_routing::Challenge {
+        seeds: [0; 8],
+        difficulty: vehicle_routing::Difficulty {
+            num_nodes: 3,
+            better_than_baseline: 0,
+        },
+        demands: vec![0, 20, 25],
+        distance_matrix: vec![vec![0, 5, 7], vec![5, 0, 4], vec![7, 4, 0]],
+        max_total_distance: 16,
+        max_capacity: 100,
+    };
+    let string = challenge.to_standard_format();
+    assert_eq!(
+        string,
+        "NAME : tig_vehicle_routing\n\
+         TYPE : CVRP\n\
+         DIMENSION : 3\n\
+         EDGE_WEIGHT_TYPE : EXPLICIT\n\
+         EDGE_WEIGHT_FORMAT : FULL_MATRIX\n\
+         CAPACITY : 100\n\
+         EDGE_WEIGHT_SECTION\n\
+         0 5 7\n\
+         5 0 4\n\
+         7 4 0\n\
+         DEMAND_SECTION\n\
+         1 0\n\
+         2 20\n\
+         3 25\n\
+         DEPOT_SECTION\n\
+         1\n\
+         -1\n\
+         EOF\n"
+    );
+
+    let mut sink = ChunkedSink(Vec::new());
+    challenge.write_standard_format(&mut sink).unwrap();
+    assert_eq!(String::from_utf8(sink.0).unwrap(), string);
+
+    let challenge = vehicle_routing::Challenge::generate_instance(
+        [7; 8],
+        &vehicle_routing::Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 250,
+        },
+    )
+    .unwrap();
+    let mut buffer = Vec::new();
+    challenge
+        .write_standard_format(BufWriter::new(&mut buffer))
+        .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        challenge.to_standard_format()
+    );
+}
@@ -0,0 +1,77 @@
+use tig_challenges::*;
+
+#[test]
+fn test_satisfiability_summary() {
+    let challenge = satisfiability::Challenge::generate_instance(
+        [1; 8],
+        &satisfiability::Difficulty {
+            num_variables: 300,
+            clauses_to_variables_percent: 425,
+        },
+    )
+    .unwrap();
+    let summary = challenge.summary();
+    assert!(summary.contains("satisfiability"));
+    assert!(summary.contains("300 variables"));
+    assert!(summary.contains("1275 clauses"));
+    assert!(summary.contains("seeds=[1, 1, 1, 1, 1, 1, 1, 1]"));
+}
+
+#[test]
+fn test_vehicle_routing_summary() {
+    let challenge = vehicle_routing::Challenge::generate_instance(
+        [2; 8],
+        &vehicle_routing::Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 100,
+        },
+    )
+    .unwrap();
+    let summary = challenge.summary();
+    assert!(summary.contains("vehicle_routing"));
+    assert!(summary.contains("40 nodes"));
+    assert!(summary.contains(&format!(
+        "max_total_distance={}",
+        challenge.max_total_distance
+    )));
+    assert!(summary.contains("seeds=[2, 2, 2, 2, 2, 2, 2, 2]"));
+}
+
+#[test]
+fn test_knapsack_summary() {
+    let challenge = knapsack::Challenge::generate_instance(
+        [3; 8],
+        &knapsack::Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        },
+    )
+    .unwrap();
+    let summary = challenge.summary();
+    assert!(summary.contains("knapsack"));
+    assert!(summary.contains("50 items"));
+    assert!(summary.contains(&format!("max_weight={}", challenge.max_weight)));
+    assert!(summary.contains(&format!("min_value={}", challenge.min_value)));
+    assert!(summary.contains("seeds=[3, 3, 3, 3, 3, 3, 3, 3]"));
+}
+
+#[test]
+fn test_vector_search_summary() {
+    // built by hand since generation always creates the full database
+    let challenge = vector_search::Challenge {
+        seeds: [4; 8],
+        difficulty: vector_search::Difficulty {
+            num_queries: 2,
+            better_than_baseline: 0,
+        },
+        vector_database: vec![vec![0.0; 5]; 3],
+        query_vectors: vec![vec![1.0; 5]; 2],
+        max_distance: 6.0,
+    };
+    let summary = challenge.summary();
+    assert!(summary.contains("vector_search"));
+    assert!(summary.contains("2 queries"));
+    assert!(summary.contains("3 database vectors of dimension 5"));
+    assert!(summary.contains("max_distance=6"));
+    assert!(summary.contains("seeds=[4, 4, 4, 4, 4, 4, 4, 4]"));
+}
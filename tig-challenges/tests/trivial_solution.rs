@@ -0,0 +1,84 @@
+use tig_challenges::*;
+
+#[test]
+fn test_knapsack_trivial_solution() {
+    let mut challenge = knapsack::Challenge::generate_instance(
+        [0; 8],
+        &knapsack::Difficulty {
+            num_items: 50,
+            better_than_baseline: 10,
+        },
+    )
+    .unwrap();
+    // an empty knapsack never beats the baseline
+    assert!(challenge.trivial_solution().is_none());
+
+    challenge.min_value = 0;
+    let solution = challenge.trivial_solution().unwrap();
+    assert!(solution.items.is_empty());
+    assert!(challenge.verify_solution(&solution).is_ok());
+}
+
+#[test]
+fn test_vehicle_routing_trivial_solution() {
+    let mut challenge = vehicle_routing::Challenge::generate_instance(
+        [0; 8],
+        &vehicle_routing::Difficulty {
+            num_nodes: 40,
+            better_than_baseline: 0,
+        },
+    )
+    .unwrap();
+    // one route per customer is longer than the baseline
+    assert!(challenge.trivial_solution().is_none());
+
+    challenge.max_total_distance = i32::MAX;
+    let solution = challenge.trivial_solution().unwrap();
+    assert_eq!(solution.routes.len(), 39);
+    assert!(challenge.verify_solution(&solution).is_ok());
+}
+
+#[test]
+fn test_satisfiability_trivial_solution() {
+    let mut challenge = satisfiability::Challenge::generate_instance(
+        [0; 8],
+        &satisfiability::Difficulty {
+            num_variables: 50,
+            clauses_to_variables_percent: 425,
+        },
+    )
+    .unwrap();
+    assert!(challenge.trivial_solution().is_none());
+
+    // every clause has a negative literal, so all false satisfies it
+    challenge.clauses = vec![vec![-1, 2, 3], vec![1, -2, 3], vec![4, 5, -6]];
+    let solution = challenge.trivial_solution().unwrap();
+    assert_eq!(solution.variables, vec![false; 50]);
+    assert!(challenge.verify_solution(&solution).is_ok());
+
+    // every clause has a positive literal, so all true satisfies it
+    challenge.clauses = vec![vec![1, 2, 3], vec![-1, -2, 4]];
+    let solution = challenge.trivial_solution().unwrap();
+    assert_eq!(solution.variables, vec![true; 50]);
+    assert!(challenge.verify_solution(&solution).is_ok());
+}
+
+#[test]
+fn test_vector_search_trivial_solution() {
+    let mut challenge = vector_search::Challenge {
+        seeds: [0; 8],
+        difficulty: vector_search::Difficulty {
+            num_queries: 2,
+            better_than_baseline: 0,
+        },
+        vector_database: vec![vec![0.0, 0.0], vec![5.0, 5.0]],
+        query_vectors: vec![vec![1.0, 0.0], vec![0.0, 1.0]],
+        max_distance: 1.0,
+    };
+    let solution = challenge.trivial_solution().unwrap();
+    assert_eq!(solution.indexes, vec![0, 0]);
+    assert!(challenge.verify_solution(&solution).is_ok());
+
+    challenge.vector_database.swap(0, 1);
+    assert!(challenge.trivial_solution().is_none());
+}
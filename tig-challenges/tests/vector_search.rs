@@ -0,0 +1,133 @@
+use tig_challenges::{vector_search::*, ChallengeTrait};
+
+fn nearest_solution(challenge: &Challenge, levels: u32) -> Solution {
+    let indexes = challenge
+        .query_vectors
+        .iter()
+        .map(|query| {
+            // a prefix of the database keeps the test fast
+            (0..1000)
+                .min_by_key(|&i| {
+                    let d = quantized_distance(query, &challenge.vector_database[i], levels);
+                    d.to_bits()
+                })
+                .unwrap()
+        })
+        .collect();
+    Solution { indexes }
+}
+
+#[test]
+fn test_quantized_instance_is_bit_identical() {
+    let difficulty = Difficulty {
+        num_queries: 3,
+        better_than_baseline: 0,
+    };
+    let levels = 256;
+    let a = Challenge::generate_quantized_instance([1; 8], &difficulty, levels).unwrap();
+    let b = Challenge::generate_quantized_instance([1; 8], &difficulty, levels).unwrap();
+
+    let bits = |c: &Challenge| -> Vec<u32> {
+        c.vector_database
+            .iter()
+            .chain(c.query_vectors.iter())
+            .flat_map(|v| v.iter().map(|x| x.to_bits()))
+            .collect()
+    };
+    assert_eq!(bits(&a), bits(&b));
+    // every coordinate sits on the grid
+    assert!(a
+        .query_vectors
+        .iter()
+        .flatten()
+        .all(|&x| (x * levels as f32).fract() == 0.0));
+
+    let solution_a = nearest_solution(&a, levels);
+    let solution_b = nearest_solution(&b, levels);
+    assert_eq!(solution_a.indexes, solution_b.indexes);
+    assert_eq!(
+        a.verify_solution_quantized(&solution_a, levels)
+            .map_err(|e| e.to_string()),
+        b.verify_solution_quantized(&solution_b, levels)
+            .map_err(|e| e.to_string())
+    );
+    // a perturbation below half a grid step does not change quantized distances
+    let mut c = b;
+    for x in c.query_vectors.iter_mut().flatten() {
+        *x += 0.4 / levels as f32;
+    }
+    for (query_a, query_c) in a.query_vectors.iter().zip(c.query_vectors.iter()) {
+        let search = &a.vector_database[0];
+        assert_eq!(
+            quantized_distance(query_a, search, levels).to_bits(),
+            quantized_distance(query_c, search, levels).to_bits()
+        );
+    }
+}
+
+#[test]
+fn test_invalid_quantization_levels() {
+    let difficulty = Difficulty {
+        num_queries: 1,
+        better_than_baseline: 0,
+    };
+    assert!(Challenge::generate_quantized_instance([0; 8], &difficulty, 0).is_err());
+}
+
+fn small_challenge() -> Challenge {
+    Challenge {
+        seeds: [0; 8],
+        difficulty: Difficulty {
+            num_queries: 2,
+            better_than_baseline: 0,
+        },
+        vector_database: vec![vec![0.0, 0.0], vec![0.5, 0.5], vec![1.0, 1.0]],
+        query_vectors: vec![vec![0.1, 0.1], vec![0.9, 0.9]],
+        max_distance: 6.0,
+    }
+}
+
+#[test]
+fn test_non_finite_values_are_rejected() {
+    let solution = Solution {
+        indexes: vec![0, 1],
+    };
+    assert!(small_challenge().verify_solution(&solution).is_ok());
+    assert!(small_challenge().check_finite().is_ok());
+
+    for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+        // a NaN distance would compare as not exceeding max_distance and pass
+        let mut challenge = small_challenge();
+        challenge.vector_database[1][0] = bad;
+        let err = challenge.verify_solution(&solution).unwrap_err();
+        assert!(err.to_string().contains("Non-finite distance"), "{}", err);
+        assert!(err.to_string().contains("query vector 1"));
+        assert!(challenge
+            .check_finite()
+            .unwrap_err()
+            .to_string()
+            .contains("database vector 1"));
+
+        let mut challenge = small_challenge();
+        challenge.query_vectors[0][1] = bad;
+        assert!(challenge.verify_solution(&solution).is_err());
+        assert!(challenge
+            .check_finite()
+            .unwrap_err()
+            .to_string()
+            .contains("query vector 0"));
+
+        let mut challenge = small_challenge();
+        challenge.max_distance = bad;
+        assert!(challenge
+            .verify_solution(&solution)
+            .unwrap_err()
+            .to_string()
+            .contains("Non-finite max distance"));
+    }
+
+    // vectors the solution doesn't select don't affect verification
+    let mut challenge = small_challenge();
+    challenge.vector_database[2][0] = f32::NAN;
+    assert!(challenge.verify_solution(&solution).is_ok());
+}
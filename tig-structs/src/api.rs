@@ -1,4 +1,4 @@
-use crate::{core::*, serializable_struct_with_getters};
+use crate::{config::DifficultyParameter, core::*, serializable_struct_with_getters};
 use serde::{Deserialize, Serialize};
 
 serializable_struct_with_getters! {
@@ -82,6 +82,24 @@ serializable_struct_with_getters! {
     }
 }
 
+serializable_struct_with_getters! {
+    GetBlockConfigResp {
+        block_id: String,
+        block_details: BlockDetails,
+        challenges: Vec<ChallengeConfig>,
+    }
+}
+
+serializable_struct_with_getters! {
+    ChallengeConfig {
+        challenge_id: String,
+        name: String,
+        difficulty_parameters: Vec<DifficultyParameter>,
+        base_frontier: Option<Frontier>,
+        scaled_frontier: Option<Frontier>,
+    }
+}
+
 serializable_struct_with_getters! {
     GetAlgorithmsReq {
         block_id: String,
@@ -128,11 +146,23 @@ serializable_struct_with_getters! {
     }
 }
 
+// versions of the SolutionData encoding this build can submit, oldest first
+pub const SOLUTION_SCHEMA_VERSIONS: [u32; 1] = [1];
+
+serializable_struct_with_getters! {
+    GetSchemaVersionsResp {
+        versions: Vec<u32>,
+    }
+}
+
 serializable_struct_with_getters! {
     SubmitBenchmarkReq {
         settings: BenchmarkSettings,
         solutions_meta_data: Vec<SolutionMetaData>,
         solution_data: SolutionData,
+        // version of the SolutionData encoding, agreed with the server. see Api::schema_version
+        #[serde(skip_serializing_if = "Option::is_none")]
+        schema_version: Option<u32>,
     }
 }
 
@@ -147,6 +177,8 @@ serializable_struct_with_getters! {
     SubmitProofReq {
         benchmark_id: String,
         solutions_data: Vec<SolutionData>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        schema_version: Option<u32>,
     }
 }
 
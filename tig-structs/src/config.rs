@@ -32,6 +32,8 @@ serializable_struct_with_getters! {
         submission_delay_multiplier: u32,
         max_samples: usize,
         lifespan_period: u32,
+        // largest solution the protocol accepts, as measured by SolutionData::solution_size
+        max_solution_size: Option<usize>,
     }
 }
 serializable_struct_with_getters! {
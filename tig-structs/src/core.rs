@@ -2,7 +2,7 @@ use crate::{config::ProtocolConfig, serializable_struct_with_getters};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::collections::{HashMap, HashSet};
-use tig_utils::{jsonify, u32_from_str, u64s_from_str};
+use tig_utils::{jsonify, stretch_seeds, u32_from_str, u64s_from_str};
 pub use tig_utils::{Frontier, Point, PreciseNumber, Transaction, U256};
 
 serializable_struct_with_getters! {
@@ -110,12 +110,29 @@ serializable_struct_with_getters! {
 }
 impl BenchmarkSettings {
     pub fn calc_seeds(&self, nonce: u64) -> [u64; 8] {
-        let mut seeds = u64s_from_str(jsonify(&self).as_str());
+        self.calc_seeds_with_salt(nonce, None)
+    }
+
+    // a salt gives an instance family disjoint from the protocol's for the same nonces, e.g. for
+    // independent local experiments. salted instances can't be verified by the protocol, so they
+    // must never be submitted. None gives the protocol's seeds
+    pub fn calc_seeds_with_salt(&self, nonce: u64, seed_salt: Option<&str>) -> [u64; 8] {
+        let mut seeds = match seed_salt {
+            None => u64s_from_str(jsonify(&self).as_str()),
+            Some(seed_salt) => u64s_from_str(format!("{}:{}", jsonify(&self), seed_salt).as_str()),
+        };
         for seed in seeds.iter_mut() {
             *seed ^= nonce;
         }
         seeds
     }
+
+    // seed stretching re-hashes the nonce's seeds stretch_rounds times, for exploring whether it
+    // evens out how hard instances are. only for exploratory benchmarking, as the protocol can't
+    // verify stretched instances. 0 rounds gives the protocol's seeds
+    pub fn calc_stretched_seeds(&self, nonce: u64, stretch_rounds: u32) -> [u64; 8] {
+        stretch_seeds(self.calc_seeds(nonce), stretch_rounds)
+    }
 }
 serializable_struct_with_getters! {
     BenchmarkDetails {
@@ -227,11 +244,95 @@ serializable_struct_with_getters! {
         runtime_signature: u32,
         fuel_consumed: u64,
         solution: Solution,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        version_pin: Option<VersionPin>,
+        // set by tig_worker::verify_and_issue_token. only valid in the process that issued it
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verification_token: Option<String>,
     }
 }
 impl SolutionData {
+    // checked constructor for solutions computed under a fuel budget of max_fuel. fields are public
+    // so deserialization and tests can build SolutionData directly
+    pub fn new(
+        nonce: u64,
+        runtime_signature: u32,
+        fuel_consumed: u64,
+        max_fuel: u64,
+        solution: Solution,
+    ) -> Result<Self, String> {
+        if fuel_consumed == 0 {
+            return Err(format!(
+                "Nonce {}: fuel_consumed is 0, but computing a solution always consumes fuel",
+                nonce
+            ));
+        }
+        if fuel_consumed > max_fuel {
+            return Err(format!(
+                "Nonce {}: fuel_consumed ({}) exceeds max_fuel ({})",
+                nonce, fuel_consumed, max_fuel
+            ));
+        }
+        Ok(Self {
+            nonce,
+            runtime_signature,
+            fuel_consumed,
+            solution,
+            version_pin: None,
+            verification_token: None,
+        })
+    }
+
     pub fn calc_solution_signature(&self) -> u32 {
-        u32_from_str(&jsonify(self))
+        // version_pin and verification_token are local metadata and must not change the signature
+        if self.version_pin.is_some() || self.verification_token.is_some() {
+            let mut data = self.clone();
+            data.version_pin = None;
+            data.verification_token = None;
+            u32_from_str(&jsonify(&data))
+        } else {
+            u32_from_str(&jsonify(self))
+        }
+    }
+
+    // bytes the solution takes up in a submitted proof, which carries it as json
+    pub fn solution_size(&self) -> usize {
+        jsonify(&self.solution).len()
+    }
+
+    pub fn version_drift(&self, expected: &VersionPin) -> Option<String> {
+        match &self.version_pin {
+            None => Some("no version pin recorded".to_string()),
+            Some(version_pin) => version_pin.drift(expected),
+        }
+    }
+}
+serializable_struct_with_getters! {
+    VersionPin {
+        challenge_version: String,
+        algorithm_version: String,
+    }
+}
+impl VersionPin {
+    pub fn drift(&self, expected: &VersionPin) -> Option<String> {
+        let mut drifts = Vec::new();
+        if self.challenge_version != expected.challenge_version {
+            drifts.push(format!(
+                "challenge_version {} != {}",
+                self.challenge_version, expected.challenge_version
+            ));
+        }
+        if self.algorithm_version != expected.algorithm_version {
+            drifts.push(format!(
+                "algorithm_version {} != {}",
+                self.algorithm_version, expected.algorithm_version
+            ));
+        }
+        if drifts.is_empty() {
+            None
+        } else {
+            Some(drifts.join(", "))
+        }
     }
 }
 
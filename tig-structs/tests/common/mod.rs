@@ -0,0 +1,14 @@
+// fixtures shared by the tests. every test file is its own crate and only uses some of them
+#![allow(dead_code)]
+
+use tig_structs::core::BenchmarkSettings;
+
+pub fn settings(challenge_id: &str, difficulty: Vec<i32>) -> BenchmarkSettings {
+    BenchmarkSettings {
+        player_id: "0x0000000000000000000000000000000000000000".to_string(),
+        block_id: "block".to_string(),
+        challenge_id: challenge_id.to_string(),
+        algorithm_id: format!("{}_a001", challenge_id),
+        difficulty,
+    }
+}
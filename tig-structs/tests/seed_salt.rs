@@ -0,0 +1,38 @@
+mod common;
+
+use tig_structs::core::BenchmarkSettings;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![50, 300])
+}
+
+#[test]
+fn test_no_salt_preserves_seeds() {
+    let settings = settings();
+    for nonce in 0..10 {
+        assert_eq!(
+            settings.calc_seeds_with_salt(nonce, None),
+            settings.calc_seeds(nonce)
+        );
+    }
+}
+
+#[test]
+fn test_salts_give_different_seeds() {
+    let settings = settings();
+    for nonce in 0..10 {
+        let unsalted = settings.calc_seeds(nonce);
+        let a = settings.calc_seeds_with_salt(nonce, Some("a"));
+        let b = settings.calc_seeds_with_salt(nonce, Some("b"));
+        assert_ne!(a, unsalted);
+        assert_ne!(b, unsalted);
+        assert_ne!(a, b);
+        // deterministic per salt
+        assert_eq!(a, settings.calc_seeds_with_salt(nonce, Some("a")));
+    }
+    // an empty salt is still a salt
+    assert_ne!(
+        settings.calc_seeds_with_salt(0, Some("")),
+        settings.calc_seeds(0)
+    );
+}
@@ -0,0 +1,40 @@
+mod common;
+
+use tig_structs::core::BenchmarkSettings;
+use tig_utils::stretch_seeds;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![50, 300])
+}
+
+#[test]
+fn test_zero_rounds_preserves_seeds() {
+    let settings = settings();
+    for nonce in 0..10 {
+        assert_eq!(
+            settings.calc_stretched_seeds(nonce, 0),
+            settings.calc_seeds(nonce)
+        );
+    }
+}
+
+#[test]
+fn test_stretched_seeds_are_deterministic_and_differ() {
+    let settings = settings();
+    for nonce in 0..10 {
+        let seeds = settings.calc_seeds(nonce);
+        let once = settings.calc_stretched_seeds(nonce, 1);
+        let twice = settings.calc_stretched_seeds(nonce, 2);
+        assert_eq!(once, settings.calc_stretched_seeds(nonce, 1));
+        assert_eq!(twice, settings.calc_stretched_seeds(nonce, 2));
+        assert_ne!(once, seeds);
+        assert_ne!(twice, once);
+        // rounds compose
+        assert_eq!(twice, stretch_seeds(once, 1));
+    }
+    // stretching keeps nonces apart
+    assert_ne!(
+        settings.calc_stretched_seeds(0, 3),
+        settings.calc_stretched_seeds(1, 3)
+    );
+}
@@ -0,0 +1,52 @@
+use serde_json::json;
+use tig_structs::core::{Solution, SolutionData};
+
+fn solution() -> Solution {
+    json!({"items": [1, 4, 7]}).as_object().unwrap().clone()
+}
+
+#[test]
+fn test_new_valid() {
+    let solution_data = SolutionData::new(7, 123, 456, 1000, solution()).unwrap();
+    assert_eq!(
+        solution_data,
+        SolutionData {
+            nonce: 7,
+            runtime_signature: 123,
+            fuel_consumed: 456,
+            solution: solution(),
+            version_pin: None,
+            verification_token: None,
+        }
+    );
+    // the whole budget may be consumed
+    assert!(SolutionData::new(7, 123, 1000, 1000, solution()).is_ok());
+    // an empty solution is a valid result of a computation
+    assert!(SolutionData::new(7, 123, 456, 1000, Solution::new()).is_ok());
+}
+
+#[test]
+fn test_new_rejects_zero_fuel() {
+    let err = SolutionData::new(7, 123, 0, 1000, solution()).unwrap_err();
+    assert!(err.contains("Nonce 7"));
+    assert!(err.contains("fuel_consumed is 0"));
+}
+
+#[test]
+fn test_new_rejects_fuel_over_budget() {
+    let err = SolutionData::new(7, 123, 1001, 1000, solution()).unwrap_err();
+    assert!(err.contains("Nonce 7"));
+    assert!(err.contains("exceeds max_fuel (1000)"));
+}
+
+#[test]
+fn test_solution_size() {
+    let solution_data = SolutionData::new(7, 123, 456, 1000, solution()).unwrap();
+    assert_eq!(solution_data.solution_size(), r#"{"items":[1,4,7]}"#.len());
+    assert_eq!(
+        solution_data.solution_size(),
+        tig_utils::jsonify(&solution_data.solution).len()
+    );
+    let empty = SolutionData::new(7, 123, 456, 1000, Solution::new()).unwrap();
+    assert_eq!(empty.solution_size(), 2);
+}
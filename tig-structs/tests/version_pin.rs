@@ -0,0 +1,55 @@
+use tig_structs::core::{Solution, SolutionData, VersionPin};
+
+fn version_pin(challenge_version: &str, algorithm_version: &str) -> VersionPin {
+    VersionPin {
+        challenge_version: challenge_version.to_string(),
+        algorithm_version: algorithm_version.to_string(),
+    }
+}
+
+fn solution_data(version_pin: Option<VersionPin>) -> SolutionData {
+    SolutionData {
+        nonce: 7,
+        runtime_signature: 123,
+        fuel_consumed: 456,
+        solution: Solution::new(),
+        version_pin,
+        verification_token: None,
+    }
+}
+
+#[test]
+fn test_version_drift() {
+    let expected = version_pin("0.1.0", "abc");
+    assert_eq!(
+        solution_data(Some(expected.clone())).version_drift(&expected),
+        None
+    );
+
+    let drift = solution_data(Some(version_pin("0.1.0", "def")))
+        .version_drift(&expected)
+        .unwrap();
+    assert!(drift.contains("algorithm_version"));
+    assert!(!drift.contains("challenge_version"));
+
+    let drift = solution_data(Some(version_pin("0.2.0", "def")))
+        .version_drift(&expected)
+        .unwrap();
+    assert!(drift.contains("algorithm_version"));
+    assert!(drift.contains("challenge_version"));
+
+    assert!(solution_data(None).version_drift(&expected).is_some());
+}
+
+#[test]
+fn test_version_pin_does_not_change_signature() {
+    let unpinned = solution_data(None);
+    let pinned = solution_data(Some(version_pin("0.1.0", "abc")));
+    assert_eq!(
+        unpinned.calc_solution_signature(),
+        pinned.calc_solution_signature()
+    );
+    assert!(!serde_json::to_string(&unpinned)
+        .unwrap()
+        .contains("version_pin"));
+}
@@ -29,3 +29,19 @@ pub fn u64s_from_str(input: &str) -> [u64; 8] {
     }
     output
 }
+
+// re-hashes seeds rounds times with keccak512. 0 rounds returns the seeds unchanged
+pub fn stretch_seeds(seeds: [u64; 8], rounds: u32) -> [u64; 8] {
+    let mut seeds = seeds;
+    for _ in 0..rounds {
+        let mut hasher = Keccak512::new();
+        for seed in seeds.iter() {
+            hasher.update(seed.to_le_bytes());
+        }
+        let result = hasher.finalize();
+        for (i, seed) in seeds.iter_mut().enumerate() {
+            *seed = u64::from_le_bytes(result[i * 8..(i + 1) * 8].try_into().unwrap());
+        }
+    }
+    seeds
+}
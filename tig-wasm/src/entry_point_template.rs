@@ -1,5 +1,7 @@
+use bincode::Options;
 use tig_algorithms::{CHALLENGE}::{ALGORITHM};
 use tig_challenges::{CHALLENGE}::*;
+use tig_challenges::{ChallengeTrait, SolveOutcome, PROVED_NO_SOLUTION_LEN};
 use tig_utils::compress_obj;
 
 #[no_mangle]
@@ -11,10 +13,25 @@ pub fn init(len: u32) -> *mut u8 {
 pub fn entry_point(ptr: *mut u8, len: u32) -> *mut u8 {
     let challenge: Challenge = {
         let challenge_data = unsafe { Vec::from_raw_parts(ptr, len as usize, len as usize) };
-        bincode::deserialize(&challenge_data).expect("Failed to deserialize challenge")
+        // must match tig-worker's instance_encoding
+        bincode::DefaultOptions::new()
+            .with_little_endian()
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .deserialize(&challenge_data)
+            .expect("Failed to deserialize challenge")
     };
-    let result: anyhow::Result<Option<Solution>> = {ALGORITHM}::solve_challenge(&challenge);
-    if let Ok(Some(solution)) = result {
+    // solve_challenge returns either an Option or a SolveOutcome
+    let result: anyhow::Result<SolveOutcome<Solution>> =
+        {ALGORITHM}::solve_challenge(&challenge).map(SolveOutcome::from);
+    // a solution longer than the instance allows can't verify, so it isn't worth serializing
+    let result = result.map(|outcome| match outcome {
+        SolveOutcome::Solved(solution) if challenge.check_solution_len(&solution).is_err() => {
+            SolveOutcome::GaveUp
+        }
+        outcome => outcome,
+    });
+    if let Ok(SolveOutcome::Solved(solution)) = result {
         let serialized_solution = compress_obj(&solution);
         let solution_length = serialized_solution.len() as u32;
         let solution_ptr = init(solution_length + 4);
@@ -29,9 +46,13 @@ pub fn entry_point(ptr: *mut u8, len: u32) -> *mut u8 {
         }
         solution_ptr
     } else {
+        let solution_length = match result {
+            Ok(SolveOutcome::ProvedNoSolution) => PROVED_NO_SOLUTION_LEN,
+            _ => 0u32,
+        };
         let solution_ptr = init(4);
         unsafe {
-            let solution_length = 0u32.to_le_bytes();
+            let solution_length = solution_length.to_le_bytes();
             std::ptr::copy_nonoverlapping(solution_length.as_ptr(), solution_ptr, 4);
         }
         solution_ptr
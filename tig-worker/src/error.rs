@@ -0,0 +1,73 @@
+use wasmi::core::TrapCode;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WasmError {
+    // the bytes are not a valid module, e.g. a corrupt or truncated download
+    CompileFailed { reason: String },
+    InstantiateFailed { reason: String },
+    MissingExport { name: String },
+    Trap { reason: String },
+    OutOfFuel { max_fuel: u64 },
+    OutOfMemory { max_memory: u64 },
+}
+
+impl WasmError {
+    // classifies an error returned by a call into the module
+    pub(crate) fn from_call(e: wasmi::Error, max_memory: u64, max_fuel: u64) -> Self {
+        match e.as_trap_code() {
+            Some(TrapCode::OutOfFuel) => WasmError::OutOfFuel { max_fuel },
+            // the store traps when memory can't grow past max_memory
+            Some(TrapCode::GrowthOperationLimited) => WasmError::OutOfMemory { max_memory },
+            _ => WasmError::Trap {
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for WasmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmError::CompileFailed { reason } => {
+                write!(f, "Failed to compile wasm module: {}", reason)
+            }
+            WasmError::InstantiateFailed { reason } => {
+                write!(f, "Failed to instantiate wasm module: {}", reason)
+            }
+            WasmError::MissingExport { name } => {
+                write!(f, "Wasm module does not export `{}`", name)
+            }
+            WasmError::Trap { reason } => write!(f, "Wasm module trapped: {}", reason),
+            WasmError::OutOfFuel { max_fuel } => {
+                write!(f, "Wasm module ran out of fuel (max_fuel {})", max_fuel)
+            }
+            WasmError::OutOfMemory { max_memory } => {
+                write!(
+                    f,
+                    "Wasm module ran out of memory (max_memory {})",
+                    max_memory
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for WasmError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComputeError {
+    // generating the nonce's instance exceeded its budget, so the wasm never ran
+    GenerationTimeout { timeout_ms: u64 },
+}
+
+impl std::fmt::Display for ComputeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComputeError::GenerationTimeout { timeout_ms } => {
+                write!(f, "Instance generation timed out after {}ms", timeout_ms)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ComputeError {}
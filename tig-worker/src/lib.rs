@@ -1,2 +1,4 @@
+mod error;
 mod worker;
+pub use error::*;
 pub use worker::*;
@@ -1,6 +1,11 @@
+mod error;
 mod worker;
 use clap::{arg, Command};
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
 use tig_structs::core::BenchmarkSettings;
 use tig_utils::{dejsonify, jsonify};
 
@@ -26,6 +31,10 @@ fn cli() -> Command {
                     arg!(--mem [MEM] "Optional maximum memory parameter for WASM VM")
                         .default_value("1000000000")
                         .value_parser(clap::value_parser!(u64)),
+                )
+                .arg(
+                    arg!(--"generation-timeout" [MS] "Optional maximum milliseconds for generating the instance")
+                        .value_parser(clap::value_parser!(u64)),
                 ),
         )
         .subcommand(
@@ -37,10 +46,28 @@ fn cli() -> Command {
                 )
                 .arg(arg!(<NONCE> "Nonce value").value_parser(clap::value_parser!(u64)))
                 .arg(
-                    arg!(<SOLUTION> "Solution json string or path to json file")
+                    arg!(<SOLUTION> "Solution json string, path to json file, or - to read stdin")
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(
+                    arg!(--difficulty [DIFFICULTY] "Optional difficulty json array to verify against, no harder than the settings' difficulty")
                         .value_parser(clap::value_parser!(String)),
                 ),
         )
+        .subcommand(
+            Command::new("explain")
+                .about("Describes the instance a nonce generates")
+                .arg(
+                    arg!(<SETTINGS> "Settings json string or path to json file")
+                        .value_parser(clap::value_parser!(String)),
+                )
+                .arg(arg!(<NONCE> "Nonce value").value_parser(clap::value_parser!(u64))),
+        )
+        .subcommand(
+            Command::new("inspect")
+                .about("Lists what a wasm imports and exports and the memory it needs. Fails if it imports host functions the worker doesn't provide")
+                .arg(arg!(<WASM> "Path to a wasm file").value_parser(clap::value_parser!(PathBuf))),
+        )
 }
 
 fn main() {
@@ -53,12 +80,19 @@ fn main() {
             sub_m.get_one::<PathBuf>("WASM").unwrap().clone(),
             *sub_m.get_one::<u64>("mem").unwrap(),
             *sub_m.get_one::<u64>("fuel").unwrap(),
+            sub_m.get_one::<u64>("generation-timeout").copied(),
         ),
         Some(("verify_solution", sub_m)) => verify_solution(
             sub_m.get_one::<String>("SETTINGS").unwrap().clone(),
             *sub_m.get_one::<u64>("NONCE").unwrap(),
             sub_m.get_one::<String>("SOLUTION").unwrap().clone(),
+            sub_m.get_one::<String>("difficulty").cloned(),
         ),
+        Some(("explain", sub_m)) => explain(
+            sub_m.get_one::<String>("SETTINGS").unwrap().clone(),
+            *sub_m.get_one::<u64>("NONCE").unwrap(),
+        ),
+        Some(("inspect", sub_m)) => inspect(sub_m.get_one::<PathBuf>("WASM").unwrap().clone()),
         _ => {}
     }
 }
@@ -69,6 +103,7 @@ fn compute_solution(
     wasm_path: PathBuf,
     max_memory: u64,
     max_fuel: u64,
+    generation_timeout_ms: Option<u64>,
 ) {
     if settings.ends_with(".json") {
         settings = fs::read_to_string(&settings).unwrap_or_else(|_| {
@@ -86,7 +121,18 @@ fn compute_solution(
         std::process::exit(1);
     });
 
-    match worker::compute_solution(&settings, nonce, wasm.as_slice(), max_memory, max_fuel) {
+    let result = match generation_timeout_ms {
+        Some(generation_timeout_ms) => worker::compute_solution_with_generation_timeout(
+            &settings,
+            nonce,
+            wasm.as_slice(),
+            max_memory,
+            max_fuel,
+            generation_timeout_ms,
+        ),
+        None => worker::compute_solution(&settings, nonce, wasm.as_slice(), max_memory, max_fuel),
+    };
+    match result {
         Ok(Some(solution_data)) => {
             println!("{}", jsonify(&solution_data));
             if solution_data.solution.len() == 0 {
@@ -114,7 +160,12 @@ fn compute_solution(
     }
 }
 
-fn verify_solution(mut settings: String, nonce: u64, mut solution: String) {
+fn verify_solution(
+    mut settings: String,
+    nonce: u64,
+    mut solution: String,
+    difficulty: Option<String>,
+) {
     if settings.ends_with(".json") {
         settings = fs::read_to_string(&settings).unwrap_or_else(|_| {
             eprintln!("Failed to read settings file: {}", settings);
@@ -126,7 +177,15 @@ fn verify_solution(mut settings: String, nonce: u64, mut solution: String) {
         std::process::exit(1);
     });
 
-    if solution.ends_with(".json") {
+    if solution == "-" {
+        solution = String::new();
+        io::stdin()
+            .read_to_string(&mut solution)
+            .unwrap_or_else(|_| {
+                eprintln!("Failed to read solution from stdin");
+                std::process::exit(1);
+            });
+    } else if solution.ends_with(".json") {
         solution = fs::read_to_string(&solution).unwrap_or_else(|_| {
             eprintln!("Failed to read solution file: {}", solution);
             std::process::exit(1);
@@ -136,8 +195,15 @@ fn verify_solution(mut settings: String, nonce: u64, mut solution: String) {
         eprintln!("Failed to parse solution");
         std::process::exit(1);
     });
+    let difficulty = match difficulty {
+        Some(difficulty) => dejsonify::<Vec<i32>>(&difficulty).unwrap_or_else(|_| {
+            eprintln!("Failed to parse difficulty");
+            std::process::exit(1);
+        }),
+        None => settings.difficulty.clone(),
+    };
 
-    match worker::verify_solution(&settings, nonce, &solution) {
+    match worker::verify_solution_at_difficulty(&settings, nonce, &solution, &difficulty) {
         Ok(()) => {
             println!("Solution is valid");
             std::process::exit(0);
@@ -148,3 +214,54 @@ fn verify_solution(mut settings: String, nonce: u64, mut solution: String) {
         }
     }
 }
+
+fn explain(mut settings: String, nonce: u64) {
+    if settings.ends_with(".json") {
+        settings = fs::read_to_string(&settings).unwrap_or_else(|_| {
+            eprintln!("Failed to read settings file: {}", settings);
+            std::process::exit(1);
+        });
+    }
+    let settings = dejsonify::<BenchmarkSettings>(&settings).unwrap_or_else(|_| {
+        eprintln!("Failed to parse settings");
+        std::process::exit(1);
+    });
+
+    match worker::explain_instance(&settings, nonce) {
+        Ok(explanation) => println!("{}", explanation),
+        Err(e) => {
+            eprintln!("Failed to generate instance: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn inspect(wasm_path: PathBuf) {
+    let wasm = fs::read(&wasm_path).unwrap_or_else(|_| {
+        eprintln!("Failed to read wasm file: {}", wasm_path.display());
+        std::process::exit(1);
+    });
+
+    match worker::inspect_wasm(wasm.as_slice()) {
+        Ok(interface) => {
+            println!("{}", jsonify(&interface));
+            for name in interface.missing_exports() {
+                eprintln!("Missing export: {}", name);
+            }
+            let unexpected_imports = interface.unexpected_imports();
+            for import in unexpected_imports.iter() {
+                eprintln!(
+                    "Unexpected import: {}.{} ({})",
+                    import.module, import.name, import.kind
+                );
+            }
+            if !unexpected_imports.is_empty() {
+                std::process::exit(1);
+            }
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
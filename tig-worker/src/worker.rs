@@ -1,9 +1,26 @@
+use crate::error::{ComputeError, WasmError};
 use anyhow::{anyhow, Result};
-use bincode;
+use bincode::Options;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    any::{type_name, Any, TypeId},
+    cmp::Ordering,
+    collections::{hash_map::RandomState, HashMap},
+    hash::BuildHasher,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, OnceLock,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 use tig_challenges::*;
-pub use tig_structs::core::{BenchmarkSettings, Solution, SolutionData};
-use tig_utils::decompress_obj;
-use wasmi::{Config, Engine, Linker, Module, Store, StoreLimitsBuilder};
+pub use tig_structs::core::{BenchmarkSettings, Solution, SolutionData, VersionPin};
+use tig_utils::{decompress_obj, dejsonify, jsonify, md5_from_bytes, stretch_seeds, u64s_from_str};
+use wasmi::{core::Pages, Config, Engine, ExternType, Linker, Module, Store, StoreLimitsBuilder};
 
 pub fn compute_solution(
     settings: &BenchmarkSettings,
@@ -12,108 +29,514 @@ pub fn compute_solution(
     max_memory: u64,
     max_fuel: u64,
 ) -> Result<Option<SolutionData>> {
-    let seeds = settings.calc_seeds(nonce);
+    let serialized_challenge = generate_serialized_instance(settings, nonce)?;
+    compute_solution_for_instance(nonce, &serialized_challenge, wasm, max_memory, max_fuel)
+}
+
+// compute_solution with instance generation limited to generation_timeout_ms, for difficulties where
+// generation itself can run away. complements max_fuel, which only limits solving. not available in
+// the browser, which can't spawn threads
+pub fn compute_solution_with_generation_timeout(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+    generation_timeout_ms: u64,
+) -> Result<Option<SolutionData>> {
+    let settings = settings.clone();
+    let serialized_challenge = generate_with_timeout(generation_timeout_ms, move || {
+        generate_serialized_instance(&settings, nonce)
+    })?;
+    compute_solution_for_instance(nonce, &serialized_challenge, wasm, max_memory, max_fuel)
+}
+
+// runs generate on its own thread and fails with ComputeError::GenerationTimeout if it takes longer
+// than timeout_ms. generation can't be interrupted, so a timed out thread is left to finish in the
+// background and its instance is dropped
+pub fn generate_with_timeout(
+    timeout_ms: u64,
+    generate: impl FnOnce() -> Result<Vec<u8>> + Send + 'static,
+) -> Result<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // the receiver is gone if generation timed out
+        let _ = sender.send(generate());
+    });
+    match receiver.recv_timeout(Duration::from_millis(timeout_ms)) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            Err(ComputeError::GenerationTimeout { timeout_ms }.into())
+        }
+        Err(RecvTimeoutError::Disconnected) => Err(anyhow!("Instance generation panicked")),
+    }
+}
+
+// how a run's instances differ from the protocol's, e.g. for independent local experiments. the
+// protocol only verifies its own instances, so solutions to any others must never be submitted. the
+// default gives the protocol's instances
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct InstanceOptions {
+    // see calc_seeds_with_salt
+    #[serde(default)]
+    pub seed_salt: Option<String>,
+    // see calc_stretched_seeds. the salted seeds are stretched, 0 rounds leaves them as they are
+    #[serde(default)]
+    pub stretch_rounds: u32,
+    // snaps vector search instances to this many levels per coordinate and verifies them with
+    // integer distances, see generate_quantized_instance. other challenges ignore it
+    #[serde(default)]
+    pub quantization_levels: Option<u32>,
+    // generates vehicle routing and vector search instances from the json dataset at this path, see
+    // generate_instance_from_dataset. other challenges ignore it
+    #[serde(default)]
+    pub dataset: Option<PathBuf>,
+}
+
+impl InstanceOptions {
+    pub fn is_protocol(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub fn calc_seeds(&self, settings: &BenchmarkSettings, nonce: u64) -> [u64; 8] {
+        stretch_seeds(
+            settings.calc_seeds_with_salt(nonce, self.seed_salt.as_deref()),
+            self.stretch_rounds,
+        )
+    }
+}
+
+// generation is split from solving so instances can be generated ahead of time, e.g. on another
+// thread while the wasm runs
+pub fn generate_serialized_instance(settings: &BenchmarkSettings, nonce: u64) -> Result<Vec<u8>> {
+    generate_serialized_instance_with_options(settings, nonce, &InstanceOptions::default())
+}
+
+pub fn generate_serialized_instance_with_options(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    options: &InstanceOptions,
+) -> Result<Vec<u8>> {
+    let seeds = options.calc_seeds(settings, nonce);
     let serialized_challenge = match settings.challenge_id.as_str() {
         "c001" => {
             let challenge =
                 satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
                     .unwrap();
-            bincode::serialize(&challenge).unwrap()
+            serialize_instance(&challenge)?
         }
         "c002" => {
-            let challenge =
-                vehicle_routing::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .unwrap();
-            bincode::serialize(&challenge).unwrap()
+            let challenge = generate_vehicle_routing_instance(settings, nonce, options).unwrap();
+            serialize_instance(&challenge)?
         }
         "c003" => {
             let challenge =
                 knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
                     .unwrap();
-            bincode::serialize(&challenge).unwrap()
+            serialize_instance(&challenge)?
         }
         "c004" => {
-            let challenge =
-                vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .unwrap();
-            bincode::serialize(&challenge).unwrap()
+            let challenge = generate_vector_search_instance(settings, nonce, options).unwrap();
+            serialize_instance(&challenge)?
         }
         _ => panic!("Unknown challenge"),
     };
+    Ok(serialized_challenge)
+}
+
+// the nonce's vehicle routing instance as the options generate it, e.g. from a dataset
+pub fn generate_vehicle_routing_instance(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    options: &InstanceOptions,
+) -> Result<vehicle_routing::Challenge> {
+    let seeds = options.calc_seeds(settings, nonce);
+    match options.dataset.as_ref() {
+        Some(path) => vehicle_routing::Challenge::generate_instance_from_dataset(
+            seeds,
+            &vehicle_routing::Difficulty::from_arr(&difficulty_arr(settings)?),
+            &*cached_dataset(path)?,
+        ),
+        None => vehicle_routing::Challenge::generate_instance_from_vec(seeds, &settings.difficulty),
+    }
+}
+
+// the nonce's vector search instance as the options generate it, e.g. quantized
+pub fn generate_vector_search_instance(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    options: &InstanceOptions,
+) -> Result<vector_search::Challenge> {
+    let seeds = options.calc_seeds(settings, nonce);
+    let difficulty =
+        || difficulty_arr(settings).map(|arr| vector_search::Difficulty::from_arr(&arr));
+    match (options.quantization_levels, options.dataset.as_ref()) {
+        (Some(_), Some(_)) => Err(anyhow!(
+            "Quantized instances cannot be generated from a dataset"
+        )),
+        (Some(levels), None) => {
+            vector_search::Challenge::generate_quantized_instance(seeds, &difficulty()?, levels)
+        }
+        (None, Some(path)) => vector_search::Challenge::generate_instance_from_dataset(
+            seeds,
+            &difficulty()?,
+            &*cached_dataset(path)?,
+        ),
+        (None, None) => {
+            vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
+        }
+    }
+}
 
+// every nonce of a run generates from the same dataset, so each is only loaded once per process
+fn cached_dataset<D: DeserializeOwned + Send + Sync + 'static>(path: &Path) -> Result<Arc<D>> {
+    type Datasets = HashMap<(PathBuf, TypeId), Arc<dyn Any + Send + Sync>>;
+    static DATASETS: OnceLock<Mutex<Datasets>> = OnceLock::new();
+    let mut datasets = DATASETS.get_or_init(Default::default).lock().unwrap();
+    let key = (path.to_path_buf(), TypeId::of::<D>());
+    if let Some(dataset) = datasets.get(&key) {
+        return Ok(dataset.clone().downcast::<D>().unwrap());
+    }
+    let dataset = Arc::new(load_dataset::<D>(path)?);
+    datasets.insert(key, dataset.clone());
+    Ok(dataset)
+}
+
+// verifies a solution to an instance from generate_vector_search_instance with the same options
+pub fn verify_vector_search_solution(
+    challenge: &vector_search::Challenge,
+    solution: &vector_search::Solution,
+    options: &InstanceOptions,
+) -> Result<()> {
+    match options.quantization_levels {
+        Some(levels) => challenge.verify_solution_quantized(solution, levels),
+        None => challenge.verify_solution(solution),
+    }
+}
+
+// instances cross machines, e.g. a benchmarker's snapshot is verified by a validator, so their
+// encoding is pinned instead of left to bincode's defaults: little endian, fixed width integers.
+// the wasm entry point decodes them with the same options
+fn instance_encoding() -> impl Options {
+    bincode::DefaultOptions::new()
+        .with_little_endian()
+        .with_fixint_encoding()
+        .allow_trailing_bytes()
+}
+
+pub fn serialize_instance<T: Serialize>(challenge: &T) -> Result<Vec<u8>> {
+    instance_encoding()
+        .serialize(challenge)
+        .map_err(|e| anyhow!("Failed to serialize instance: {}", e))
+}
+
+pub fn deserialize_instance<T: DeserializeOwned>(instance_bytes: &[u8]) -> Result<T> {
+    instance_encoding()
+        .deserialize(instance_bytes)
+        .map_err(|e| anyhow!(e))
+}
+
+// serialized_challenge must come from generate_serialized_instance for the same nonce
+pub fn compute_solution_for_instance(
+    nonce: u64,
+    serialized_challenge: &[u8],
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+) -> Result<Option<SolutionData>> {
+    let (solution_data, _) =
+        run_entry_point(nonce, serialized_challenge, wasm, max_memory, max_fuel)?;
+    Ok(Some(solution_data))
+}
+
+// like compute_solution, but tells apart a solver that proved the instance has no solution from one
+// that gave up. only a Solved outcome carries solution data
+pub fn compute_outcome(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+) -> Result<SolveOutcome<SolutionData>> {
+    let serialized_challenge = generate_serialized_instance(settings, nonce)?;
+    compute_outcome_for_instance(nonce, &serialized_challenge, wasm, max_memory, max_fuel)
+}
+
+pub fn compute_outcome_for_instance(
+    nonce: u64,
+    serialized_challenge: &[u8],
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+) -> Result<SolveOutcome<SolutionData>> {
+    let (solution_data, solution_len) =
+        run_entry_point(nonce, serialized_challenge, wasm, max_memory, max_fuel)?;
+    Ok(match solution_len {
+        0 => SolveOutcome::GaveUp,
+        PROVED_NO_SOLUTION_LEN => SolveOutcome::ProvedNoSolution,
+        _ => SolveOutcome::Solved(solution_data),
+    })
+}
+
+fn engine() -> Engine {
     let mut config = Config::default();
     config.update_runtime_signature(true);
     config.consume_fuel(true);
+    Engine::new(&config)
+}
+
+// compiles the wasm without running it, e.g. to catch a corrupt download before it is cached
+pub fn check_wasm(wasm: &[u8]) -> std::result::Result<(), WasmError> {
+    Module::new(&engine(), wasm)
+        .map(|_| ())
+        .map_err(|e| WasmError::CompileFailed {
+            reason: e.to_string(),
+        })
+}
+
+// host functions the worker links into a module. run_entry_point links none, so a module that
+// imports anything fails to instantiate
+pub const HOST_IMPORTS: [(&str, &str); 0] = [];
+// exports run_entry_point calls
+pub const REQUIRED_EXPORTS: [&str; 3] = ["memory", "init", "entry_point"];
+const PAGE_SIZE: u64 = 65536;
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WasmImport {
+    pub module: String,
+    pub name: String,
+    // e.g. a function's signature
+    pub kind: String,
+    // not one of HOST_IMPORTS
+    pub unexpected: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WasmExport {
+    pub name: String,
+    pub kind: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct WasmInterface {
+    pub imports: Vec<WasmImport>,
+    pub exports: Vec<WasmExport>,
+    // bytes of memory the module starts with and the most it declares it can grow to. None if it
+    // neither imports nor exports a memory, or declares no max
+    pub initial_memory: Option<u64>,
+    pub max_memory: Option<u64>,
+}
+
+impl WasmInterface {
+    pub fn unexpected_imports(&self) -> Vec<&WasmImport> {
+        self.imports.iter().filter(|x| x.unexpected).collect()
+    }
+
+    pub fn missing_exports(&self) -> Vec<&'static str> {
+        REQUIRED_EXPORTS
+            .into_iter()
+            .filter(|name| !self.exports.iter().any(|x| x.name == *name))
+            .collect()
+    }
+}
+
+// what a module imports and exports and how much memory it needs, from compiling it without
+// running it. for auditing a downloaded algorithm before trusting it
+pub fn inspect_wasm(wasm: &[u8]) -> std::result::Result<WasmInterface, WasmError> {
+    let module = Module::new(&engine(), wasm).map_err(|e| WasmError::CompileFailed {
+        reason: e.to_string(),
+    })?;
+    let imports: Vec<WasmImport> = module
+        .imports()
+        .map(|x| WasmImport {
+            module: x.module().to_string(),
+            name: x.name().to_string(),
+            kind: extern_kind(x.ty()),
+            unexpected: !HOST_IMPORTS.contains(&(x.module(), x.name())),
+        })
+        .collect();
+    let exports: Vec<WasmExport> = module
+        .exports()
+        .map(|x| WasmExport {
+            name: x.name().to_string(),
+            kind: extern_kind(x.ty()),
+        })
+        .collect();
+    let memory = module
+        .imports()
+        .map(|x| x.ty().clone())
+        .chain(module.exports().map(|x| x.ty().clone()))
+        .find_map(|ty| match ty {
+            ExternType::Memory(memory) => Some(memory),
+            _ => None,
+        });
+    Ok(WasmInterface {
+        imports,
+        exports,
+        initial_memory: memory.as_ref().map(|x| pages_to_bytes(x.initial_pages())),
+        max_memory: memory
+            .as_ref()
+            .and_then(|x| x.maximum_pages())
+            .map(pages_to_bytes),
+    })
+}
+
+fn extern_kind(ty: &ExternType) -> String {
+    match ty {
+        ExternType::Func(func) => format!("func {:?} -> {:?}", func.params(), func.results()),
+        ExternType::Memory(_) => "memory".to_string(),
+        ExternType::Table(_) => "table".to_string(),
+        ExternType::Global(_) => "global".to_string(),
+    }
+}
+
+fn pages_to_bytes(pages: Pages) -> u64 {
+    u32::from(pages) as u64 * PAGE_SIZE
+}
+
+// runs the wasm on the instance. returns the solution data, with an empty solution if the solver
+// found none, and the solution length the entry point reported. failures of the wasm itself are
+// returned as a WasmError, which callers can downcast to
+fn run_entry_point(
+    nonce: u64,
+    serialized_challenge: &[u8],
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+) -> Result<(SolutionData, u32)> {
     let limits = StoreLimitsBuilder::new()
         .memory_size(max_memory as usize)
         .memories(1)
         .trap_on_grow_failure(true)
         .build();
     // Setup instance of wasm module
-    let engine = Engine::new(&config);
+    let engine = engine();
     let mut store = Store::new(&engine, limits);
     store.limiter(|lim| lim);
     store.set_fuel(max_fuel).unwrap();
     let linker = Linker::new(&engine);
-    let module = Module::new(store.engine(), wasm).expect("Failed to instantiate module");
+    let module = Module::new(store.engine(), wasm).map_err(|e| WasmError::CompileFailed {
+        reason: e.to_string(),
+    })?;
 
     let instance = &linker
         .instantiate(&mut store, &module)
-        .expect("Failed to instantiate linker")
-        .start(&mut store)
-        .expect("Failed to start module");
+        .and_then(|x| x.start(&mut store))
+        .map_err(|e| WasmError::InstantiateFailed {
+            reason: e.to_string(),
+        })?;
 
     let memory = instance
         .get_memory(&store, "memory")
-        .expect("Failed to find memory");
+        .ok_or_else(|| WasmError::MissingExport {
+            name: "memory".to_string(),
+        })?;
 
     // Run algorithm
     let init = instance
         .get_typed_func::<u32, u32>(&store, "init")
-        .expect("Failed to find `init` function");
+        .map_err(|_| WasmError::MissingExport {
+            name: "init".to_string(),
+        })?;
     let entry_point = instance
         .get_typed_func::<(u32, u32), u32>(&store, "entry_point")
-        .expect("Failed to find `entry_point` function");
+        .map_err(|_| WasmError::MissingExport {
+            name: "entry_point".to_string(),
+        })?;
 
     let challenge_len = serialized_challenge.len() as u32;
-    let challenge_ptr: u32 = init.call(&mut store, challenge_len).unwrap();
+    let challenge_ptr: u32 = init
+        .call(&mut store, challenge_len)
+        .map_err(|e| WasmError::from_call(e, max_memory, max_fuel))?;
     memory
-        .write(&mut store, challenge_ptr as usize, &serialized_challenge)
-        .expect("Failed to write serialized challenge to `memory`");
+        .write(&mut store, challenge_ptr as usize, serialized_challenge)
+        .map_err(|e| WasmError::Trap {
+            reason: format!("Failed to write serialized challenge to `memory`: {}", e),
+        })?;
     let solution_ptr = entry_point
         .call(&mut store, (challenge_ptr, challenge_len))
-        .map_err(|e| anyhow!("Failed to call function: {:?}", e))?;
+        .map_err(|e| WasmError::from_call(e, max_memory, max_fuel))?;
 
     // Get runtime signature
     let runtime_signature_u64 = store.get_runtime_signature();
     let runtime_signature = (runtime_signature_u64 as u32) ^ ((runtime_signature_u64 >> 32) as u32);
     let fuel_consumed = max_fuel - store.get_fuel().unwrap();
-    // Read solution from memory
+    // Read solution from memory. the pointer and length come from the module, so a bad one is the
+    // module's fault, like any other trap
     let mut solution_len_bytes = [0u8; 4];
     memory
         .read(&store, solution_ptr as usize, &mut solution_len_bytes)
-        .expect("Failed to read solution length from memory");
+        .map_err(|e| WasmError::Trap {
+            reason: format!("Failed to read solution length from `memory`: {}", e),
+        })?;
     let solution_len = u32::from_le_bytes(solution_len_bytes);
-    let mut serialized_solution = vec![0u8; solution_len as usize];
-    memory
-        .read(
-            &store,
-            (solution_ptr + 4) as usize,
-            &mut serialized_solution,
-        )
-        .expect("Failed to read solution from memory");
-    let mut solution_data = SolutionData {
-        nonce,
-        runtime_signature,
-        fuel_consumed,
-        solution: Solution::new(),
+    let solution = if solution_len != 0 && solution_len != PROVED_NO_SOLUTION_LEN {
+        let solution_start = solution_ptr as usize + 4;
+        // checked before allocating, the length can be anything up to 4GB
+        if solution_start + solution_len as usize > memory.data(&store).len() {
+            return Err(WasmError::Trap {
+                reason: format!(
+                    "Solution of {} bytes at {} is out of bounds of `memory`",
+                    solution_len, solution_start
+                ),
+            }
+            .into());
+        }
+        let mut serialized_solution = vec![0u8; solution_len as usize];
+        memory
+            .read(&store, solution_start, &mut serialized_solution)
+            .map_err(|e| WasmError::Trap {
+                reason: format!("Failed to read solution from `memory`: {}", e),
+            })?;
+        decompress_obj(&serialized_solution).map_err(|e| WasmError::Trap {
+            reason: format!("Failed to decompress solution: {}", e),
+        })?
+    } else {
+        Solution::new()
     };
-    if solution_len != 0 {
-        solution_data.solution =
-            decompress_obj(&serialized_solution).expect("Failed to decompress solution");
+    let solution_data =
+        SolutionData::new(nonce, runtime_signature, fuel_consumed, max_fuel, solution)
+            .map_err(|e| anyhow!(e))?;
+    Ok((solution_data, solution_len))
+}
+
+// retries a nonce that ran out of memory once with max_memory raised to retry_max_memory. only meant
+// for local exploration: the protocol recomputes solutions under the block's limits, so a solution
+// that needed the raised limit will not verify if submitted
+pub fn compute_solution_with_retry(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    wasm: &[u8],
+    max_memory: u64,
+    max_fuel: u64,
+    retry_max_memory: u64,
+) -> Result<Option<SolutionData>> {
+    match compute_solution(settings, nonce, wasm, max_memory, max_fuel) {
+        Err(e)
+            if retry_max_memory > max_memory
+                && matches!(
+                    e.downcast_ref::<WasmError>(),
+                    Some(WasmError::OutOfMemory { .. })
+                ) =>
+        {
+            compute_solution(settings, nonce, wasm, retry_max_memory, max_fuel).map_err(|e2| {
+                anyhow!(
+                    "{} (retry with max_memory {} also failed: {})",
+                    e,
+                    retry_max_memory,
+                    e2
+                )
+            })
+        }
+        result => result,
+    }
+}
+
+pub fn calc_version_pin(wasm: &[u8]) -> VersionPin {
+    VersionPin {
+        challenge_version: tig_challenges::VERSION.to_string(),
+        algorithm_version: md5_from_bytes(wasm),
     }
-    Ok(Some(solution_data))
 }
 
 pub fn verify_solution(
@@ -121,25 +544,264 @@ pub fn verify_solution(
     nonce: u64,
     solution: &Solution,
 ) -> Result<()> {
+    verify_solution_at_difficulty(settings, nonce, solution, &settings.difficulty)
+}
+
+// verify_solution for the nonce's instance generated with the options, see
+// generate_serialized_instance_with_options
+pub fn verify_solution_with_options(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    options: &InstanceOptions,
+) -> Result<()> {
+    verify_nonce_at_difficulty(settings, nonce, solution, &settings.difficulty, options)
+}
+
+// verifies the solution to the instance at the nonce against the thresholds of the requested
+// difficulty. the instance is still generated at the settings' difficulty, so a solution found
+// there satisfies any easier requested difficulty. a requested difficulty harder than the
+// settings', or one that changes the instance itself, e.g. its number of items, is rejected
+pub fn verify_solution_at_difficulty(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    difficulty: &[i32],
+) -> Result<()> {
+    verify_nonce_at_difficulty(
+        settings,
+        nonce,
+        solution,
+        difficulty,
+        &InstanceOptions::default(),
+    )
+}
+
+fn verify_nonce_at_difficulty(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    difficulty: &[i32],
+    options: &InstanceOptions,
+) -> Result<()> {
+    let seeds = options.calc_seeds(settings, nonce);
+    match settings.challenge_id.as_str() {
+        "c001" => verify_at::<
+            satisfiability::Challenge,
+            satisfiability::Solution,
+            satisfiability::Difficulty,
+            2,
+        >(seeds, settings, solution, difficulty),
+        "c002" if options.dataset.is_some() => verify_instance_at::<
+            vehicle_routing::Challenge,
+            vehicle_routing::Solution,
+            vehicle_routing::Difficulty,
+            2,
+        >(
+            &generate_vehicle_routing_instance(settings, nonce, options)?,
+            settings,
+            solution,
+            difficulty,
+        ),
+        "c002" => verify_at::<
+            vehicle_routing::Challenge,
+            vehicle_routing::Solution,
+            vehicle_routing::Difficulty,
+            2,
+        >(seeds, settings, solution, difficulty),
+        "c003" => verify_at::<knapsack::Challenge, knapsack::Solution, knapsack::Difficulty, 2>(
+            seeds, settings, solution, difficulty,
+        ),
+        "c004" if options.quantization_levels.is_some() => {
+            verify_quantized(settings, nonce, solution, difficulty, options)
+        }
+        "c004" if options.dataset.is_some() => verify_instance_at::<
+            vector_search::Challenge,
+            vector_search::Solution,
+            vector_search::Difficulty,
+            2,
+        >(
+            &generate_vector_search_instance(settings, nonce, options)?,
+            settings,
+            solution,
+            difficulty,
+        ),
+        "c004" => verify_at::<
+            vector_search::Challenge,
+            vector_search::Solution,
+            vector_search::Difficulty,
+            2,
+        >(seeds, settings, solution, difficulty),
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+// verify_at for a quantized vector search instance. its distances are only computed from the grid
+// at the settings' difficulty, so no other difficulty is verified
+fn verify_quantized(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    difficulty: &[i32],
+    options: &InstanceOptions,
+) -> Result<()> {
+    if difficulty != settings.difficulty.as_slice() {
+        return Err(anyhow!(
+            "Quantized instances are only verified at the solution's difficulty {:?}",
+            settings.difficulty
+        ));
+    }
+    let challenge = generate_vector_search_instance(settings, nonce, options)?;
+    let solution = vector_search::Solution::try_from(solution.clone()).map_err(|_| {
+        anyhow!(
+            "Invalid solution. Cannot convert to {}",
+            type_name::<vector_search::Solution>()
+        )
+    })?;
+    verify_vector_search_solution(&challenge, &solution, options)
+        .map_err(|e| anyhow!("{} ({})", e, challenge.summary()))
+}
+
+fn verify_at<C, T, U, const N: usize>(
+    seeds: [u64; 8],
+    settings: &BenchmarkSettings,
+    solution: &Solution,
+    difficulty: &[i32],
+) -> Result<()>
+where
+    C: ChallengeTrait<T, U, N>,
+    T: SolutionTrait + TryFrom<Solution>,
+    U: DifficultyTrait<N>,
+{
+    let challenge = C::generate_instance_from_vec(seeds, &settings.difficulty)
+        .unwrap_or_else(|_| panic!("Failed to generate {} instance", type_name::<C>()));
+    verify_instance_at::<C, T, U, N>(&challenge, settings, solution, difficulty)
+}
+
+// verify_at for an instance generated otherwise, e.g. from a dataset
+fn verify_instance_at<C, T, U, const N: usize>(
+    challenge: &C,
+    settings: &BenchmarkSettings,
+    solution: &Solution,
+    difficulty: &[i32],
+) -> Result<()>
+where
+    C: ChallengeTrait<T, U, N>,
+    T: SolutionTrait + TryFrom<Solution>,
+    U: DifficultyTrait<N>,
+{
+    let solution = T::try_from(solution.clone())
+        .map_err(|_| anyhow!("Invalid solution. Cannot convert to {}", type_name::<T>()))?;
+    let result = if difficulty == settings.difficulty.as_slice() {
+        challenge.verify_solution(&solution)
+    } else {
+        let requested = <[i32; N]>::try_from(difficulty)
+            .map(|arr| U::from_arr(&arr))
+            .map_err(|_| anyhow!("Invalid difficulty length"))?;
+        let solved = U::from_arr(&difficulty_arr::<N>(settings)?);
+        // only a solution found at least as hard as requested is strictly stronger
+        match C::compare_difficulty(&solved, &requested) {
+            Some(Ordering::Greater | Ordering::Equal) => {
+                challenge.verify_solution_at_difficulty(&solution, &requested)
+            }
+            _ => Err(anyhow!(
+                "Requested difficulty {:?} is harder than the solution's {:?}",
+                difficulty,
+                settings.difficulty
+            )),
+        }
+    };
+    result.map_err(|e| anyhow!("{} ({})", e, challenge.summary()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verification {
+    // trusted a verification token issued by this process
+    Token,
+    // ran verify_solution
+    Full,
+}
+
+// verify_solution on solution_data, then stores a token in it that lets verify_solution_data skip
+// verifying it again within this process
+pub fn verify_and_issue_token(
+    settings: &BenchmarkSettings,
+    solution_data: &mut SolutionData,
+) -> Result<()> {
+    verify_solution(settings, solution_data.nonce, &solution_data.solution)?;
+    solution_data.verification_token = Some(calc_verification_token(
+        settings,
+        solution_data.nonce,
+        &solution_data.solution,
+    ));
+    Ok(())
+}
+
+// trusts a verification token issued by this process for the same settings, nonce and solution.
+// any other token, or none, falls back to verify_solution
+pub fn verify_solution_data(
+    settings: &BenchmarkSettings,
+    solution_data: &SolutionData,
+) -> Result<Verification> {
+    let expected = calc_verification_token(settings, solution_data.nonce, &solution_data.solution);
+    if solution_data.verification_token.as_ref() == Some(&expected) {
+        return Ok(Verification::Token);
+    }
+    verify_solution(settings, solution_data.nonce, &solution_data.solution)?;
+    Ok(Verification::Full)
+}
+
+// keyed hash of what was verified. the key is drawn once per process and never leaves it, so other
+// processes can neither forge tokens nor reuse ones issued here. in the browser the hash keys of
+// RandomState aren't random, so tokens there are only as safe as the page
+fn calc_verification_token(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+) -> String {
+    static KEY: OnceLock<[u64; 2]> = OnceLock::new();
+    let key = KEY.get_or_init(|| {
+        let state = RandomState::new();
+        [state.hash_one(0u8), state.hash_one(1u8)]
+    });
+    u64s_from_str(&format!(
+        "{:016x}{:016x}:{}:{}:{}",
+        key[0],
+        key[1],
+        jsonify(settings),
+        nonce,
+        jsonify(solution)
+    ))
+    .iter()
+    .map(|x| format!("{:016x}", x))
+    .collect()
+}
+
+// ChallengeTrait::qualifier of a solution for the nonce's instance. fails if it doesn't verify
+pub fn solution_qualifier(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+) -> Result<f64> {
     let seeds = settings.calc_seeds(nonce);
     match settings.challenge_id.as_str() {
         "c001" => {
             let challenge =
-                satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .expect("Failed to generate satisfiability instance");
+                satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
             match satisfiability::Solution::try_from(solution.clone()) {
-                Ok(solution) => challenge.verify_solution(&solution),
+                Ok(solution) => challenge.qualifier(&solution),
                 Err(_) => Err(anyhow!(
                     "Invalid solution. Cannot convert to satisfiability::Solution"
                 )),
             }
         }
         "c002" => {
-            let challenge =
-                vehicle_routing::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .expect("Failed to generate vehicle_routing instance");
+            let challenge = vehicle_routing::Challenge::generate_instance_from_vec(
+                seeds,
+                &settings.difficulty,
+            )?;
             match vehicle_routing::Solution::try_from(solution.clone()) {
-                Ok(solution) => challenge.verify_solution(&solution),
+                Ok(solution) => challenge.qualifier(&solution),
                 Err(_) => Err(anyhow!(
                     "Invalid solution. Cannot convert to vehicle_routing::Solution"
                 )),
@@ -147,10 +809,9 @@ pub fn verify_solution(
         }
         "c003" => {
             let challenge =
-                knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .expect("Failed to generate knapsack instance");
+                knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
             match knapsack::Solution::try_from(solution.clone()) {
-                Ok(solution) => challenge.verify_solution(&solution),
+                Ok(solution) => challenge.qualifier(&solution),
                 Err(_) => Err(anyhow!(
                     "Invalid solution. Cannot convert to knapsack::Solution"
                 )),
@@ -158,10 +819,134 @@ pub fn verify_solution(
         }
         "c004" => {
             let challenge =
-                vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
-                    .expect("Failed to generate vector_search instance");
+                vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
+            match vector_search::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge.qualifier(&solution),
+                Err(_) => Err(anyhow!(
+                    "Invalid solution. Cannot convert to vector_search::Solution"
+                )),
+            }
+        }
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+// md5 of ChallengeTrait::essential_bytes of a solution for the nonce's instance. solutions that are
+// equivalent for the instance, e.g. differing only in fields the verifier recomputes, hash equal
+pub fn solution_hash(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+) -> Result<String> {
+    let seeds = settings.calc_seeds(nonce);
+    let essential_bytes = match settings.challenge_id.as_str() {
+        "c001" => {
+            let challenge =
+                satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
+            match satisfiability::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge.essential_bytes(&solution),
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Invalid solution. Cannot convert to satisfiability::Solution"
+                    ))
+                }
+            }
+        }
+        "c002" => {
+            let challenge = vehicle_routing::Challenge::generate_instance_from_vec(
+                seeds,
+                &settings.difficulty,
+            )?;
+            match vehicle_routing::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge.essential_bytes(&solution),
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Invalid solution. Cannot convert to vehicle_routing::Solution"
+                    ))
+                }
+            }
+        }
+        "c003" => {
+            let challenge =
+                knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
+            match knapsack::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge.essential_bytes(&solution),
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Invalid solution. Cannot convert to knapsack::Solution"
+                    ))
+                }
+            }
+        }
+        "c004" => {
+            let challenge =
+                vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
             match vector_search::Solution::try_from(solution.clone()) {
-                Ok(solution) => challenge.verify_solution(&solution),
+                Ok(solution) => challenge.essential_bytes(&solution),
+                Err(_) => {
+                    return Err(anyhow!(
+                        "Invalid solution. Cannot convert to vector_search::Solution"
+                    ))
+                }
+            }
+        }
+        _ => panic!("Unknown challenge"),
+    };
+    Ok(md5_from_bytes(&essential_bytes))
+}
+
+// verifies against a snapshot of the instance, as serialized by generate_serialized_instance when
+// the solution was computed, instead of regenerating it. the check then stands even if generation
+// has changed since. the snapshot decides the instance, so it must come from trusted storage
+pub fn verify_against_instance(
+    challenge_id: &str,
+    instance_bytes: &[u8],
+    solution: &Solution,
+) -> Result<()> {
+    match challenge_id {
+        "c001" => {
+            let challenge: satisfiability::Challenge = deserialize_instance(instance_bytes)
+                .map_err(|e| anyhow!("Failed to deserialize satisfiability instance: {}", e))?;
+            match satisfiability::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge
+                    .verify_solution(&solution)
+                    .map_err(|e| anyhow!("{} ({})", e, challenge.summary())),
+                Err(_) => Err(anyhow!(
+                    "Invalid solution. Cannot convert to satisfiability::Solution"
+                )),
+            }
+        }
+        "c002" => {
+            let challenge: vehicle_routing::Challenge = deserialize_instance(instance_bytes)
+                .map_err(|e| anyhow!("Failed to deserialize vehicle_routing instance: {}", e))?;
+            match vehicle_routing::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge
+                    .verify_solution(&solution)
+                    .map_err(|e| anyhow!("{} ({})", e, challenge.summary())),
+                Err(_) => Err(anyhow!(
+                    "Invalid solution. Cannot convert to vehicle_routing::Solution"
+                )),
+            }
+        }
+        "c003" => {
+            let challenge: knapsack::Challenge = deserialize_instance(instance_bytes)
+                .map_err(|e| anyhow!("Failed to deserialize knapsack instance: {}", e))?;
+            match knapsack::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge
+                    .verify_solution(&solution)
+                    .map_err(|e| anyhow!("{} ({})", e, challenge.summary())),
+                Err(_) => Err(anyhow!(
+                    "Invalid solution. Cannot convert to knapsack::Solution"
+                )),
+            }
+        }
+        "c004" => {
+            let challenge: vector_search::Challenge = deserialize_instance(instance_bytes)
+                .map_err(|e| anyhow!("Failed to deserialize vector_search instance: {}", e))?;
+            match vector_search::Solution::try_from(solution.clone()) {
+                Ok(solution) => challenge
+                    .verify_solution(&solution)
+                    .map_err(|e| anyhow!("{} ({})", e, challenge.summary())),
                 Err(_) => Err(anyhow!(
                     "Invalid solution. Cannot convert to vector_search::Solution"
                 )),
@@ -170,3 +955,436 @@ pub fn verify_solution(
         _ => panic!("Unknown challenge"),
     }
 }
+
+// md5 of the instance as serialized by generate_serialized_instance, but without its seeds. nonces
+// that generate the same problem have the same fingerprint
+pub fn instance_fingerprint(challenge_id: &str, instance_bytes: &[u8]) -> Result<String> {
+    let unseeded = match challenge_id {
+        "c001" => unseeded::<satisfiability::Challenge>(instance_bytes, |c| &mut c.seeds),
+        "c002" => unseeded::<vehicle_routing::Challenge>(instance_bytes, |c| &mut c.seeds),
+        "c003" => unseeded::<knapsack::Challenge>(instance_bytes, |c| &mut c.seeds),
+        "c004" => unseeded::<vector_search::Challenge>(instance_bytes, |c| &mut c.seeds),
+        _ => panic!("Unknown challenge"),
+    }?;
+    Ok(md5_from_bytes(&unseeded))
+}
+
+fn unseeded<T: Serialize + DeserializeOwned>(
+    instance_bytes: &[u8],
+    seeds: fn(&mut T) -> &mut [u64; 8],
+) -> Result<Vec<u8>> {
+    let mut challenge: T = deserialize_instance(instance_bytes)
+        .map_err(|e| anyhow!("Failed to deserialize instance: {}", e))?;
+    *seeds(&mut challenge) = [0; 8];
+    serialize_instance(&challenge)
+}
+
+// ChallengeTrait::search_space_log2 of a snapshot of the instance, as serialized by
+// generate_serialized_instance. comparable across challenges, unlike their difficulty parameters
+pub fn search_space_log2(challenge_id: &str, instance_bytes: &[u8]) -> Result<f64> {
+    match challenge_id {
+        "c001" => deserialize_instance::<satisfiability::Challenge>(instance_bytes)
+            .map(|challenge| challenge.search_space_log2())
+            .map_err(|e| anyhow!("Failed to deserialize satisfiability instance: {}", e)),
+        "c002" => deserialize_instance::<vehicle_routing::Challenge>(instance_bytes)
+            .map(|challenge| challenge.search_space_log2())
+            .map_err(|e| anyhow!("Failed to deserialize vehicle_routing instance: {}", e)),
+        "c003" => deserialize_instance::<knapsack::Challenge>(instance_bytes)
+            .map(|challenge| challenge.search_space_log2())
+            .map_err(|e| anyhow!("Failed to deserialize knapsack instance: {}", e)),
+        "c004" => deserialize_instance::<vector_search::Challenge>(instance_bytes)
+            .map(|challenge| challenge.search_space_log2())
+            .map_err(|e| anyhow!("Failed to deserialize vector_search instance: {}", e)),
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+// ChallengeTrait::expected_solve_probability at the settings' difficulty
+pub fn expected_solve_probability(settings: &BenchmarkSettings) -> Result<Option<f64>> {
+    match settings.challenge_id.as_str() {
+        "c001" => Ok(satisfiability::Challenge::expected_solve_probability(
+            &satisfiability::Difficulty::from_arr(&difficulty_arr(settings)?),
+        )),
+        "c002" => Ok(vehicle_routing::Challenge::expected_solve_probability(
+            &vehicle_routing::Difficulty::from_arr(&difficulty_arr(settings)?),
+        )),
+        "c003" => Ok(knapsack::Challenge::expected_solve_probability(
+            &knapsack::Difficulty::from_arr(&difficulty_arr(settings)?),
+        )),
+        "c004" => Ok(vector_search::Challenge::expected_solve_probability(
+            &vector_search::Difficulty::from_arr(&difficulty_arr(settings)?),
+        )),
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+// ChallengeTrait::explain of the instance at the nonce
+pub fn explain_instance(settings: &BenchmarkSettings, nonce: u64) -> Result<String> {
+    let seeds = settings.calc_seeds(nonce);
+    match settings.challenge_id.as_str() {
+        "c001" => {
+            satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
+                .map(|challenge| challenge.explain())
+        }
+        "c002" => {
+            vehicle_routing::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
+                .map(|challenge| challenge.explain())
+        }
+        "c003" => knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
+            .map(|challenge| challenge.explain()),
+        "c004" => vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)
+            .map(|challenge| challenge.explain()),
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+// index of the hardest of difficulties the solution to the instance at the nonce satisfies, None if
+// it satisfies none. difficulties must be sorted from easiest to hardest. the instance is generated
+// once and, as a solution that satisfies a difficulty satisfies every easier one, only about log2 of
+// the difficulties are verified. a difficulty that changes the instance itself, e.g. its number of
+// items, is never satisfied
+pub fn highest_difficulty_satisfied(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    difficulties: &[Vec<i32>],
+) -> Result<Option<usize>> {
+    let seeds = settings.calc_seeds(nonce);
+    match settings.challenge_id.as_str() {
+        "c001" => highest_satisfied::<
+            satisfiability::Challenge,
+            satisfiability::Solution,
+            satisfiability::Difficulty,
+            2,
+        >(
+            &satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?,
+            solution,
+            difficulties,
+        ),
+        "c002" => highest_satisfied::<
+            vehicle_routing::Challenge,
+            vehicle_routing::Solution,
+            vehicle_routing::Difficulty,
+            2,
+        >(
+            &vehicle_routing::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?,
+            solution,
+            difficulties,
+        ),
+        "c003" => {
+            highest_satisfied::<knapsack::Challenge, knapsack::Solution, knapsack::Difficulty, 2>(
+                &knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?,
+                solution,
+                difficulties,
+            )
+        }
+        "c004" => highest_satisfied::<
+            vector_search::Challenge,
+            vector_search::Solution,
+            vector_search::Difficulty,
+            2,
+        >(
+            &vector_search::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?,
+            solution,
+            difficulties,
+        ),
+        _ => panic!("Unknown challenge"),
+    }
+}
+
+fn highest_satisfied<C, T, U, const N: usize>(
+    challenge: &C,
+    solution: &Solution,
+    difficulties: &[Vec<i32>],
+) -> Result<Option<usize>>
+where
+    C: ChallengeTrait<T, U, N>,
+    T: SolutionTrait + TryFrom<Solution>,
+    U: DifficultyTrait<N>,
+{
+    let solution = T::try_from(solution.clone())
+        .map_err(|_| anyhow!("Invalid solution. Cannot convert to {}", type_name::<T>()))?;
+    let difficulties = difficulties
+        .iter()
+        .map(|difficulty| {
+            <[i32; N]>::try_from(difficulty.as_slice())
+                .map(|arr| U::from_arr(&arr))
+                .map_err(|_| anyhow!("Invalid difficulty length"))
+        })
+        .collect::<Result<Vec<U>>>()?;
+    // the binary search below relies on every difficulty being at least as hard as the one before
+    if difficulties.windows(2).any(|pair| {
+        !matches!(
+            C::compare_difficulty(&pair[1], &pair[0]),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    }) {
+        return Err(anyhow!(
+            "Difficulties must be sorted from easiest to hardest"
+        ));
+    }
+    let num_satisfied = difficulties.partition_point(|difficulty| {
+        challenge
+            .verify_solution_at_difficulty(&solution, difficulty)
+            .is_ok()
+    });
+    Ok(num_satisfied.checked_sub(1))
+}
+
+fn difficulty_arr<const N: usize>(settings: &BenchmarkSettings) -> Result<[i32; N]> {
+    settings
+        .difficulty
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow!("Invalid difficulty length"))
+}
+
+// verifies each (nonce, solution) with verify_solution on at most threads threads, so a validator
+// can leave cores for its other duties. results are aligned with solutions and don't depend on
+// threads. not available in the browser, which can't spawn threads
+pub fn verify_batch(
+    settings: &BenchmarkSettings,
+    solutions: &[(u64, Solution)],
+    threads: usize,
+) -> Vec<Result<()>> {
+    map_batch(solutions, threads, |nonce, solution| {
+        verify_solution(settings, nonce, solution)
+    })
+}
+
+#[derive(Serialize, Debug, Clone, Default, PartialEq)]
+pub struct BatchReport {
+    pub num_verified: u32,
+    // (nonce, error) of every solution that fails, sorted
+    pub failures: Vec<(u64, String)>,
+    // sum of solution_qualifier over the solutions that verify
+    pub total_qualifier: f64,
+}
+
+// aggregate of verifying a batch like verify_batch. identical, down to its serialized bytes, for
+// the same solutions whatever the threads or their order in the batch: results are sorted by nonce
+// before aggregating, as float addition depends on the order of its terms
+pub fn verify_batch_report(
+    settings: &BenchmarkSettings,
+    solutions: &[(u64, Solution)],
+    threads: usize,
+) -> BatchReport {
+    let qualifiers = map_batch(solutions, threads, |nonce, solution| {
+        solution_qualifier(settings, nonce, solution).map_err(|e| e.to_string())
+    });
+    let mut results: Vec<(u64, std::result::Result<f64, String>)> = solutions
+        .iter()
+        .map(|(nonce, _)| *nonce)
+        .zip(qualifiers)
+        .collect();
+    // a nonce can be in the batch more than once
+    results.sort_by(|(a_nonce, a), (b_nonce, b)| {
+        a_nonce.cmp(b_nonce).then_with(|| match (a, b) {
+            (Ok(a), Ok(b)) => a.total_cmp(b),
+            (Err(a), Err(b)) => a.cmp(b),
+            (Ok(_), Err(_)) => std::cmp::Ordering::Less,
+            (Err(_), Ok(_)) => std::cmp::Ordering::Greater,
+        })
+    });
+    let mut report = BatchReport::default();
+    for (nonce, result) in results {
+        match result {
+            Ok(qualifier) => {
+                report.num_verified += 1;
+                report.total_qualifier += qualifier;
+            }
+            Err(e) => report.failures.push((nonce, e)),
+        }
+    }
+    report
+}
+
+// f over every (nonce, solution) on at most threads scoped threads, aligned with solutions
+fn map_batch<T: Send>(
+    solutions: &[(u64, Solution)],
+    threads: usize,
+    f: impl Fn(u64, &Solution) -> T + Sync,
+) -> Vec<T> {
+    if solutions.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = solutions.len().div_ceil(threads.clamp(1, solutions.len()));
+    let f = &f;
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = solutions
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(nonce, solution)| f(*nonce, solution))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+// verify_solution in a subprocess running the tig-worker binary at worker_path, so a verifier that
+// crashes or hangs on adversarial input takes down the subprocess instead of the validator. the
+// solution is sent over the subprocess' stdin. a crash, running out of max_memory bytes or taking
+// longer than timeout_ms rejects the solution. not available in the browser
+pub fn verify_solution_sandboxed(
+    worker_path: &Path,
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    timeout_ms: u64,
+    max_memory: u64,
+) -> Result<()> {
+    let mut command = process::Command::new(worker_path);
+    command
+        .arg("verify_solution")
+        .arg(jsonify(settings))
+        .arg(nonce.to_string())
+        .arg("-")
+        .stdin(process::Stdio::piped())
+        .stdout(process::Stdio::null())
+        .stderr(process::Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        let limit = libc::rlimit {
+            rlim_cur: max_memory as libc::rlim_t,
+            rlim_max: max_memory as libc::rlim_t,
+        };
+        // safe as setrlimit is async-signal-safe
+        unsafe {
+            command.pre_exec(move || {
+                if libc::setrlimit(libc::RLIMIT_AS, &limit) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+    let mut child = command
+        .spawn()
+        .map_err(|e| anyhow!("Failed to start verifier {}: {}", worker_path.display(), e))?;
+    // a verifier that exits before reading the solution closes the pipe, which the exit status
+    // below reports instead
+    let _ = child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(jsonify(solution).as_bytes());
+    let mut stderr = child.stderr.take().unwrap();
+    let stderr = std::thread::spawn(move || {
+        let mut output = String::new();
+        let _ = stderr.read_to_string(&mut output);
+        output
+    });
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= Duration::from_millis(timeout_ms) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(anyhow!("Verifier timed out after {}ms", timeout_ms));
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    let stderr = stderr.join().unwrap_or_default();
+    match status.code() {
+        Some(0) => Ok(()),
+        // verify_solution's error
+        Some(1) => Err(anyhow!("{}", stderr.trim())),
+        _ => Err(anyhow!("Verifier crashed ({}): {}", status, stderr.trim())),
+    }
+}
+
+// the challenge's repair of a solution that didn't verify for the nonce's instance, if the repaired
+// solution verifies. like trivial_solution, it is not produced by the algorithm's wasm and must never
+// be submitted
+pub fn repair_solution(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    solution: &Solution,
+    options: &InstanceOptions,
+) -> Result<Option<Solution>> {
+    let seeds = options.calc_seeds(settings, nonce);
+    let repaired = match settings.challenge_id.as_str() {
+        "c001" => {
+            let challenge =
+                satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
+            satisfiability::Solution::try_from(solution.clone())
+                .ok()
+                .and_then(|solution| challenge.repair(&solution))
+                .map(|s| jsonify(&s))
+        }
+        "c002" => {
+            let challenge = generate_vehicle_routing_instance(settings, nonce, options)?;
+            vehicle_routing::Solution::try_from(solution.clone())
+                .ok()
+                .and_then(|solution| challenge.repair(&solution))
+                .map(|s| jsonify(&s))
+        }
+        "c003" => {
+            let challenge =
+                knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?;
+            knapsack::Solution::try_from(solution.clone())
+                .ok()
+                .and_then(|solution| challenge.repair(&solution))
+                .map(|s| jsonify(&s))
+        }
+        "c004" => {
+            let challenge = generate_vector_search_instance(settings, nonce, options)?;
+            vector_search::Solution::try_from(solution.clone())
+                .ok()
+                .and_then(|solution| challenge.repair(&solution))
+                .map(|s| jsonify(&s))
+        }
+        _ => panic!("Unknown challenge"),
+    };
+    match repaired {
+        Some(solution) => Ok(Some(
+            dejsonify::<Solution>(&solution).map_err(|e| anyhow!("Invalid solution: {}", e))?,
+        )),
+        None => Ok(None),
+    }
+}
+
+// the challenge's trivial_solution for the nonce's instance, if one verifies. such solutions are not
+// produced by the algorithm's wasm, so the protocol cannot reproduce them and they must never be
+// submitted
+pub fn trivial_solution(
+    settings: &BenchmarkSettings,
+    nonce: u64,
+    options: &InstanceOptions,
+) -> Result<Option<Solution>> {
+    let seeds = options.calc_seeds(settings, nonce);
+    let solution = match settings.challenge_id.as_str() {
+        "c001" => {
+            satisfiability::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?
+                .trivial_solution()
+                .map(|s| jsonify(&s))
+        }
+        "c002" => generate_vehicle_routing_instance(settings, nonce, options)?
+            .trivial_solution()
+            .map(|s| jsonify(&s)),
+        "c003" => knapsack::Challenge::generate_instance_from_vec(seeds, &settings.difficulty)?
+            .trivial_solution()
+            .map(|s| jsonify(&s)),
+        "c004" => generate_vector_search_instance(settings, nonce, options)?
+            .trivial_solution()
+            .map(|s| jsonify(&s)),
+        _ => panic!("Unknown challenge"),
+    };
+    match solution {
+        Some(solution) => Ok(Some(
+            dejsonify::<Solution>(&solution).map_err(|e| anyhow!("Invalid solution: {}", e))?,
+        )),
+        None => Ok(None),
+    }
+}
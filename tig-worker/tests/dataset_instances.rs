@@ -0,0 +1,106 @@
+mod common;
+
+use std::path::PathBuf;
+use tig_challenges::{load_dataset, vector_search, vehicle_routing, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance_with_options, verify_solution_with_options,
+    InstanceOptions, Solution,
+};
+
+fn from_dataset(name: &str) -> InstanceOptions {
+    InstanceOptions {
+        dataset: Some(
+            PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join(format!("../tig-challenges/tests/datasets/{}.json", name)),
+        ),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_vehicle_routing_dataset_option() {
+    let settings = common::settings("c002", vec![6, 0]);
+    let options = from_dataset("vehicle_routing");
+    assert!(!options.is_protocol());
+    let dataset: vehicle_routing::Dataset =
+        load_dataset(options.dataset.as_ref().unwrap()).unwrap();
+    for nonce in 0..5 {
+        let challenge: vehicle_routing::Challenge = deserialize_instance(
+            &generate_serialized_instance_with_options(&settings, nonce, &options).unwrap(),
+        )
+        .unwrap();
+        assert!(vehicle_routing::Challenge::instances_equal(
+            &challenge,
+            &vehicle_routing::Challenge::generate_instance_from_dataset(
+                options.calc_seeds(&settings, nonce),
+                &challenge.difficulty,
+                &dataset
+            )
+            .unwrap()
+        ));
+        let routes = vehicle_routing::calc_baseline_routes(
+            challenge.difficulty.num_nodes,
+            challenge.max_capacity,
+            &challenge.demands,
+            &challenge.distance_matrix,
+        )
+        .unwrap();
+        let solution: Solution =
+            dejsonify(&jsonify(&vehicle_routing::Solution { routes })).unwrap();
+        assert!(verify_solution_with_options(&settings, nonce, &solution, &options).is_ok());
+    }
+}
+
+#[test]
+fn test_vector_search_dataset_option() {
+    let settings = common::settings("c004", vec![3, 0]);
+    let options = from_dataset("vector_search");
+    let dataset: vector_search::Dataset = load_dataset(options.dataset.as_ref().unwrap()).unwrap();
+    for nonce in 0..5 {
+        let challenge: vector_search::Challenge = deserialize_instance(
+            &generate_serialized_instance_with_options(&settings, nonce, &options).unwrap(),
+        )
+        .unwrap();
+        assert!(vector_search::Challenge::instances_equal(
+            &challenge,
+            &vector_search::Challenge::generate_instance_from_dataset(
+                options.calc_seeds(&settings, nonce),
+                &challenge.difficulty,
+                &dataset
+            )
+            .unwrap()
+        ));
+        let indexes = challenge
+            .query_vectors
+            .iter()
+            .map(|query| {
+                (0..challenge.vector_database.len())
+                    .min_by_key(|&i| {
+                        vector_search::euclidean_distance(query, &challenge.vector_database[i])
+                            .to_bits()
+                    })
+                    .unwrap()
+            })
+            .collect();
+        let solution: Solution = dejsonify(&jsonify(&vector_search::Solution { indexes })).unwrap();
+        assert!(verify_solution_with_options(&settings, nonce, &solution, &options).is_ok());
+    }
+}
+
+#[test]
+fn test_quantized_dataset_is_rejected() {
+    let settings = common::settings("c004", vec![3, 0]);
+    let options = InstanceOptions {
+        quantization_levels: Some(256),
+        ..from_dataset("vector_search")
+    };
+    let solution = dejsonify(&jsonify(&vector_search::Solution {
+        indexes: vec![0, 1, 2],
+    }))
+    .unwrap();
+    let err = verify_solution_with_options(&settings, 0, &solution, &options)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("from a dataset"), "{}", err);
+}
@@ -0,0 +1,79 @@
+use tig_challenges::knapsack::{Challenge, Difficulty};
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance, serialize_instance, BenchmarkSettings,
+};
+
+fn challenge() -> Challenge {
+    Challenge {
+        seeds: [0x0102030405060708, 0, 0, 0, 0, 0, 0, u64::MAX],
+        difficulty: Difficulty {
+            num_items: 2,
+            better_than_baseline: 0x0a0b0c0d,
+        },
+        weights: vec![0x11223344, 1],
+        values: vec![0xaabbccdd, 2],
+        max_weight: 300,
+        min_value: 0x01000000,
+    }
+}
+
+#[test]
+fn test_known_instance_bytes() {
+    let mut expected: Vec<u8> = Vec::new();
+    // seeds
+    expected.extend([0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01]);
+    expected.extend([0x00; 6 * 8]);
+    expected.extend([0xff; 8]);
+    // num_items is a usize, encoded as 8 bytes even where usize is 4, e.g. in wasm
+    expected.extend([0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    expected.extend([0x0d, 0x0c, 0x0b, 0x0a]);
+    // weights and values, each prefixed by their length as 8 bytes
+    expected.extend([0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    expected.extend([0x44, 0x33, 0x22, 0x11, 0x01, 0x00, 0x00, 0x00]);
+    expected.extend([0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+    expected.extend([0xdd, 0xcc, 0xbb, 0xaa, 0x02, 0x00, 0x00, 0x00]);
+    // max_weight and min_value
+    expected.extend([0x2c, 0x01, 0x00, 0x00]);
+    expected.extend([0x00, 0x00, 0x00, 0x01]);
+
+    let bytes = serialize_instance(&challenge()).unwrap();
+    assert_eq!(bytes, expected);
+
+    let decoded: Challenge = deserialize_instance(&expected).unwrap();
+    assert_eq!(decoded.seeds, challenge().seeds);
+    assert_eq!(decoded.difficulty.num_items, 2);
+    assert_eq!(decoded.difficulty.better_than_baseline, 0x0a0b0c0d);
+    assert_eq!(decoded.weights, challenge().weights);
+    assert_eq!(decoded.values, challenge().values);
+    assert_eq!((decoded.max_weight, decoded.min_value), (300, 0x01000000));
+}
+
+#[test]
+fn test_generated_instance_bytes() {
+    let settings = BenchmarkSettings {
+        player_id: "0x0000000000000000000000000000000000000000".to_string(),
+        block_id: "block".to_string(),
+        challenge_id: "c003".to_string(),
+        algorithm_id: "c003_a001".to_string(),
+        difficulty: vec![50, 10],
+    };
+    let bytes = generate_serialized_instance(&settings, 7).unwrap();
+    // the instance starts with its seeds, least significant byte first
+    let seeds: Vec<u8> = settings
+        .calc_seeds(7)
+        .iter()
+        .flat_map(|seed| seed.to_le_bytes())
+        .collect();
+    assert_eq!(bytes[..64], seeds[..]);
+    assert_eq!(bytes[64..72], 50u64.to_le_bytes());
+    assert_eq!(bytes[72..76], 10u32.to_le_bytes());
+
+    let challenge: Challenge = deserialize_instance(&bytes).unwrap();
+    assert_eq!(serialize_instance(&challenge).unwrap(), bytes);
+}
+
+#[test]
+fn test_truncated_instance() {
+    let bytes = serialize_instance(&challenge()).unwrap();
+    assert!(deserialize_instance::<Challenge>(&bytes[..bytes.len() - 1]).is_err());
+}
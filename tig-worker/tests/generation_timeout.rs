@@ -0,0 +1,53 @@
+mod common;
+
+use std::{thread, time::Duration};
+use tig_worker::{
+    compute_solution_with_generation_timeout, generate_serialized_instance, generate_with_timeout,
+    ComputeError,
+};
+
+#[test]
+fn test_slow_generation_times_out() {
+    let err = generate_with_timeout(50, || {
+        thread::sleep(Duration::from_millis(2000));
+        Ok(Vec::new())
+    })
+    .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ComputeError>(),
+        Some(&ComputeError::GenerationTimeout { timeout_ms: 50 })
+    );
+    assert!(err.to_string().contains("50ms"));
+}
+
+#[test]
+fn test_fast_generation_returns_instance() {
+    let instance = generate_with_timeout(5000, || Ok(vec![1, 2, 3])).unwrap();
+    assert_eq!(instance, vec![1, 2, 3]);
+
+    let settings = common::settings("c001", vec![50, 300]);
+    let expected = generate_serialized_instance(&settings, 7).unwrap();
+    let instance =
+        generate_with_timeout(5000, move || generate_serialized_instance(&settings, 7)).unwrap();
+    assert_eq!(instance, expected);
+
+    assert!(generate_with_timeout(5000, || panic!("generation failed")).is_err());
+}
+
+#[test]
+fn test_generation_timeout_trips_before_solving() {
+    // a huge instance can't be generated in 1ms, so the wasm, which isn't even valid, never runs
+    let err = compute_solution_with_generation_timeout(
+        &common::settings("c001", vec![200_000, 420]),
+        0,
+        &[],
+        1 << 20,
+        1_000_000,
+        1,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<ComputeError>(),
+        Some(&ComputeError::GenerationTimeout { timeout_ms: 1 })
+    );
+}
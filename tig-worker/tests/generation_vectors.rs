@@ -0,0 +1,62 @@
+mod common;
+
+use serde::Deserialize;
+use tig_utils::{dejsonify, md5_from_bytes};
+use tig_worker::{generate_serialized_instance, BenchmarkSettings};
+
+// fingerprints of instances as generated when the vectors were recorded. the protocol regenerates
+// instances to verify solutions, so generation must not drift. a vector only changes together with
+// a bump of tig_challenges::VERSION
+const VECTORS: &str = include_str!("generation_vectors.json");
+
+#[derive(Deserialize)]
+struct Vector {
+    challenge_id: String,
+    difficulty: Vec<i32>,
+    nonce: u64,
+    // md5 of the instance as serialized by generate_serialized_instance
+    fingerprint: String,
+}
+
+// seeds depend on all the settings, so the ones a vector doesn't record are fixed
+fn settings(vector: &Vector) -> BenchmarkSettings {
+    BenchmarkSettings {
+        block_id: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
+        ..common::settings(&vector.challenge_id, vector.difficulty.clone())
+    }
+}
+
+#[test]
+fn test_generation_reproduces_vectors() {
+    let vectors: Vec<Vector> = dejsonify(VECTORS).unwrap();
+    for challenge_id in ["c001", "c002", "c003", "c004"] {
+        assert!(
+            vectors.iter().any(|v| v.challenge_id == challenge_id),
+            "No vectors for {}",
+            challenge_id
+        );
+    }
+    // reports every vector that drifted, not just the first
+    let mismatches: Vec<String> = vectors
+        .iter()
+        .filter_map(|vector| {
+            let instance = generate_serialized_instance(&settings(vector), vector.nonce).unwrap();
+            let fingerprint = md5_from_bytes(&instance);
+            (fingerprint != vector.fingerprint).then(|| {
+                format!(
+                    "{} {:?} nonce {}: expected {}, got {}",
+                    vector.challenge_id,
+                    vector.difficulty,
+                    vector.nonce,
+                    vector.fingerprint,
+                    fingerprint
+                )
+            })
+        })
+        .collect();
+    assert!(
+        mismatches.is_empty(),
+        "Instance generation changed:\n{}",
+        mismatches.join("\n")
+    );
+}
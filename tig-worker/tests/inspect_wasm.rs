@@ -0,0 +1,70 @@
+use tig_worker::{inspect_wasm, WasmError, REQUIRED_EXPORTS};
+
+const PAGE_SIZE: u64 = 65536;
+
+// (module
+//   (import "env" "abort" (func (param i32) (result i32)))
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0)
+//   (func (export "entry_point") (param i32 i32) (result i32) i32.const 0))
+const IMPORTING_WASM: [u8; 93] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // types
+    0x02, 0x0d, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x05, 0x61, 0x62, 0x6f, 0x72, 0x74, 0x00, 0x00, // imports
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x1f, 0x03, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x01, // "init"
+    0x0b, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00, 0x02, // "entry_point"
+    0x0a, 0x0b, 0x02, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+    0x04, 0x00, 0x41, 0x00, 0x0b, // entry_point
+];
+
+// (module
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0))
+const NO_ENTRY_POINT_WASM: [u8; 52] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // types
+    0x03, 0x02, 0x01, 0x00, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x11, 0x02, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, // "init"
+    0x0a, 0x06, 0x01, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+];
+
+#[test]
+fn test_inspect_lists_exports_and_flags_unexpected_import() {
+    let interface = inspect_wasm(&IMPORTING_WASM).unwrap();
+    let exports: Vec<&str> = interface.exports.iter().map(|x| x.name.as_str()).collect();
+    assert_eq!(exports, REQUIRED_EXPORTS.to_vec());
+    assert!(interface.missing_exports().is_empty());
+    assert_eq!(interface.initial_memory, Some(PAGE_SIZE));
+    assert_eq!(interface.max_memory, None);
+
+    let unexpected = interface.unexpected_imports();
+    assert_eq!(unexpected.len(), 1);
+    assert_eq!(unexpected[0].module, "env");
+    assert_eq!(unexpected[0].name, "abort");
+    assert!(unexpected[0].kind.starts_with("func"));
+}
+
+#[test]
+fn test_inspect_reports_missing_solve_export() {
+    let interface = inspect_wasm(&NO_ENTRY_POINT_WASM).unwrap();
+    assert!(interface.imports.is_empty());
+    assert!(interface.unexpected_imports().is_empty());
+    assert_eq!(interface.missing_exports(), vec!["entry_point"]);
+}
+
+#[test]
+fn test_inspect_rejects_invalid_module() {
+    assert!(matches!(
+        inspect_wasm(&IMPORTING_WASM[..40]),
+        Err(WasmError::CompileFailed { .. })
+    ));
+}
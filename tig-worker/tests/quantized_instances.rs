@@ -0,0 +1,91 @@
+mod common;
+
+use tig_challenges::{vector_search, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{
+    deserialize_instance, generate_serialized_instance, generate_serialized_instance_with_options,
+    generate_vector_search_instance, verify_solution_with_options, BenchmarkSettings,
+    InstanceOptions, Solution,
+};
+
+const LEVELS: u32 = 256;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c004", vec![3, 0])
+}
+
+fn quantized(levels: u32) -> InstanceOptions {
+    InstanceOptions {
+        quantization_levels: Some(levels),
+        ..Default::default()
+    }
+}
+
+// the nearest database vector to each query by quantized distance
+fn nearest_solution(challenge: &vector_search::Challenge) -> Solution {
+    let indexes = challenge
+        .query_vectors
+        .iter()
+        .map(|query| {
+            (0..challenge.vector_database.len())
+                .min_by_key(|&i| {
+                    vector_search::quantized_distance(query, &challenge.vector_database[i], LEVELS)
+                        .to_bits()
+                })
+                .unwrap()
+        })
+        .collect();
+    dejsonify(&jsonify(&vector_search::Solution { indexes })).unwrap()
+}
+
+#[test]
+fn test_quantized_option_generates_quantized_instances() {
+    let settings = settings();
+    let options = quantized(LEVELS);
+    assert!(!options.is_protocol());
+    let generate = |options: &InstanceOptions| -> vector_search::Challenge {
+        deserialize_instance(
+            &generate_serialized_instance_with_options(&settings, 0, options).unwrap(),
+        )
+        .unwrap()
+    };
+    let challenge = generate(&options);
+    assert!(challenge
+        .query_vectors
+        .iter()
+        .chain(challenge.vector_database.iter())
+        .flatten()
+        .all(|&x| (x * LEVELS as f32).fract() == 0.0));
+    assert!(vector_search::Challenge::instances_equal(
+        &challenge,
+        &vector_search::Challenge::generate_quantized_instance(
+            settings.calc_seeds(0),
+            &vector_search::Difficulty {
+                num_queries: 3,
+                better_than_baseline: 0,
+            },
+            LEVELS,
+        )
+        .unwrap()
+    ));
+    let protocol: vector_search::Challenge =
+        deserialize_instance(&generate_serialized_instance(&settings, 0).unwrap()).unwrap();
+    assert!(!vector_search::Challenge::instances_equal(
+        &challenge, &protocol
+    ));
+}
+
+#[test]
+fn test_quantized_option_verifies_with_quantized_distances() {
+    let settings = settings();
+    let options = quantized(LEVELS);
+    let challenge = generate_vector_search_instance(&settings, 0, &options).unwrap();
+    let solution = nearest_solution(&challenge);
+    assert!(verify_solution_with_options(&settings, 0, &solution, &options).is_ok());
+
+    // verification goes through the quantized path, which rejects invalid levels
+    let err = verify_solution_with_options(&settings, 0, &solution, &quantized(0))
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Quantization levels"), "{}", err);
+}
@@ -0,0 +1,83 @@
+mod common;
+
+use tig_worker::{compute_solution, compute_solution_with_retry, BenchmarkSettings};
+
+const PAGE_SIZE: u64 = 65536;
+
+// (module
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0)
+//   (func (export "entry_point") (param i32 i32) (result i32)
+//     i32.const 4
+//     memory.grow
+//     i32.const -1
+//     i32.eq
+//     if unreachable end
+//     ;; points at zeroed memory, i.e. an empty solution
+//     i32.const 60000))
+// needs 5 pages of memory, so traps unless max_memory allows growing by 4 pages
+const GROWING_WASM: [u8; 91] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x1f, 0x03, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, // "init"
+    0x0b, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00, 0x01, // "entry_point"
+    0x0a, 0x18, 0x02, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+    0x11, 0x00, 0x41, 0x04, 0x40, 0x00, 0x41, 0x7f, 0x46, 0x04, 0x40, 0x00, 0x0b, 0x41, 0xe0, 0xd4,
+    0x03, 0x0b, // entry_point
+];
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c003", vec![5, 0])
+}
+
+#[test]
+fn test_retry_with_raised_memory() {
+    let settings = settings();
+    let max_fuel = 1_000_000;
+
+    // traps at the default limit
+    assert!(compute_solution(&settings, 0, &GROWING_WASM, 2 * PAGE_SIZE, max_fuel).is_err());
+
+    // succeeds once the retry raises the limit
+    let solution_data = compute_solution_with_retry(
+        &settings,
+        0,
+        &GROWING_WASM,
+        2 * PAGE_SIZE,
+        max_fuel,
+        8 * PAGE_SIZE,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(solution_data.nonce, 0);
+    assert!(solution_data.solution.is_empty());
+
+    // a raised limit that is still too low keeps the failure
+    let err = compute_solution_with_retry(
+        &settings,
+        0,
+        &GROWING_WASM,
+        2 * PAGE_SIZE,
+        max_fuel,
+        3 * PAGE_SIZE,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("retry"));
+
+    // no retry without a raised limit
+    let err = compute_solution_with_retry(
+        &settings,
+        0,
+        &GROWING_WASM,
+        2 * PAGE_SIZE,
+        max_fuel,
+        2 * PAGE_SIZE,
+    )
+    .unwrap_err();
+    assert!(!err.to_string().contains("retry"));
+}
@@ -0,0 +1,57 @@
+mod common;
+
+use tig_utils::dejsonify;
+use tig_worker::{solution_hash, BenchmarkSettings, Solution};
+
+fn hash(settings: &BenchmarkSettings, solution: &str) -> String {
+    solution_hash(settings, 0, &dejsonify::<Solution>(solution).unwrap()).unwrap()
+}
+
+#[test]
+fn test_recomputable_fields_do_not_change_hash() {
+    let settings = common::settings("c003", vec![50, 10]);
+    let hash = |solution| hash(&settings, solution);
+    let expected = hash(r#"{"items":[1,4,9]}"#);
+    // the total value can be recomputed from the instance, and item order doesn't matter
+    assert_eq!(hash(r#"{"items":[1,4,9],"total_value":123}"#), expected);
+    assert_eq!(hash(r#"{"items":[9,1,4]}"#), expected);
+    assert_ne!(hash(r#"{"items":[1,4,10]}"#), expected);
+    assert!(solution_hash(
+        &settings,
+        0,
+        &dejsonify::<Solution>(r#"{"routes":[]}"#).unwrap()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_route_order_does_not_change_hash() {
+    let settings = common::settings("c002", vec![10, 0]);
+    let hash = |solution| hash(&settings, solution);
+    let expected = hash(r#"{"routes":[[0,1,2,0],[0,3,4,0]]}"#);
+    assert_eq!(
+        hash(r#"{"routes":[[0,3,4,0],[0,1,2,0]],"total_distance":42}"#),
+        expected
+    );
+    // the order of nodes within a route does matter
+    assert_ne!(hash(r#"{"routes":[[0,2,1,0],[0,3,4,0]]}"#), expected);
+}
+
+#[test]
+fn test_satisfiability_hash() {
+    let settings = common::settings("c001", vec![10, 300]);
+    let hash = |solution| hash(&settings, solution);
+    assert_eq!(
+        hash(r#"{"variables":[1,0,1,1],"num_satisfied":12}"#),
+        hash(r#"{"variables":[1,0,1,1]}"#)
+    );
+    assert_ne!(
+        hash(r#"{"variables":[1,0,1,0]}"#),
+        hash(r#"{"variables":[1,0,1,1]}"#)
+    );
+    // an extra false variable packs into the same bits, but is a different solution
+    assert_ne!(
+        hash(r#"{"variables":[1,0,1,1,0]}"#),
+        hash(r#"{"variables":[1,0,1,1]}"#)
+    );
+}
@@ -0,0 +1,95 @@
+mod common;
+
+use tig_challenges::{satisfiability, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{
+    verify_and_issue_token, verify_solution_data, BenchmarkSettings, Solution, SolutionData,
+    Verification,
+};
+
+const NUM_VARIABLES: usize = 10;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![NUM_VARIABLES as i32, 40])
+}
+
+// brute forces the first nonce whose instance is satisfiable
+fn solved_solution_data(settings: &BenchmarkSettings) -> SolutionData {
+    (0..)
+        .find_map(|nonce| {
+            let challenge = satisfiability::Challenge::generate_instance_from_vec(
+                settings.calc_seeds(nonce),
+                &settings.difficulty,
+            )
+            .unwrap();
+            (0..1u32 << NUM_VARIABLES)
+                .map(|bits| satisfiability::Solution {
+                    variables: (0..NUM_VARIABLES).map(|i| bits & (1 << i) != 0).collect(),
+                })
+                .find(|solution| challenge.verify_solution(solution).is_ok())
+                .map(|solution| {
+                    let solution: Solution = dejsonify(&jsonify(&solution)).unwrap();
+                    SolutionData::new(nonce, 0, 1, 1, solution).unwrap()
+                })
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_valid_token_short_circuits() {
+    let settings = settings();
+    let mut solution_data = solved_solution_data(&settings);
+    assert_eq!(
+        verify_solution_data(&settings, &solution_data).unwrap(),
+        Verification::Full
+    );
+    verify_and_issue_token(&settings, &mut solution_data).unwrap();
+    assert!(solution_data.verification_token.is_some());
+    assert_eq!(
+        verify_solution_data(&settings, &solution_data).unwrap(),
+        Verification::Token
+    );
+    // the token is local metadata and doesn't change the signature
+    let mut untokened = solution_data.clone();
+    untokened.verification_token = None;
+    assert_eq!(
+        solution_data.calc_solution_signature(),
+        untokened.calc_solution_signature()
+    );
+}
+
+#[test]
+fn test_invalid_token_triggers_full_verification() {
+    let settings = settings();
+    let mut solution_data = solved_solution_data(&settings);
+    verify_and_issue_token(&settings, &mut solution_data).unwrap();
+
+    let mut forged = solution_data.clone();
+    forged.verification_token = Some("0".repeat(128));
+    assert_eq!(
+        verify_solution_data(&settings, &forged).unwrap(),
+        Verification::Full
+    );
+
+    // a token doesn't carry over to a tampered solution, which then fails full verification
+    let mut tampered = solution_data.clone();
+    tampered.solution = dejsonify(r#"{"variables":[1,0]}"#).unwrap();
+    assert!(verify_solution_data(&settings, &tampered).is_err());
+
+    // nor to another nonce
+    let mut moved = solution_data.clone();
+    moved.nonce += 1;
+    assert_ne!(
+        verify_solution_data(&settings, &moved).ok(),
+        Some(Verification::Token)
+    );
+}
+
+#[test]
+fn test_failed_verification_issues_no_token() {
+    let settings = settings();
+    let mut solution_data = solved_solution_data(&settings);
+    solution_data.solution = dejsonify(r#"{"variables":[1,0]}"#).unwrap();
+    assert!(verify_and_issue_token(&settings, &mut solution_data).is_err());
+    assert!(solution_data.verification_token.is_none());
+}
@@ -0,0 +1,87 @@
+mod common;
+
+use tig_challenges::{satisfiability, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{
+    generate_serialized_instance, verify_against_instance, verify_solution, BenchmarkSettings,
+    Solution,
+};
+
+const NUM_VARIABLES: usize = 10;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![NUM_VARIABLES as i32, 200])
+}
+
+fn to_solution(variables: Vec<bool>) -> Solution {
+    dejsonify(&jsonify(&satisfiability::Solution { variables })).unwrap()
+}
+
+// brute forces the nonce's instance, which is small enough to try every assignment
+fn solve(settings: &BenchmarkSettings, nonce: u64) -> Option<Vec<bool>> {
+    let challenge = satisfiability::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap();
+    (0..1u32 << NUM_VARIABLES)
+        .map(|bits| (0..NUM_VARIABLES).map(|i| bits & (1 << i) != 0).collect())
+        .find(|variables: &Vec<bool>| {
+            challenge
+                .verify_solution(&satisfiability::Solution {
+                    variables: variables.clone(),
+                })
+                .is_ok()
+        })
+}
+
+#[test]
+fn test_snapshot_agrees_with_fresh_generation() {
+    let settings = settings();
+    let mut num_checked = 0;
+    for nonce in 0..20 {
+        let snapshot = generate_serialized_instance(&settings, nonce).unwrap();
+        let mut solutions = vec![to_solution(vec![false; NUM_VARIABLES])];
+        if let Some(variables) = solve(&settings, nonce) {
+            solutions.push(to_solution(variables));
+        }
+        for solution in solutions {
+            let from_snapshot = verify_against_instance("c001", &snapshot, &solution);
+            let fresh = verify_solution(&settings, nonce, &solution);
+            assert_eq!(from_snapshot.is_ok(), fresh.is_ok());
+            num_checked += 1;
+        }
+    }
+    assert!(num_checked > 20);
+}
+
+#[test]
+fn test_snapshot_decides_the_instance() {
+    let settings = settings();
+    // a solution to one nonce's instance and a snapshot of another that it doesn't solve
+    let (nonce, variables, other_snapshot) = (0..20)
+        .find_map(|nonce| {
+            let variables = solve(&settings, nonce)?;
+            let solution = to_solution(variables.clone());
+            (0..20).find_map(|other| {
+                let snapshot = generate_serialized_instance(&settings, other).unwrap();
+                verify_against_instance("c001", &snapshot, &solution)
+                    .is_err()
+                    .then(|| (nonce, variables.clone(), snapshot))
+            })
+        })
+        .unwrap();
+    let solution = to_solution(variables);
+    assert!(verify_solution(&settings, nonce, &solution).is_ok());
+    assert!(verify_against_instance("c001", &other_snapshot, &solution).is_err());
+}
+
+#[test]
+fn test_corrupt_snapshot_is_rejected() {
+    let snapshot = generate_serialized_instance(&settings(), 0).unwrap();
+    let solution = to_solution(vec![false; NUM_VARIABLES]);
+    let err = verify_against_instance("c001", &snapshot[..snapshot.len() / 2], &solution)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("Failed to deserialize"), "{}", err);
+}
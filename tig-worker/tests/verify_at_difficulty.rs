@@ -0,0 +1,74 @@
+mod common;
+
+use tig_challenges::{knapsack, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{verify_solution, verify_solution_at_difficulty, BenchmarkSettings, Solution};
+
+const NUM_ITEMS: usize = 12;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c003", vec![NUM_ITEMS as i32, 10])
+}
+
+// brute forces the nonce's instance for its most valuable items, which is only a solution if it
+// beats the baseline by the settings' margin
+fn solve(settings: &BenchmarkSettings, nonce: u64) -> Option<Solution> {
+    let challenge = knapsack::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap();
+    let items = (0..1u32 << NUM_ITEMS)
+        .map(|mask| {
+            (0..NUM_ITEMS)
+                .filter(|i| mask & (1 << i) != 0)
+                .collect::<Vec<usize>>()
+        })
+        .filter(|items| {
+            items.iter().map(|&i| challenge.weights[i]).sum::<u32>() <= challenge.max_weight
+        })
+        .max_by_key(|items| items.iter().map(|&i| challenge.values[i]).sum::<u32>())?;
+    let solution = knapsack::Solution { items };
+    challenge
+        .verify_solution(&solution)
+        .is_ok()
+        .then(|| dejsonify(&jsonify(&solution)).unwrap())
+}
+
+#[test]
+fn test_harder_solution_satisfies_easier_difficulty() {
+    let settings = settings();
+    let (nonce, solution) = (0..20)
+        .find_map(|nonce| solve(&settings, nonce).map(|solution| (nonce, solution)))
+        .unwrap();
+    assert!(verify_solution(&settings, nonce, &solution).is_ok());
+    assert!(
+        verify_solution_at_difficulty(&settings, nonce, &solution, &settings.difficulty).is_ok()
+    );
+    assert!(
+        verify_solution_at_difficulty(&settings, nonce, &solution, &[NUM_ITEMS as i32, 0]).is_ok()
+    );
+}
+
+#[test]
+fn test_harder_difficulty_than_solved_is_rejected() {
+    let settings = settings();
+    let (nonce, solution) = (0..20)
+        .find_map(|nonce| solve(&settings, nonce).map(|solution| (nonce, solution)))
+        .unwrap();
+    for difficulty in [[NUM_ITEMS as i32, 11], [NUM_ITEMS as i32 + 1, 0]] {
+        let err = verify_solution_at_difficulty(&settings, nonce, &solution, &difficulty)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("harder than the solution's"), "{}", err);
+    }
+}
+
+#[test]
+fn test_weak_solution_is_rejected_at_easier_difficulty() {
+    let settings = settings();
+    let solution = dejsonify(&jsonify(&knapsack::Solution { items: vec![] })).unwrap();
+    assert!(
+        verify_solution_at_difficulty(&settings, 0, &solution, &[NUM_ITEMS as i32, 0]).is_err()
+    );
+}
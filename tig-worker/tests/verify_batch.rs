@@ -0,0 +1,121 @@
+mod common;
+
+use tig_algorithms::c003::c003_a001;
+use tig_challenges::{knapsack, satisfiability, ChallengeTrait};
+use tig_utils::{dejsonify, jsonify};
+use tig_worker::{verify_batch, verify_batch_report, BenchmarkSettings, Solution};
+
+const NUM_VARIABLES: usize = 10;
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c001", vec![NUM_VARIABLES as i32, 200])
+}
+
+// brute forces the nonce's instance, falling back to all false if it is unsatisfiable
+fn solve(settings: &BenchmarkSettings, nonce: u64) -> Solution {
+    let challenge = satisfiability::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(nonce),
+        &settings.difficulty,
+    )
+    .unwrap();
+    let variables: Vec<bool> = (0..1u32 << NUM_VARIABLES)
+        .map(|bits| (0..NUM_VARIABLES).map(|i| bits & (1 << i) != 0).collect())
+        .find(|variables: &Vec<bool>| {
+            challenge
+                .verify_solution(&satisfiability::Solution {
+                    variables: variables.clone(),
+                })
+                .is_ok()
+        })
+        .unwrap_or(vec![false; NUM_VARIABLES]);
+    dejsonify(&jsonify(&satisfiability::Solution { variables })).unwrap()
+}
+
+#[test]
+fn test_results_do_not_depend_on_threads() {
+    let settings = settings();
+    let mut solutions: Vec<(u64, Solution)> = (0..30)
+        .map(|nonce| (nonce, solve(&settings, nonce)))
+        .collect();
+    // a few solutions that fail for different reasons
+    solutions[3].1 = dejsonify(r#"{"variables":[1,0]}"#).unwrap();
+    solutions[17].1 = dejsonify(r#"{"items":[]}"#).unwrap();
+    solutions[25].0 = 26;
+
+    let results = |threads: usize| -> Vec<Result<(), String>> {
+        verify_batch(&settings, &solutions, threads)
+            .into_iter()
+            .map(|result| result.map_err(|e| e.to_string()))
+            .collect()
+    };
+    let expected = results(1);
+    assert_eq!(expected.len(), solutions.len());
+    for (i, result) in expected.iter().enumerate() {
+        if [3, 17, 25].contains(&i) {
+            assert!(result.is_err());
+        }
+    }
+    // 0 threads still verifies, and more threads than solutions is capped
+    for threads in [0, 2, 3, 7, 8, 30, 100] {
+        assert_eq!(results(threads), expected, "threads = {}", threads);
+    }
+    assert!(verify_batch(&settings, &[], 4).is_empty());
+}
+
+#[test]
+fn test_report_bytes_do_not_depend_on_scheduling() {
+    // knapsack qualifiers vary per solution, so the total depends on the order it's summed in
+    let settings = BenchmarkSettings {
+        challenge_id: "c003".to_string(),
+        algorithm_id: "c003_a001".to_string(),
+        difficulty: vec![50, 0],
+        ..settings()
+    };
+    let mut solutions: Vec<(u64, Solution)> = (0..40)
+        .map(|nonce| {
+            let challenge = knapsack::Challenge::generate_instance_from_vec(
+                settings.calc_seeds(nonce),
+                &settings.difficulty,
+            )
+            .unwrap();
+            let solution = c003_a001::solve_challenge(&challenge)
+                .unwrap()
+                .unwrap_or(knapsack::Solution { items: Vec::new() });
+            (nonce, dejsonify(&jsonify(&solution)).unwrap())
+        })
+        .collect();
+    solutions[5].1 = dejsonify(r#"{"items":[0,0]}"#).unwrap();
+    solutions[30].1 = dejsonify(r#"{"variables":[1,0]}"#).unwrap();
+    // the same nonce twice, failing differently
+    solutions.push((30, dejsonify(r#"{"items":[999]}"#).unwrap()));
+
+    let expected = verify_batch_report(&settings, &solutions, 1);
+    assert!(expected.num_verified > 0);
+    assert_eq!(
+        expected.num_verified as usize + expected.failures.len(),
+        solutions.len()
+    );
+    let failed_nonces: Vec<u64> = expected.failures.iter().map(|(nonce, _)| *nonce).collect();
+    assert!(failed_nonces.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(
+        failed_nonces.iter().filter(|&&nonce| nonce == 30).count(),
+        2
+    );
+    let expected_bytes = jsonify(&expected);
+
+    let mut reordered = solutions.clone();
+    for run in 0..10 {
+        reordered.rotate_left(7);
+        if run % 2 == 1 {
+            reordered.reverse();
+        }
+        for threads in [1, 3, 8] {
+            let report = verify_batch_report(&settings, &reordered, threads);
+            assert_eq!(jsonify(&report), expected_bytes, "threads = {}", threads);
+        }
+    }
+    assert_eq!(
+        jsonify(&verify_batch_report(&settings, &[], 4)),
+        jsonify(&tig_worker::BatchReport::default())
+    );
+}
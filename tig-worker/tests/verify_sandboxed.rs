@@ -0,0 +1,86 @@
+mod common;
+
+use std::path::Path;
+use tig_challenges::vehicle_routing::calc_baseline_routes;
+use tig_challenges::{vehicle_routing, ChallengeTrait};
+use tig_utils::dejsonify;
+use tig_worker::{verify_solution, verify_solution_sandboxed, BenchmarkSettings, Solution};
+
+const MAX_MEMORY: u64 = 4_000_000_000;
+
+fn worker_path() -> &'static Path {
+    Path::new(env!("CARGO_BIN_EXE_tig-worker"))
+}
+
+fn routes_solution(routes: &[Vec<usize>]) -> Solution {
+    dejsonify(&format!("{{\"routes\": {:?}}}", routes)).unwrap()
+}
+
+#[test]
+fn test_crashing_input_is_rejected() {
+    let settings = common::settings("c002", vec![40, 0]);
+    // a node that doesn't exist panics the vehicle routing verifier
+    let solution = routes_solution(&[vec![0, 1000, 0]]);
+    assert!(std::panic::catch_unwind(|| verify_solution(&settings, 0, &solution)).is_err());
+
+    let err = verify_solution_sandboxed(worker_path(), &settings, 0, &solution, 10000, MAX_MEMORY)
+        .unwrap_err();
+    assert!(err.to_string().contains("Verifier crashed"), "{}", err);
+}
+
+#[test]
+fn test_agrees_with_in_process() {
+    let settings = common::settings("c002", vec![40, 0]);
+    let challenge = vehicle_routing::Challenge::generate_instance_from_vec(
+        settings.calc_seeds(0),
+        &settings.difficulty,
+    )
+    .unwrap();
+    // the baseline reaches a difficulty 0 better than itself
+    let routes = calc_baseline_routes(
+        40,
+        challenge.max_capacity,
+        &challenge.demands,
+        &challenge.distance_matrix,
+    )
+    .unwrap();
+
+    let solution = routes_solution(&routes);
+    assert!(verify_solution(&settings, 0, &solution).is_ok());
+    assert!(
+        verify_solution_sandboxed(worker_path(), &settings, 0, &solution, 10000, MAX_MEMORY)
+            .is_ok()
+    );
+
+    let solution = routes_solution(&routes[1..]);
+    let expected = verify_solution(&settings, 0, &solution).unwrap_err();
+    let err = verify_solution_sandboxed(worker_path(), &settings, 0, &solution, 10000, MAX_MEMORY)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains(&expected.to_string()),
+        "{} {}",
+        err,
+        expected
+    );
+}
+
+#[test]
+fn test_timeout_rejects() {
+    // generating a vector search instance takes longer than the timeout
+    let settings = common::settings("c004", vec![5, 0]);
+    let solution: Solution = dejsonify("{\"indexes\": [0, 0, 0, 0, 0]}").unwrap();
+    let err = verify_solution_sandboxed(worker_path(), &settings, 0, &solution, 50, MAX_MEMORY)
+        .unwrap_err();
+    assert!(err.to_string().contains("timed out"), "{}", err);
+}
+
+#[test]
+fn test_memory_limit_rejects() {
+    // too little memory to even start
+    let settings = common::settings("c002", vec![40, 0]);
+    let solution = routes_solution(&[vec![0, 1, 0]]);
+    assert!(
+        verify_solution_sandboxed(worker_path(), &settings, 0, &solution, 10000, 1_000_000)
+            .is_err()
+    );
+}
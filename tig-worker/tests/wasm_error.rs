@@ -0,0 +1,180 @@
+mod common;
+
+use tig_worker::{check_wasm, compute_solution, BenchmarkSettings, WasmError};
+
+const PAGE_SIZE: u64 = 65536;
+const MAX_FUEL: u64 = 1_000_000;
+
+// (module
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0)
+//   (func (export "entry_point") (param i32 i32) (result i32) <body>))
+// up to the code section, which depends on the body
+const MODULE_PREFIX: [u8; 65] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // types
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x1f, 0x03, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, // "init"
+    0x0b, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00, 0x01, // "entry_point"
+];
+const INIT_CODE: [u8; 5] = [0x04, 0x00, 0x41, 0x00, 0x0b];
+
+fn wasm_with_entry_point(body: &[u8]) -> Vec<u8> {
+    let body_len = body.len() as u8;
+    let mut wasm = MODULE_PREFIX.to_vec();
+    wasm.extend([0x0a, 3 + INIT_CODE.len() as u8 + body_len, 0x02]);
+    wasm.extend(INIT_CODE);
+    // no locals
+    wasm.extend([1 + body_len, 0x00]);
+    wasm.extend(body);
+    wasm
+}
+
+// unreachable
+const TRAP_BODY: [u8; 2] = [0x00, 0x0b];
+// loop br 0 end i32.const 0
+const LOOP_BODY: [u8; 8] = [0x03, 0x40, 0x0c, 0x00, 0x0b, 0x41, 0x00, 0x0b];
+// i32.const 4 memory.grow i32.const -1 i32.eq if unreachable end i32.const 60000. needs 5 pages
+const GROWING_BODY: [u8; 16] = [
+    0x41, 0x04, 0x40, 0x00, 0x41, 0x7f, 0x46, 0x04, 0x40, 0x00, 0x0b, 0x41, 0xe0, 0xd4, 0x03, 0x0b,
+];
+// the solution pointers below are wrong, which the worker only finds reading them after the call
+// i32.const 70000, past the single page of memory
+const OUT_OF_BOUNDS_BODY: [u8; 5] = [0x41, 0xf0, 0xa2, 0x04, 0x0b];
+// i32.const 0 i32.const 1000000 i32.store i32.const 0, a length running past the end of memory
+const OVERLONG_BODY: [u8; 12] = [
+    0x41, 0x00, 0x41, 0xc0, 0x84, 0x3d, 0x36, 0x02, 0x00, 0x41, 0x00, 0x0b,
+];
+// i32.const 60000 i32.const 4 i32.store i32.const 60000, 4 zero bytes that aren't compressed
+const UNDECOMPRESSABLE_BODY: [u8; 14] = [
+    0x41, 0xe0, 0xd4, 0x03, 0x41, 0x04, 0x36, 0x02, 0x00, 0x41, 0xe0, 0xd4, 0x03, 0x0b,
+];
+
+// (module
+//   (import "env" "abort" (func (param i32) (result i32)))
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0)
+//   (func (export "entry_point") (param i32 i32) (result i32) i32.const 0))
+// the import can't be resolved, the worker provides no host functions
+const IMPORTING_WASM: [u8; 93] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x0c, 0x02, 0x60, 0x01, 0x7f, 0x01, 0x7f, 0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7f, // types
+    0x02, 0x0d, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x05, 0x61, 0x62, 0x6f, 0x72, 0x74, 0x00, 0x00, // imports
+    0x03, 0x03, 0x02, 0x00, 0x01, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x1f, 0x03, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x01, // "init"
+    0x0b, 0x65, 0x6e, 0x74, 0x72, 0x79, 0x5f, 0x70, 0x6f, 0x69, 0x6e, 0x74, 0x00, 0x02, // "entry_point"
+    0x0a, 0x0b, 0x02, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+    0x04, 0x00, 0x41, 0x00, 0x0b, // entry_point
+];
+
+// (module
+//   (memory (export "memory") 1)
+//   (func (export "init") (param i32) (result i32) i32.const 0))
+const NO_ENTRY_POINT_WASM: [u8; 52] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // header
+    0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f, // types
+    0x03, 0x02, 0x01, 0x00, // functions
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory
+    0x07, 0x11, 0x02, // exports
+    0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00, // "memory"
+    0x04, 0x69, 0x6e, 0x69, 0x74, 0x00, 0x00, // "init"
+    0x0a, 0x06, 0x01, // code
+    0x04, 0x00, 0x41, 0x00, 0x0b, // init
+];
+
+fn settings() -> BenchmarkSettings {
+    common::settings("c003", vec![5, 0])
+}
+
+fn wasm_error(wasm: &[u8], max_memory: u64) -> WasmError {
+    compute_solution(&settings(), 0, wasm, max_memory, MAX_FUEL)
+        .unwrap_err()
+        .downcast::<WasmError>()
+        .unwrap()
+}
+
+#[test]
+fn test_compile_failed() {
+    let wasm = wasm_with_entry_point(&TRAP_BODY);
+    // e.g. a truncated download
+    let truncated = &wasm[..wasm.len() / 2];
+    assert!(matches!(
+        wasm_error(truncated, 2 * PAGE_SIZE),
+        WasmError::CompileFailed { .. }
+    ));
+    assert!(matches!(
+        check_wasm(truncated),
+        Err(WasmError::CompileFailed { .. })
+    ));
+    assert!(matches!(
+        check_wasm(b"not a wasm module"),
+        Err(WasmError::CompileFailed { .. })
+    ));
+    assert_eq!(check_wasm(&wasm), Ok(()));
+}
+
+#[test]
+fn test_instantiate_failed() {
+    assert_eq!(check_wasm(&IMPORTING_WASM), Ok(()));
+    assert!(matches!(
+        wasm_error(&IMPORTING_WASM, 2 * PAGE_SIZE),
+        WasmError::InstantiateFailed { .. }
+    ));
+}
+
+#[test]
+fn test_missing_export() {
+    assert_eq!(
+        wasm_error(&NO_ENTRY_POINT_WASM, 2 * PAGE_SIZE),
+        WasmError::MissingExport {
+            name: "entry_point".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_trap() {
+    assert!(matches!(
+        wasm_error(&wasm_with_entry_point(&TRAP_BODY), 2 * PAGE_SIZE),
+        WasmError::Trap { .. }
+    ));
+}
+
+#[test]
+fn test_bad_solution_is_a_trap() {
+    for body in [
+        &OUT_OF_BOUNDS_BODY[..],
+        &OVERLONG_BODY[..],
+        &UNDECOMPRESSABLE_BODY[..],
+    ] {
+        let error = wasm_error(&wasm_with_entry_point(body), 2 * PAGE_SIZE);
+        assert!(matches!(error, WasmError::Trap { .. }), "{:?}", error);
+    }
+}
+
+#[test]
+fn test_out_of_fuel() {
+    assert_eq!(
+        wasm_error(&wasm_with_entry_point(&LOOP_BODY), 2 * PAGE_SIZE),
+        WasmError::OutOfFuel { max_fuel: MAX_FUEL }
+    );
+}
+
+#[test]
+fn test_out_of_memory() {
+    let wasm = wasm_with_entry_point(&GROWING_BODY);
+    assert_eq!(
+        wasm_error(&wasm, 2 * PAGE_SIZE),
+        WasmError::OutOfMemory {
+            max_memory: 2 * PAGE_SIZE
+        }
+    );
+    assert!(compute_solution(&settings(), 0, &wasm, 8 * PAGE_SIZE, MAX_FUEL).is_ok());
+}